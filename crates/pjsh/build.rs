@@ -0,0 +1,32 @@
+use std::process::Command;
+
+/// Embeds build information in the compiled binary.
+fn main() {
+    let commit = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|commit| commit.trim().to_owned())
+        .filter(|commit| !commit.is_empty())
+        .unwrap_or_else(|| String::from("unknown"));
+
+    // Cargo exposes each enabled feature as an environment variable.
+    let mut features: Vec<String> = std::env::vars()
+        .filter_map(|(key, _)| key.strip_prefix("CARGO_FEATURE_").map(str::to_owned))
+        .map(|feature| feature.to_lowercase().replace('_', "-"))
+        .collect();
+    features.sort();
+
+    println!("cargo:rustc-env=PJSH_GIT_COMMIT={commit}");
+    println!("cargo:rustc-env=PJSH_BUILD_PROFILE={}", env("PROFILE"));
+    println!("cargo:rustc-env=PJSH_BUILD_TARGET={}", env("TARGET"));
+    println!("cargo:rustc-env=PJSH_FEATURES={}", features.join(","));
+    println!("cargo:rerun-if-changed=../../.git/HEAD");
+}
+
+/// Returns the value of an environment variable set by Cargo.
+fn env(key: &str) -> String {
+    std::env::var(key).unwrap_or_else(|_| String::from("unknown"))
+}
@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use clap::Parser;
+use clap::{ArgGroup, CommandFactory, Parser};
 use parking_lot::Mutex;
 use pjsh_builtins::exit_with_parse_error;
 use pjsh_complete::{Completer, Completion};
@@ -20,24 +20,48 @@
 
 /// Define shell completions.
 ///
+/// Prints all registered completions if no names are given.
+///
 /// This is a built-in shell command.
 #[derive(Parser)]
 #[clap(name = NAME, version)]
+#[clap(group(ArgGroup::new("targets").args(["names", "commands"]).multiple(true)))]
 struct CompleteOpts {
-    /// Name for which completions exist.
-    name: String,
+    /// Names for which completions exist.
+    names: Vec<String>,
+
+    /// A name for which completions exist. May be repeated.
+    #[clap(short = 'c', value_name = "NAME")]
+    commands: Vec<String>,
 
     /// A pre-defined action.
-    #[clap(short = 'A')]
+    #[clap(short = 'A', requires = "targets")]
     action: Option<String>,
 
+    /// Retrieve completions by calling `<name> --pjsh-complete -- <line> <point>`.
+    ///
+    /// The program should print one completion per line, optionally followed by
+    /// a tab and a description.
+    #[clap(long, requires = "targets")]
+    external: bool,
+
     /// A function to call in order to retrieve completions.
-    #[clap(short = 'F')]
+    ///
+    /// The function should print one completion per line.
+    #[clap(short = 'F', requires = "targets")]
     function: Option<String>,
 
     /// A fixed list of words.
-    #[clap(short = 'W')]
+    #[clap(short = 'W', requires = "targets")]
     wordlist: Option<String>,
+
+    /// Print registered completions in a reusable format.
+    #[clap(short, conflicts_with_all = ["action", "external", "function", "wordlist", "remove"])]
+    print: bool,
+
+    /// Remove the registered completions for all names.
+    #[clap(short, conflicts_with_all = ["action", "external", "function", "wordlist"])]
+    remove: bool,
 }
 
 /// Implementation for the "complete" built-in command.
@@ -59,31 +83,74 @@ fn name(&self) -> &str {
         "complete"
     }
 
+    fn description(&self) -> Option<String> {
+        CompleteOpts::command().get_about().map(ToString::to_string)
+    }
+
+    fn usage(&self) -> Option<String> {
+        let usage = CompleteOpts::command().render_usage().to_string();
+        Some(usage.trim_start_matches("Usage: ").to_owned())
+    }
+
     fn run(&self, args: &mut Args) -> CommandResult {
         match CompleteOpts::try_parse_from(args.context.args()) {
-            Ok(opts) => {
+            Ok(mut opts) => {
                 let mut completer = self.completer.lock();
+                let mut names = std::mem::take(&mut opts.commands);
+                names.append(&mut opts.names);
 
-                if let Some(action) = opts.action {
-                    let completion = match action.as_str() {
+                if opts.print || names.is_empty() {
+                    for (name, completion) in completer.registered_completions() {
+                        if !names.is_empty() && !names.iter().any(|n| n == name) {
+                            continue;
+                        }
+
+                        if let Err(error) =
+                            writeln!(args.io.stdout, "{}", describe(name, completion))
+                        {
+                            let _ = writeln!(args.io.stderr, "{NAME}: {error}");
+                            return CommandResult::code(GENERAL_ERROR);
+                        }
+                    }
+                    return CommandResult::code(SUCCESS);
+                }
+
+                if opts.remove {
+                    let mut code = SUCCESS;
+                    for name in names {
+                        if !completer.unregister_completion(&name) {
+                            let _ = writeln!(
+                                args.io.stderr,
+                                "{NAME}: {name}: no completion specification"
+                            );
+                            code = GENERAL_ERROR;
+                        }
+                    }
+                    return CommandResult::code(code);
+                }
+
+                let completion = if let Some(action) = opts.action {
+                    match action.as_str() {
                         "directory" => Completion::Directory,
                         "file" => Completion::File,
+                        "prefix" => Completion::Prefix,
                         _ => {
                             let _ = writeln!(args.io.stderr, "Unknown action: {action}");
                             return CommandResult::code(GENERAL_ERROR);
                         }
-                    };
-                    completer.register_completion(opts.name, completion);
+                    }
+                } else if opts.external {
+                    Completion::External
+                } else if let Some(function) = opts.function {
+                    Completion::Function(function)
+                } else if let Some(wordlist) = opts.wordlist {
+                    Completion::Constant(words(wordlist))
+                } else {
                     return CommandResult::code(SUCCESS);
-                }
-
-                if let Some(function) = opts.function {
-                    completer.register_completion(opts.name, Completion::Function(function));
-                    return CommandResult::code(SUCCESS);
-                }
+                };
 
-                if let Some(wordlist) = opts.wordlist {
-                    completer.register_completion(opts.name, Completion::Constant(words(wordlist)));
+                for name in names {
+                    completer.register_completion(name, completion.clone());
                 }
 
                 CommandResult::code(SUCCESS)
@@ -93,6 +160,21 @@ fn run(&self, args: &mut Args) -> CommandResult {
     }
 }
 
+/// Describes a registered completion as a "complete" command that registers it.
+fn describe(name: &str, completion: &Completion) -> String {
+    match completion {
+        Completion::Constant(words) => {
+            let words = words.join(" ").replace('\'', "\\'");
+            format!("{NAME} -W '{words}' {name}")
+        }
+        Completion::Directory => format!("{NAME} -A directory {name}"),
+        Completion::External => format!("{NAME} --external {name}"),
+        Completion::File => format!("{NAME} -A file {name}"),
+        Completion::Function(function) => format!("{NAME} -F {function} {name}"),
+        Completion::Prefix => format!("{NAME} -A prefix {name}"),
+    }
+}
+
 /// Returns a `Vec<String>` of all whitespace-separated words in a string.
 fn words(wordlist: String) -> Vec<String> {
     wordlist
@@ -100,3 +182,111 @@ fn words(wordlist: String) -> Vec<String> {
         .map(|word| word.to_string())
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Seek};
+
+    use pjsh_core::{command::Io, Context};
+
+    use super::*;
+
+    /// Status code indicating a usage error.
+    const BUILTIN_ERROR: i32 = 2;
+
+    /// Runs the "complete" built-in command, returning its exit code and stdout.
+    fn complete(completer: &Arc<Mutex<Completer>>, args: &[&str]) -> (i32, String) {
+        let mut ctx = Context::default();
+        ctx.replace_args(Some(
+            std::iter::once(NAME)
+                .chain(args.iter().copied())
+                .map(ToString::to_string)
+                .collect(),
+        ));
+        let mut stdout = tempfile::tempfile().unwrap();
+        let mut io = Io::new(
+            Box::new(std::io::empty()),
+            Box::new(stdout.try_clone().unwrap()),
+            Box::new(std::io::sink()),
+        );
+
+        let code = match Complete::new(Arc::clone(completer)).run(&mut Args::new(&mut ctx, &mut io))
+        {
+            CommandResult::Builtin(result) => result.code,
+            CommandResult::Process(_) => unreachable!(),
+        };
+
+        let mut output = String::new();
+        stdout.rewind().unwrap();
+        stdout.read_to_string(&mut output).unwrap();
+        (code, output)
+    }
+
+    #[test]
+    fn it_registers_prints_and_removes_completions() {
+        let completer = Arc::new(Mutex::new(Completer::default()));
+
+        assert_eq!(
+            complete(&completer, &["-c", "git", "-W", "clone pull"]).0,
+            SUCCESS
+        );
+        assert_eq!(
+            complete(&completer, &["-F", "_complete_ssh", "ssh", "scp"]).0,
+            SUCCESS
+        );
+        assert_eq!(complete(&completer, &["-A", "directory", "cd"]).0, SUCCESS);
+
+        assert_eq!(
+            complete(&completer, &["-p"]),
+            (
+                SUCCESS,
+                "complete -A directory cd\n\
+                 complete -W 'clone pull' git\n\
+                 complete -F _complete_ssh scp\n\
+                 complete -F _complete_ssh ssh\n"
+                    .into()
+            )
+        );
+        assert_eq!(
+            complete(&completer, &["-p", "git"]),
+            (SUCCESS, "complete -W 'clone pull' git\n".into())
+        );
+
+        assert_eq!(
+            complete(&completer, &["-W", "it's \"quoted\"", "quotes"]).0,
+            SUCCESS
+        );
+        assert_eq!(
+            complete(&completer, &["-p", "quotes"]),
+            (SUCCESS, "complete -W 'it\\'s \"quoted\"' quotes\n".into())
+        );
+
+        assert_eq!(
+            complete(&completer, &["-r", "ssh", "scp", "quotes"]).0,
+            SUCCESS
+        );
+        assert_eq!(complete(&completer, &["-r", "ssh"]).0, GENERAL_ERROR);
+        assert_eq!(
+            complete(&completer, &[]),
+            (
+                SUCCESS,
+                "complete -A directory cd\ncomplete -W 'clone pull' git\n".into()
+            )
+        );
+    }
+
+    #[test]
+    fn it_requires_names_for_new_completions() {
+        let completer = Arc::new(Mutex::new(Completer::default()));
+
+        for args in [
+            &["-W", "clone pull"][..],
+            &["-F", "_complete_ssh"],
+            &["-A", "file"],
+            &["--external"],
+        ] {
+            assert_eq!(complete(&completer, args), (BUILTIN_ERROR, String::new()));
+        }
+        assert_eq!(complete(&completer, &[]), (SUCCESS, String::new()));
+    }
+}
@@ -0,0 +1,210 @@
+use std::{
+    backtrace::Backtrace,
+    collections::VecDeque,
+    fmt::Write as _,
+    fs::OpenOptions,
+    io::{ErrorKind, Write as _},
+    panic::AssertUnwindSafe,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use parking_lot::Mutex;
+use pjsh_core::Context;
+
+use crate::version_info;
+
+/// Exit code of a shell that has crashed due to a panic.
+pub(crate) const CRASH_EXIT_CODE: u8 = 70;
+
+/// Directory to write crash reports to, relative to the user's home directory.
+const CRASH_REPORT_DIR: &str = ".pjsh";
+
+/// Maximum number of recent input lines to include in a crash report.
+const MAX_RECENT_INPUT: usize = 10;
+
+/// The most recent lines of input, oldest first.
+static RECENT_INPUT: Mutex<VecDeque<String>> = parking_lot::const_mutex(VecDeque::new());
+
+/// Message and backtrace of the most recent panic.
+static LAST_PANIC: Mutex<Option<(String, String)>> = parking_lot::const_mutex(None);
+
+/// Records a line of input to include in crash reports.
+///
+/// Only the most recent lines are kept.
+pub(crate) fn record_input(line: &str) {
+    let mut input = RECENT_INPUT.lock();
+    if input.len() == MAX_RECENT_INPUT {
+        input.pop_front();
+    }
+    input.push_back(line.trim_end().to_owned());
+}
+
+/// Runs the shell, writing a crash report to the user's home directory if it
+/// panics.
+///
+/// Reports are only ever written to a local file. They include the names of
+/// the context's scopes, but never the values of any variables.
+pub(crate) fn report_crashes(
+    context: &Arc<Mutex<Context>>,
+    run: impl FnOnce() -> ExitCode,
+) -> ExitCode {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let backtrace = Backtrace::force_capture().to_string();
+        *LAST_PANIC.lock() = Some((info.to_string(), backtrace));
+        default_hook(info);
+    }));
+
+    if let Ok(exit_code) = std::panic::catch_unwind(AssertUnwindSafe(run)) {
+        return exit_code;
+    }
+
+    // The context is unlocked while unwinding, unless it is held elsewhere.
+    let scopes = context.try_lock().map(|context| {
+        context
+            .scope_names()
+            .into_iter()
+            .map(str::to_owned)
+            .collect()
+    });
+    let report = crash_report(LAST_PANIC.lock().take(), scopes);
+
+    match dirs::home_dir().map(|home| write_crash_report(&home, &report)) {
+        Some(Ok(path)) => eprintln!(
+            "pjsh: crashed, a report has been written to {}",
+            path.display()
+        ),
+        Some(Err(error)) => eprintln!("pjsh: crashed, failed to write a report: {error}"),
+        None => eprintln!("pjsh: crashed, failed to write a report: unknown home directory"),
+    }
+
+    ExitCode::from(CRASH_EXIT_CODE)
+}
+
+/// Returns the contents of a crash report.
+///
+/// Scope names are `None` if they could not be determined.
+fn crash_report(panic: Option<(String, String)>, scopes: Option<Vec<String>>) -> String {
+    let (message, backtrace) = panic.unwrap_or_else(|| ("unknown panic".into(), String::new()));
+    let mut report = String::from("# pjsh crash report\n");
+
+    let _ = write!(report, "\n## Panic\n{message}\n");
+    let _ = write!(report, "\n## Backtrace\n{}\n", backtrace.trim_end());
+
+    report.push_str("\n## Recent Input\n");
+    let input = RECENT_INPUT.lock();
+    if input.is_empty() {
+        report.push_str("(none)\n");
+    }
+    for line in input.iter() {
+        let _ = writeln!(report, "> {line}");
+    }
+
+    report.push_str("\n## Version\n");
+    for (key, value) in version_info() {
+        let _ = writeln!(report, "{key}: {value}");
+    }
+
+    report.push_str("\n## Scopes\n");
+    match scopes {
+        Some(scopes) => scopes.iter().for_each(|scope| {
+            let _ = writeln!(report, "{scope}");
+        }),
+        None => report.push_str("(unavailable)\n"),
+    }
+
+    report
+}
+
+/// Writes a crash report to a new file within a home directory.
+///
+/// Files are named after the current time and process ID, and are only
+/// readable by the user. Existing files are never overwritten.
+///
+/// Returns the path of the written file.
+fn write_crash_report(home: &Path, report: &str) -> std::io::Result<PathBuf> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+
+    let dir = home.join(CRASH_REPORT_DIR);
+    std::fs::create_dir_all(&dir)?;
+
+    let prefix = format!("crash-{timestamp}-{}", std::process::id());
+    let mut path = dir.join(format!("{prefix}.txt"));
+    let mut attempt = 1;
+    let mut file = loop {
+        match crash_report_options().open(&path) {
+            Err(error) if error.kind() == ErrorKind::AlreadyExists => {
+                attempt += 1;
+                path = dir.join(format!("{prefix}-{attempt}.txt"));
+            }
+            result => break result?,
+        }
+    };
+
+    file.write_all(report.as_bytes())?;
+    Ok(path)
+}
+
+/// Returns options for creating new crash report files.
+fn crash_report_options() -> OpenOptions {
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    options
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_builds_crash_reports() {
+        for i in 0..=MAX_RECENT_INPUT {
+            record_input(&format!("echo {i}\n"));
+        }
+
+        let report = crash_report(
+            Some(("panicked at main.rs".into(), "0: main".into())),
+            Some(vec!["global".into(), "f".into()]),
+        );
+
+        assert!(report.contains("\n## Panic\npanicked at main.rs\n"));
+        assert!(report.contains("\n## Backtrace\n0: main\n"));
+        assert!(
+            !report.contains("> echo 0\n"),
+            "old input should be dropped"
+        );
+        assert!(report.contains(&format!("> echo {MAX_RECENT_INPUT}\n")));
+        assert!(report.contains("\nversion: "));
+        assert!(report.ends_with("\n## Scopes\nglobal\nf\n"));
+    }
+
+    #[test]
+    fn it_writes_crash_reports_to_new_private_files() {
+        let home = tempfile::TempDir::new().unwrap();
+
+        let first = write_crash_report(home.path(), "first").unwrap();
+        let second = write_crash_report(home.path(), "second").unwrap();
+        assert_ne!(first, second);
+        assert_eq!(std::fs::read_to_string(first).unwrap(), "first");
+        assert_eq!(std::fs::read_to_string(&second).unwrap(), "second");
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(second).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+}
@@ -1,7 +1,3 @@
-use annotate_snippets::{
-    display_list::{DisplayList, FormatOptions},
-    snippet::{Annotation, AnnotationType, Slice, Snippet, SourceAnnotation},
-};
 use pjsh_parse::ParseError;
 
 use crate::shell::ShellError;
@@ -21,6 +17,7 @@ fn display_error(&self, error: ShellError) {
             ShellError::ParseError(error, _) => eprintln!("pjsh: {error}"),
             ShellError::EvalError(error) => eprintln!("pjsh: {error}"),
             ShellError::IoError(error) => eprintln!("pjsh: {error}"),
+            ShellError::Terminated(signal) => eprintln!("pjsh: terminated by signal {signal}"),
         }
     }
 }
@@ -36,40 +33,15 @@ fn display_error(&self, error: ShellError) {
             }
             ShellError::EvalError(error) => eprintln!("pjsh: {error}"),
             ShellError::IoError(error) => eprintln!("pjsh: {error}"),
+            ShellError::Terminated(signal) => eprintln!("pjsh: terminated by signal {signal}"),
         }
     }
 }
 
 /// Prints details related to a parse error.
 fn print_parse_error_details(line: &str, error: &ParseError) {
-    let Some(span) = error.span() else {
-        eprintln!("pjsh: {error}");
-        return;
-    };
-
-    let snippet = Snippet {
-        title: Some(Annotation {
-            label: Some("parse error"),
-            id: None,
-            annotation_type: AnnotationType::Error,
-        }),
-        footer: vec![],
-        slices: vec![Slice {
-            source: line,
-            line_start: 1,
-            origin: None,
-            fold: true,
-            annotations: vec![SourceAnnotation {
-                label: error.help(),
-                annotation_type: AnnotationType::Error,
-                range: (span.start, span.end),
-            }],
-        }],
-        opt: FormatOptions {
-            color: true,
-            ..Default::default()
-        },
-    };
-
-    println!("{}", DisplayList::from(snippet));
+    match error.snippet(line, true) {
+        Some(snippet) => println!("{snippet}"),
+        None => eprintln!("pjsh: {error}"),
+    }
 }
@@ -1,22 +1,26 @@
 mod builtins;
 
+mod crash;
 mod error;
 mod shell;
 
-use std::fs::{read_to_string, File};
+use std::io::{stdout, ErrorKind, Write};
 use std::process::ExitCode;
 use std::{env::current_exe, path::PathBuf, sync::Arc};
 
-use clap::{crate_version, Parser};
+use clap::{crate_version, ArgGroup, Parser};
 use error::{ErrorHandler, GuidingErrorHandler, SimpleErrorHandler};
 use parking_lot::Mutex;
 use pjsh_complete::Completer;
-use pjsh_core::{utils::path_to_string, Context};
-use pjsh_eval::{execute_statement, interpolate_word};
+use pjsh_core::{utils::path_to_string, Context, STRICT};
+use pjsh_eval::{execute_statement, interpolate_word, EvalError};
 use pjsh_parse::{parse, parse_interpolation};
 use shell::context::initialized_context;
+use shell::utils::{has_strict_pragma, read_script, run_exit_trap, shut_down};
 pub use shell::Shell;
-use shell::{CommandShell, FileParseShell, FileShell, InteractiveShell, StdinShell};
+use shell::{
+    CommandShell, FileParseShell, FileShell, InteractiveShell, OutputFormat, ShellError, StdinShell,
+};
 
 /// Init script to always source when starting a new shell.
 const INIT_ALWAYS_SCRIPT_NAME: &str = ".pjsh/init-always.pjsh";
@@ -31,13 +35,43 @@
 #[derive(Parser)]
 #[clap(
     about = "A small shell for command interpretation.",
-    version = crate_version!()
+    version = crate_version!(),
+    disable_version_flag = true,
+    group = ArgGroup::new("info").args(["version", "list_builtins", "list_filters"])
 )]
 struct Opts {
+    /// Print version information.
+    #[clap(short = 'V', long = "version")]
+    version: bool,
+
+    /// Print detailed version information, one `key: value` pair per line.
+    #[clap(long = "verbose", requires = "version", conflicts_with = "json")]
+    verbose: bool,
+
+    /// Print version information or lists as JSON.
+    #[clap(long = "json", requires = "info")]
+    json: bool,
+
+    /// Print the names of all built-in commands, one per line.
+    #[clap(long = "list-builtins")]
+    list_builtins: bool,
+
+    /// Print the names of all filters, one per line.
+    #[clap(long = "list-filters")]
+    list_filters: bool,
+
     /// Execute a command rather than a script file.
     #[clap(short = 'c', long = "command", requires = "script_file")]
     is_command: bool,
 
+    /// Output format for the results of a command.
+    #[clap(long = "output", value_enum, requires = "is_command")]
+    output_format: Option<OutputFormat>,
+
+    /// Maximum number of bytes to capture from each output stream.
+    #[clap(long = "output-limit", requires = "output_format")]
+    output_limit: Option<usize>,
+
     /// Print the AST without executing it.
     #[clap(
         long = "parse",
@@ -46,10 +80,49 @@ struct Opts {
     )]
     is_parse_only: bool,
 
+    /// Annotate each parsed statement with its source span.
+    #[clap(long = "spans", requires = "is_parse_only")]
+    is_spanned: bool,
+
+    /// Print the names of the functions defined by the script file instead of
+    /// the AST.
+    #[clap(
+        long = "functions",
+        requires = "is_parse_only",
+        conflicts_with = "is_spanned"
+    )]
+    list_functions: bool,
+
+    /// Print a report of where time is spent when executing the script file.
+    #[clap(
+        long = "profile",
+        requires = "script_file",
+        conflicts_with_all = ["is_command", "is_parse_only"]
+    )]
+    profile: bool,
+
+    /// Write the duration of each command executed by the script file to a
+    /// file as tab-separated values.
+    #[clap(
+        long = "profile-output",
+        value_name = "PATH",
+        requires = "script_file",
+        conflicts_with_all = ["is_command", "is_parse_only"]
+    )]
+    profile_output: Option<PathBuf>,
+
     /// Force an interactive shell.
     #[clap(short = 'i', long = "interactive")]
     force_interactive: bool,
 
+    /// Disable globbing, keeping patterns such as `*` as literal words.
+    #[clap(short = 'f', long = "no-globbing")]
+    no_globbing: bool,
+
+    /// Panic after starting the shell in order to test crash reports.
+    #[clap(long = "crash-test", hide = true)]
+    crash_test: bool,
+
     /// Script file.
     script_file: Option<String>,
 
@@ -60,6 +133,21 @@ struct Opts {
 /// Entrypoint for the application.
 pub fn main() -> ExitCode {
     let mut opts = Opts::parse();
+
+    if opts.version {
+        print_version(&opts);
+        return ExitCode::SUCCESS;
+    }
+    if opts.list_builtins || opts.list_filters {
+        return match print_names(&opts) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(error) if error.kind() == ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+            Err(error) => {
+                eprintln!("pjsh: {error}");
+                ExitCode::FAILURE
+            }
+        };
+    }
     let interactive = opts.force_interactive || !opts.is_command && opts.script_file.is_none();
 
     let first_arg = match &opts.is_command {
@@ -81,25 +169,117 @@ pub fn main() -> ExitCode {
         false => opts.script_file.as_ref().map(PathBuf::from),
     };
 
-    let (context, completer) = initialized_context(args, script_file);
+    let (mut context, completer) = initialized_context(args, script_file);
+    context.options.noglob = opts.no_globbing;
+    // The context is not thread-safe, but shells and the line editor's helper
+    // share it through the `Arc<Mutex<Context>>` that their APIs require.
+    #[allow(clippy::arc_with_non_send_sync)]
     let context = Arc::new(Mutex::new(context));
 
-    source_init_scripts(interactive, &mut context.lock());
+    // Init scripts are sourced within the same crash reporting as the shell itself.
+    let start = || {
+        source_init_scripts(interactive, &mut context.lock());
+        assert!(!opts.crash_test, "crash test requested");
+        run(&opts, Arc::clone(&context), completer)
+    };
 
     // Not guaranteed to exit.
-    let exit_code = run(&opts, Arc::clone(&context), completer);
+    let exit_code = match interactive || opts.crash_test {
+        true => crash::report_crashes(&context, start),
+        false => start(),
+    };
 
     // If the shell exits cleanly, attempt to stop all threads and processes that it has spawned.
-    let context = context.lock();
-    let host = &mut context.host.lock();
-    host.join_all_threads();
-    host.kill_all_processes();
+    shut_down(&mut context.lock(), run_exit_trap);
 
     exit_code
 }
 
+/// Returns build information about the shell as ordered `(key, value)` pairs.
+fn version_info() -> Vec<(&'static str, String)> {
+    vec![
+        ("version", crate_version!().to_owned()),
+        ("commit", env!("PJSH_GIT_COMMIT").to_owned()),
+        ("profile", env!("PJSH_BUILD_PROFILE").to_owned()),
+        ("target", env!("PJSH_BUILD_TARGET").to_owned()),
+        ("features", env!("PJSH_FEATURES").to_owned()),
+    ]
+}
+
+/// Prints version information to stdout in the format requested by the options.
+fn print_version(opts: &Opts) {
+    if opts.json {
+        let info: serde_json::Map<String, serde_json::Value> = version_info()
+            .into_iter()
+            .map(|(key, value)| match key {
+                "features" => {
+                    let features: Vec<&str> = value.split(',').filter(|f| !f.is_empty()).collect();
+                    (key.to_owned(), serde_json::Value::from(features))
+                }
+                _ => (key.to_owned(), serde_json::Value::from(value)),
+            })
+            .collect();
+        println!("{}", serde_json::Value::Object(info));
+    } else if opts.verbose {
+        for (key, value) in version_info() {
+            println!("{key}: {value}");
+        }
+    } else {
+        println!("pjsh {}", crate_version!());
+    }
+}
+
+/// Prints the names of all built-in commands or filters to stdout in the format
+/// requested by the options.
+///
+/// The names are read from an initialized context, without sourcing any init
+/// scripts, so that they always match what is available within the shell.
+/// Built-in commands are listed with a summary when printed as JSON.
+fn print_names(opts: &Opts) -> std::io::Result<()> {
+    let (context, _) = initialized_context(vec![String::from("pjsh")], None);
+    let mut entries: Vec<(&str, Option<String>)> = if opts.list_builtins {
+        context
+            .builtins
+            .iter()
+            .map(|(name, builtin)| (name.as_str(), builtin.description()))
+            .collect()
+    } else {
+        context
+            .filters
+            .keys()
+            .map(|name| (name.as_str(), None))
+            .collect()
+    };
+    entries.sort_unstable_by_key(|(name, _)| *name);
+
+    let mut stdout = stdout().lock();
+    if opts.json {
+        let entries: Vec<serde_json::Value> = entries
+            .into_iter()
+            .map(|(name, summary)| match opts.list_builtins {
+                true => serde_json::json!({ "name": name, "summary": summary }),
+                false => serde_json::json!({ "name": name }),
+            })
+            .collect();
+        writeln!(stdout, "{}", serde_json::Value::from(entries))?;
+    } else {
+        for (name, _) in entries {
+            writeln!(stdout, "{name}")?;
+        }
+    }
+
+    stdout.flush()
+}
+
 /// Interpolates a string using a [`Context`].
+///
+/// Falls back to the uninterpolated string if it cannot be interpolated. Parse
+/// errors are only reported once for each string, as the same string is
+/// typically interpolated repeatedly (e.g. for each prompt).
 fn interpolate(src: &str, context: Arc<Mutex<Context>>) -> String {
+    /// The most recent string that could not be parsed.
+    static INVALID_SRC: Mutex<Option<String>> = parking_lot::const_mutex(None);
+
     match parse_interpolation(src).map(|word| interpolate_word(&word, &context.lock())) {
         Ok(Ok(string)) => string,
         Ok(Err(eval_error)) => {
@@ -107,7 +287,14 @@ fn interpolate(src: &str, context: Arc<Mutex<Context>>) -> String {
             src.to_string()
         }
         Err(parse_error) => {
-            eprintln!("pjsh: {}", parse_error);
+            let mut invalid_src = INVALID_SRC.lock();
+            if invalid_src.as_deref() != Some(src) {
+                match parse_error.snippet(src, true) {
+                    Some(snippet) => eprintln!("{snippet}"),
+                    None => eprintln!("pjsh: {}", parse_error),
+                }
+                *invalid_src = Some(src.to_string());
+            }
             src.to_string()
         }
     }
@@ -126,9 +313,16 @@ pub(crate) fn run_shell<S: Shell, E: ErrorHandler>(
         return ExitCode::FAILURE;
     }
 
-    if let Err(error) = shell.run(Arc::clone(&context)) {
-        error_handler.display_error(error);
-        return ExitCode::FAILURE;
+    match shell.run(Arc::clone(&context)) {
+        // Shells exiting due to exit, errexit, or a trapped signal use the exit code set when exiting.
+        Ok(()) | Err(ShellError::Terminated(_)) => (),
+        Err(ShellError::EvalError(EvalError::CommandFailed(code) | EvalError::Exit(code))) => {
+            context.lock().register_exit(code);
+        }
+        Err(error) => {
+            error_handler.display_error(error);
+            return ExitCode::FAILURE;
+        }
     }
 
     if let Err(error) = shell.exit() {
@@ -146,15 +340,31 @@ fn run(opts: &Opts, context: Arc<Mutex<Context>>, completer: Arc<Mutex<Completer
     if opts.is_command {
         // The script_file argument is a command rather than a file path.
         let cmd = opts.script_file.to_owned().expect("cmd should be defined");
-        return run_shell(CommandShell::new(cmd), &GuidingErrorHandler, context);
+        let format = opts.output_format.unwrap_or_default();
+        let shell = CommandShell::new(cmd).with_output(format, opts.output_limit);
+        return run_shell(shell, &GuidingErrorHandler, context);
     }
 
     if let Some(script_file) = &opts.script_file {
-        let file = File::open(script_file).expect("script file should be readable");
+        let file = PathBuf::from(script_file);
         return if opts.is_parse_only {
-            run_shell(FileParseShell::new(file), &GuidingErrorHandler, context)
+            let mut shell = FileParseShell::new(file);
+            if opts.is_spanned {
+                shell = shell.with_spans();
+            }
+            if opts.list_functions {
+                shell = shell.with_functions();
+            }
+            run_shell(shell, &GuidingErrorHandler, context)
         } else {
-            run_shell(FileShell::new(file), &GuidingErrorHandler, context)
+            let mut shell = FileShell::new(file);
+            if opts.profile {
+                shell = shell.with_profile(script_file.clone());
+            }
+            if let Some(path) = &opts.profile_output {
+                shell = shell.with_profile_output(path.clone());
+            }
+            run_shell(shell, &GuidingErrorHandler, context)
         };
     }
 
@@ -202,10 +412,21 @@ fn source_init_scripts(interactive: bool, context: &mut Context) {
 /// Sources a file.
 pub(crate) fn source_file(file: PathBuf, context: &mut Context) {
     let mut io = context.io();
-    let Ok(file_contents) = read_to_string(&file) else {
-        let _ = writeln!(io.stderr, "pjsh: file is not readable: {}", path_to_string(&file));
-        return;
+    let file_contents = match read_script(&file, &mut io.stderr) {
+        Ok(file_contents) => file_contents,
+        Err(error) => {
+            let _ = writeln!(io.stderr, "pjsh: {error}");
+            return;
+        }
     };
+
+    // Strict mode enabled by a pragma only applies to the sourced file.
+    let is_strict = has_strict_pragma(&file_contents);
+    let options = context.options;
+    if is_strict {
+        context.options.set(STRICT, true);
+    }
+
     match parse(&file_contents, &context.aliases) {
         Ok(program) => {
             for statement in program.statements {
@@ -221,4 +442,8 @@ pub(crate) fn source_file(file: PathBuf, context: &mut Context) {
             let _ = writeln!(io.stderr, "pjsh: {error}");
         }
     }
+
+    if is_strict {
+        context.options = options;
+    }
 }
@@ -1,8 +1,16 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{Read, Seek, Write},
+    sync::Arc,
+    time::Instant,
+};
 
 use parking_lot::Mutex;
-use pjsh_core::Context;
+use pjsh_core::{Context, FileDescriptor, FD_STDERR, FD_STDOUT};
+use pjsh_eval::EvalError;
 use pjsh_parse::parse;
+use tempfile::tempfile;
 
 use crate::Shell;
 
@@ -11,16 +19,94 @@
     ShellError, ShellResult,
 };
 
+/// Output format for the results of executing a command.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Pass output through to the shell's standard output and standard error.
+    #[default]
+    Text,
+
+    /// Capture all output and print a single JSON object once the command exits.
+    Json,
+}
+
 /// A shell that executes a command from a string.
 pub struct CommandShell {
     /// Command to execute.
     command: String,
+
+    /// Output format for the command's results.
+    output_format: OutputFormat,
+
+    /// Maximum number of bytes to capture from each output stream.
+    output_limit: Option<usize>,
 }
 
 impl CommandShell {
     /// Constructs a new file shell.
     pub fn new(command: String) -> Self {
-        Self { command }
+        Self {
+            command,
+            output_format: OutputFormat::default(),
+            output_limit: None,
+        }
+    }
+
+    /// Sets the output format for the command's results.
+    ///
+    /// Structured formats capture at most `limit` bytes from each output stream
+    /// if a limit is given.
+    pub fn with_output(mut self, format: OutputFormat, limit: Option<usize>) -> Self {
+        self.output_format = format;
+        self.output_limit = limit;
+        self
+    }
+
+    /// Executes the command while capturing its output, and prints the result
+    /// as a single JSON object to stdout.
+    fn run_json(&self, context: &mut Context) -> ShellResult<()> {
+        let stdout = tempfile().map_err(ShellError::IoError)?;
+        let mut stderr = tempfile().map_err(ShellError::IoError)?;
+        for (fd, file) in [(FD_STDOUT, &stdout), (FD_STDERR, &stderr)] {
+            let handle = file.try_clone().map_err(ShellError::IoError)?;
+            context.set_file_descriptor(fd, FileDescriptor::FileHandle(handle));
+        }
+
+        let start = Instant::now();
+        let aliases = &HashMap::new();
+        let result = parse(&self.command, aliases)
+            .map_err(|error| ShellError::ParseError(error, self.command.clone()))
+            .and_then(|program| eval_program(&program, context, exit_on_error));
+
+        // Errors are reported as part of the captured output rather than by the shell.
+        // Commands exiting due to exit or errexit only determine the exit code.
+        let message = match result {
+            Ok(()) => None,
+            Err(ShellError::EvalError(EvalError::CommandFailed(code) | EvalError::Exit(code))) => {
+                context.register_exit(code);
+                None
+            }
+            Err(ShellError::Error(error)) => Some(error),
+            Err(ShellError::ParseError(error, _)) => Some(format!("parse error: {error}")),
+            Err(ShellError::EvalError(error)) => Some(error.to_string()),
+            Err(ShellError::IoError(error)) => Some(error.to_string()),
+            Err(ShellError::Terminated(signal)) => Some(format!("terminated by signal {signal}")),
+        };
+        if let Some(message) = message {
+            let _ = writeln!(stderr, "pjsh: {message}");
+            context.register_exit(1);
+        }
+        let duration = start.elapsed();
+
+        let output = serde_json::json!({
+            "stdout": read_captured(stdout, self.output_limit)?,
+            "stderr": read_captured(stderr, self.output_limit)?,
+            "exit_code": context.last_exit(),
+            "duration_ms": duration.as_millis() as u64,
+        });
+        println!("{output}");
+
+        Ok(())
     }
 }
 
@@ -30,6 +116,10 @@ fn init(&mut self) -> ShellResult<()> {
     }
 
     fn run(&mut self, context: Arc<Mutex<Context>>) -> ShellResult<()> {
+        if self.output_format == OutputFormat::Json {
+            return self.run_json(&mut context.lock());
+        }
+
         // Non-interactive shells should not use aliases.
         let aliases = &HashMap::new();
 
@@ -42,3 +132,19 @@ fn exit(self) -> ShellResult<()> {
         Ok(()) // Intentionally left blank.
     }
 }
+
+/// Reads captured output from the start of a file.
+///
+/// At most `limit` bytes are read if a limit is given. Invalid UTF-8 sequences
+/// are replaced with [`char::REPLACEMENT_CHARACTER`].
+fn read_captured(mut file: File, limit: Option<usize>) -> ShellResult<String> {
+    file.rewind().map_err(ShellError::IoError)?;
+
+    let mut buf = Vec::new();
+    let limit = limit.map_or(u64::MAX, |limit| limit as u64);
+    file.take(limit)
+        .read_to_end(&mut buf)
+        .map_err(ShellError::IoError)?;
+
+    Ok(String::from_utf8_lossy(&buf).into_owned())
+}
@@ -8,7 +8,10 @@
 use crate::{builtins::complete::Complete, source_file};
 use parking_lot::Mutex;
 use pjsh_complete::Completer;
-use pjsh_core::{utils::path_to_string, Context, Filter, Scope, FD_STDERR, FD_STDIN, FD_STDOUT};
+use pjsh_core::{
+    command::BuiltinRegistry, utils::path_to_string, Context, Filter, Scope, FD_STDERR, FD_STDIN,
+    FD_STDOUT,
+};
 
 /// Constructs a new initialized execution context containing some common environment variables such
 /// as `$PS1` and `$PS2`.
@@ -22,9 +25,10 @@ pub fn initialized_context(
         environment_scope(script_file.clone()),
         pjsh_scope(script_file),
         global_scope(args),
-    ]);
-    register_builtins(&mut context, Arc::clone(&completer));
+    ])
+    .with_builtins_from(default_builtins(Arc::clone(&completer)));
     register_filters(&mut context);
+    register_platform_vars(&mut context);
 
     context.set_file_descriptor(FD_STDIN, pjsh_core::FileDescriptor::Stdin);
     context.set_file_descriptor(FD_STDOUT, pjsh_core::FileDescriptor::Stdout);
@@ -35,11 +39,14 @@ pub fn initialized_context(
 
 /// Returns a scope containing all environment variables belonging to the
 /// current process.
+///
+/// The process's environment variables are exported from the scope so that
+/// they are passed on to programs started by the shell.
 fn environment_scope(script_file: Option<PathBuf>) -> Scope {
     let mut vars: HashMap<String, Option<pjsh_core::Value>> = std::env::vars()
-        .into_iter()
         .map(|(key, value)| (key, Some(pjsh_core::Value::Word(value))))
         .collect();
+    let exported_keys: HashSet<String> = vars.keys().cloned().collect();
 
     // Inject the initial (current) script path if known and not already present.
     if !vars.contains_key("PJSH_INITIAL_SCRIPT_PATH") {
@@ -74,13 +81,19 @@ fn environment_scope(script_file: Option<PathBuf>) -> Scope {
         None,
         vars,
         HashMap::default(),
-        HashSet::default(),
+        exported_keys,
     )
 }
 
 /// Returns a scope containing shell-specific default variables.
 fn pjsh_scope(script_file: Option<PathBuf>) -> Scope {
     let mut vars = HashMap::from([
+        (
+            "PJSH_TIME_FORMAT".to_owned(),
+            Some(pjsh_core::Value::Word(
+                pjsh_filters::DEFAULT_TIME_FORMAT.to_owned(),
+            )),
+        ),
         (
             "PS1".to_owned(),
             Some(pjsh_core::Value::Word("\\$ ".to_owned())),
@@ -132,25 +145,25 @@ fn global_scope(args: Vec<String>) -> Scope {
     )
 }
 
-/// Registers built-in commands in a context.
-fn register_builtins(context: &mut Context, completer: Arc<Mutex<Completer>>) {
-    context.register_builtin(Box::new(pjsh_builtins::Alias));
-    context.register_builtin(Box::new(pjsh_builtins::Cd));
-    context.register_builtin(Box::new(Complete::new(completer)));
-    context.register_builtin(Box::new(pjsh_builtins::Echo));
-    context.register_builtin(Box::new(pjsh_builtins::Exit));
-    context.register_builtin(Box::new(pjsh_builtins::Export));
-    context.register_builtin(Box::new(pjsh_builtins::False));
-    context.register_builtin(Box::new(pjsh_builtins::Interpolate));
-    context.register_builtin(Box::new(pjsh_builtins::Pwd));
-    context.register_builtin(Box::new(pjsh_builtins::Sleep));
-    context.register_builtin(Box::new(pjsh_builtins::Source::new(source_file)));
-    context.register_builtin(Box::new(pjsh_builtins::SourceShorthand::new(source_file)));
-    context.register_builtin(Box::new(pjsh_builtins::True));
-    context.register_builtin(Box::new(pjsh_builtins::Type));
-    context.register_builtin(Box::new(pjsh_builtins::Unalias));
-    context.register_builtin(Box::new(pjsh_builtins::Unset));
-    context.register_builtin(Box::new(pjsh_builtins::Which));
+/// Registers read-only variables describing the host's platform, allowing
+/// portable scripts to branch on `$PJSH_OS` and `$PJSH_ARCH`.
+fn register_platform_vars(context: &mut Context) {
+    let platform = context.host.lock().platform();
+    for (name, value) in [("PJSH_OS", platform.os), ("PJSH_ARCH", platform.arch)] {
+        let _ = context.set_var(name.to_owned(), pjsh_core::Value::Word(value.to_owned()));
+        let _ = context.make_var_readonly(name.to_owned());
+    }
+}
+
+/// Returns the shell's default built-in commands.
+///
+/// Embedders may add, remove, or override individual commands before passing
+/// the registry to [`Context::with_builtins_from`].
+pub fn default_builtins(completer: Arc<Mutex<Completer>>) -> BuiltinRegistry {
+    pjsh_builtins::standard_builtins()
+        .with(Box::new(Complete::new(completer)))
+        .with(Box::new(pjsh_builtins::Source::new(source_file)))
+        .with(Box::new(pjsh_builtins::SourceShorthand::new(source_file)))
 }
 
 /// Registers built-in filters in a context.
@@ -160,21 +173,37 @@ fn register_filters(context: &mut Context) {
         assert!(previous.is_none(), "filters should not be registered twice");
     };
 
+    register(context, Box::new(pjsh_filters::DiffFilter));
+    register(context, Box::new(pjsh_filters::EpochFilter));
     register(context, Box::new(pjsh_filters::FirstFilter));
+    register(context, Box::new(pjsh_filters::HeadFilter));
     register(context, Box::new(pjsh_filters::JoinFilter));
     register(context, Box::new(pjsh_filters::LastFilter));
     register(context, Box::new(pjsh_filters::LenFilter));
     register(context, Box::new(pjsh_filters::LinesFilter));
     register(context, Box::new(pjsh_filters::LowercaseFilter));
+    register(context, Box::new(pjsh_filters::MatchFilter));
+    register(context, Box::new(pjsh_filters::NowFilter));
+    register(context, Box::new(pjsh_filters::NowUtcFilter));
     register(context, Box::new(pjsh_filters::NthFilter));
+    register(context, Box::new(pjsh_filters::PathJoinFilter));
+    register(context, Box::new(pjsh_filters::PathListFilter));
     register(context, Box::new(pjsh_filters::ReplaceFilter));
     register(context, Box::new(pjsh_filters::ReverseFilter));
     register(context, Box::new(pjsh_filters::SortFilter));
     register(context, Box::new(pjsh_filters::SplitFilter));
+    register(context, Box::new(pjsh_filters::TailFilter));
+    register(context, Box::new(pjsh_filters::TrimEndFilter));
+    register(context, Box::new(pjsh_filters::TrimFilter));
+    register(context, Box::new(pjsh_filters::TrimStartFilter));
     register(context, Box::new(pjsh_filters::UcfirstFilter));
     register(context, Box::new(pjsh_filters::UniqueFilter));
     register(context, Box::new(pjsh_filters::UppercaseFilter));
     register(context, Box::new(pjsh_filters::WordsFilter));
+
+    // The map filter can only apply filters that have already been registered.
+    let filters = context.filters.clone();
+    register(context, Box::new(pjsh_filters::MapFilter::new(filters)));
 }
 
 #[cfg(test)]
@@ -187,21 +216,51 @@ mod tests {
     fn it_registers_builtins() {
         let expected_builtins = vec![
             ".",
+            "[",
             "alias",
+            "assert-equal",
+            "bg",
+            "bookmark",
             "cd",
             "complete",
+            "dirs",
+            "disown",
             "echo",
+            "env",
+            "eval",
+            "exec",
             "exit",
             "export",
             "false",
+            "fg",
+            "hash",
+            "help",
+            "history",
             "interpolate",
+            "jobs",
+            "kill",
+            "let",
+            "path",
+            "popd",
+            "printf",
+            "pushd",
             "pwd",
+            "read",
+            "readonly",
+            "seq",
+            "set",
+            "shift",
             "sleep",
             "source",
+            "test",
+            "trap",
             "true",
             "type",
+            "ulimit",
+            "umask",
             "unalias",
             "unset",
+            "wait",
             "which",
         ];
 
@@ -216,6 +275,71 @@ fn it_registers_builtins() {
         assert_eq!(builtins, expected_builtins);
     }
 
+    #[test]
+    fn it_describes_builtins() {
+        let (context, _) = initialized_context(Vec::new(), None);
+
+        for (name, builtin) in &context.builtins {
+            assert!(builtin.description().is_some(), "{name}");
+            let usage = builtin.usage().unwrap_or_default();
+            assert!(usage.starts_with(name.as_str()), "{name}: {usage}");
+        }
+    }
+
+    #[test]
+    fn it_composes_builtins() {
+        let (context, completer) = initialized_context(Vec::new(), None);
+        let mut context = context.with_builtins_from(default_builtins(completer).without("cd"));
+        context
+            .set_var("PWD".into(), Value::Word("/tmp".into()))
+            .unwrap();
+
+        let mut execute = |src: &str| {
+            let program = pjsh_parse::parse(src, &HashMap::new()).unwrap();
+            for statement in &program.statements {
+                let _ = pjsh_eval::execute_statement(statement, &mut context);
+            }
+        };
+
+        // Without the built-in, "cd" cannot change the shell's working directory.
+        execute("cd /");
+        execute("if true { result := ok }");
+        assert_eq!(context.get_builtin("cd").map(|cd| cd.name()), None);
+        assert_eq!(context.get_var("PWD"), Some(&Value::Word("/tmp".into())));
+        assert_eq!(context.get_var("result"), Some(&Value::Word("ok".into())));
+    }
+
+    #[test]
+    fn it_imports_exported_environment_variables() {
+        std::env::set_var("PJSH_TEST_IMPORTED_VAR", "imported");
+        let (context, _) = initialized_context(Vec::new(), None);
+
+        assert_eq!(
+            context.get_var("PJSH_TEST_IMPORTED_VAR"),
+            Some(&Value::Word("imported".into()))
+        );
+        assert_eq!(
+            context.exported_vars().get("PJSH_TEST_IMPORTED_VAR"),
+            Some(&"imported")
+        );
+    }
+
+    #[test]
+    fn it_registers_platform_variables() {
+        let (context, _) = initialized_context(Vec::new(), None);
+
+        assert_eq!(
+            context.get_var("PJSH_OS"),
+            Some(&Value::Word(std::env::consts::OS.into()))
+        );
+        assert_eq!(
+            context.get_var("PJSH_ARCH"),
+            Some(&Value::Word(std::env::consts::ARCH.into()))
+        );
+        assert!(context.is_var_readonly("PJSH_OS"));
+        assert!(context.is_var_readonly("PJSH_ARCH"));
+    }
+
     #[test]
     fn it_registers_script_path_variables() {
         let script_file = PathBuf::from("/tmp/test_script.pjsh");
@@ -1,26 +1,57 @@
-use std::{collections::HashMap, fs::File, io::Read, sync::Arc};
+use std::{collections::HashMap, fmt::Write, path::PathBuf, sync::Arc};
 
 use parking_lot::Mutex;
-use pjsh_core::Context;
-use pjsh_parse::parse;
+use pjsh_ast::{
+    visit::{walk_program, walk_statement, Visitor},
+    Program, Statement,
+};
+use pjsh_core::{Context, Profile, STRICT};
+use pjsh_parse::{parse, parse_spanned, Span};
 
 use crate::Shell;
 
 use super::{
-    utils::{eval_program, exit_on_error},
+    profile::{render_report, TsvProfileSink},
+    utils::{eval_program, exit_on_error, has_strict_pragma, read_script},
     ShellError, ShellResult,
 };
 
 /// A shell that executes a script file.
 pub struct FileShell {
-    /// Script file to execute.
-    file: File,
+    /// Path to the script file to execute.
+    path: PathBuf,
+
+    /// Name of the script file to use in a profile report, if the script
+    /// should be profiled.
+    profile_name: Option<String>,
+
+    /// Path to write the duration of each executed command to, if commands
+    /// should be profiled.
+    profile_output: Option<PathBuf>,
 }
 
 impl FileShell {
     /// Constructs a new file shell.
-    pub fn new(file: File) -> Self {
-        Self { file }
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            profile_name: None,
+            profile_output: None,
+        }
+    }
+
+    /// Profiles the script, printing a report to stderr once it has been
+    /// executed. The script is referred to as `name` within the report.
+    pub fn with_profile(mut self, name: String) -> Self {
+        self.profile_name = Some(name);
+        self
+    }
+
+    /// Writes the duration of each executed command to a file at `path` as
+    /// tab-separated values.
+    pub fn with_profile_output(mut self, path: PathBuf) -> Self {
+        self.profile_output = Some(path);
+        self
     }
 }
 
@@ -33,13 +64,27 @@ fn run(&mut self, context: Arc<Mutex<Context>>) -> ShellResult<()> {
         // Non-interactive shells should not use aliases.
         let aliases = &HashMap::new();
 
-        let mut src = String::new();
-        self.file
-            .read_to_string(&mut src)
-            .map_err(ShellError::IoError)?;
+        let src = read_script(&self.path, &mut std::io::stderr()).map_err(ShellError::IoError)?;
 
-        let program = parse(&src, aliases).map_err(|error| ShellError::ParseError(error, src))?;
-        eval_program(&program, &mut context.lock(), exit_on_error)
+        if has_strict_pragma(&src) {
+            context.lock().options.set(STRICT, true);
+        }
+
+        let mut context = context.lock();
+        if let Some(path) = &self.profile_output {
+            let sink = TsvProfileSink::create(path).map_err(ShellError::IoError)?;
+            context.profile_sink = Some(Box::new(sink));
+        }
+
+        let result = match &self.profile_name {
+            Some(name) => profile_program(&src, name, &mut context),
+            None => parse(&src, aliases)
+                .map_err(|error| ShellError::ParseError(error, src))
+                .and_then(|program| eval_program(&program, &mut context, exit_on_error)),
+        };
+
+        context.profile_sink = None; // Flushes the output file.
+        result
     }
 
     fn exit(self) -> ShellResult<()> {
@@ -47,16 +92,58 @@ fn exit(self) -> ShellResult<()> {
     }
 }
 
+/// Evaluates a program while profiling it, printing a report to stderr once
+/// the program has been executed.
+fn profile_program(src: &str, name: &str, context: &mut Context) -> ShellResult<()> {
+    // Non-interactive shells should not use aliases.
+    let aliases = &HashMap::new();
+
+    let (program, spans) =
+        parse_spanned(src, aliases).map_err(|error| ShellError::ParseError(error, src.into()))?;
+
+    context.profile = Some(Profile::default());
+    let result = eval_program(&program, context, exit_on_error);
+
+    if let Some(profile) = context.profile.take() {
+        eprint!("{}", render_report(&profile, &program, &spans, src, name));
+    }
+
+    result
+}
+
 /// A shell that parses a script file.
 pub struct FileParseShell {
-    /// Script file to parse.
-    file: File,
+    /// Path to the script file to parse.
+    path: PathBuf,
+
+    /// Whether to annotate statements with their source spans.
+    spans: bool,
+
+    /// Whether to print the names of defined functions rather than the AST.
+    functions: bool,
 }
 
 impl FileParseShell {
     /// Constructs a new file shell.
-    pub fn new(file: File) -> Self {
-        Self { file }
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            spans: false,
+            functions: false,
+        }
+    }
+
+    /// Annotates each statement with its span within the script file.
+    pub fn with_spans(mut self) -> Self {
+        self.spans = true;
+        self
+    }
+
+    /// Prints the names of the functions that the script file defines, one per
+    /// line, rather than its AST.
+    pub fn with_functions(mut self) -> Self {
+        self.functions = true;
+        self
     }
 }
 
@@ -69,12 +156,23 @@ fn run(&mut self, _context: Arc<Mutex<Context>>) -> ShellResult<()> {
         // Non-interactive shells should not use aliases.
         let aliases = &HashMap::new();
 
-        let mut src = String::new();
-        self.file
-            .read_to_string(&mut src)
-            .map_err(ShellError::IoError)?;
+        let src = read_script(&self.path, &mut std::io::stderr()).map_err(ShellError::IoError)?;
+
+        if self.spans {
+            let (program, spans) = parse_spanned(&src, aliases)
+                .map_err(|error| ShellError::ParseError(error, src.clone()))?;
+            print!("{}", render_spanned_program(&program, &spans, &src));
+            return Ok(());
+        }
 
         let program = parse(&src, aliases).map_err(|error| ShellError::ParseError(error, src))?;
+        if self.functions {
+            let mut names = FunctionNames::default();
+            walk_program(&mut names, &program);
+            names.0.iter().for_each(|name| println!("{name}"));
+            return Ok(());
+        }
+
         println!("{:#?}", program);
 
         Ok(())
@@ -84,3 +182,70 @@ fn exit(self) -> ShellResult<()> {
         Ok(()) // Intentionally left blank.
     }
 }
+
+/// Collects the names of the functions that a program defines, including
+/// nested definitions, in the order that they are defined.
+///
+/// Functions that are defined within subshells are not visible to the rest of
+/// the program, and are therefore skipped.
+#[derive(Default)]
+struct FunctionNames(Vec<String>);
+
+impl Visitor for FunctionNames {
+    fn visit_program(&mut self, _program: &Program) {
+        // The visited program is nested within a subshell.
+    }
+
+    fn visit_statement(&mut self, statement: &Statement) {
+        if let Statement::Function(function) = statement {
+            self.0.push(function.name.clone());
+        }
+
+        walk_statement(self, statement);
+    }
+}
+
+/// Renders the statements of a program, each preceded by its byte offset range
+/// and its line and column within the source.
+fn render_spanned_program(program: &Program, spans: &[Span], src: &str) -> String {
+    let mut output = String::new();
+    for (statement, span) in program.statements.iter().zip(spans) {
+        let span = trim_span(src, span);
+        let line_start = src[..span.start].rfind('\n').map_or(0, |index| index + 1);
+        let line = src[..span.start].matches('\n').count() + 1;
+        let column = src[line_start..span.start].chars().count() + 1;
+
+        let _ = writeln!(output, "{}..{} ({line}:{column})", span.start, span.end);
+        let _ = writeln!(output, "{statement:#?}");
+    }
+    output
+}
+
+/// Shrinks a statement span to exclude surrounding whitespace and separators.
+fn trim_span(src: &str, span: &Span) -> Span {
+    let is_trimmed = |c: char| c.is_whitespace() || c == ';';
+    let end = span.end.min(src.len());
+    let start = span.start.min(end);
+
+    let text = src.get(start..end).unwrap_or_default();
+    let trimmed_start = text.trim_start_matches(is_trimmed);
+    let trimmed = trimmed_start.trim_end_matches(is_trimmed);
+
+    let start = start + (text.len() - trimmed_start.len());
+    Span::new(start, start + trimmed.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_renders_statement_spans() {
+        let src = "echo a\n  echo b;\n";
+        let (program, spans) = parse_spanned(src, &HashMap::new()).unwrap();
+
+        let rendered = render_spanned_program(&program, &spans, src);
+        let headers: Vec<&str> = rendered.lines().filter(|l| l.contains("..")).collect();
+        assert_eq!(headers, vec!["0..6 (1:1)", "9..15 (2:3)"]);
+    }
+}
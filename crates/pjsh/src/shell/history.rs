@@ -0,0 +1,235 @@
+use std::{borrow::Cow, path::Path, sync::Arc};
+
+use parking_lot::Mutex;
+use rustyline::{
+    history::{FileHistory, History, SearchDirection, SearchResult},
+    Config,
+};
+
+/// Command history backed by a history file.
+///
+/// The history is shared between the line editor and the shell's context using
+/// [`SharedHistory`].
+pub(crate) struct ShellHistory {
+    /// History entries.
+    entries: FileHistory,
+
+    /// Whether entries have been removed since the history was loaded.
+    ///
+    /// Removing entries requires the history file to be rewritten, as the file
+    /// can otherwise only be appended to.
+    modified: bool,
+}
+
+impl ShellHistory {
+    /// Constructs a new empty history.
+    pub(crate) fn new(config: Config) -> Self {
+        Self {
+            entries: FileHistory::with_config(config),
+            modified: false,
+        }
+    }
+}
+
+impl pjsh_core::History for ShellHistory {
+    fn entries(&self) -> Vec<String> {
+        self.entries.iter().cloned().collect()
+    }
+
+    fn clear(&mut self) {
+        let _ = self.entries.clear(); // Clearing an in-memory history never fails.
+        self.modified = true;
+    }
+
+    fn remove(&mut self, index: usize) -> bool {
+        if index >= self.entries.len() {
+            return false;
+        }
+
+        // Entries can't be removed individually, so all other entries are re-added.
+        let entries = pjsh_core::History::entries(self);
+        let _ = self.entries.clear();
+        for (i, entry) in entries.into_iter().enumerate() {
+            if i != index {
+                let _ = self.entries.add_owned(entry);
+            }
+        }
+
+        self.modified = true;
+        true
+    }
+}
+
+/// A handle to a [`ShellHistory`] that can be used by the line editor.
+#[derive(Clone)]
+pub(crate) struct SharedHistory(pub(crate) Arc<Mutex<ShellHistory>>);
+
+/// Returns an owned copy of a search result.
+fn owned(result: Option<SearchResult<'_>>) -> Option<SearchResult<'static>> {
+    result.map(|result| SearchResult {
+        entry: Cow::Owned(result.entry.into_owned()),
+        idx: result.idx,
+        pos: result.pos,
+    })
+}
+
+impl History for SharedHistory {
+    fn get(
+        &self,
+        index: usize,
+        dir: SearchDirection,
+    ) -> rustyline::Result<Option<SearchResult<'_>>> {
+        self.0.lock().entries.get(index, dir).map(owned)
+    }
+
+    fn add(&mut self, line: &str) -> rustyline::Result<bool> {
+        self.0.lock().entries.add(line)
+    }
+
+    fn add_owned(&mut self, line: String) -> rustyline::Result<bool> {
+        self.0.lock().entries.add_owned(line)
+    }
+
+    fn len(&self) -> usize {
+        self.0.lock().entries.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.lock().entries.is_empty()
+    }
+
+    fn set_max_len(&mut self, len: usize) -> rustyline::Result<()> {
+        self.0.lock().entries.set_max_len(len)
+    }
+
+    fn ignore_dups(&mut self, yes: bool) -> rustyline::Result<()> {
+        self.0.lock().entries.ignore_dups(yes)
+    }
+
+    fn ignore_space(&mut self, yes: bool) {
+        self.0.lock().entries.ignore_space(yes);
+    }
+
+    fn save(&mut self, path: &Path) -> rustyline::Result<()> {
+        self.0.lock().entries.save(path)
+    }
+
+    fn append(&mut self, path: &Path) -> rustyline::Result<()> {
+        let mut history = self.0.lock();
+        if !history.modified {
+            return history.entries.append(path);
+        }
+
+        // The file may contain removed entries, and must therefore be replaced
+        // by the current entries.
+        match std::fs::remove_file(path) {
+            Err(error) if error.kind() != std::io::ErrorKind::NotFound => return Err(error.into()),
+            _ => (),
+        }
+        history.modified = false;
+        history.entries.append(path)
+    }
+
+    fn load(&mut self, path: &Path) -> rustyline::Result<()> {
+        self.0.lock().entries.load(path)
+    }
+
+    fn clear(&mut self) -> rustyline::Result<()> {
+        pjsh_core::History::clear(&mut *self.0.lock());
+        Ok(())
+    }
+
+    fn search(
+        &self,
+        term: &str,
+        start: usize,
+        dir: SearchDirection,
+    ) -> rustyline::Result<Option<SearchResult<'_>>> {
+        self.0.lock().entries.search(term, start, dir).map(owned)
+    }
+
+    fn starts_with(
+        &self,
+        term: &str,
+        start: usize,
+        dir: SearchDirection,
+    ) -> rustyline::Result<Option<SearchResult<'_>>> {
+        self.0
+            .lock()
+            .entries
+            .starts_with(term, start, dir)
+            .map(owned)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pjsh_core::History as _;
+    use tempfile::TempDir;
+
+    use super::*;
+
+    /// Returns a shared history containing some entries.
+    fn history_with_entries(entries: &[&str]) -> SharedHistory {
+        let mut history = SharedHistory(Arc::new(Mutex::new(ShellHistory::new(Config::default()))));
+        for entry in entries {
+            history.add(entry).unwrap();
+        }
+        history
+    }
+
+    /// Loads the entries in a history file.
+    fn load_entries(path: &Path) -> Vec<String> {
+        let mut history = history_with_entries(&[]);
+        history.load(path).unwrap();
+        let entries = history.0.lock().entries();
+        entries
+    }
+
+    #[test]
+    fn it_shares_entries_with_the_editor() {
+        let history = history_with_entries(&["first", "second", "third"]);
+
+        assert!(history.0.lock().remove(1));
+        assert_eq!(history.len(), 2);
+        assert_eq!(
+            history
+                .get(1, SearchDirection::Forward)
+                .unwrap()
+                .unwrap()
+                .entry,
+            "third"
+        );
+    }
+
+    #[test]
+    fn it_rewrites_modified_history_files() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("history.txt");
+        history_with_entries(&["old1", "old2"])
+            .append(&path)
+            .unwrap();
+
+        let mut history = history_with_entries(&[]);
+        history.load(&path).unwrap();
+        history.add("new").unwrap();
+        history.0.lock().remove(0);
+        history.append(&path).unwrap();
+
+        assert_eq!(load_entries(&path), vec!["old2", "new"]);
+    }
+
+    #[test]
+    fn it_appends_to_unmodified_history_files() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("history.txt");
+        history_with_entries(&["old"]).append(&path).unwrap();
+
+        let mut history = history_with_entries(&[]);
+        history.load(&path).unwrap();
+        history.add("new").unwrap();
+        history.append(&path).unwrap();
+
+        assert_eq!(load_entries(&path), vec!["old", "new"]);
+    }
+}
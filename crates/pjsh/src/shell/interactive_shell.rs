@@ -1,4 +1,4 @@
-use std::{borrow::Cow, path::PathBuf, sync::Arc};
+use std::{borrow::Cow, io::Write, path::PathBuf, sync::Arc};
 
 use parking_lot::Mutex;
 use pjsh_complete::Completer;
@@ -9,15 +9,16 @@
     error::ReadlineError,
     highlight::{Highlighter, MatchingBracketHighlighter},
     hint::{Hinter, HistoryHinter},
-    history::FileHistory,
     validate::{self, ValidationResult, Validator},
     CompletionType, Config, Editor,
 };
 use rustyline_derive::Helper;
 
-use crate::{interpolate, interrupt, Shell, USER_HISTORY_FILE_NAME};
+use crate::{crash, interpolate, interrupt, Shell, USER_HISTORY_FILE_NAME};
 
 use super::{
+    history::{SharedHistory, ShellHistory},
+    prompt::Prompt,
     utils::{eval_program, print_error},
     ShellError, ShellResult,
 };
@@ -41,7 +42,7 @@ pub(crate) enum ShellInput {
 /// Reads input from stdin.
 pub struct InteractiveShell {
     /// Rustyline editor.
-    editor: Editor<ShellHelper, FileHistory>,
+    editor: Editor<ShellHelper, SharedHistory>,
 }
 
 impl InteractiveShell {
@@ -56,6 +57,10 @@ pub fn new(context: Arc<Mutex<Context>>, completer: Arc<Mutex<Completer>>) -> Se
             }
         };
 
+        let config = Config::builder().completion_type(completion_type).build();
+        let history = Arc::new(Mutex::new(ShellHistory::new(config)));
+        context.lock().history = history.clone();
+
         let helper = ShellHelper {
             context,
             highlighter: MatchingBracketHighlighter::new(),
@@ -64,8 +69,8 @@ pub fn new(context: Arc<Mutex<Context>>, completer: Arc<Mutex<Completer>>) -> Se
             colored_prompt: "$ ".to_owned(),
         };
 
-        let config = Config::builder().completion_type(completion_type).build();
-        let mut editor = Editor::with_config(config).expect("terminal editor should be configured");
+        let mut editor = Editor::with_history(config, SharedHistory(history))
+            .expect("terminal editor should be configured");
         editor.set_helper(Some(helper));
 
         Self { editor }
@@ -73,12 +78,23 @@ pub fn new(context: Arc<Mutex<Context>>, completer: Arc<Mutex<Completer>>) -> Se
 
     /// Returns a prompted line of input.
     fn prompt_line(&mut self, prompt: &str) -> ShellInput {
+        // Only the final line of the prompt is redrawn by the editor, e.g. when
+        // the terminal is resized. Any preceding lines are printed beforehand.
+        let prompt = Prompt::new(prompt);
+        if !prompt.header.is_empty() {
+            let mut stdout = std::io::stdout();
+            let _ = write!(stdout, "{}", prompt.header).and_then(|_| stdout.flush());
+        }
+
         // Set a colored prompt from the input.
-        // This prompt allows ANSI control sequences to be passed to the terminal.
-        self.editor.helper_mut().expect("No helper").colored_prompt = prompt.to_string();
+        // This prompt allows ANSI control sequences to be passed to the terminal,
+        // while the editor computes the prompt's width from the plain prompt.
+        let plain_prompt = prompt.plain_line();
+        self.editor.helper_mut().expect("No helper").colored_prompt = prompt.line;
 
-        match self.editor.readline(prompt) {
+        match self.editor.readline(&plain_prompt) {
             Ok(mut line) => {
+                crash::record_input(&line);
                 line.push('\n');
                 ShellInput::Line(line)
             }
@@ -8,11 +8,14 @@
 mod command_shell;
 pub(crate) mod context;
 mod file_shell;
+mod history;
 mod interactive_shell;
+mod profile;
+mod prompt;
 mod stdin_shell;
 pub(crate) mod utils;
 
-pub(crate) use command_shell::CommandShell;
+pub(crate) use command_shell::{CommandShell, OutputFormat};
 pub(crate) use file_shell::{FileParseShell, FileShell};
 pub(crate) use interactive_shell::InteractiveShell;
 pub(crate) use stdin_shell::StdinShell;
@@ -30,6 +33,11 @@ pub enum ShellError {
 
     /// A generic I/O-related error.
     IoError(io::Error),
+
+    /// The shell was terminated by a trapped signal once its handler had run.
+    ///
+    /// Contains the signal number.
+    Terminated(i32),
 }
 
 /// Result type for shell operations.
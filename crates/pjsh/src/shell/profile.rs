@@ -0,0 +1,138 @@
+use std::{
+    fmt::Write,
+    fs::File,
+    io::{self, BufWriter, Write as _},
+    path::Path,
+    time::Duration,
+};
+
+use pjsh_ast::Program;
+use pjsh_core::{Profile, ProfileSink, Timing};
+use pjsh_parse::Span;
+
+/// Maximum number of characters to include from a statement's source.
+const EXCERPT_LENGTH: usize = 60;
+
+/// A single line in a profile report.
+struct ReportEntry {
+    /// Time spent executing.
+    timing: Timing,
+
+    /// Location of the profiled code.
+    location: String,
+
+    /// Excerpt of the profiled code.
+    excerpt: String,
+}
+
+/// A profile sink writing the duration of each executed command to a file as
+/// tab-separated values.
+pub(crate) struct TsvProfileSink {
+    /// Buffered writer for the output file.
+    writer: BufWriter<File>,
+}
+
+impl TsvProfileSink {
+    /// Creates an output file at `path` containing a header row.
+    pub(crate) fn create(path: &Path) -> io::Result<Self> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writeln!(writer, "command\tmicroseconds")?;
+        Ok(Self { writer })
+    }
+}
+
+impl ProfileSink for TsvProfileSink {
+    fn record_command(&mut self, name: &str, duration: Duration) {
+        // Profiling should never interrupt the script.
+        let _ = writeln!(self.writer, "{name}\t{}", duration.as_micros());
+    }
+}
+
+/// Renders a report of the time spent executing a program.
+///
+/// Entries are sorted by their total duration, starting with the slowest.
+/// Statements are located within the source file `name` containing `src`.
+pub(crate) fn render_report(
+    profile: &Profile,
+    program: &Program,
+    spans: &[Span],
+    src: &str,
+    name: &str,
+) -> String {
+    let mut entries: Vec<ReportEntry> = program
+        .statements
+        .iter()
+        .zip(spans)
+        .filter_map(|(statement, span)| {
+            let timing = profile.statement(statement)?;
+            let (line, excerpt) = locate(src, span);
+            Some(ReportEntry {
+                timing,
+                location: format!("{name}:{line}"),
+                excerpt,
+            })
+        })
+        .collect();
+
+    entries.extend(profile.functions().map(|(function, timing)| ReportEntry {
+        timing,
+        location: format!("fn {function}"),
+        excerpt: String::new(),
+    }));
+
+    entries.sort_by(|a, b| {
+        b.timing
+            .duration
+            .cmp(&a.timing.duration)
+            .then_with(|| a.location.cmp(&b.location))
+    });
+
+    let mut report = format!("{:>12}  {:>5}  location\n", "duration", "count");
+    for entry in entries {
+        let duration = format!("{:.3?}", entry.timing.duration);
+        let line = format!(
+            "{duration:>12}  {:>5}  {}  {}",
+            entry.timing.count, entry.location, entry.excerpt
+        );
+        let _ = writeln!(report, "{}", line.trim_end());
+    }
+
+    report
+}
+
+/// Returns the line number of a span within some source, along with an
+/// excerpt of the span's first line.
+fn locate(src: &str, span: &Span) -> (usize, String) {
+    let text = src.get(span.start..span.end).unwrap_or_default();
+    let start = span.start + (text.len() - text.trim_start().len());
+    let line = src[..start].matches('\n').count() + 1;
+
+    let first_line = text.trim_start().lines().next().unwrap_or_default();
+    let first_line = first_line.trim_end_matches(|c: char| c.is_whitespace() || c == ';');
+    let mut excerpt: String = first_line.chars().take(EXCERPT_LENGTH).collect();
+    if first_line.chars().count() > EXCERPT_LENGTH {
+        excerpt.push_str("...");
+    }
+
+    (line, excerpt)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_locates_statements() {
+        let src = "first\n\n  second arg;\nthird";
+        assert_eq!(locate(src, &Span::new(0, 5)), (1, "first".into()));
+        assert_eq!(locate(src, &Span::new(5, 19)), (3, "second arg".into()));
+        assert_eq!(locate(src, &Span::new(20, 26)), (4, "third".into()));
+    }
+
+    #[test]
+    fn it_truncates_long_excerpts() {
+        let src = "x".repeat(EXCERPT_LENGTH + 1);
+        let (_, excerpt) = locate(&src, &Span::new(0, src.len()));
+        assert_eq!(excerpt, format!("{}...", "x".repeat(EXCERPT_LENGTH)));
+    }
+}
@@ -0,0 +1,139 @@
+/// An interpolated prompt, split into lines that are printed before reading
+/// input and the final line on which input is edited.
+///
+/// Line editors only redraw the line being edited, so earlier prompt lines must
+/// not be passed to the editor. Doing so duplicates prompt fragments and
+/// misplaces the cursor when the editor redraws its line, e.g. after the
+/// terminal has been resized.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct Prompt {
+    /// Lines preceding the editing line, including their line endings.
+    ///
+    /// May contain ANSI control sequences.
+    pub header: String,
+
+    /// Final prompt line, on which input is edited.
+    ///
+    /// May contain ANSI control sequences.
+    pub line: String,
+}
+
+impl Prompt {
+    /// Splits a prompt into its header and its final line.
+    pub(crate) fn new(prompt: &str) -> Self {
+        match prompt.rsplit_once('\n') {
+            Some((header, line)) => Self {
+                header: format!("{header}\n"),
+                line: line.to_owned(),
+            },
+            None => Self {
+                header: String::new(),
+                line: prompt.to_owned(),
+            },
+        }
+    }
+
+    /// Returns the final prompt line without ANSI control sequences.
+    ///
+    /// Line editors compute the width of the prompt from this line, so that the
+    /// cursor is placed correctly regardless of any colors in the prompt.
+    pub(crate) fn plain_line(&self) -> String {
+        strip_ansi(&self.line)
+    }
+}
+
+/// Removes ANSI control sequences, such as colors, from a text.
+///
+/// Both CSI sequences (`ESC [ ... final`) and OSC sequences (`ESC ] ... BEL`
+/// or `ESC ] ... ESC \`) are removed. Readline-style non-printing markers
+/// (`\x01` and `\x02`) are removed as well.
+fn strip_ansi(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\x1b' => match chars.next() {
+                // CSI: parameters and intermediates until a final byte in @..~.
+                Some('[') => {
+                    for ch in chars.by_ref() {
+                        if ('@'..='~').contains(&ch) {
+                            break;
+                        }
+                    }
+                }
+                // OSC: until BEL or ESC \.
+                Some(']') => {
+                    while let Some(ch) = chars.next() {
+                        if ch == '\x07' {
+                            break;
+                        }
+                        if ch == '\x1b' && chars.next_if_eq(&'\\').is_some() {
+                            break;
+                        }
+                    }
+                }
+                // Character set designations, such as `ESC ( B`.
+                Some('(' | ')') => {
+                    chars.next();
+                }
+                // Other two-character escape sequences.
+                _ => (),
+            },
+            '\x01' | '\x02' => (),
+            ch => output.push(ch),
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_splits_single_line_prompts() {
+        assert_eq!(
+            Prompt::new("\x1b[32m$\x1b[0m "),
+            Prompt {
+                header: String::new(),
+                line: "\x1b[32m$\x1b[0m ".into(),
+            }
+        );
+    }
+
+    #[test]
+    fn it_splits_multi_line_prompts() {
+        let prompt = Prompt::new("\x1b[1;34m~/src\x1b[0m (\x1b[33mmain\x1b[0m)\n\x1b[32m❯\x1b[0m ");
+        assert_eq!(
+            prompt,
+            Prompt {
+                header: "\x1b[1;34m~/src\x1b[0m (\x1b[33mmain\x1b[0m)\n".into(),
+                line: "\x1b[32m❯\x1b[0m ".into(),
+            }
+        );
+        assert_eq!(prompt.plain_line(), "❯ ");
+
+        let prompt = Prompt::new("first\nsecond\n\x1b[1m$ \x1b[m");
+        assert_eq!(prompt.header, "first\nsecond\n");
+        assert_eq!(prompt.plain_line(), "$ ");
+
+        let prompt = Prompt::new("trailing newline\n");
+        assert_eq!(prompt.header, "trailing newline\n");
+        assert_eq!(prompt.line, "");
+    }
+
+    #[test]
+    fn it_strips_ansi_control_sequences() {
+        assert_eq!(strip_ansi("\x1b[38;5;208morange\x1b[0m"), "orange");
+        assert_eq!(strip_ansi("\x1b]0;title\x07$ "), "$ ");
+        assert_eq!(
+            strip_ansi("\x1b]8;;https://example.com\x1b\\link\x1b]8;;\x1b\\"),
+            "link"
+        );
+        assert_eq!(strip_ansi("\x01\x1b[1m\x02bold\x01\x1b[0m\x02"), "bold");
+        assert_eq!(strip_ansi("\x1b(Bplain"), "plain");
+        assert_eq!(strip_ansi("unterminated \x1b[1"), "unterminated ");
+    }
+}
@@ -1,9 +1,88 @@
+use std::{
+    fs::File,
+    io::{self, Read, Write},
+    path::Path,
+};
+
 use pjsh_ast::Program;
-use pjsh_core::Context;
+use pjsh_core::{
+    signal::{is_terminating, take_pending_signals, EXIT},
+    utils::path_to_string,
+    Context,
+};
 use pjsh_eval::{execute_statement, EvalError};
+use pjsh_parse::parse;
 
 use super::{ShellError, ShellResult};
 
+/// Comment enabling strict mode for a script when placed on its first line.
+const STRICT_PRAGMA: &str = "#pragma strict";
+
+/// Byte order mark that may precede the contents of UTF-8 encoded files.
+const UTF8_BOM: char = '\u{feff}';
+
+/// Reads the source of a script file.
+///
+/// The file is read in full before it is parsed, so that a script that is
+/// rewritten while being executed cannot be partially read. A leading byte
+/// order mark is removed, and a warning is written to `stderr` if the script's
+/// line endings are unlikely to be parsed as intended.
+///
+/// # Errors
+///
+/// Returns an error, naming the file, if the file cannot be read in full or is
+/// not valid UTF-8.
+pub(crate) fn read_script(path: &Path, stderr: &mut dyn Write) -> io::Result<String> {
+    let name = path_to_string(path);
+    let mut file = File::open(path)
+        .map_err(|error| io::Error::new(error.kind(), format!("{name}: {error}")))?;
+
+    let size = file.metadata().map_or(0, |metadata| metadata.len());
+    let mut bytes = Vec::new();
+    if let Err(error) = file.read_to_end(&mut bytes) {
+        let read = bytes.len();
+        let message = format!("{name}: read failed after {read} of {size} bytes: {error}");
+        return Err(io::Error::new(error.kind(), message));
+    }
+
+    let mut src = String::from_utf8(bytes)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, format!("{name}: {error}")))?;
+    strip_bom(&mut src);
+
+    if let Some(warning) = line_ending_warning(&src) {
+        let _ = writeln!(stderr, "pjsh: {name}: warning: {warning}");
+    }
+
+    Ok(src)
+}
+
+/// Returns a warning if a script contains carriage return (`\r`) line endings
+/// without a line feed, or a mix of CRLF and LF line endings.
+///
+/// Such line endings are treated inconsistently within quoted words and comments.
+fn line_ending_warning(src: &str) -> Option<&'static str> {
+    let crlf = src.matches("\r\n").count();
+    let cr = src.matches('\r').count() - crlf;
+    let lf = src.matches('\n').count() - crlf;
+
+    if cr > 0 {
+        return Some("the script contains CR-only line endings; convert them to LF or CRLF");
+    }
+
+    if crlf > 0 && lf > 0 {
+        return Some("the script contains mixed line endings; convert them to either LF or CRLF");
+    }
+
+    None
+}
+
+/// Removes a leading UTF-8 byte order mark from the source of a script.
+fn strip_bom(src: &mut String) {
+    if src.starts_with(UTF8_BOM) {
+        src.drain(..UTF8_BOM.len_utf8());
+    }
+}
+
 /// Evaluates a program.
 ///
 /// # Errors
@@ -24,13 +103,70 @@ pub(crate) fn eval_program<ErrorHandler>(
         if let Err(err) = execute_statement(statement, context) {
             error_handler(err)?;
         }
+
+        run_pending_traps(context)?;
+    }
+
+    Ok(())
+}
+
+/// Runs the handlers of trapped signals that have been received since the last
+/// check.
+///
+/// Handlers are run between statements rather than from within the signal
+/// handler itself, where the state of the shell may be inconsistent.
+///
+/// # Errors
+///
+/// Returns [`ShellError::Terminated`] if a received signal should terminate
+/// the shell. The exit code is set to `128 + signal` in this case.
+pub(crate) fn run_pending_traps(context: &mut Context) -> ShellResult<()> {
+    for signal in take_pending_signals() {
+        if let Some(handler) = context.traps.get(&signal).cloned() {
+            run_trap(&handler, context);
+        }
+
+        if is_terminating(signal) {
+            context.register_exit(128 + signal);
+            return Err(ShellError::Terminated(signal));
+        }
     }
 
     Ok(())
 }
 
+/// Runs the handler for the `EXIT` pseudo-signal, if one is installed.
+///
+/// The handler is removed, so that it is run at most once.
+pub(crate) fn run_exit_trap(context: &mut Context) {
+    if let Some(handler) = context.traps.remove(&EXIT) {
+        run_trap(&handler, context);
+    }
+}
+
+/// Runs a trap handler in a context.
+///
+/// Errors are printed rather than returned, and the last exit code is kept.
+fn run_trap(handler: &str, context: &mut Context) {
+    let last_exit = context.last_exit();
+    let aliases = context.aliases.clone();
+    match parse(handler, &aliases) {
+        Ok(program) => {
+            let _ = eval_program(&program, context, print_error);
+        }
+        Err(error) => eprintln!("pjsh: trap: {error}"),
+    }
+    context.register_exit(last_exit);
+}
+
 /// Prints an evaluation error.
+///
+/// Requests to exit the shell are returned rather than printed.
 pub(crate) fn print_error(error: EvalError) -> ShellResult<()> {
+    if let EvalError::Exit(_) = error {
+        return Err(ShellError::EvalError(error));
+    }
+
     eprintln!("pjsh: {error}");
     Ok(())
 }
@@ -39,3 +175,176 @@ pub(crate) fn print_error(error: EvalError) -> ShellResult<()> {
 pub(crate) fn exit_on_error(error: EvalError) -> ShellResult<()> {
     Err(ShellError::EvalError(error))
 }
+
+/// Returns `true` if a script starts with a `#pragma strict` comment, which
+/// enables strict mode for the script.
+///
+/// The pragma may follow a shebang line.
+pub(crate) fn has_strict_pragma(src: &str) -> bool {
+    let mut lines = src.lines();
+    let first_line = match lines.next() {
+        Some(line) if line.starts_with("#!") => lines.next(),
+        line => line,
+    };
+    first_line.is_some_and(|line| line.trim() == STRICT_PRAGMA)
+}
+
+/// Shuts down a shell that exits cleanly.
+///
+/// Shutdown happens in the following order:
+///
+/// 1. Exit handlers, such as `EXIT` traps, are run. Background jobs are still
+///    running at this point, so that exit handlers can interact with them.
+/// 2. All threads spawned by the shell are joined.
+/// 3. All remaining child processes are killed.
+pub(crate) fn shut_down(context: &mut Context, run_exit_handlers: impl FnOnce(&mut Context)) {
+    run_exit_handlers(context);
+
+    let mut host = context.host.lock();
+    host.join_all_threads();
+    host.kill_all_processes();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashSet,
+        process::{Child, Command},
+        sync::Arc,
+        thread::JoinHandle,
+    };
+
+    use parking_lot::Mutex;
+    use pjsh_core::{Host, Job, JobState};
+
+    use super::*;
+
+    /// A host that records the order in which it is shut down.
+    ///
+    /// The host does not manage any jobs, processes, or threads.
+    struct RecordingHost(Arc<Mutex<Vec<&'static str>>>);
+
+    impl Host for RecordingHost {
+        fn add_child_process(&mut self, _child: Child, _command: Option<String>) -> usize {
+            0
+        }
+
+        fn add_thread(&mut self, _thread: JoinHandle<i32>) -> usize {
+            0
+        }
+
+        fn kill_all_processes(&mut self) {
+            self.0.lock().push("kill");
+        }
+
+        fn join_all_threads(&mut self) {
+            self.0.lock().push("join");
+        }
+
+        fn take_exited_child_processes(&mut self) -> HashSet<u32> {
+            HashSet::new()
+        }
+
+        fn take_child_process(&mut self, _id: Option<usize>) -> Option<(usize, Child)> {
+            None
+        }
+
+        fn resume_child_process(&mut self, _id: Option<usize>) -> io::Result<usize> {
+            Err(io::ErrorKind::NotFound.into())
+        }
+
+        fn kill_child_process(&mut self, _id: usize, _signal: i32) -> io::Result<()> {
+            Err(io::ErrorKind::NotFound.into())
+        }
+
+        fn kill_process(&mut self, _pid: u32, _signal: i32) -> io::Result<()> {
+            Err(io::ErrorKind::Unsupported.into())
+        }
+
+        fn job_id(&self, _pid: u32) -> Option<usize> {
+            None
+        }
+
+        fn try_wait_job(&mut self, _id: usize) -> io::Result<JobState> {
+            Err(io::ErrorKind::NotFound.into())
+        }
+
+        fn jobs(&mut self) -> Vec<Job> {
+            Vec::new()
+        }
+
+        fn exec(&mut self, _command: Command) -> io::Error {
+            io::ErrorKind::Unsupported.into()
+        }
+    }
+
+    #[test]
+    fn it_detects_strict_pragmas() {
+        assert!(has_strict_pragma("#pragma strict\necho a"));
+        assert!(has_strict_pragma("#!/bin/pjsh\r\n#pragma strict\r\n"));
+        assert!(!has_strict_pragma("echo a\n#pragma strict\n"));
+        assert!(!has_strict_pragma("# pragma strict"));
+        assert!(!has_strict_pragma(""));
+    }
+
+    #[test]
+    fn it_runs_exit_handlers_before_stopping_jobs() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let mut context = Context::default();
+        context.host = Arc::new(Mutex::new(RecordingHost(Arc::clone(&events))));
+
+        shut_down(&mut context, |_| events.lock().push("trap"));
+
+        assert_eq!(*events.lock(), vec!["trap", "join", "kill"]);
+    }
+
+    #[test]
+    fn it_runs_exit_traps_once() {
+        let mut context = Context::default();
+        context.traps.insert(EXIT, "false".into());
+        context.register_exit(3);
+
+        run_exit_trap(&mut context);
+
+        assert!(context.traps.is_empty());
+        assert_eq!(context.last_exit(), 3);
+    }
+
+    #[test]
+    fn it_accepts_consistent_line_endings() {
+        assert_eq!(line_ending_warning("a\nb\n"), None);
+        assert_eq!(line_ending_warning("a\r\nb\r\n"), None);
+        assert_eq!(line_ending_warning("a"), None);
+    }
+
+    #[test]
+    fn it_warns_about_cr_only_line_endings() {
+        assert!(line_ending_warning("a\rb\r").is_some_and(|warning| warning.contains("CR-only")));
+        assert!(line_ending_warning("a\r\nb\r").is_some_and(|warning| warning.contains("CR-only")));
+    }
+
+    #[test]
+    fn it_warns_about_mixed_line_endings() {
+        assert!(line_ending_warning("a\r\nb\n").is_some_and(|warning| warning.contains("mixed")));
+    }
+
+    #[test]
+    fn it_reads_scripts_without_a_bom() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("script.pjsh");
+        std::fs::write(&path, "\u{feff}echo a\r\n").unwrap();
+
+        let mut stderr = Vec::new();
+        assert_eq!(read_script(&path, &mut stderr).unwrap(), "echo a\r\n");
+        assert!(stderr.is_empty());
+    }
+
+    #[test]
+    fn it_names_unreadable_scripts_in_errors() {
+        let error = read_script(Path::new("/path/to/missing.pjsh"), &mut Vec::new()).unwrap_err();
+        assert!(
+            error.to_string().starts_with("/path/to/missing.pjsh: "),
+            "{error}"
+        );
+    }
+}
@@ -0,0 +1,43 @@
+#![cfg(unix)]
+
+use std::{
+    io::{BufRead, BufReader, Write},
+    process::{Command, Stdio},
+};
+
+#[test]
+fn it_exits_quietly_when_a_program_writes_to_a_closed_pipe() {
+    let output = Command::new(env!("CARGO_BIN_EXE_pjsh"))
+        .args(["-c", "set -o pipefail; yes | head -n 1; echo $?"])
+        .output()
+        .expect("pjsh should be executable");
+
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "y\n141\n");
+    assert_eq!(String::from_utf8_lossy(&output.stderr), "");
+}
+
+#[test]
+fn it_exits_quietly_when_a_builtin_writes_to_a_closed_pipe() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_pjsh"))
+        .args(["-c", "echo first; read line; echo second"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("pjsh should be executable");
+
+    // Close the reading end of stdout after the first line, similar to `head -n 1`.
+    let mut stdout = BufReader::new(child.stdout.take().unwrap());
+    let mut first = String::new();
+    stdout.read_line(&mut first).unwrap();
+    drop(stdout);
+    assert_eq!(first, "first\n");
+
+    let mut stdin = child.stdin.take().unwrap();
+    writeln!(stdin, "continue").unwrap();
+    drop(stdin);
+
+    let output = child.wait_with_output().unwrap();
+    assert_eq!(output.status.code(), Some(141));
+    assert_eq!(String::from_utf8_lossy(&output.stderr), "");
+}
@@ -0,0 +1,71 @@
+use std::process::Command;
+
+use serde_json::Value;
+
+/// Executes a command using `pjsh -c --output json` and parses the printed
+/// JSON object.
+fn json_output(command: &str, extra_args: &[&str]) -> Value {
+    let output = Command::new(env!("CARGO_BIN_EXE_pjsh"))
+        .args(["--output", "json"])
+        .args(extra_args)
+        .args(["-c", command])
+        .output()
+        .expect("pjsh should be executable");
+
+    serde_json::from_slice(&output.stdout).expect("output should be valid JSON")
+}
+
+#[test]
+fn it_captures_stdout_and_exit_code() {
+    let output = json_output("echo hello", &[]);
+
+    assert_eq!(output["stdout"], "hello\n");
+    assert_eq!(output["stderr"], "");
+    assert_eq!(output["exit_code"], 0);
+    assert!(output["duration_ms"].is_u64());
+}
+
+#[test]
+fn it_captures_stderr_from_builtins() {
+    let output = json_output("cd /path/to/missing/dir", &[]);
+
+    assert_eq!(output["stdout"], "");
+    assert_eq!(output["stderr"], "cd: Path is not a directory.\n");
+    assert_eq!(output["exit_code"], 1);
+}
+
+#[test]
+fn it_captures_evaluation_errors() {
+    let output = json_output("pjsh-missing-command", &[]);
+
+    assert_eq!(output["stdout"], "");
+    assert_eq!(output["exit_code"], 1);
+    assert!(output["stderr"]
+        .as_str()
+        .is_some_and(|stderr| stderr.starts_with("pjsh: ")));
+}
+
+#[test]
+fn it_captures_exit_codes_from_exit() {
+    let output = json_output("echo hi; exit 3", &[]);
+
+    assert_eq!(output["stdout"], "hi\n");
+    assert_eq!(output["stderr"], "");
+    assert_eq!(output["exit_code"], 3);
+}
+
+#[test]
+fn it_captures_exit_codes_from_failed_commands_with_errexit() {
+    let output = json_output("set -e; echo hi; false; echo unreachable", &[]);
+
+    assert_eq!(output["stdout"], "hi\n");
+    assert_eq!(output["stderr"], "");
+    assert_eq!(output["exit_code"], 1);
+}
+
+#[test]
+fn it_limits_captured_output() {
+    let output = json_output("echo 0123456789", &["--output-limit", "4"]);
+
+    assert_eq!(output["stdout"], "0123");
+}
@@ -0,0 +1,59 @@
+use std::process::Command;
+
+/// Executes a script using `pjsh -c` and returns its stdout.
+fn stdout(script: &str) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_pjsh"))
+        .args(["-c", script])
+        .output()
+        .expect("pjsh should be executable");
+
+    assert!(output.status.success(), "{output:?}");
+    String::from_utf8(output.stdout).expect("output should be valid UTF-8")
+}
+
+#[test]
+fn it_rewrites_commands_with_a_prefix() {
+    let script = r#"
+        fn wrap(args...) {
+          echo "wrapper:"
+          for arg in $args { echo $arg }
+        }
+        fn rewrite(cmd args...) {
+          if [[ $cmd == greet ]] {
+            echo wrap
+            echo $cmd
+            for arg in $args { echo $arg }
+          } else {
+            false
+          }
+        }
+        PJSH_COMMAND_REWRITE := rewrite
+        greet 'hello world'
+        echo unchanged
+    "#;
+
+    assert_eq!(stdout(script), "wrapper:\ngreet\nhello world\nunchanged\n");
+}
+
+#[test]
+fn it_rewrites_each_command_once() {
+    let script = r#"
+        fn rewrite(cmd args...) {
+          echo echo
+          echo $cmd
+          for arg in $args { echo $arg }
+        }
+        PJSH_COMMAND_REWRITE := rewrite
+        echo hello
+    "#;
+
+    assert_eq!(stdout(script), "echo hello\n");
+}
+
+#[test]
+fn it_ignores_undefined_rewrite_functions() {
+    assert_eq!(
+        stdout("PJSH_COMMAND_REWRITE := missing; echo hello"),
+        "hello\n"
+    );
+}
@@ -0,0 +1,70 @@
+use std::process::Command;
+
+/// Executes a script using `pjsh -c` and returns its stdout.
+fn stdout(script: &str) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_pjsh"))
+        .args(["-c", script])
+        .output()
+        .expect("pjsh should be executable");
+
+    assert!(output.status.success(), "{output:?}");
+    String::from_utf8(output.stdout).expect("output should be valid UTF-8")
+}
+
+#[test]
+fn it_pipes_processes_within_substitutions() {
+    assert_eq!(stdout(r"echo $(printf 'b\na\n' | sort)"), "a\nb\n");
+}
+
+#[test]
+fn it_executes_multiple_statements_within_substitutions() {
+    assert_eq!(stdout("echo $(echo first; echo second)"), "first\nsecond\n");
+}
+
+#[test]
+fn it_nests_substitutions() {
+    assert_eq!(stdout("echo $(echo $(echo nested))"), "nested\n");
+}
+
+#[test]
+fn it_separates_substitutions_from_value_pipelines() {
+    let script = r#"
+        value := word
+        echo $(printf 'b\na\n' | sort | head -n 1)
+        echo ${value | uppercase}
+        echo `[$(echo ")" | cat)] ${value | uppercase}`
+    "#;
+
+    assert_eq!(stdout(script), "a\nWORD\n[)] WORD\n");
+}
+
+#[test]
+fn it_filters_values_within_interpolated_words() {
+    let script = r#"
+        value := "hello world"
+        echo `<${value | uppercase}>`
+        echo `<${value | replace world pjsh | uppercase}>`
+        echo "${value | uppercase}"
+    "#;
+
+    // Quoted words are never interpolated.
+    assert_eq!(
+        stdout(script),
+        "<HELLO WORLD>\n<HELLO PJSH>\n${value | uppercase}\n"
+    );
+}
+
+#[test]
+fn it_warns_about_backtick_command_substitutions() {
+    let output = Command::new(env!("CARGO_BIN_EXE_pjsh"))
+        .args(["-c", "`true`; msg := `hello world`; echo $msg"])
+        .output()
+        .expect("pjsh should be executable");
+
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "hello world\n");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "pjsh: warning: backticks create interpolated words, not command substitutions: \
+         use $(true) to capture a command's output\n"
+    );
+}
@@ -0,0 +1,34 @@
+use std::process::Command;
+
+use tempfile::TempDir;
+
+#[test]
+fn it_writes_crash_reports() {
+    let home = TempDir::new().expect("a temporary directory should be created");
+    let output = Command::new(env!("CARGO_BIN_EXE_pjsh"))
+        .args(["--crash-test", "-c", "true"])
+        .env("HOME", home.path())
+        .output()
+        .expect("pjsh should be executable");
+
+    assert_eq!(output.status.code(), Some(70));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("a report has been written to"), "{stderr}");
+
+    let reports: Vec<_> = std::fs::read_dir(home.path().join(".pjsh"))
+        .expect("the report directory should exist")
+        .map(|entry| entry.expect("the report should be readable").path())
+        .collect();
+    assert_eq!(reports.len(), 1);
+    let file_name = reports[0].file_name().unwrap().to_string_lossy();
+    assert!(file_name.starts_with("crash-") && file_name.ends_with(".txt"));
+
+    let report = std::fs::read_to_string(&reports[0]).expect("the report should be readable");
+    for section in ["Panic", "Backtrace", "Recent Input", "Version", "Scopes"] {
+        assert!(
+            report.contains(&format!("\n## {section}\n")),
+            "missing section: {section}"
+        );
+    }
+    assert!(report.contains("crash test requested"));
+}
@@ -0,0 +1,32 @@
+#![cfg(unix)]
+
+use std::process::Command;
+
+/// Returns `true` if a process is running.
+fn is_running(pid: &str) -> bool {
+    Command::new("kill")
+        .args(["-0", pid])
+        .status()
+        .expect("kill should be executable")
+        .success()
+}
+
+#[test]
+fn it_does_not_kill_disowned_jobs_when_exiting() {
+    // The job must be a program, as built-in commands are never asynchronous,
+    // and must not inherit the output pipes, as reading the output would
+    // otherwise block until the job exits.
+    let script = "sh -c 'exec sleep 30' > /dev/null 2> /dev/null &\ndisown\necho $!";
+    let output = Command::new(env!("CARGO_BIN_EXE_pjsh"))
+        .args(["-c", script])
+        .output()
+        .expect("pjsh should be executable");
+    assert!(output.status.success(), "{output:?}");
+
+    let pid = String::from_utf8(output.stdout).expect("output should be valid UTF-8");
+    let pid = pid.trim();
+    let is_running = is_running(pid);
+    let _ = Command::new("kill").arg(pid).status();
+
+    assert!(is_running, "the disowned job should survive the shell");
+}
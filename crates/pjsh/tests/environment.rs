@@ -0,0 +1,61 @@
+use std::process::Command;
+
+/// Executes a script using `pjsh -c` with a `$PATH` and returns its stdout.
+fn stdout(script: &str, path: &str) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_pjsh"))
+        .args(["-c", script])
+        .env("PATH", path)
+        .output()
+        .expect("pjsh should be executable");
+
+    assert!(output.status.success(), "{output:?}");
+    String::from_utf8(output.stdout).expect("output should be valid UTF-8")
+}
+
+/// Returns the `$PATH` of the test process.
+fn path() -> String {
+    std::env::var("PATH").expect("PATH should be set")
+}
+
+#[test]
+fn it_passes_the_inherited_environment_to_programs() {
+    assert_eq!(stdout("printenv PATH", &path()), format!("{}\n", path()));
+}
+
+#[test]
+fn it_passes_modified_inherited_variables_to_programs() {
+    let script = "PATH := `${PATH}:/pjsh/test/bin`; printenv PATH";
+
+    assert_eq!(
+        stdout(script, &path()),
+        format!("{}:/pjsh/test/bin\n", path())
+    );
+}
+
+#[test]
+fn it_does_not_pass_unset_variables_to_programs() {
+    let output = Command::new(env!("CARGO_BIN_EXE_pjsh"))
+        .args(["-c", "unset PJSH_TEST_VAR; printenv PJSH_TEST_VAR"])
+        .env("PJSH_TEST_VAR", "value")
+        .output()
+        .expect("pjsh should be executable");
+
+    assert!(!output.status.success(), "{output:?}");
+    assert!(output.stdout.is_empty(), "{output:?}");
+}
+
+#[test]
+fn it_runs_programs_with_a_modified_environment() {
+    // printenv exits with 1 as PJSH_TEST_VAR is unset for the program.
+    let script =
+        "env -u PJSH_TEST_VAR PJSH_OTHER_VAR=other printenv PJSH_OTHER_VAR PJSH_TEST_VAR\n\
+                  echo `$?`; printenv PJSH_TEST_VAR";
+    let output = Command::new(env!("CARGO_BIN_EXE_pjsh"))
+        .args(["-c", script])
+        .env("PJSH_TEST_VAR", "value")
+        .output()
+        .expect("pjsh should be executable");
+
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(output.stdout, b"other\n1\nvalue\n");
+}
@@ -0,0 +1,106 @@
+use std::process::{Command, Output};
+
+/// Executes a script using `pjsh -c`.
+fn execute(script: &str) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_pjsh"))
+        .args(["-c", script])
+        .output()
+        .expect("pjsh should be executable")
+}
+
+/// Executes a script using `pjsh -c` and returns its stdout.
+fn stdout(script: &str) -> String {
+    let output = execute(script);
+    assert!(output.status.success(), "{output:?}");
+    String::from_utf8(output.stdout).expect("output should be valid UTF-8")
+}
+
+#[test]
+fn it_executes_generated_pipelines() {
+    let script = r#"
+        pipeline := "printf 'b\na\n' | sort"
+        eval $pipeline
+    "#;
+
+    assert_eq!(stdout(script), "a\nb\n");
+}
+
+#[test]
+fn it_keeps_generated_definitions() {
+    let script = r#"
+        eval 'fn greet() { echo hello }; greeting := hi'
+        greet
+        echo $greeting
+    "#;
+
+    assert_eq!(stdout(script), "hello\nhi\n");
+}
+
+#[test]
+fn it_returns_the_last_exit_code() {
+    assert_eq!(
+        stdout("eval false; echo `$?`; eval ''; echo `$?`"),
+        "1\n0\n"
+    );
+}
+
+#[test]
+fn it_expands_the_callers_positional_arguments() {
+    let script = r#"
+        fn f(a) { eval 'echo $1 $#' }
+        f x
+    "#;
+
+    assert_eq!(stdout(script), "x 1\n");
+}
+
+#[test]
+fn it_exits_the_shell() {
+    let output = execute("eval 'exit 7'; echo after");
+
+    assert_eq!(output.status.code(), Some(7));
+    assert_eq!(
+        (&output.stdout[..], &output.stderr[..]),
+        (&b""[..], &b""[..])
+    );
+}
+
+#[test]
+fn it_returns_from_the_calling_function() {
+    let script = r#"
+        fn f() { eval 'return 4'; echo unreachable }
+        f
+        echo `returned: $?`
+    "#;
+
+    assert_eq!(stdout(script), "returned: 4\n");
+}
+
+#[test]
+fn it_controls_the_enclosing_loop() {
+    let script = r#"
+        for i in [a b c d] {
+            if [[ $i == b ]] { eval continue }
+            if [[ $i == d ]] { eval break }
+            echo $i
+        }
+    "#;
+
+    assert_eq!(stdout(script), "a\nc\n");
+}
+
+#[test]
+fn it_redirects_generated_output() {
+    assert_eq!(stdout("eval 'echo b; echo a' | sort"), "a\nb\n");
+}
+
+#[test]
+fn it_reports_incomplete_code() {
+    let output = execute("eval 'if true {'; echo `code: $?`");
+
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "code: 1\n");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "eval: incomplete sequence: if true {\n"
+    );
+}
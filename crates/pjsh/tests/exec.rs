@@ -0,0 +1,46 @@
+use std::process::{Command, Output};
+
+use tempfile::TempDir;
+
+/// Executes a script using `pjsh -c` and returns its output.
+fn execute(script: &str) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_pjsh"))
+        .args(["-c", script])
+        .output()
+        .expect("pjsh should be executable")
+}
+
+#[cfg(unix)]
+#[test]
+fn it_replaces_the_shell_process() {
+    let output = execute("exec sh -c 'echo replaced; exit 3'; echo after");
+
+    assert_eq!(output.status.code(), Some(3), "{output:?}");
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "replaced\n");
+}
+
+#[test]
+fn it_continues_if_the_program_is_missing() {
+    let output = execute("exec /path/to/missing/program; echo after");
+
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "after\n");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "exec: /path/to/missing/program: command not found\n"
+    );
+}
+
+#[test]
+fn it_keeps_redirects_without_a_command() {
+    let dir = TempDir::new().expect("temporary directory should be created");
+    let file = dir.path().join("output.txt");
+    let script = format!("exec >'{}'; echo first; echo second", file.display());
+
+    let output = execute(&script);
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "");
+    assert_eq!(
+        std::fs::read_to_string(file).expect("output should be written"),
+        "first\nsecond\n"
+    );
+}
@@ -0,0 +1,58 @@
+use std::{
+    path::Path,
+    process::{Command, Output},
+};
+
+use tempfile::TempDir;
+
+/// Executes a script using `pjsh -c`, with additional leading arguments.
+fn execute(args: &[&str], script: &str, dir: &Path) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_pjsh"))
+        .args(args)
+        .args(["-c", script])
+        .current_dir(dir)
+        .env("PWD", dir)
+        .output()
+        .expect("pjsh should be executable")
+}
+
+/// Returns a temporary directory containing the files `a` and `b`.
+fn dir_with_files() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(dir.path().join("a"), "").unwrap();
+    std::fs::write(dir.path().join("b"), "").unwrap();
+    dir
+}
+
+#[test]
+fn it_expands_globs_by_default() {
+    let dir = dir_with_files();
+    let output = execute(&[], "echo *", dir.path());
+
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "a b\n");
+}
+
+#[test]
+fn it_keeps_globs_literal_with_noglob() {
+    let dir = dir_with_files();
+    let output = execute(&[], "set -f; echo *; set +f; echo *", dir.path());
+
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "*\na b\n");
+}
+
+#[test]
+fn it_keeps_globs_literal_with_the_no_globbing_flag() {
+    let dir = dir_with_files();
+    let output = execute(&["--no-globbing"], "echo *", dir.path());
+
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "*\n");
+}
+
+#[test]
+fn it_keeps_brace_groups_without_alternatives_literal() {
+    let dir = TempDir::new().unwrap();
+    let output = execute(&[], "echo {x} a{x}b {a,b}", dir.path());
+
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "{x} a{x}b a b\n");
+}
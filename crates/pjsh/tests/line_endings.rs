@@ -0,0 +1,110 @@
+use std::{path::Path, process::Command};
+
+use tempfile::TempDir;
+
+/// Byte order mark written by some Windows editors.
+const BOM: &str = "\u{feff}";
+
+/// Writes a script using a BOM and CRLF line endings.
+fn write_windows_script(path: &Path, lines: &[&str]) {
+    let script = format!("{BOM}{}\r\n", lines.join("\r\n"));
+    std::fs::write(path, script).expect("script should be writable");
+}
+
+/// Executes a script file and returns its stdout.
+fn execute(script_file: &Path) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_pjsh"))
+        .arg(script_file)
+        .output()
+        .expect("pjsh should be executable");
+
+    assert!(output.status.success(), "{output:?}");
+    String::from_utf8(output.stdout).expect("output should be valid UTF-8")
+}
+
+#[test]
+fn it_executes_scripts_with_bom_and_crlf() {
+    let dir = TempDir::new().unwrap();
+    let script_file = dir.path().join("script.pjsh");
+    write_windows_script(
+        &script_file,
+        &[
+            "name := value",
+            "echo `[$name]`",
+            "quoted := \"first",
+            "second\"",
+            "echo `[$quoted]`",
+            "if true {",
+            "  echo block",
+            "}",
+        ],
+    );
+
+    assert_eq!(execute(&script_file), "[value]\n[first\nsecond]\nblock\n");
+}
+
+#[test]
+fn it_sources_scripts_with_bom_and_crlf() {
+    let dir = TempDir::new().unwrap();
+    let sourced_file = dir.path().join("sourced.pjsh");
+    write_windows_script(
+        &sourced_file,
+        &["name := value", "if true {", "  echo block", "}"],
+    );
+
+    let script_file = dir.path().join("script.pjsh");
+    let sourced = sourced_file.to_string_lossy();
+    write_windows_script(
+        &script_file,
+        &[&format!("source {sourced}"), "echo `[$name]`"],
+    );
+
+    assert_eq!(execute(&script_file), "block\n[value]\n");
+}
+
+/// Executes a script file and returns its stderr.
+fn stderr(script_file: &Path) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_pjsh"))
+        .arg(script_file)
+        .output()
+        .expect("pjsh should be executable");
+
+    String::from_utf8(output.stderr).expect("output should be valid UTF-8")
+}
+
+#[test]
+fn it_warns_about_scripts_with_cr_only_line_endings() {
+    let dir = TempDir::new().unwrap();
+    let script_file = dir.path().join("script.pjsh");
+    std::fs::write(&script_file, "echo a\recho b\r").unwrap();
+
+    let warnings = stderr(&script_file);
+    assert_eq!(warnings.matches("warning").count(), 1, "{warnings}");
+    assert!(warnings.contains("script.pjsh: warning: "), "{warnings}");
+    assert!(warnings.contains("CR-only"), "{warnings}");
+}
+
+#[test]
+fn it_warns_about_sourced_scripts_with_mixed_line_endings() {
+    let dir = TempDir::new().unwrap();
+    let sourced_file = dir.path().join("sourced.pjsh");
+    std::fs::write(&sourced_file, "echo a\r\necho b\n").unwrap();
+
+    let script_file = dir.path().join("script.pjsh");
+    let sourced = sourced_file.to_string_lossy();
+    std::fs::write(&script_file, format!("source {sourced}\n")).unwrap();
+
+    let warnings = stderr(&script_file);
+    assert_eq!(warnings.matches("warning").count(), 1, "{warnings}");
+    assert!(warnings.contains("sourced.pjsh: warning: "), "{warnings}");
+    assert!(warnings.contains("mixed"), "{warnings}");
+}
+
+#[test]
+fn it_does_not_warn_about_scripts_with_crlf() {
+    let dir = TempDir::new().unwrap();
+    let script_file = dir.path().join("script.pjsh");
+    write_windows_script(&script_file, &["echo a", "echo b"]);
+
+    assert_eq!(stderr(&script_file), "");
+}
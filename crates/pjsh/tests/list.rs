@@ -0,0 +1,79 @@
+use std::process::{Command, Stdio};
+
+/// Executes pjsh with some arguments and an unreadable home directory.
+///
+/// Returns its standard output.
+fn stdout(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_pjsh"))
+        .args(args)
+        .env("HOME", "/path/to/missing/home")
+        .output()
+        .expect("pjsh should be executable");
+
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).expect("output should be valid UTF-8")
+}
+
+#[test]
+fn it_lists_builtins() {
+    let output = stdout(&["--list-builtins"]);
+    let names: Vec<&str> = output.lines().collect();
+
+    for name in ["cd", "echo", "help"] {
+        assert!(names.contains(&name), "missing built-in: {name}");
+    }
+}
+
+#[test]
+fn it_lists_filters() {
+    let output = stdout(&["--list-filters"]);
+    let names: Vec<&str> = output.lines().collect();
+
+    for name in ["join", "sort", "uppercase"] {
+        assert!(names.contains(&name), "missing filter: {name}");
+    }
+}
+
+#[test]
+fn it_lists_builtins_as_json() {
+    let output = stdout(&["--list-builtins", "--json"]);
+    let entries: serde_json::Value = serde_json::from_str(&output).expect("output should be JSON");
+    let cd = entries
+        .as_array()
+        .expect("output should be an array")
+        .iter()
+        .find(|entry| entry["name"] == "cd")
+        .expect("cd should be listed");
+
+    assert!(cd["summary"].is_string());
+}
+
+#[test]
+fn it_lists_filters_as_json() {
+    let output = stdout(&["--list-filters", "--json"]);
+    let entries: serde_json::Value = serde_json::from_str(&output).expect("output should be JSON");
+    let join = entries
+        .as_array()
+        .expect("output should be an array")
+        .iter()
+        .find(|entry| entry["name"] == "join")
+        .expect("join should be listed");
+
+    assert_eq!(join, &serde_json::json!({ "name": "join" }));
+}
+
+#[test]
+fn it_exits_quietly_on_broken_pipes() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_pjsh"))
+        .arg("--list-builtins")
+        .env("HOME", "/path/to/missing/home")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("pjsh should be executable");
+    drop(child.stdout.take());
+
+    let output = child.wait_with_output().expect("pjsh should exit");
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stderr), "");
+}
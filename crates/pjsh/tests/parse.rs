@@ -0,0 +1,40 @@
+use std::process::Command;
+
+use tempfile::TempDir;
+
+/// Parses a script file using `pjsh --parse` with some options and returns its
+/// stdout.
+fn parse_with(options: &[&str], script: &str) -> String {
+    let dir = TempDir::new().expect("temporary directory should be created");
+    let script_file = dir.path().join("script.pjsh");
+    std::fs::write(&script_file, script).expect("script should be writable");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_pjsh"))
+        .arg("--parse")
+        .args(options)
+        .arg(&script_file)
+        .output()
+        .expect("pjsh should be executable");
+
+    assert!(output.status.success(), "{output:?}");
+    String::from_utf8(output.stdout).expect("output should be valid UTF-8")
+}
+
+#[test]
+fn it_prints_statement_spans() {
+    let output = parse_with(&["--spans"], "echo hello\n");
+
+    assert!(output.starts_with("0..10 (1:1)\n"), "{output}");
+    assert!(output.contains("\"hello\""), "{output}");
+}
+
+#[test]
+fn it_prints_defined_functions() {
+    let script =
+        "fn outer() {\n  fn inner() { echo }\n}\n(fn hidden() {})\nif true { fn branch() {} }\n";
+
+    assert_eq!(
+        parse_with(&["--functions"], script),
+        "outer\ninner\nbranch\n"
+    );
+}
@@ -0,0 +1,39 @@
+use std::{
+    process::Command,
+    time::{Duration, Instant},
+};
+
+/// Executes a script using `pjsh -c` and returns its stdout.
+fn stdout(script: &str) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_pjsh"))
+        .args(["-c", script])
+        .output()
+        .expect("pjsh should be executable");
+
+    assert!(output.status.success(), "{output:?}");
+    String::from_utf8(output.stdout).expect("output should be valid UTF-8")
+}
+
+#[test]
+fn it_pipes_programs_into_builtins() {
+    assert_eq!(stdout("printf 'a\\nb\\n' | read line; echo $line"), "a\n");
+    assert_eq!(stdout("fn f() { cat }; echo hi | cat | f"), "hi\n");
+}
+
+#[test]
+fn it_pipes_builtins_into_builtins() {
+    assert_eq!(stdout("echo hi | read line; echo $line"), "hi\n");
+    assert_eq!(stdout("echo first | echo second | cat"), "second\n");
+}
+
+#[test]
+fn it_streams_builtin_output_to_programs() {
+    let start = Instant::now();
+    assert_eq!(stdout("seq 1 100000000 | head -n 2"), "1\n2\n");
+    assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+fn it_stops_programs_writing_to_builtins_that_do_not_read() {
+    assert_eq!(stdout("yes | echo done"), "done\n");
+}
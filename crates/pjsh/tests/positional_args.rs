@@ -0,0 +1,61 @@
+use std::process::{Command, Output};
+
+/// Executes a script using `pjsh -c` with some positional arguments.
+fn execute(script: &str, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_pjsh"))
+        .args(["-c", script])
+        .args(args)
+        .output()
+        .expect("pjsh should be executable")
+}
+
+fn stdout(output: &Output) -> &str {
+    std::str::from_utf8(&output.stdout).expect("output should be valid UTF-8")
+}
+
+#[test]
+fn it_shifts_script_arguments() {
+    let output = execute(
+        "echo $1; shift; echo $1; shift 5; echo `failed: $?`; echo $2",
+        &["a", "b", "c"],
+    );
+
+    assert_eq!(stdout(&output), "a\nb\nfailed: 1\nc\n");
+}
+
+#[test]
+fn it_shifts_function_arguments() {
+    let output = execute(
+        "fn f(args...) { shift 2; echo $1 }\nf x y z\necho $1",
+        &["outer"],
+    );
+
+    assert_eq!(stdout(&output), "z\nouter\n");
+}
+
+#[test]
+fn it_expands_all_positional_arguments() {
+    let output = execute(
+        "fn count(args...) { echo $# }\n\
+         fn f(args...) { echo $#; echo $*; echo `[$@]`; count $@; count $*; for arg in $@ { echo $arg } }\n\
+         f a b c",
+        &["outer"],
+    );
+
+    assert_eq!(stdout(&output), "3\na b c\n[a b c]\n3\n1\na\nb\nc\n");
+}
+
+#[test]
+fn it_counts_remaining_arguments_after_shifting() {
+    let output = execute("shift; echo $1 $#", &["a", "b", "c"]);
+
+    assert_eq!(stdout(&output), "b 2\n");
+}
+
+#[test]
+fn it_ignores_commands_expanding_to_no_words() {
+    let output = execute("echo a; $@; echo `code: $?`", &[]);
+
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "a\ncode: 0\n");
+}
@@ -0,0 +1,125 @@
+use std::process::Command;
+
+use tempfile::TempDir;
+
+/// Executes a script file using `pjsh --profile` and returns its stderr.
+fn profile(script: &str) -> String {
+    let dir = TempDir::new().expect("temporary directory should be created");
+    let script_file = dir.path().join("script.pjsh");
+    std::fs::write(&script_file, script).expect("script should be writable");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_pjsh"))
+        .arg("--profile")
+        .arg(&script_file)
+        .output()
+        .expect("pjsh should be executable");
+
+    assert!(output.status.success(), "{output:?}");
+    String::from_utf8(output.stderr).expect("output should be valid UTF-8")
+}
+
+#[test]
+fn it_reports_the_slowest_statement_first() {
+    let report = profile("true\nsleep 1\nfn f() { true }\nf\nf\n");
+    let entries: Vec<&str> = report.lines().skip(1).collect();
+
+    assert!(entries[0].ends_with("script.pjsh:2  sleep 1"), "{report}");
+    assert!(report.contains("script.pjsh:1  true"), "{report}");
+}
+
+#[test]
+fn it_reports_function_calls() {
+    let report = profile("fn f() { true }\nf\nf\n");
+
+    let function = report
+        .lines()
+        .find(|line| line.contains("fn f"))
+        .expect("function calls should be reported");
+    let count = function.split_whitespace().nth(1);
+    assert_eq!(count, Some("2"), "{report}");
+}
+
+/// Executes a script file using `pjsh --profile-output` and returns the
+/// profiled commands along with their durations in microseconds.
+fn profile_output(script: &str) -> Vec<(String, u128)> {
+    let dir = TempDir::new().expect("temporary directory should be created");
+    let script_file = dir.path().join("script.pjsh");
+    let profile_file = dir.path().join("profile.tsv");
+    std::fs::write(&script_file, script).expect("script should be writable");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_pjsh"))
+        .arg("--profile-output")
+        .arg(&profile_file)
+        .arg(&script_file)
+        .output()
+        .expect("pjsh should be executable");
+    assert!(output.status.success(), "{output:?}");
+
+    let profile = std::fs::read_to_string(profile_file).expect("profile should be written");
+    let mut lines = profile.lines();
+    assert_eq!(lines.next(), Some("command\tmicroseconds"));
+
+    lines
+        .map(|line| {
+            let (command, duration) = line.split_once('\t').expect("rows should have two columns");
+            let duration = duration.parse().expect("durations should be numeric");
+            (command.to_owned(), duration)
+        })
+        .collect()
+}
+
+#[test]
+fn it_writes_command_timings_to_a_file() {
+    let commands: Vec<String> = profile_output("true\nfn f() { echo a }\nf | cat\n")
+        .into_iter()
+        .map(|(command, _)| command)
+        .collect();
+    assert_eq!(commands, vec!["true", "echo", "f", "cat"]);
+}
+
+#[test]
+fn it_times_commands_from_their_own_start() {
+    let profile = profile_output("sleep 0.5 | env true\n");
+    let (_, duration) = profile
+        .iter()
+        .find(|(command, _)| command == "true")
+        .expect("the program should be profiled");
+    assert!(*duration < 500_000, "{profile:?}");
+}
+
+#[test]
+fn it_profiles_asynchronous_pipelines() {
+    let profile = profile_output("/bin/sleep 0.1 &\n");
+    assert!(
+        profile.iter().any(|(command, _)| command == "sleep"),
+        "{profile:?}"
+    );
+}
+
+#[test]
+fn it_profiles_and_writes_command_timings_at_once() {
+    let dir = TempDir::new().expect("temporary directory should be created");
+    let script_file = dir.path().join("script.pjsh");
+    let profile_file = dir.path().join("profile.tsv");
+    std::fs::write(&script_file, "true\n").expect("script should be writable");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_pjsh"))
+        .arg("--profile")
+        .arg("--profile-output")
+        .arg(&profile_file)
+        .arg(&script_file)
+        .output()
+        .expect("pjsh should be executable");
+    assert!(output.status.success(), "{output:?}");
+
+    let report = String::from_utf8(output.stderr).expect("output should be valid UTF-8");
+    assert!(report.contains("script.pjsh:1  true"), "{report}");
+    let profile = std::fs::read_to_string(profile_file).expect("profile should be written");
+    assert_eq!(
+        profile
+            .lines()
+            .nth(1)
+            .and_then(|line| line.split('\t').next()),
+        Some("true")
+    );
+}
@@ -0,0 +1,17 @@
+use std::process::Command;
+
+#[test]
+fn it_keeps_variables_marked_in_functions_readonly() {
+    let script = "var := before\nfn lock() { readonly var }\nlock\nvar := after\necho $var";
+    let output = Command::new(env!("CARGO_BIN_EXE_pjsh"))
+        .args(["-c", script])
+        .output()
+        .expect("pjsh should be executable");
+
+    assert!(!output.status.success(), "{output:?}");
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "pjsh: readonly variable: var\n"
+    );
+}
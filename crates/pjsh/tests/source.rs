@@ -0,0 +1,74 @@
+use std::process::Command;
+
+use tempfile::TempDir;
+
+#[test]
+fn it_sources_files_relative_to_nested_scripts() {
+    let root = TempDir::new().expect("temporary directory should be created");
+    let (scripts, other) = (root.path().join("scripts"), root.path().join("other"));
+    std::fs::create_dir_all(scripts.join("lib")).expect("directories should be created");
+    std::fs::create_dir_all(&other).expect("directories should be created");
+
+    let files = [
+        ("main.pjsh", "source -r lib/helper.pjsh\necho main\n"),
+        (
+            "lib/helper.pjsh",
+            "source -r ./greeting.pjsh\necho helper\n",
+        ),
+        ("lib/greeting.pjsh", "echo greeting\n"),
+    ];
+    for (name, contents) in files {
+        std::fs::write(scripts.join(name), contents).expect("script should be writable");
+    }
+
+    let output = Command::new(env!("CARGO_BIN_EXE_pjsh"))
+        .arg(scripts.join("main.pjsh"))
+        .current_dir(&other)
+        .env("PWD", &other)
+        .output()
+        .expect("pjsh should be executable");
+
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "greeting\nhelper\nmain\n"
+    );
+}
+
+#[test]
+fn it_requires_a_script_for_relative_files() {
+    let output = Command::new(env!("CARGO_BIN_EXE_pjsh"))
+        .args(["-c", "source -r file.pjsh"])
+        .env_remove("PJSH_CURRENT_SCRIPT_DIR")
+        .output()
+        .expect("pjsh should be executable");
+
+    assert_eq!(output.status.code(), Some(1), "{output:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "source: file.pjsh: no current script to resolve the path from\n"
+    );
+}
+
+#[test]
+fn it_restores_function_args_after_sourcing() {
+    let dir = TempDir::new().expect("temporary directory should be created");
+    let script = dir.path().join("script.pjsh");
+    std::fs::write(&script, "true\n").expect("script should be writable");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_pjsh"))
+        .args([
+            "-c",
+            "fn f(a) { source $SCRIPT s1 s2; echo `function: $1 $#` }; f x; echo `global: $1 $#`",
+            "global",
+        ])
+        .env("SCRIPT", &script)
+        .output()
+        .expect("pjsh should be executable");
+
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "function: x 1\nglobal: global 1\n"
+    );
+}
@@ -0,0 +1,146 @@
+use std::{
+    path::Path,
+    process::{Command, Output},
+};
+
+use tempfile::TempDir;
+
+/// Executes a script using `pjsh -c`.
+fn execute(script: &str, dir: &Path) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_pjsh"))
+        .args(["-c", script])
+        .current_dir(dir)
+        .env("PWD", dir)
+        .output()
+        .expect("pjsh should be executable")
+}
+
+/// Executes a script file.
+fn execute_file(script_file: &Path) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_pjsh"))
+        .arg(script_file)
+        .current_dir(script_file.parent().unwrap())
+        .env("PWD", script_file.parent().unwrap())
+        .output()
+        .expect("pjsh should be executable")
+}
+
+fn stdout(output: &Output) -> &str {
+    std::str::from_utf8(&output.stdout).expect("output should be valid UTF-8")
+}
+
+fn stderr(output: &Output) -> &str {
+    std::str::from_utf8(&output.stderr).expect("output should be valid UTF-8")
+}
+
+#[test]
+fn it_exits_on_failed_commands_in_strict_mode() {
+    let dir = TempDir::new().unwrap();
+    let output = execute("set -o strict; echo before; false; echo after", dir.path());
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "before\n");
+}
+
+#[test]
+fn it_exits_on_failed_subshells_in_strict_mode() {
+    let dir = TempDir::new().unwrap();
+    let output = execute(
+        "set -o strict; ( echo inner; false ); echo after",
+        dir.path(),
+    );
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "inner\n");
+}
+
+#[test]
+fn it_does_not_exit_on_expected_failures() {
+    let dir = TempDir::new().unwrap();
+    let script = r#"
+        set -e
+        false || echo fallback
+        false && echo unreachable
+        if false { echo unreachable }
+        echo reached
+    "#;
+    let output = execute(script, dir.path());
+
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(stdout(&output), "fallback\nreached\n");
+}
+
+#[test]
+fn it_uses_failed_pipeline_segments_with_pipefail() {
+    let dir = TempDir::new().unwrap();
+    let script = "false | cat; echo $?; set -o pipefail; false | cat; echo $?";
+    let output = execute(script, dir.path());
+
+    assert_eq!(stdout(&output), "0\n1\n");
+}
+
+#[test]
+fn it_refuses_to_overwrite_files_with_noclobber() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(dir.path().join("file"), "original").unwrap();
+    let output = execute("set -C; echo new >> file; echo new > file", dir.path());
+
+    assert!(
+        stderr(&output).contains("cannot overwrite existing file"),
+        "{output:?}"
+    );
+    assert_eq!(
+        std::fs::read_to_string(dir.path().join("file")).unwrap(),
+        "originalnew\n"
+    );
+}
+
+#[test]
+fn it_expands_undefined_variables_without_nounset() {
+    let dir = TempDir::new().unwrap();
+    let output = execute("set +u; echo $UNDEFINED_VARIABLE end", dir.path());
+
+    assert_eq!(stdout(&output), " end\n");
+}
+
+#[test]
+fn it_warns_about_unsplit_words() {
+    let dir = TempDir::new().unwrap();
+    let output = execute("set -o warnsplit; words := 'a b'; echo $words", dir.path());
+
+    assert_eq!(stdout(&output), "a b\n");
+    assert!(
+        stderr(&output).contains("$words contains whitespace"),
+        "{output:?}"
+    );
+}
+
+#[test]
+fn it_enables_strict_mode_using_a_pragma() {
+    let dir = TempDir::new().unwrap();
+    let script_file = dir.path().join("script.pjsh");
+    std::fs::write(
+        &script_file,
+        "#!/usr/bin/env pjsh\n#pragma strict\necho before\nfalse\necho after\n",
+    )
+    .unwrap();
+    let output = execute_file(&script_file);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "before\n");
+}
+
+#[test]
+fn it_restores_options_after_sourcing_strict_scripts() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("strict.pjsh"),
+        "#pragma strict\nset | grep errexit\n",
+    )
+    .unwrap();
+    let script = "source strict.pjsh; set | grep errexit; false; echo reached";
+    let output = execute(script, dir.path());
+
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(stdout(&output), "set -o errexit\nset +o errexit\nreached\n");
+}
@@ -0,0 +1,69 @@
+use std::process::{Command, Output};
+
+/// Executes a script using `pjsh -c` in a fixed time zone.
+fn execute(script: &str, time_zone: &str) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_pjsh"))
+        .args(["-c", script])
+        .env("TZ", time_zone)
+        .output()
+        .expect("pjsh should be executable")
+}
+
+#[test]
+fn it_formats_the_current_time_in_the_local_time_zone() {
+    let script = "format := '%z'; echo `offsets: ${format | now} ${format | now_utc}`";
+
+    let output = execute(script, "UTC");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "offsets: +0000 +0000\n"
+    );
+
+    let output = execute(script, "EST5");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "offsets: -0500 +0000\n"
+    );
+}
+
+#[test]
+fn it_formats_the_current_time_using_the_default_format() {
+    let output = execute("echo ${PJSH_TIME_FORMAT | now}", "UTC");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    // The default format is "%Y-%m-%d %H:%M:%S".
+    let time = stdout.trim_end();
+    assert_eq!(time.len(), 19, "unexpected time: {time}");
+    assert!(time.chars().enumerate().all(|(i, c)| match i {
+        4 | 7 => c == '-',
+        10 => c == ' ',
+        13 | 16 => c == ':',
+        _ => c.is_ascii_digit(),
+    }));
+}
+
+#[test]
+fn it_reports_invalid_time_formats() {
+    let output = execute("format := '%Q'; echo ${format | now}", "UTC");
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("invalid time format: %Q"));
+}
+
+#[test]
+fn it_formats_empty_words_using_the_environment_format() {
+    let output = Command::new(env!("CARGO_BIN_EXE_pjsh"))
+        .args([
+            "-c",
+            "empty := ''; echo `offsets: ${empty | now} ${empty | now_utc}`",
+        ])
+        .env("TZ", "EST5")
+        .env("PJSH_TIME_FORMAT", "%z")
+        .output()
+        .expect("pjsh should be executable");
+
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "offsets: -0500 +0000\n"
+    );
+}
@@ -0,0 +1,51 @@
+use std::process::{Command, Output};
+
+/// Executes a script using `pjsh -c`.
+fn execute(script: &str) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_pjsh"))
+        .args(["-c", script])
+        .output()
+        .expect("pjsh should be executable")
+}
+
+fn stdout(output: &Output) -> &str {
+    std::str::from_utf8(&output.stdout).expect("output should be valid UTF-8")
+}
+
+#[test]
+fn it_runs_exit_traps_when_exiting() {
+    let output = execute("trap 'echo bye' EXIT; echo hi; false");
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "hi\nbye\n");
+}
+
+#[test]
+fn it_runs_exit_traps_when_exiting_due_to_errexit() {
+    let output = execute("set -e; trap 'echo cleanup' EXIT; false; echo unreachable");
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "cleanup\n");
+}
+
+#[test]
+fn it_prints_and_resets_traps() {
+    let output =
+        execute("trap 'echo a' INT; trap 'echo b' EXIT; trap -p; trap - INT EXIT; trap -p");
+
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(
+        stdout(&output),
+        "trap -- 'echo b' EXIT\ntrap -- 'echo a' SIGINT\n"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn it_runs_signal_traps_before_terminating() {
+    let script = "trap 'echo caught' TERM; trap 'echo exiting' EXIT; kill $$; echo unreachable";
+    let output = execute(script);
+
+    assert_eq!(output.status.code(), Some(128 + 15), "{output:?}");
+    assert_eq!(stdout(&output), "caught\nexiting\n");
+}
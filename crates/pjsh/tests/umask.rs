@@ -0,0 +1,38 @@
+#![cfg(unix)]
+
+use std::{os::unix::fs::PermissionsExt, process::Command};
+
+use tempfile::TempDir;
+
+// Masks are process-wide, so they are only modified within child processes.
+
+#[test]
+fn it_sets_and_prints_the_mask() {
+    let output = Command::new(env!("CARGO_BIN_EXE_pjsh"))
+        .args(["-c", "umask 027; umask; umask -S"])
+        .output()
+        .expect("pjsh should be executable");
+
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "0027\nu=rwx,g=rx,o=\n"
+    );
+}
+
+#[test]
+fn it_applies_the_mask_to_redirects_and_child_processes() {
+    let dir = TempDir::new().unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_pjsh"))
+        .args(["-c", "umask 077; echo contents > new-file; sh -c umask"])
+        .current_dir(dir.path())
+        .env("PWD", dir.path())
+        .output()
+        .expect("pjsh should be executable");
+
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "0077\n");
+
+    let metadata = std::fs::metadata(dir.path().join("new-file")).unwrap();
+    assert_eq!(metadata.permissions().mode() & 0o777, 0o600);
+}
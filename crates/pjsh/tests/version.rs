@@ -0,0 +1,46 @@
+use std::process::Command;
+
+/// Executes pjsh with some arguments and returns its standard output.
+fn stdout(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_pjsh"))
+        .args(args)
+        .output()
+        .expect("pjsh should be executable");
+
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).expect("output should be valid UTF-8")
+}
+
+const KEYS: [&str; 5] = ["version", "commit", "profile", "target", "features"];
+
+#[test]
+fn it_prints_the_version() {
+    assert_eq!(
+        stdout(&["--version"]),
+        format!("pjsh {}\n", env!("CARGO_PKG_VERSION"))
+    );
+}
+
+#[test]
+fn it_prints_verbose_version_info() {
+    let output = stdout(&["--version", "--verbose"]);
+    let keys: Vec<&str> = output
+        .lines()
+        .filter_map(|line| line.split_once(": ").map(|(key, _)| key))
+        .collect();
+
+    assert_eq!(keys, KEYS);
+    assert!(output.contains(&format!("version: {}\n", env!("CARGO_PKG_VERSION"))));
+}
+
+#[test]
+fn it_prints_json_version_info() {
+    let output = stdout(&["--version", "--json"]);
+    let info: serde_json::Value = serde_json::from_str(&output).expect("output should be JSON");
+
+    for key in KEYS {
+        assert!(info.get(key).is_some(), "missing key: {key}");
+    }
+    assert_eq!(info["version"], env!("CARGO_PKG_VERSION"));
+    assert!(info["features"].is_array());
+}
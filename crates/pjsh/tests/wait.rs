@@ -0,0 +1,36 @@
+use std::process::Command;
+
+/// Executes a script using `pjsh -c` and returns its stdout.
+fn stdout(script: &str) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_pjsh"))
+        .args(["-c", script])
+        .output()
+        .expect("pjsh should be executable");
+
+    assert!(output.status.success(), "{output:?}");
+    String::from_utf8(output.stdout).expect("output should be valid UTF-8")
+}
+
+#[test]
+fn it_sets_the_exit_code_of_waited_jobs() {
+    let script = r#"
+        sh -c "exit 3" &
+        wait %1
+        echo $?
+    "#;
+
+    assert_eq!(stdout(script), "3\n");
+}
+
+#[test]
+fn it_sets_the_exit_code_of_listed_jobs() {
+    let script = r#"
+        sh -c "exit 4" &
+        sleep 1
+        jobs > /dev/null
+        wait %1
+        echo $?
+    "#;
+
+    assert_eq!(stdout(script), "4\n");
+}
@@ -19,6 +19,39 @@ pub enum Condition {
     /// Typically `[[ is-path word ]]` or `[[ -e word ]]`.
     IsPath(Word),
 
+    /// True if the given word can be resolved to a readable path.
+    ///
+    /// Typically `[[ is-readable word ]]` or `[[ -r word ]]`.
+    IsReadable(Word),
+
+    /// True if the given word can be resolved to a writable path.
+    ///
+    /// Typically `[[ is-writable word ]]` or `[[ -w word ]]`.
+    IsWritable(Word),
+
+    /// True if the given word can be resolved to an executable path.
+    ///
+    /// Typically `[[ is-executable word ]]` or `[[ -x word ]]`.
+    IsExecutable(Word),
+
+    /// True if the given word can be resolved to an existing path that is not
+    /// empty.
+    ///
+    /// Typically `[[ is-non-empty word ]]` or `[[ -s word ]]`.
+    IsNonEmpty(Word),
+
+    /// True if the first word can be resolved to a path that has been modified
+    /// more recently than the path that the second word resolves to.
+    ///
+    /// Typically `[[ a -nt b ]]`.
+    IsNewer(Word, Word),
+
+    /// True if the first word can be resolved to a path that has been modified
+    /// less recently than the path that the second word resolves to.
+    ///
+    /// Typically `[[ a -ot b ]]`.
+    IsOlder(Word, Word),
+
     // Word-related conditions.
     /// True if the given word is empty.
     ///
@@ -33,7 +66,7 @@ pub enum Condition {
     // Comparisons.
     /// True if the two given words are considered equal.
     ///
-    /// Typically `[[ a == b ]]`.
+    /// Typically `[[ a == b ]]` or `[[ a = b ]]`.
     Eq(Word, Word),
 
     /// True if the two given words are not considered equal.
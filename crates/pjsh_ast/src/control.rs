@@ -60,6 +60,9 @@ pub struct ForOfIterableLoop {
     /// Abstract iteration rule.
     pub iteration_rule: IterationRule,
 
+    /// Source of the iterable.
+    pub source: IterationSource,
+
     /// Iterable.
     pub iterable: Word,
 
@@ -80,6 +83,19 @@ pub enum IterationRule {
     Words,
 }
 
+/// The source of the value iterated over in a [`ForOfIterableLoop`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IterationSource {
+    /// Iterate over the interpolated iterable.
+    #[default]
+    Value,
+
+    /// Iterate over the contents of the file at the interpolated iterable path.
+    ///
+    /// Typically `for line in lines of file path`.
+    File,
+}
+
 /// Represents a conditional, "switch", statements over multiple values.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Switch {
@@ -88,6 +104,32 @@ pub struct Switch {
 
     /// Branches to execute conditionally based on input.
     ///
-    /// A branch is executed if its word matches the input.
-    pub branches: Vec<(Word, Block)>,
+    /// The first branch with a key matching the input is executed. The default
+    /// branch, if any, is executed if no key matches the input.
+    pub branches: Vec<SwitchBranch>,
+}
+
+/// A branch within a [`Switch`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SwitchBranch {
+    /// Keys to match against the switch input.
+    ///
+    /// Literal keys may contain glob patterns, such as `*.txt`.
+    ///
+    /// The default branch, typically `_ { ... }` or `default { ... }`, has no keys.
+    pub keys: Vec<Word>,
+
+    /// Name of a variable to bind the matched input to within the body.
+    ///
+    /// Typically `key as name { ... }`.
+    pub binding: Option<String>,
+
+    /// Statements to execute if the branch is taken.
+    pub body: Block,
+
+    /// Whether the body of the next branch should be executed after this one,
+    /// regardless of its keys.
+    ///
+    /// Typically a `fallthrough` statement at the end of the body.
+    pub fallthrough: bool,
 }
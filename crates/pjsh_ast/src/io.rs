@@ -27,6 +27,9 @@ pub fn new(source: FileDescriptor, target: FileDescriptor, mode: RedirectMode) -
 /// The mode to use when redirecting file descriptors.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum RedirectMode {
+    /// Read from the source, such as a file or a here-string.
+    Read,
+
     /// Write to the target file descriptor, replacing any previous data.
     Write,
 
@@ -53,4 +56,9 @@ pub enum FileDescriptor {
 
     /// A file to read data from or write data to.
     File(Word),
+
+    /// A value to read data from.
+    ///
+    /// Words are read as a single line. Lists are read with one item per line.
+    Value(Word),
 }
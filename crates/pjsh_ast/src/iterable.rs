@@ -46,6 +46,19 @@ pub struct ItemIterable {
     index: usize,
 }
 
+impl ItemIterable {
+    /// Returns all items, including items that have already been iterated over.
+    pub(crate) fn words(&self) -> &[Word] {
+        &self.items
+    }
+
+    /// Returns all items mutably, including items that have already been
+    /// iterated over.
+    pub(crate) fn words_mut(&mut self) -> &mut [Word] {
+        &mut self.items
+    }
+}
+
 impl From<Vec<Word>> for ItemIterable {
     fn from(items: Vec<Word>) -> Self {
         Self { items, index: 0 }
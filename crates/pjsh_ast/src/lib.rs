@@ -7,17 +7,21 @@
 mod list;
 mod pipeline;
 mod program;
+pub mod visit;
 mod word;
 
 pub use command::Command;
 pub use condition::Condition;
 pub use control::{
-    ConditionalChain, ConditionalLoop, ForIterableLoop, ForOfIterableLoop, IterationRule, Switch,
+    ConditionalChain, ConditionalLoop, ForIterableLoop, ForOfIterableLoop, IterationRule,
+    IterationSource, Switch, SwitchBranch,
 };
 pub use filter::Filter;
 pub use io::{FileDescriptor, Redirect, RedirectMode};
 pub use iterable::{Iterable, NumericRange};
 pub use list::List;
 pub use pipeline::{Pipeline, PipelineSegment};
-pub use program::{AndOr, AndOrOp, Assignment, Block, Function, Program, Statement, Value};
+pub use program::{
+    AndOr, AndOrOp, Assignment, Block, Comments, Function, Program, Statement, Value,
+};
 pub use word::{InterpolationUnit, ValuePipeline, Word};
@@ -32,6 +32,62 @@ pub enum Statement {
 
     /// A nested program body.
     Subshell(Program),
+
+    /// Exits the `n`-th enclosing loop.
+    ///
+    /// Typically `break` or `break n`.
+    Break(usize),
+
+    /// Skips to the next iteration of the `n`-th enclosing loop.
+    ///
+    /// Typically `continue` or `continue n`.
+    Continue(usize),
+
+    /// Exits the enclosing function, optionally with an exit code.
+    ///
+    /// Typically `return` or `return n`.
+    Return(Option<Word>),
+
+    /// Continues with the body of the next switch branch.
+    ///
+    /// Only valid as the last statement of a switch branch, where it is
+    /// represented by [`SwitchBranch::fallthrough`](crate::SwitchBranch::fallthrough).
+    Fallthrough,
+
+    /// A statement along with its surrounding comments.
+    ///
+    /// Comments do not affect evaluation, but are kept so that programs can be
+    /// formatted without losing them.
+    Commented(Box<Statement>, Comments),
+}
+
+impl Statement {
+    /// Returns the statement without any surrounding comments.
+    pub fn uncommented(&self) -> &Statement {
+        match self {
+            Statement::Commented(statement, _) => statement.uncommented(),
+            statement => statement,
+        }
+    }
+}
+
+/// Comments surrounding a [`Statement`].
+///
+/// Comments are stored as written, including their leading `#`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Comments {
+    /// Comments on the lines above the statement.
+    pub leading: Vec<String>,
+
+    /// A comment at the end of the statement's last line.
+    pub trailing: Option<String>,
+}
+
+impl Comments {
+    /// Returns `true` if there are no comments.
+    pub fn is_empty(&self) -> bool {
+        self.leading.is_empty() && self.trailing.is_none()
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
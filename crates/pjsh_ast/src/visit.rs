@@ -0,0 +1,730 @@
+//! Traversal of abstract syntax trees.
+//!
+//! A [`Visitor`] is called once for each node in a tree. All visitor methods
+//! default to calling the matching `walk_*` function, which visits the node's
+//! children. Implementations only need to override the methods for the nodes
+//! that they are interested in, and may call the `walk_*` function from within
+//! an overridden method in order to continue the traversal into child nodes.
+//!
+//! A [`MutVisitor`] works the same way, but allows nodes to be modified.
+
+use crate::{
+    AndOr, Assignment, Block, Command, Condition, FileDescriptor, Filter, Function,
+    InterpolationUnit, Iterable, Pipeline, PipelineSegment, Program, Redirect, Statement, Value,
+    ValuePipeline, Word,
+};
+
+/// A visitor for immutable abstract syntax trees.
+pub trait Visitor {
+    /// Visits a program.
+    fn visit_program(&mut self, program: &Program) {
+        walk_program(self, program);
+    }
+
+    /// Visits a statement.
+    fn visit_statement(&mut self, statement: &Statement) {
+        walk_statement(self, statement);
+    }
+
+    /// Visits a pipeline.
+    fn visit_pipeline(&mut self, pipeline: &Pipeline) {
+        walk_pipeline(self, pipeline);
+    }
+
+    /// Visits a command.
+    fn visit_command(&mut self, command: &Command) {
+        walk_command(self, command);
+    }
+
+    /// Visits a word.
+    fn visit_word(&mut self, word: &Word) {
+        walk_word(self, word);
+    }
+
+    /// Visits a redirect.
+    fn visit_redirect(&mut self, redirect: &Redirect) {
+        walk_redirect(self, redirect);
+    }
+
+    /// Visits a condition.
+    fn visit_condition(&mut self, condition: &Condition) {
+        walk_condition(self, condition);
+    }
+}
+
+/// Visits all statements in a program.
+pub fn walk_program<V: Visitor + ?Sized>(visitor: &mut V, program: &Program) {
+    walk_statements(visitor, &program.statements);
+}
+
+/// Visits all nodes within a statement.
+pub fn walk_statement<V: Visitor + ?Sized>(visitor: &mut V, statement: &Statement) {
+    match statement {
+        Statement::AndOr(and_or) => walk_and_or(visitor, and_or),
+        Statement::Assignment(Assignment { key, value }) => {
+            visitor.visit_word(key);
+            match value {
+                Value::List(list) => list.items.iter().for_each(|item| visitor.visit_word(item)),
+                Value::Word(word) => visitor.visit_word(word),
+            }
+        }
+        Statement::Function(Function { body, .. }) => walk_block(visitor, body),
+        Statement::If(chain) => {
+            chain
+                .conditions
+                .iter()
+                .for_each(|condition| walk_and_or(visitor, condition));
+            chain
+                .branches
+                .iter()
+                .for_each(|branch| walk_block(visitor, branch));
+        }
+        Statement::ForIn(for_loop) => {
+            if let Iterable::Items(items) = &for_loop.iterable {
+                items
+                    .words()
+                    .iter()
+                    .for_each(|item| visitor.visit_word(item));
+            }
+            walk_block(visitor, &for_loop.body);
+        }
+        Statement::ForOfIn(for_loop) => {
+            visitor.visit_word(&for_loop.iterable);
+            walk_block(visitor, &for_loop.body);
+        }
+        Statement::Switch(switch) => {
+            visitor.visit_word(&switch.input);
+            for branch in &switch.branches {
+                branch.keys.iter().for_each(|key| visitor.visit_word(key));
+                walk_block(visitor, &branch.body);
+            }
+        }
+        Statement::While(while_loop) => {
+            walk_and_or(visitor, &while_loop.condition);
+            walk_block(visitor, &while_loop.body);
+        }
+        Statement::Subshell(program) => visitor.visit_program(program),
+        Statement::Return(Some(code)) => visitor.visit_word(code),
+        Statement::Commented(statement, _) => visitor.visit_statement(statement),
+        Statement::Break(_)
+        | Statement::Continue(_)
+        | Statement::Return(None)
+        | Statement::Fallthrough => {}
+    }
+}
+
+/// Visits all segments in a pipeline.
+pub fn walk_pipeline<V: Visitor + ?Sized>(visitor: &mut V, pipeline: &Pipeline) {
+    for segment in &pipeline.segments {
+        match segment {
+            PipelineSegment::Command(command) => visitor.visit_command(command),
+            PipelineSegment::Condition(condition) => visitor.visit_condition(condition),
+        }
+    }
+}
+
+/// Visits all arguments and redirects in a command.
+pub fn walk_command<V: Visitor + ?Sized>(visitor: &mut V, command: &Command) {
+    command
+        .arguments
+        .iter()
+        .for_each(|arg| visitor.visit_word(arg));
+    command
+        .redirects
+        .iter()
+        .for_each(|redirect| visitor.visit_redirect(redirect));
+}
+
+/// Visits all programs and words that are nested within a word.
+pub fn walk_word<V: Visitor + ?Sized>(visitor: &mut V, word: &Word) {
+    match word {
+        Word::Literal(_) | Word::Quoted(_) | Word::Variable(_) => {}
+        Word::Subshell(program) | Word::ProcessSubstitution(program) => {
+            visitor.visit_program(program)
+        }
+        Word::Interpolation(units) => {
+            for unit in units {
+                match unit {
+                    InterpolationUnit::ValuePipeline(pipeline) => {
+                        walk_value_pipeline(visitor, pipeline)
+                    }
+                    InterpolationUnit::Subshell(program) => visitor.visit_program(program),
+                    InterpolationUnit::Literal(_)
+                    | InterpolationUnit::Unicode(_)
+                    | InterpolationUnit::Variable(_)
+                    | InterpolationUnit::VariableLength(_)
+                    | InterpolationUnit::Arithmetic(_)
+                    | InterpolationUnit::Substring(_, _, _) => {}
+                }
+            }
+        }
+        Word::ValuePipeline(pipeline) => walk_value_pipeline(visitor, pipeline),
+    }
+}
+
+/// Visits the words in a redirect's file descriptors.
+pub fn walk_redirect<V: Visitor + ?Sized>(visitor: &mut V, redirect: &Redirect) {
+    for fd in [&redirect.source, &redirect.target] {
+        match fd {
+            FileDescriptor::File(word) | FileDescriptor::Value(word) => visitor.visit_word(word),
+            FileDescriptor::Number(_) => {}
+        }
+    }
+}
+
+/// Visits all words and nested conditions in a condition.
+pub fn walk_condition<V: Visitor + ?Sized>(visitor: &mut V, condition: &Condition) {
+    match condition {
+        Condition::IsDirectory(word)
+        | Condition::IsFile(word)
+        | Condition::IsPath(word)
+        | Condition::IsReadable(word)
+        | Condition::IsWritable(word)
+        | Condition::IsExecutable(word)
+        | Condition::IsNonEmpty(word)
+        | Condition::Empty(word)
+        | Condition::NotEmpty(word) => visitor.visit_word(word),
+        Condition::Eq(a, b)
+        | Condition::Ne(a, b)
+        | Condition::Matches(a, b)
+        | Condition::IsNewer(a, b)
+        | Condition::IsOlder(a, b) => {
+            visitor.visit_word(a);
+            visitor.visit_word(b);
+        }
+        Condition::Invert(condition) => visitor.visit_condition(condition),
+    }
+}
+
+/// Visits a list of statements.
+fn walk_statements<V: Visitor + ?Sized>(visitor: &mut V, statements: &[Statement]) {
+    statements
+        .iter()
+        .for_each(|statement| visitor.visit_statement(statement));
+}
+
+/// Visits all statements in a block.
+fn walk_block<V: Visitor + ?Sized>(visitor: &mut V, block: &Block) {
+    walk_statements(visitor, &block.statements);
+}
+
+/// Visits all pipelines in an and-or construct.
+fn walk_and_or<V: Visitor + ?Sized>(visitor: &mut V, and_or: &AndOr) {
+    and_or
+        .pipelines
+        .iter()
+        .for_each(|pipeline| visitor.visit_pipeline(pipeline));
+}
+
+/// Visits all filter names and arguments in a value pipeline.
+fn walk_value_pipeline<V: Visitor + ?Sized>(visitor: &mut V, pipeline: &ValuePipeline) {
+    for Filter { name, args } in &pipeline.filters {
+        visitor.visit_word(name);
+        args.iter().for_each(|arg| visitor.visit_word(arg));
+    }
+}
+
+/// A visitor for mutable abstract syntax trees.
+///
+/// Used to transform trees in place.
+pub trait MutVisitor {
+    /// Visits a program.
+    fn visit_program_mut(&mut self, program: &mut Program) {
+        walk_program_mut(self, program);
+    }
+
+    /// Visits a statement.
+    fn visit_statement_mut(&mut self, statement: &mut Statement) {
+        walk_statement_mut(self, statement);
+    }
+
+    /// Visits a pipeline.
+    fn visit_pipeline_mut(&mut self, pipeline: &mut Pipeline) {
+        walk_pipeline_mut(self, pipeline);
+    }
+
+    /// Visits a command.
+    fn visit_command_mut(&mut self, command: &mut Command) {
+        walk_command_mut(self, command);
+    }
+
+    /// Visits a word.
+    fn visit_word_mut(&mut self, word: &mut Word) {
+        walk_word_mut(self, word);
+    }
+
+    /// Visits a redirect.
+    fn visit_redirect_mut(&mut self, redirect: &mut Redirect) {
+        walk_redirect_mut(self, redirect);
+    }
+
+    /// Visits a condition.
+    fn visit_condition_mut(&mut self, condition: &mut Condition) {
+        walk_condition_mut(self, condition);
+    }
+}
+
+/// Visits all statements in a program.
+pub fn walk_program_mut<V: MutVisitor + ?Sized>(visitor: &mut V, program: &mut Program) {
+    walk_statements_mut(visitor, &mut program.statements);
+}
+
+/// Visits all nodes within a statement.
+pub fn walk_statement_mut<V: MutVisitor + ?Sized>(visitor: &mut V, statement: &mut Statement) {
+    match statement {
+        Statement::AndOr(and_or) => walk_and_or_mut(visitor, and_or),
+        Statement::Assignment(Assignment { key, value }) => {
+            visitor.visit_word_mut(key);
+            match value {
+                Value::List(list) => list
+                    .items
+                    .iter_mut()
+                    .for_each(|item| visitor.visit_word_mut(item)),
+                Value::Word(word) => visitor.visit_word_mut(word),
+            }
+        }
+        Statement::Function(Function { body, .. }) => walk_block_mut(visitor, body),
+        Statement::If(chain) => {
+            chain
+                .conditions
+                .iter_mut()
+                .for_each(|condition| walk_and_or_mut(visitor, condition));
+            chain
+                .branches
+                .iter_mut()
+                .for_each(|branch| walk_block_mut(visitor, branch));
+        }
+        Statement::ForIn(for_loop) => {
+            if let Iterable::Items(items) = &mut for_loop.iterable {
+                items
+                    .words_mut()
+                    .iter_mut()
+                    .for_each(|item| visitor.visit_word_mut(item));
+            }
+            walk_block_mut(visitor, &mut for_loop.body);
+        }
+        Statement::ForOfIn(for_loop) => {
+            visitor.visit_word_mut(&mut for_loop.iterable);
+            walk_block_mut(visitor, &mut for_loop.body);
+        }
+        Statement::Switch(switch) => {
+            visitor.visit_word_mut(&mut switch.input);
+            for branch in &mut switch.branches {
+                branch
+                    .keys
+                    .iter_mut()
+                    .for_each(|key| visitor.visit_word_mut(key));
+                walk_block_mut(visitor, &mut branch.body);
+            }
+        }
+        Statement::While(while_loop) => {
+            walk_and_or_mut(visitor, &mut while_loop.condition);
+            walk_block_mut(visitor, &mut while_loop.body);
+        }
+        Statement::Subshell(program) => visitor.visit_program_mut(program),
+        Statement::Return(Some(code)) => visitor.visit_word_mut(code),
+        Statement::Commented(statement, _) => visitor.visit_statement_mut(statement),
+        Statement::Break(_)
+        | Statement::Continue(_)
+        | Statement::Return(None)
+        | Statement::Fallthrough => {}
+    }
+}
+
+/// Visits all segments in a pipeline.
+pub fn walk_pipeline_mut<V: MutVisitor + ?Sized>(visitor: &mut V, pipeline: &mut Pipeline) {
+    for segment in &mut pipeline.segments {
+        match segment {
+            PipelineSegment::Command(command) => visitor.visit_command_mut(command),
+            PipelineSegment::Condition(condition) => visitor.visit_condition_mut(condition),
+        }
+    }
+}
+
+/// Visits all arguments and redirects in a command.
+pub fn walk_command_mut<V: MutVisitor + ?Sized>(visitor: &mut V, command: &mut Command) {
+    command
+        .arguments
+        .iter_mut()
+        .for_each(|arg| visitor.visit_word_mut(arg));
+    command
+        .redirects
+        .iter_mut()
+        .for_each(|redirect| visitor.visit_redirect_mut(redirect));
+}
+
+/// Visits all programs and words that are nested within a word.
+pub fn walk_word_mut<V: MutVisitor + ?Sized>(visitor: &mut V, word: &mut Word) {
+    match word {
+        Word::Literal(_) | Word::Quoted(_) | Word::Variable(_) => {}
+        Word::Subshell(program) | Word::ProcessSubstitution(program) => {
+            visitor.visit_program_mut(program)
+        }
+        Word::Interpolation(units) => {
+            for unit in units {
+                match unit {
+                    InterpolationUnit::ValuePipeline(pipeline) => {
+                        walk_value_pipeline_mut(visitor, pipeline)
+                    }
+                    InterpolationUnit::Subshell(program) => visitor.visit_program_mut(program),
+                    InterpolationUnit::Literal(_)
+                    | InterpolationUnit::Unicode(_)
+                    | InterpolationUnit::Variable(_)
+                    | InterpolationUnit::VariableLength(_)
+                    | InterpolationUnit::Arithmetic(_)
+                    | InterpolationUnit::Substring(_, _, _) => {}
+                }
+            }
+        }
+        Word::ValuePipeline(pipeline) => walk_value_pipeline_mut(visitor, pipeline),
+    }
+}
+
+/// Visits the words in a redirect's file descriptors.
+pub fn walk_redirect_mut<V: MutVisitor + ?Sized>(visitor: &mut V, redirect: &mut Redirect) {
+    for fd in [&mut redirect.source, &mut redirect.target] {
+        match fd {
+            FileDescriptor::File(word) | FileDescriptor::Value(word) => {
+                visitor.visit_word_mut(word)
+            }
+            FileDescriptor::Number(_) => {}
+        }
+    }
+}
+
+/// Visits all words and nested conditions in a condition.
+pub fn walk_condition_mut<V: MutVisitor + ?Sized>(visitor: &mut V, condition: &mut Condition) {
+    match condition {
+        Condition::IsDirectory(word)
+        | Condition::IsFile(word)
+        | Condition::IsPath(word)
+        | Condition::IsReadable(word)
+        | Condition::IsWritable(word)
+        | Condition::IsExecutable(word)
+        | Condition::IsNonEmpty(word)
+        | Condition::Empty(word)
+        | Condition::NotEmpty(word) => visitor.visit_word_mut(word),
+        Condition::Eq(a, b)
+        | Condition::Ne(a, b)
+        | Condition::Matches(a, b)
+        | Condition::IsNewer(a, b)
+        | Condition::IsOlder(a, b) => {
+            visitor.visit_word_mut(a);
+            visitor.visit_word_mut(b);
+        }
+        Condition::Invert(condition) => visitor.visit_condition_mut(condition),
+    }
+}
+
+/// Visits a list of statements.
+fn walk_statements_mut<V: MutVisitor + ?Sized>(visitor: &mut V, statements: &mut [Statement]) {
+    statements
+        .iter_mut()
+        .for_each(|statement| visitor.visit_statement_mut(statement));
+}
+
+/// Visits all statements in a block.
+fn walk_block_mut<V: MutVisitor + ?Sized>(visitor: &mut V, block: &mut Block) {
+    walk_statements_mut(visitor, &mut block.statements);
+}
+
+/// Visits all pipelines in an and-or construct.
+fn walk_and_or_mut<V: MutVisitor + ?Sized>(visitor: &mut V, and_or: &mut AndOr) {
+    and_or
+        .pipelines
+        .iter_mut()
+        .for_each(|pipeline| visitor.visit_pipeline_mut(pipeline));
+}
+
+/// Visits all filter names and arguments in a value pipeline.
+fn walk_value_pipeline_mut<V: MutVisitor + ?Sized>(visitor: &mut V, pipeline: &mut ValuePipeline) {
+    for Filter { name, args } in &mut pipeline.filters {
+        visitor.visit_word_mut(name);
+        args.iter_mut().for_each(|arg| visitor.visit_word_mut(arg));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::{
+        ConditionalChain, ConditionalLoop, ForIterableLoop, ForOfIterableLoop, IterationRule,
+        IterationSource, List, RedirectMode, Switch, SwitchBranch,
+    };
+
+    use super::*;
+
+    /// Records the kinds of visited nodes and the contents of literal words.
+    #[derive(Default)]
+    struct Recorder {
+        /// Number of visits per node kind.
+        visits: HashMap<&'static str, usize>,
+
+        /// Literal words in visiting order.
+        literals: Vec<String>,
+    }
+
+    impl Recorder {
+        fn record(&mut self, kind: &'static str) {
+            *self.visits.entry(kind).or_default() += 1;
+        }
+    }
+
+    impl Visitor for Recorder {
+        fn visit_program(&mut self, program: &Program) {
+            self.record("program");
+            walk_program(self, program);
+        }
+
+        fn visit_statement(&mut self, statement: &Statement) {
+            self.record("statement");
+            walk_statement(self, statement);
+        }
+
+        fn visit_pipeline(&mut self, pipeline: &Pipeline) {
+            self.record("pipeline");
+            walk_pipeline(self, pipeline);
+        }
+
+        fn visit_command(&mut self, command: &Command) {
+            self.record("command");
+            walk_command(self, command);
+        }
+
+        fn visit_word(&mut self, word: &Word) {
+            self.record("word");
+            if let Word::Literal(literal) = word {
+                self.literals.push(literal.clone());
+            }
+            walk_word(self, word);
+        }
+
+        fn visit_redirect(&mut self, redirect: &Redirect) {
+            self.record("redirect");
+            walk_redirect(self, redirect);
+        }
+
+        fn visit_condition(&mut self, condition: &Condition) {
+            self.record("condition");
+            walk_condition(self, condition);
+        }
+    }
+
+    fn literal(word: &str) -> Word {
+        Word::Literal(word.into())
+    }
+
+    fn pipeline(segments: Vec<PipelineSegment>) -> Pipeline {
+        Pipeline {
+            is_async: false,
+            segments,
+        }
+    }
+
+    fn and_or(command: &str) -> AndOr {
+        AndOr {
+            operators: vec![],
+            pipelines: vec![pipeline(vec![PipelineSegment::Command(Command {
+                arguments: vec![literal(command)],
+                redirects: vec![],
+            })])],
+        }
+    }
+
+    fn block(command: &str) -> Block {
+        Block {
+            statements: vec![Statement::AndOr(and_or(command))],
+        }
+    }
+
+    fn program(command: &str) -> Program {
+        Program {
+            statements: vec![Statement::AndOr(and_or(command))],
+        }
+    }
+
+    /// Returns a program containing every kind of node.
+    fn complex_program() -> Program {
+        let value_pipeline = |name: &str, args: Vec<Word>| ValuePipeline {
+            base: "base".into(),
+            filters: vec![Filter {
+                name: literal(name),
+                args,
+            }],
+        };
+        let command = Command {
+            arguments: vec![
+                literal("arg"),
+                Word::Interpolation(vec![
+                    InterpolationUnit::Literal("unit".into()),
+                    InterpolationUnit::ValuePipeline(value_pipeline(
+                        "filter",
+                        vec![literal("filter-arg")],
+                    )),
+                    InterpolationUnit::Subshell(program("interpolated")),
+                ]),
+                Word::ValuePipeline(Box::new(value_pipeline("other-filter", vec![]))),
+            ],
+            redirects: vec![
+                Redirect::new(
+                    FileDescriptor::Number(1),
+                    FileDescriptor::File(literal("file")),
+                    RedirectMode::Write,
+                ),
+                Redirect::new(
+                    FileDescriptor::Value(literal("value")),
+                    FileDescriptor::Number(0),
+                    RedirectMode::Read,
+                ),
+            ],
+        };
+        let condition = Condition::Invert(Box::new(Condition::Eq(literal("a"), literal("b"))));
+
+        Program {
+            statements: vec![
+                Statement::AndOr(AndOr {
+                    operators: vec![crate::AndOrOp::And],
+                    pipelines: vec![
+                        pipeline(vec![
+                            PipelineSegment::Command(command),
+                            PipelineSegment::Condition(condition),
+                        ]),
+                        pipeline(vec![PipelineSegment::Command(Command {
+                            arguments: vec![Word::ProcessSubstitution(program("substituted"))],
+                            redirects: vec![],
+                        })]),
+                    ],
+                }),
+                Statement::Assignment(Assignment::new(
+                    literal("key"),
+                    Value::List(List::from(vec![literal("item1"), literal("item2")])),
+                )),
+                Statement::Function(Function::new("f".into(), vec![], None, block("body"))),
+                Statement::If(ConditionalChain {
+                    conditions: vec![and_or("if")],
+                    branches: vec![block("then"), block("else")],
+                }),
+                Statement::ForIn(ForIterableLoop {
+                    variable: "i".into(),
+                    iterable: Iterable::from(vec![literal("for-item")]),
+                    body: block("for-body"),
+                }),
+                Statement::ForOfIn(ForOfIterableLoop {
+                    variable: "line".into(),
+                    iteration_rule: IterationRule::Lines,
+                    source: IterationSource::Value,
+                    iterable: literal("for-of-item"),
+                    body: block("for-of-body"),
+                }),
+                Statement::Switch(Switch {
+                    input: literal("input"),
+                    branches: vec![SwitchBranch {
+                        keys: vec![literal("key1"), literal("key2")],
+                        binding: None,
+                        body: block("case"),
+                        fallthrough: false,
+                    }],
+                }),
+                Statement::While(ConditionalLoop {
+                    condition: and_or("while"),
+                    body: block("while-body"),
+                }),
+                Statement::Subshell(program("subshell")),
+                Statement::Break(1),
+            ],
+        }
+    }
+
+    /// Literal words in [`complex_program`] in visiting order.
+    const LITERALS: [&str; 28] = [
+        "arg",
+        "filter",
+        "filter-arg",
+        "interpolated",
+        "other-filter",
+        "file",
+        "value",
+        "a",
+        "b",
+        "substituted",
+        "key",
+        "item1",
+        "item2",
+        "body",
+        "if",
+        "then",
+        "else",
+        "for-item",
+        "for-body",
+        "for-of-item",
+        "for-of-body",
+        "input",
+        "key1",
+        "key2",
+        "case",
+        "while",
+        "while-body",
+        "subshell",
+    ];
+
+    #[test]
+    fn it_visits_every_node_once() {
+        let mut recorder = Recorder::default();
+        recorder.visit_program(&complex_program());
+
+        assert_eq!(recorder.literals, LITERALS);
+        assert_eq!(
+            recorder.visits,
+            HashMap::from([
+                ("program", 4),
+                ("statement", 20),
+                ("pipeline", 14),
+                ("command", 14),
+                ("word", 31),
+                ("redirect", 2),
+                ("condition", 2),
+            ])
+        );
+    }
+
+    #[test]
+    fn it_only_visits_overridden_nodes() {
+        /// Counts commands without overriding other methods.
+        struct CommandCounter(usize);
+        impl Visitor for CommandCounter {
+            fn visit_command(&mut self, command: &Command) {
+                self.0 += 1;
+                walk_command(self, command);
+            }
+        }
+
+        let mut counter = CommandCounter(0);
+        counter.visit_program(&complex_program());
+        assert_eq!(counter.0, 14);
+    }
+
+    #[test]
+    fn it_transforms_every_word() {
+        /// Converts literal words into uppercase.
+        struct Uppercase;
+        impl MutVisitor for Uppercase {
+            fn visit_word_mut(&mut self, word: &mut Word) {
+                if let Word::Literal(literal) = word {
+                    *literal = literal.to_uppercase();
+                }
+                walk_word_mut(self, word);
+            }
+        }
+
+        let mut program = complex_program();
+        Uppercase.visit_program_mut(&mut program);
+
+        let mut recorder = Recorder::default();
+        recorder.visit_program(&program);
+        assert_eq!(
+            recorder.literals,
+            LITERALS.map(|literal| literal.to_uppercase())
+        );
+    }
+}
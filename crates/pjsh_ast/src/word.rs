@@ -39,6 +39,18 @@ pub enum InterpolationUnit {
     /// A variable name for a value that is resolved at runtime.
     Variable(String),
 
+    /// A variable name whose value's length, in characters, is resolved at runtime.
+    VariableLength(String),
+
+    /// An arithmetic expression, such as `i + 1`, that is evaluated at runtime.
+    Arithmetic(String),
+
+    /// A variable name along with a character offset and an optional length of
+    /// a substring of its value, which is resolved at runtime.
+    ///
+    /// Negative offsets count from the end of the value.
+    Substring(String, isize, Option<usize>),
+
     /// A value-based pipeline.
     ValuePipeline(ValuePipeline),
 
@@ -33,6 +33,14 @@ fn name(&self) -> &str {
         NAME
     }
 
+    fn description(&self) -> Option<String> {
+        utils::description::<AliasOpts>()
+    }
+
+    fn usage(&self) -> Option<String> {
+        utils::usage::<AliasOpts>(NAME)
+    }
+
     fn run(&self, args: &mut Args) -> CommandResult {
         match AliasOpts::try_parse_from(args.context.args()) {
             Ok(opts) => match (opts.name, opts.value) {
@@ -100,7 +108,7 @@ mod tests {
 
     use pjsh_core::{Context, Scope};
 
-    use crate::utils::{file_contents, mock_io};
+    use crate::utils::{assert_prints_help, assert_prints_usage_error, file_contents, mock_io};
 
     use super::*;
 
@@ -182,4 +190,14 @@ fn it_can_define_an_alias() {
             unreachable!()
         }
     }
+
+    #[test]
+    fn it_prints_help() {
+        assert_prints_help(&Alias);
+    }
+
+    #[test]
+    fn it_prints_usage_errors() {
+        assert_prints_usage_error(&Alias, &["alias", "name", "value", "extra"]);
+    }
 }
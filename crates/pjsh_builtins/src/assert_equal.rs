@@ -0,0 +1,131 @@
+use clap::Parser;
+use pjsh_core::{
+    command::{Args, Command, CommandResult},
+    utils::diff_lines,
+};
+
+use crate::{status, utils};
+
+/// Command name.
+const NAME: &str = "assert-equal";
+
+/// Assert that two values are equal.
+///
+/// Exits with a non-zero status and prints a line-based diff of the values to
+/// stderr if they differ.
+///
+/// This is a built-in shell command.
+#[derive(Parser)]
+#[clap(name = NAME, version)]
+struct AssertEqualOpts {
+    /// Expected value.
+    expected: String,
+
+    /// Actual value.
+    actual: String,
+
+    /// Message to print if the values differ.
+    #[clap(short, long)]
+    message: Option<String>,
+}
+
+/// Implementation for the "assert-equal" built-in command.
+#[derive(Clone)]
+pub struct AssertEqual;
+impl Command for AssertEqual {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn description(&self) -> Option<String> {
+        utils::description::<AssertEqualOpts>()
+    }
+
+    fn usage(&self) -> Option<String> {
+        utils::usage::<AssertEqualOpts>(NAME)
+    }
+
+    fn run(&self, args: &mut Args) -> CommandResult {
+        match AssertEqualOpts::try_parse_from(args.context.args()) {
+            Ok(opts) if opts.expected == opts.actual => CommandResult::code(status::SUCCESS),
+            Ok(opts) => {
+                let message = opts.message.as_deref().unwrap_or("values differ");
+                let _ = writeln!(args.io.stderr, "{NAME}: {message}");
+                let _ = writeln!(args.io.stderr, "--- expected\n+++ actual");
+
+                let expected: Vec<&str> = opts.expected.split('\n').collect();
+                let actual: Vec<&str> = opts.actual.split('\n').collect();
+                for line in diff_lines(&expected, &actual) {
+                    let _ = writeln!(args.io.stderr, "{line}");
+                }
+
+                CommandResult::code(status::GENERAL_ERROR)
+            }
+            Err(error) => utils::exit_with_parse_error(args.io, error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pjsh_core::Context;
+
+    use crate::utils::{assert_prints_help, assert_prints_usage_error, file_contents, mock_io};
+
+    use super::*;
+
+    /// Runs the "assert-equal" built-in command, returning its exit code and stderr.
+    fn assert_equal(args: &[&str]) -> (i32, String) {
+        let mut ctx = Context::default();
+        ctx.replace_args(Some(
+            std::iter::once(NAME)
+                .chain(args.iter().copied())
+                .map(ToString::to_string)
+                .collect(),
+        ));
+        let (mut io, _stdout, mut stderr) = mock_io();
+
+        match AssertEqual.run(&mut Args::new(&mut ctx, &mut io)) {
+            CommandResult::Builtin(result) => (result.code, file_contents(&mut stderr)),
+            CommandResult::Process(_) => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_accepts_equal_values() {
+        assert_eq!(
+            assert_equal(&["first\nsecond", "first\nsecond"]),
+            (status::SUCCESS, String::new())
+        );
+    }
+
+    #[test]
+    fn it_prints_a_diff_of_unequal_values() {
+        assert_eq!(
+            assert_equal(&["a\nthe quick fox\nc", "a\nthe slow fox\nc\nd"]),
+            (
+                status::GENERAL_ERROR,
+                "assert-equal: values differ\n--- expected\n+++ actual\n \
+                 a\n-the [-quick-] fox\n+the {+slow+} fox\n c\n+d\n"
+                    .into()
+            )
+        );
+    }
+
+    #[test]
+    fn it_prints_custom_messages() {
+        let (code, stderr) = assert_equal(&["-m", "unexpected output", "a", "b"]);
+        assert_eq!(code, status::GENERAL_ERROR);
+        assert!(stderr.starts_with("assert-equal: unexpected output\n"));
+    }
+
+    #[test]
+    fn it_prints_help() {
+        assert_prints_help(&AssertEqual);
+    }
+
+    #[test]
+    fn it_prints_usage_errors() {
+        assert_prints_usage_error(&AssertEqual, &["assert-equal", "only-one"]);
+    }
+}
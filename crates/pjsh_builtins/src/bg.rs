@@ -0,0 +1,89 @@
+use clap::Parser;
+use pjsh_core::command::{Args, Command, CommandResult};
+
+use crate::{status, utils};
+
+/// Command name.
+const NAME: &str = "bg";
+
+/// Resume a stopped background job.
+///
+/// Resuming jobs is only supported on Unix-like platforms.
+///
+/// This is a built-in shell command.
+#[derive(Parser)]
+#[clap(name = NAME, version)]
+struct BgOpts {
+    /// Job id, optionally prefixed by "%".
+    ///
+    /// Defaults to the most recently started job.
+    #[clap(value_parser = utils::parse_job_id)]
+    job: Option<usize>,
+}
+
+/// Implementation for the "bg" built-in command.
+#[derive(Clone)]
+pub struct Bg;
+impl Command for Bg {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn description(&self) -> Option<String> {
+        utils::description::<BgOpts>()
+    }
+
+    fn usage(&self) -> Option<String> {
+        utils::usage::<BgOpts>(NAME)
+    }
+
+    fn run(&self, args: &mut Args) -> CommandResult {
+        match BgOpts::try_parse_from(args.context.args()) {
+            Ok(opts) => background(opts, args),
+            Err(error) => utils::exit_with_parse_error(args.io, error),
+        }
+    }
+}
+
+/// Resumes a stopped background job.
+///
+/// Returns an exit code.
+fn background(opts: BgOpts, args: &mut Args) -> CommandResult {
+    let result = args.context.host.lock().resume_child_process(opts.job);
+    match result {
+        Ok(_) => CommandResult::code(status::SUCCESS),
+        Err(error) => {
+            let _ = writeln!(args.io.stderr, "{NAME}: {error}");
+            CommandResult::code(status::GENERAL_ERROR)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::{assert_prints_help, assert_prints_usage_error, run_builtin};
+
+    use super::*;
+
+    #[test]
+    fn it_fails_without_jobs() {
+        assert_eq!(
+            run_builtin(&Bg, &["bg"]),
+            (
+                status::GENERAL_ERROR,
+                String::new(),
+                "bg: no such job\n".into()
+            )
+        );
+    }
+
+    #[test]
+    fn it_prints_help() {
+        assert_prints_help(&Bg);
+    }
+
+    #[test]
+    fn it_prints_usage_errors() {
+        assert_prints_usage_error(&Bg, &["bg", "%first"]);
+    }
+}
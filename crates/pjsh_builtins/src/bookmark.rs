@@ -0,0 +1,259 @@
+use std::{ffi::OsString, path::PathBuf};
+
+use clap::{Parser, Subcommand};
+use pjsh_core::{
+    command::{Args, Command, CommandResult},
+    utils::{bookmarks_file, path_to_string, resolve_path, word_var, Bookmarks, BookmarksLock},
+};
+
+use crate::{status, utils};
+
+/// Command name.
+const NAME: &str = "bookmark";
+
+/// Manage directory bookmarks.
+///
+/// Bookmarks are stored in "~/.pjsh/bookmarks" and can be used as directories
+/// by prefixing their name with ":", e.g. "cd :proj".
+///
+/// This is a built-in shell command.
+#[derive(Parser)]
+#[clap(name = NAME, version)]
+struct BookmarkOpts {
+    #[clap(subcommand)]
+    action: BookmarkAction,
+}
+
+#[derive(Subcommand)]
+enum BookmarkAction {
+    /// Add a bookmark, replacing any bookmark with the same name.
+    Add {
+        /// Bookmark name.
+        name: String,
+
+        /// Bookmarked directory. Defaults to the current working directory.
+        directory: Option<OsString>,
+    },
+
+    /// List all bookmarks.
+    List,
+
+    /// Remove a bookmark.
+    Remove {
+        /// Bookmark name.
+        name: String,
+    },
+}
+
+/// Implementation for the "bookmark" built-in command.
+#[derive(Clone)]
+pub struct Bookmark;
+impl Command for Bookmark {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn description(&self) -> Option<String> {
+        utils::description::<BookmarkOpts>()
+    }
+
+    fn usage(&self) -> Option<String> {
+        utils::usage::<BookmarkOpts>(NAME)
+    }
+
+    fn run(&self, args: &mut Args) -> CommandResult {
+        match BookmarkOpts::try_parse_from(args.context.args()) {
+            Ok(opts) => bookmark(opts.action, args),
+            Err(error) => utils::exit_with_parse_error(args.io, error),
+        }
+    }
+}
+
+/// Adds, lists or removes bookmarks.
+///
+/// Returns an exit code.
+fn bookmark(action: BookmarkAction, args: &mut Args) -> CommandResult {
+    let Some(file) = bookmarks_file(args.context) else {
+        return exit_with_error(args, "HOME is not set");
+    };
+
+    // Prevent concurrent shells from overwriting each other's changes.
+    let _lock = match action {
+        BookmarkAction::List => None,
+        _ => match BookmarksLock::acquire(&file) {
+            Ok(lock) => Some(lock),
+            Err(error) => return exit_with_error(args, &error.to_string()),
+        },
+    };
+    let mut bookmarks = Bookmarks::load(&file);
+
+    match action {
+        BookmarkAction::Add { name, directory } => {
+            let directory = match directory {
+                Some(directory) => resolve_path(args.context, directory),
+                None => PathBuf::from(word_var(args.context, "PWD").unwrap_or("/")),
+            };
+
+            if !directory.is_dir() {
+                let message = format!("{}: not a directory", path_to_string(&directory));
+                return exit_with_error(args, &message);
+            }
+
+            if let Err(error) = bookmarks.insert(&name, directory) {
+                return exit_with_error(args, &error);
+            }
+        }
+        BookmarkAction::List => {
+            for (name, path) in bookmarks.iter() {
+                if let Err(error) = writeln!(args.io.stdout, "{name}\t{}", path_to_string(path)) {
+                    return utils::exit_with_write_error(NAME, args.io, error);
+                }
+            }
+            return CommandResult::code(status::SUCCESS);
+        }
+        BookmarkAction::Remove { name } => {
+            if bookmarks.remove(&name).is_none() {
+                return exit_with_error(args, &format!("{name}: no such bookmark"));
+            }
+        }
+    }
+
+    match bookmarks.save(&file) {
+        Ok(()) => CommandResult::code(status::SUCCESS),
+        Err(error) => exit_with_error(args, &error.to_string()),
+    }
+}
+
+/// Prints an error message to standard error.
+///
+/// Returns an exit code.
+fn exit_with_error(args: &mut Args, message: &str) -> CommandResult {
+    let _ = writeln!(args.io.stderr, "{NAME}: {message}");
+    CommandResult::code(status::GENERAL_ERROR)
+}
+
+#[cfg(test)]
+mod tests {
+    use pjsh_core::{Context, Value};
+    use tempfile::TempDir;
+
+    use crate::utils::{assert_prints_help, assert_prints_usage_error, file_contents, mock_io};
+
+    use super::*;
+
+    /// Runs the "bookmark" built-in command with `$HOME` set to a directory.
+    ///
+    /// Returns the exit code, stdout and stderr.
+    fn run(home: &TempDir, args: &[&str]) -> (i32, String, String) {
+        let mut ctx = Context::default();
+        ctx.set_var("HOME".into(), Value::Word(path_to_string(home.path())))
+            .unwrap();
+        ctx.replace_args(Some(
+            std::iter::once(NAME)
+                .chain(args.iter().copied())
+                .map(ToString::to_string)
+                .collect(),
+        ));
+        let (mut io, mut stdout, mut stderr) = mock_io();
+
+        match Bookmark.run(&mut Args::new(&mut ctx, &mut io)) {
+            CommandResult::Builtin(result) => (
+                result.code,
+                file_contents(&mut stdout),
+                file_contents(&mut stderr),
+            ),
+            CommandResult::Process(_) => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_adds_lists_and_removes_bookmarks() {
+        let home = TempDir::new().unwrap();
+        let project = TempDir::new().unwrap();
+        let project_path = path_to_string(project.path());
+
+        assert_eq!(
+            run(&home, &["add", "proj", &project_path]),
+            (status::SUCCESS, String::new(), String::new())
+        );
+        assert_eq!(
+            run(&home, &["list"]),
+            (
+                status::SUCCESS,
+                format!("proj\t{project_path}\n"),
+                String::new()
+            )
+        );
+        assert_eq!(
+            run(&home, &["remove", "proj"]),
+            (status::SUCCESS, String::new(), String::new())
+        );
+        assert_eq!(
+            run(&home, &["list"]),
+            (status::SUCCESS, String::new(), String::new())
+        );
+    }
+
+    #[test]
+    fn it_rejects_invalid_bookmarks() {
+        let home = TempDir::new().unwrap();
+        let home_path = path_to_string(home.path());
+
+        assert_eq!(
+            run(&home, &["add", "has space", &home_path]),
+            (
+                status::GENERAL_ERROR,
+                String::new(),
+                "bookmark: has space: invalid bookmark name\n".into()
+            )
+        );
+        assert_eq!(
+            run(&home, &["add", "missing", "/path/to/missing/dir"]).0,
+            status::GENERAL_ERROR
+        );
+        #[cfg(unix)]
+        {
+            let lines_path = home.path().join("a\nb");
+            std::fs::create_dir(&lines_path).unwrap();
+            let lines_path = path_to_string(lines_path);
+            assert_eq!(
+                run(&home, &["add", "lines", &lines_path]),
+                (
+                    status::GENERAL_ERROR,
+                    String::new(),
+                    format!("bookmark: {lines_path}: paths cannot contain line breaks\n")
+                )
+            );
+        }
+        assert_eq!(
+            run(&home, &["remove", "missing"]),
+            (
+                status::GENERAL_ERROR,
+                String::new(),
+                "bookmark: missing: no such bookmark\n".into()
+            )
+        );
+    }
+
+    #[test]
+    fn it_tolerates_corrupt_bookmark_files() {
+        let home = TempDir::new().unwrap();
+        std::fs::create_dir_all(home.path().join(".pjsh")).unwrap();
+        std::fs::write(home.path().join(".pjsh/bookmarks"), b"\xff\xfe\x00garbage").unwrap();
+
+        assert_eq!(
+            run(&home, &["list"]),
+            (status::SUCCESS, String::new(), String::new())
+        );
+    }
+
+    #[test]
+    fn it_prints_help() {
+        assert_prints_help(&Bookmark);
+    }
+
+    #[test]
+    fn it_prints_usage_errors() {
+        assert_prints_usage_error(&Bookmark, &["bookmark", "unknown"]);
+    }
+}
@@ -4,8 +4,7 @@
 use pjsh_core::{
     command::Io,
     command::{Args, Command, CommandResult},
-    utils::{path_to_string, resolve_path, word_var},
-    Value,
+    utils::{bookmarks_file, resolve_path, word_var, Bookmarks},
 };
 
 use crate::{status, utils};
@@ -25,6 +24,10 @@ struct CdOpts {
     ///
     /// If supplied with the directory "-", the working directory is changed to
     /// the shell's previous working directory.
+    ///
+    /// Directories starting with ":" refer to bookmarks, e.g. ":proj" for the
+    /// bookmark "proj" (see the "bookmark" built-in command). Directories named
+    /// ":proj" are used if there is no such bookmark.
     directory: Option<OsString>,
 }
 
@@ -36,6 +39,14 @@ fn name(&self) -> &str {
         NAME
     }
 
+    fn description(&self) -> Option<String> {
+        utils::description::<CdOpts>()
+    }
+
+    fn usage(&self) -> Option<String> {
+        utils::usage::<CdOpts>(NAME)
+    }
+
     fn run(&self, args: &mut Args) -> CommandResult {
         match CdOpts::try_parse_from(args.context.args()) {
             Ok(opts) => change_directory(opts, args),
@@ -52,6 +63,20 @@ fn run(&self, args: &mut Args) -> CommandResult {
 fn change_directory(opts: CdOpts, args: &mut Args) -> CommandResult {
     let directory = match &opts.directory {
         Some(dir) if dir == "-" => word_var(args.context, "OLDPWD").map(PathBuf::from),
+        Some(dir) if dir.to_string_lossy().starts_with(':') => {
+            let name = &dir.to_string_lossy()[1..];
+            let bookmarks = bookmarks_file(args.context).map(Bookmarks::load);
+            match bookmarks.as_ref().and_then(|bookmarks| bookmarks.get(name)) {
+                Some(path) => Some(resolve_path(args.context, path)),
+                None if resolve_path(args.context, dir).is_dir() => {
+                    Some(resolve_path(args.context, dir))
+                }
+                None => {
+                    let message = format!("{name}: no such bookmark.");
+                    return exit_with_error(status::GENERAL_ERROR, args.io, &message);
+                }
+            }
+        }
         Some(dir) => Some(resolve_path(args.context, dir)),
         None => word_var(args.context, "HOME").map(PathBuf::from),
     };
@@ -63,15 +88,10 @@ fn change_directory(opts: CdOpts, args: &mut Args) -> CommandResult {
                 return exit_with_error(status::GENERAL_ERROR, args.io, "Path is not a directory.");
             }
 
-            // Keep track of the old working directory within the context.
-            if let Some(pwd) = args.context.get_var("PWD").map(|pwd| pwd.to_owned()) {
-                args.context.set_var("OLDPWD".to_owned(), pwd);
-            }
-
-            // Set the current working directory within the current context.
-            let new_path = path_to_string(&path);
-            args.context
-                .set_var("PWD".to_string(), Value::Word(new_path.clone()));
+            let new_path = match utils::set_working_directory(args.context, &path) {
+                Ok(new_path) => new_path,
+                Err(err) => return exit_with_error(status::GENERAL_ERROR, args.io, &err),
+            };
 
             // Using "-" as a directory should be equivalent to "cd - && pwd".
             if opts.directory.filter(|p| p == "-").is_some() {
@@ -101,10 +121,10 @@ mod tests {
         path::Path,
     };
 
-    use pjsh_core::{utils::path_to_string, Context, Scope};
+    use pjsh_core::{utils::path_to_string, Context, Scope, Value};
     use tempfile::TempDir;
 
-    use crate::utils::{file_contents, mock_io};
+    use crate::utils::{assert_prints_help, assert_prints_usage_error, file_contents, mock_io};
 
     use super::*;
 
@@ -124,7 +144,8 @@ fn cd_context<P: AsRef<Path>>(path: P) -> Context {
     fn it_can_change_working_directory() {
         let dir = TempDir::new().unwrap();
         let mut ctx = cd_context(&dir);
-        ctx.set_var("PWD".into(), Value::Word("old-pwd".into()));
+        ctx.set_var("PWD".into(), Value::Word("old-pwd".into()))
+            .unwrap();
         let (mut io, _stdout, _stderr) = mock_io();
         let cd = Cd {};
 
@@ -151,7 +172,8 @@ fn it_can_change_working_directory_to_home() {
             HashMap::default(),
             HashSet::default(),
         )]);
-        ctx.set_var("HOME".into(), Value::Word(path_to_string(&home)));
+        ctx.set_var("HOME".into(), Value::Word(path_to_string(&home)))
+            .unwrap();
         let (mut io, _stdout, _stderr) = mock_io();
         let cd = Cd {};
 
@@ -174,7 +196,8 @@ fn it_can_change_working_directory_to_oldpwd() {
             HashMap::default(),
             HashSet::default(),
         )]);
-        ctx.set_var("OLDPWD".into(), Value::Word(path_to_string(&oldpwd)));
+        ctx.set_var("OLDPWD".into(), Value::Word(path_to_string(&oldpwd)))
+            .unwrap();
         let (mut io, mut stdout, _stderr) = mock_io();
         let cd = Cd {};
 
@@ -191,6 +214,72 @@ fn it_can_change_working_directory_to_oldpwd() {
         }
     }
 
+    #[test]
+    fn it_can_change_working_directory_to_bookmarks() {
+        let home = TempDir::new().unwrap();
+        let project = TempDir::new().unwrap();
+        let mut bookmarks = Bookmarks::default();
+        bookmarks
+            .insert("proj", project.path().to_path_buf())
+            .unwrap();
+        bookmarks.save(home.path().join(".pjsh/bookmarks")).unwrap();
+
+        let mut ctx = cd_context(":proj");
+        ctx.set_var("HOME".into(), Value::Word(path_to_string(&home)))
+            .unwrap();
+        let (mut io, _stdout, _stderr) = mock_io();
+
+        let mut args = Args::new(&mut ctx, &mut io);
+        if let CommandResult::Builtin(result) = Cd.run(&mut args) {
+            assert_eq!(result.code, status::SUCCESS);
+            assert_eq!(
+                ctx.get_var("PWD"),
+                Some(&Value::Word(path_to_string(project.path())))
+            );
+        } else {
+            unreachable!()
+        }
+
+        let mut ctx = cd_context(":missing");
+        ctx.set_var("HOME".into(), Value::Word(path_to_string(&home)))
+            .unwrap();
+        let (mut io, _stdout, mut stderr) = mock_io();
+
+        let mut args = Args::new(&mut ctx, &mut io);
+        if let CommandResult::Builtin(result) = Cd.run(&mut args) {
+            assert_eq!(result.code, status::GENERAL_ERROR);
+            assert_eq!(ctx.get_var("PWD"), None);
+            assert_eq!(
+                file_contents(&mut stderr),
+                "cd: missing: no such bookmark.\n"
+            );
+        } else {
+            unreachable!()
+        }
+    }
+
+    #[test]
+    fn it_falls_back_to_directories_named_like_bookmarks() {
+        let home = TempDir::new().unwrap();
+        let dir = home.path().join(":missing");
+        std::fs::create_dir(&dir).unwrap();
+
+        let mut ctx = cd_context(":missing");
+        ctx.set_var("HOME".into(), Value::Word(path_to_string(&home)))
+            .unwrap();
+        ctx.set_var("PWD".into(), Value::Word(path_to_string(&home)))
+            .unwrap();
+        let (mut io, _stdout, _stderr) = mock_io();
+
+        let mut args = Args::new(&mut ctx, &mut io);
+        if let CommandResult::Builtin(result) = Cd.run(&mut args) {
+            assert_eq!(result.code, status::SUCCESS);
+            assert_eq!(ctx.get_var("PWD"), Some(&Value::Word(path_to_string(dir))));
+        } else {
+            unreachable!()
+        }
+    }
+
     #[test]
     fn it_cannot_change_working_directory_to_missing_directories() {
         let dir = PathBuf::from("/path/to/missing/dir");
@@ -206,4 +295,14 @@ fn it_cannot_change_working_directory_to_missing_directories() {
             unreachable!()
         }
     }
+
+    #[test]
+    fn it_prints_help() {
+        assert_prints_help(&Cd);
+    }
+
+    #[test]
+    fn it_prints_usage_errors() {
+        assert_prints_usage_error(&Cd, &["cd", "first", "second"]);
+    }
 }
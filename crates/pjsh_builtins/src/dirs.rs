@@ -0,0 +1,391 @@
+use std::{
+    ffi::OsString,
+    path::{Path, PathBuf},
+};
+
+use clap::Parser;
+use pjsh_core::{
+    command::{Args, Command, CommandResult},
+    utils::{path_to_string, resolve_path, word_var},
+    Context,
+};
+
+use crate::{status, utils};
+
+/// Command name for [`Dirs`].
+const DIRS_NAME: &str = "dirs";
+
+/// Command name for [`Popd`].
+const POPD_NAME: &str = "popd";
+
+/// Command name for [`Pushd`].
+const PUSHD_NAME: &str = "pushd";
+
+/// Print the directory stack.
+///
+/// The current working directory is printed first, followed by the most
+/// recently pushed directories.
+///
+/// This is a built-in shell command.
+#[derive(Parser)]
+#[clap(name = DIRS_NAME, version)]
+struct DirsOpts;
+
+/// Remove the top directory from the directory stack, and change to it.
+///
+/// This is a built-in shell command.
+#[derive(Parser)]
+#[clap(name = POPD_NAME, version)]
+struct PopdOpts;
+
+/// Push the working directory onto the directory stack, and change directory.
+///
+/// If no directory is supplied, the working directory is swapped with the top
+/// directory in the stack.
+///
+/// This is a built-in shell command.
+#[derive(Parser)]
+#[clap(name = PUSHD_NAME, version)]
+struct PushdOpts {
+    /// Directory to change to.
+    directory: Option<OsString>,
+}
+
+/// Implementation for the "dirs" built-in command.
+#[derive(Clone)]
+pub struct Dirs;
+impl Command for Dirs {
+    fn name(&self) -> &str {
+        DIRS_NAME
+    }
+
+    fn description(&self) -> Option<String> {
+        utils::description::<DirsOpts>()
+    }
+
+    fn usage(&self) -> Option<String> {
+        utils::usage::<DirsOpts>(DIRS_NAME)
+    }
+
+    fn run(&self, args: &mut Args) -> CommandResult {
+        match DirsOpts::try_parse_from(args.context.args()) {
+            Ok(_) => print_stack(DIRS_NAME, args),
+            Err(error) => utils::exit_with_parse_error(args.io, error),
+        }
+    }
+}
+
+/// Implementation for the "popd" built-in command.
+#[derive(Clone)]
+pub struct Popd;
+impl Command for Popd {
+    fn name(&self) -> &str {
+        POPD_NAME
+    }
+
+    fn description(&self) -> Option<String> {
+        utils::description::<PopdOpts>()
+    }
+
+    fn usage(&self) -> Option<String> {
+        utils::usage::<PopdOpts>(POPD_NAME)
+    }
+
+    fn run(&self, args: &mut Args) -> CommandResult {
+        match PopdOpts::try_parse_from(args.context.args()) {
+            Ok(_) => pop_directory(args),
+            Err(error) => utils::exit_with_parse_error(args.io, error),
+        }
+    }
+}
+
+/// Implementation for the "pushd" built-in command.
+#[derive(Clone)]
+pub struct Pushd;
+impl Command for Pushd {
+    fn name(&self) -> &str {
+        PUSHD_NAME
+    }
+
+    fn description(&self) -> Option<String> {
+        utils::description::<PushdOpts>()
+    }
+
+    fn usage(&self) -> Option<String> {
+        utils::usage::<PushdOpts>(PUSHD_NAME)
+    }
+
+    fn run(&self, args: &mut Args) -> CommandResult {
+        match PushdOpts::try_parse_from(args.context.args()) {
+            Ok(opts) => push_directory(opts, args),
+            Err(error) => utils::exit_with_parse_error(args.io, error),
+        }
+    }
+}
+
+/// Changes to the top directory in a context's directory stack, removing it
+/// from the stack.
+///
+/// Returns an exit code.
+fn pop_directory(args: &mut Args) -> CommandResult {
+    let Some(directory) = args.context.dir_stack.last().cloned() else {
+        let _ = writeln!(args.io.stderr, "{POPD_NAME}: directory stack empty");
+        return CommandResult::code(status::GENERAL_ERROR);
+    };
+
+    if !directory.is_dir() {
+        return not_a_directory(POPD_NAME, &directory, args);
+    }
+
+    if let Err(err) = utils::set_working_directory(args.context, &directory) {
+        let _ = writeln!(args.io.stderr, "{POPD_NAME}: {err}");
+        return CommandResult::code(status::GENERAL_ERROR);
+    }
+
+    args.context.dir_stack.pop();
+    print_stack(POPD_NAME, args)
+}
+
+/// Pushes the working directory of a context onto its directory stack, and
+/// changes directory.
+///
+/// Returns an exit code.
+fn push_directory(opts: PushdOpts, args: &mut Args) -> CommandResult {
+    let directory = match &opts.directory {
+        Some(directory) => resolve_path(args.context, directory),
+        None => match args.context.dir_stack.last() {
+            Some(directory) => directory.clone(),
+            None => {
+                let _ = writeln!(args.io.stderr, "{PUSHD_NAME}: no other directory");
+                return CommandResult::code(status::GENERAL_ERROR);
+            }
+        },
+    };
+
+    if !directory.is_dir() {
+        return not_a_directory(PUSHD_NAME, &directory, args);
+    }
+
+    let pwd = word_var(args.context, "PWD").map(PathBuf::from);
+    if let Err(err) = utils::set_working_directory(args.context, &directory) {
+        let _ = writeln!(args.io.stderr, "{PUSHD_NAME}: {err}");
+        return CommandResult::code(status::GENERAL_ERROR);
+    }
+
+    // Swap the top two entries if no directory is supplied.
+    if opts.directory.is_none() {
+        args.context.dir_stack.pop();
+    }
+
+    if let Some(pwd) = pwd {
+        args.context.dir_stack.push(pwd);
+    }
+
+    print_stack(PUSHD_NAME, args)
+}
+
+/// Prints an error message stating that a path is not a directory.
+///
+/// Returns an exit code.
+fn not_a_directory(name: &str, path: &Path, args: &mut Args) -> CommandResult {
+    let path = path_to_string(path);
+    let _ = writeln!(args.io.stderr, "{name}: {path}: not a directory");
+    CommandResult::code(status::GENERAL_ERROR)
+}
+
+/// Prints a context's directory stack to stdout.
+///
+/// Returns an exit code.
+fn print_stack(name: &str, args: &mut Args) -> CommandResult {
+    if let Err(error) = writeln!(args.io.stdout, "{}", format_stack(args.context)) {
+        let _ = writeln!(args.io.stderr, "{name}: {error}");
+        return CommandResult::code(status::GENERAL_ERROR);
+    }
+
+    CommandResult::code(status::SUCCESS)
+}
+
+/// Returns a context's working directory and directory stack as a line of
+/// space-separated directories.
+///
+/// Directories within the home directory are abbreviated using `~`.
+fn format_stack(context: &Context) -> String {
+    let home = word_var(context, "HOME").map(Path::new);
+    let pwd = word_var(context, "PWD").map(PathBuf::from);
+
+    pwd.iter()
+        .chain(context.dir_stack.iter().rev())
+        .map(|directory| abbreviate_home(directory, home))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Replaces a leading home directory in a path with `~`.
+fn abbreviate_home(path: &Path, home: Option<&Path>) -> String {
+    match home.and_then(|home| path.strip_prefix(home).ok()) {
+        Some(relative) if relative.as_os_str().is_empty() => "~".to_owned(),
+        Some(relative) => format!("~/{}", path_to_string(relative)),
+        None => path_to_string(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pjsh_core::Value;
+    use tempfile::TempDir;
+
+    use crate::utils::{assert_prints_help, assert_prints_usage_error, file_contents, mock_io};
+
+    use super::*;
+
+    /// Runs a built-in command within a context.
+    ///
+    /// Returns the exit code along with the contents written to stdout and stderr.
+    fn run(command: &dyn Command, args: &[&str], ctx: &mut Context) -> (i32, String, String) {
+        ctx.replace_args(Some(args.iter().map(ToString::to_string).collect()));
+        let (mut io, mut stdout, mut stderr) = mock_io();
+        let mut args = Args::new(ctx, &mut io);
+
+        let CommandResult::Builtin(result) = command.run(&mut args) else {
+            unreachable!()
+        };
+        (
+            result.code,
+            file_contents(&mut stdout),
+            file_contents(&mut stderr),
+        )
+    }
+
+    /// Returns a context with a working directory.
+    fn context_in(dir: &Path) -> Context {
+        let mut ctx = Context::default();
+        ctx.set_var("PWD".into(), Value::Word(path_to_string(dir)))
+            .unwrap();
+        ctx
+    }
+
+    /// Returns the canonical path of a temporary directory as a string.
+    fn dir_string(dir: &TempDir) -> String {
+        path_to_string(dir.path().canonicalize().unwrap())
+    }
+
+    #[test]
+    fn it_pushes_and_pops_directories() {
+        let first = TempDir::new().unwrap();
+        let second = TempDir::new().unwrap();
+        let second_str = dir_string(&second);
+        let mut ctx = context_in(first.path());
+        let pwd = |ctx: &Context| word_var(ctx, "PWD").map(ToString::to_string);
+        let oldpwd = |ctx: &Context| word_var(ctx, "OLDPWD").map(ToString::to_string);
+
+        assert_eq!(
+            run(&Pushd, &["pushd", &second_str], &mut ctx),
+            (
+                status::SUCCESS,
+                format!("{second_str} {}\n", path_to_string(first.path())),
+                String::new()
+            )
+        );
+        assert_eq!(pwd(&ctx), Some(second_str.clone()));
+        assert_eq!(oldpwd(&ctx), Some(path_to_string(first.path())));
+
+        assert_eq!(run(&Popd, &["popd"], &mut ctx).0, status::SUCCESS);
+        assert_eq!(pwd(&ctx), Some(path_to_string(first.path())));
+        assert_eq!(oldpwd(&ctx), Some(second_str));
+        assert!(ctx.dir_stack.is_empty());
+
+        assert_eq!(
+            run(&Popd, &["popd"], &mut ctx),
+            (
+                status::GENERAL_ERROR,
+                String::new(),
+                "popd: directory stack empty\n".into()
+            )
+        );
+        assert_eq!(
+            run(&Dirs, &["dirs"], &mut ctx).1,
+            format!("{}\n", path_to_string(first.path()))
+        );
+    }
+
+    #[test]
+    fn it_swaps_the_top_directories() {
+        let first = TempDir::new().unwrap();
+        let second = TempDir::new().unwrap();
+        let (first_str, second_str) = (dir_string(&first), dir_string(&second));
+        let mut ctx = context_in(&first.path().canonicalize().unwrap());
+
+        assert_eq!(
+            run(&Pushd, &["pushd"], &mut ctx),
+            (
+                status::GENERAL_ERROR,
+                String::new(),
+                "pushd: no other directory\n".into()
+            )
+        );
+
+        run(&Pushd, &["pushd", &second_str], &mut ctx);
+        assert_eq!(
+            run(&Pushd, &["pushd"], &mut ctx).1,
+            format!("{first_str} {second_str}\n")
+        );
+        assert_eq!(word_var(&ctx, "PWD"), Some(first_str.as_str()));
+        assert_eq!(word_var(&ctx, "OLDPWD"), Some(second_str.as_str()));
+    }
+
+    #[test]
+    fn it_rejects_missing_directories() {
+        let dir = TempDir::new().unwrap();
+        let missing = dir.path().join("missing");
+        let mut ctx = context_in(dir.path());
+
+        assert_eq!(
+            run(&Pushd, &["pushd", &path_to_string(&missing)], &mut ctx),
+            (
+                status::GENERAL_ERROR,
+                String::new(),
+                format!("pushd: {}: not a directory\n", path_to_string(&missing))
+            )
+        );
+        assert!(ctx.dir_stack.is_empty());
+
+        ctx.dir_stack.push(missing.clone());
+        assert_eq!(run(&Popd, &["popd"], &mut ctx).0, status::GENERAL_ERROR);
+        assert_eq!(ctx.dir_stack, vec![missing]);
+    }
+
+    #[test]
+    fn it_abbreviates_the_home_directory() {
+        let home = Path::new("/home/user");
+        assert_eq!(abbreviate_home(Path::new("/home/user"), Some(home)), "~");
+        assert_eq!(
+            abbreviate_home(Path::new("/home/user/src/pjsh"), Some(home)),
+            "~/src/pjsh"
+        );
+        assert_eq!(
+            abbreviate_home(Path::new("/home/username"), Some(home)),
+            "/home/username"
+        );
+        assert_eq!(abbreviate_home(Path::new("/home/user"), None), "/home/user");
+
+        let mut ctx = context_in(Path::new("/home/user/src"));
+        ctx.set_var("HOME".into(), Value::Word("/home/user".into()))
+            .unwrap();
+        ctx.dir_stack = vec!["/tmp".into(), "/home/user".into()];
+        assert_eq!(format_stack(&ctx), "~/src ~ /tmp");
+    }
+
+    #[test]
+    fn it_prints_help() {
+        assert_prints_help(&Dirs);
+        assert_prints_help(&Popd);
+        assert_prints_help(&Pushd);
+    }
+
+    #[test]
+    fn it_prints_usage_errors() {
+        assert_prints_usage_error(&Dirs, &["dirs", "extra"]);
+        assert_prints_usage_error(&Popd, &["popd", "extra"]);
+        assert_prints_usage_error(&Pushd, &["pushd", "first", "second"]);
+    }
+}
@@ -0,0 +1,168 @@
+use clap::Parser;
+use pjsh_core::command::{Args, Command, CommandResult};
+
+use crate::{status, utils};
+
+/// Command name.
+const NAME: &str = "disown";
+
+/// Remove background jobs from the shell.
+///
+/// Disowned jobs are no longer listed by "jobs", and keep running when the
+/// shell exits instead of being killed.
+///
+/// This is a built-in shell command.
+#[derive(Parser)]
+#[clap(name = NAME, version)]
+struct DisownOpts {
+    /// Disown all jobs.
+    #[clap(short = 'a', conflicts_with = "jobs")]
+    all: bool,
+
+    /// Job ids, optionally prefixed by "%".
+    ///
+    /// Defaults to the most recently started job.
+    #[clap(value_parser = utils::parse_job_id)]
+    jobs: Vec<usize>,
+}
+
+/// Implementation for the "disown" built-in command.
+#[derive(Clone)]
+pub struct Disown;
+impl Command for Disown {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn description(&self) -> Option<String> {
+        utils::description::<DisownOpts>()
+    }
+
+    fn usage(&self) -> Option<String> {
+        utils::usage::<DisownOpts>(NAME)
+    }
+
+    fn run(&self, args: &mut Args) -> CommandResult {
+        match DisownOpts::try_parse_from(args.context.args()) {
+            Ok(opts) => disown(opts, args),
+            Err(error) => utils::exit_with_parse_error(args.io, error),
+        }
+    }
+}
+
+/// Removes jobs from the context's host without killing them.
+///
+/// Returns an exit code.
+fn disown(opts: DisownOpts, args: &mut Args) -> CommandResult {
+    let mut host = args.context.host.lock();
+
+    // Child processes are detached, rather than killed, when they are dropped.
+    if opts.all {
+        while host.take_child_process(None).is_some() {}
+        return CommandResult::code(status::SUCCESS);
+    }
+
+    if opts.jobs.is_empty() {
+        if host.take_child_process(None).is_none() {
+            let _ = writeln!(args.io.stderr, "{NAME}: no such job");
+            return CommandResult::code(status::GENERAL_ERROR);
+        }
+        return CommandResult::code(status::SUCCESS);
+    }
+
+    let mut code = status::SUCCESS;
+    for id in opts.jobs {
+        if host.take_child_process(Some(id)).is_none() {
+            let _ = writeln!(args.io.stderr, "{NAME}: %{id}: no such job");
+            code = status::GENERAL_ERROR;
+        }
+    }
+
+    CommandResult::code(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::process;
+
+    use pjsh_core::Context;
+
+    use crate::utils::{assert_prints_help, assert_prints_usage_error, file_contents, mock_io};
+
+    use super::*;
+
+    /// Runs the "disown" built-in within a context.
+    ///
+    /// Returns the exit code along with the contents written to stderr.
+    fn run_disown(args: &[&str], ctx: &mut Context) -> (i32, String) {
+        ctx.replace_args(Some(args.iter().map(ToString::to_string).collect()));
+        let (mut io, _stdout, mut stderr) = mock_io();
+        let mut args = Args::new(ctx, &mut io);
+
+        let CommandResult::Builtin(result) = Disown.run(&mut args) else {
+            unreachable!()
+        };
+        (result.code, file_contents(&mut stderr))
+    }
+
+    /// Returns a context with a number of jobs that exit immediately.
+    fn context_with_jobs(count: usize) -> Context {
+        let ctx = Context::default();
+        for _ in 0..count {
+            let child = process::Command::new("sh")
+                .args(["-c", "exit 0"])
+                .spawn()
+                .unwrap();
+            ctx.host.lock().add_child_process(child, None);
+        }
+        ctx
+    }
+
+    /// Returns the ids of all jobs within a context.
+    fn job_ids(ctx: &Context) -> Vec<usize> {
+        ctx.host.lock().jobs().iter().map(|job| job.id).collect()
+    }
+
+    #[test]
+    fn it_disowns_jobs() {
+        let mut ctx = context_with_jobs(3);
+
+        assert_eq!(run_disown(&["disown", "%2"], &mut ctx), (0, String::new()));
+        assert_eq!(run_disown(&["disown"], &mut ctx), (0, String::new()));
+        assert_eq!(job_ids(&ctx), [1]);
+    }
+
+    #[test]
+    fn it_disowns_all_jobs() {
+        let mut ctx = context_with_jobs(3);
+
+        assert_eq!(run_disown(&["disown", "-a"], &mut ctx), (0, String::new()));
+        assert_eq!(job_ids(&ctx), Vec::<usize>::new());
+        assert_eq!(run_disown(&["disown", "-a"], &mut ctx), (0, String::new()));
+    }
+
+    #[test]
+    fn it_requires_existing_jobs() {
+        let mut ctx = context_with_jobs(1);
+
+        assert_eq!(
+            run_disown(&["disown", "%1", "%2"], &mut ctx),
+            (status::GENERAL_ERROR, "disown: %2: no such job\n".into())
+        );
+        assert_eq!(
+            run_disown(&["disown"], &mut ctx),
+            (status::GENERAL_ERROR, "disown: no such job\n".into())
+        );
+    }
+
+    #[test]
+    fn it_prints_help() {
+        assert_prints_help(&Disown);
+    }
+
+    #[test]
+    fn it_prints_usage_errors() {
+        assert_prints_usage_error(&Disown, &["disown", "%first"]);
+        assert_prints_usage_error(&Disown, &["disown", "-a", "1"]);
+    }
+}
@@ -31,6 +31,14 @@ fn name(&self) -> &str {
         NAME
     }
 
+    fn description(&self) -> Option<String> {
+        utils::description::<EchoOpts>()
+    }
+
+    fn usage(&self) -> Option<String> {
+        utils::usage::<EchoOpts>(NAME)
+    }
+
     fn run(&self, args: &mut Args) -> CommandResult {
         match EchoOpts::try_parse_from(args.context.args()) {
             Ok(opts) => print_text(opts, args.io),
@@ -43,16 +51,10 @@ fn run(&self, args: &mut Args) -> CommandResult {
 fn print_text(opts: EchoOpts, io: &mut Io) -> CommandResult {
     match try_print_words(opts, io) {
         Ok(_) => CommandResult::code(status::SUCCESS),
-        Err(error) => print_error(status::GENERAL_ERROR, &error.to_string(), io),
+        Err(error) => utils::exit_with_write_error(NAME, io, error),
     }
 }
 
-/// Prints an error message to stderr and returns a status code.
-fn print_error(status: i32, error: &str, io: &mut Io) -> CommandResult {
-    let _ = writeln!(io.stderr, "{}: {}", NAME, error);
-    CommandResult::code(status)
-}
-
 /// Tries to print words to stdout.
 fn try_print_words(opts: EchoOpts, io: &mut Io) -> std::io::Result<()> {
     let mut words = opts.text.iter();
@@ -83,7 +85,9 @@ mod tests {
 
     use pjsh_core::{Context, Scope};
 
-    use crate::utils::{file_contents, mock_io};
+    use crate::utils::{
+        assert_prints_help, assert_prints_usage_error, broken_pipe_io, file_contents, mock_io,
+    };
 
     use super::*;
 
@@ -155,4 +159,34 @@ fn it_can_print_without_final_newline() {
             unreachable!()
         }
     }
+
+    #[test]
+    fn it_exits_quietly_on_broken_pipes() {
+        let mut ctx = Context::with_scopes(vec![Scope::new(
+            String::new(),
+            Some(vec!["echo".into(), "message".into()]),
+            HashMap::default(),
+            HashMap::default(),
+            HashSet::default(),
+        )]);
+        let (mut io, mut stderr) = broken_pipe_io();
+        let mut args = Args::new(&mut ctx, &mut io);
+
+        if let CommandResult::Builtin(result) = Echo.run(&mut args) {
+            assert_eq!(result.code, status::BROKEN_PIPE);
+            assert_eq!(&file_contents(&mut stderr), "");
+        } else {
+            unreachable!()
+        }
+    }
+
+    #[test]
+    fn it_prints_help() {
+        assert_prints_help(&Echo);
+    }
+
+    #[test]
+    fn it_prints_usage_errors() {
+        assert_prints_usage_error(&Echo, &["echo", "--unknown"]);
+    }
 }
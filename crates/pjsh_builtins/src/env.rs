@@ -0,0 +1,211 @@
+use std::collections::BTreeMap;
+
+use clap::Parser;
+use pjsh_core::{
+    command::{Args, Command, CommandResult},
+    find_in_path,
+};
+use pjsh_eval::call_external_program;
+
+use crate::{status, utils};
+
+/// Command name.
+const NAME: &str = "env";
+
+/// Print exported variables, or run a program with a modified environment.
+///
+/// Leading "KEY=VALUE" arguments are added to the program's environment
+/// without modifying any variables within the shell. If no program is
+/// supplied, the resulting environment is printed instead.
+///
+/// This is a built-in shell command.
+#[derive(Parser)]
+#[clap(name = NAME, version)]
+struct EnvOpts {
+    /// Start with an empty environment.
+    #[clap(short = 'i', long = "ignore-environment")]
+    ignore_environment: bool,
+
+    /// Remove a variable from the environment.
+    #[clap(short = 'u', long = "unset", value_name = "KEY")]
+    unset: Vec<String>,
+
+    /// Variable assignments followed by a program and its arguments.
+    #[clap(
+        value_name = "KEY=VALUE|PROGRAM",
+        allow_hyphen_values = true,
+        trailing_var_arg = true
+    )]
+    args: Vec<String>,
+}
+
+/// Implementation for the "env" built-in command.
+#[derive(Clone)]
+pub struct Env;
+impl Command for Env {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn description(&self) -> Option<String> {
+        utils::description::<EnvOpts>()
+    }
+
+    fn usage(&self) -> Option<String> {
+        utils::usage::<EnvOpts>(NAME)
+    }
+
+    fn run(&self, args: &mut Args) -> CommandResult {
+        match EnvOpts::try_parse_from(args.context.args()) {
+            Ok(opts) => env(opts, args),
+            Err(error) => utils::exit_with_parse_error(args.io, error),
+        }
+    }
+}
+
+/// Prints an environment, or runs a program within it.
+///
+/// Returns an exit code, or a process to spawn.
+fn env(opts: EnvOpts, args: &mut Args) -> CommandResult {
+    let mut vars: BTreeMap<String, String> = match opts.ignore_environment {
+        true => BTreeMap::new(),
+        false => args
+            .context
+            .exported_vars()
+            .into_iter()
+            .map(|(key, value)| (key.to_owned(), value.to_owned()))
+            .collect(),
+    };
+
+    for key in &opts.unset {
+        vars.remove(key);
+    }
+
+    let mut command_args = opts.args.into_iter().peekable();
+    while let Some((key, value)) = command_args.peek().and_then(|arg| parse_assignment(arg)) {
+        vars.insert(key, value);
+        command_args.next();
+    }
+
+    let Some(name) = command_args.next() else {
+        for (key, value) in vars {
+            if let Err(error) = writeln!(args.io.stdout, "{key}={value}") {
+                return utils::exit_with_write_error(NAME, args.io, error);
+            }
+        }
+        return CommandResult::code(status::SUCCESS);
+    };
+
+    let Some(program) = find_in_path(&name, args.context).filter(|path| path.is_file()) else {
+        let _ = writeln!(args.io.stderr, "{NAME}: {name}: command not found");
+        return CommandResult::code(status::COMMAND_NOT_FOUND);
+    };
+
+    let command_args: Vec<String> = command_args.collect();
+    match call_external_program(&program, &command_args, args.context) {
+        Ok(mut command) => {
+            command.env_clear();
+            command.envs(vars);
+            CommandResult::from(command)
+        }
+        Err(error) => {
+            let _ = writeln!(args.io.stderr, "{NAME}: {name}: {error}");
+            CommandResult::code(status::CANNOT_EXECUTE)
+        }
+    }
+}
+
+/// Parses a `KEY=VALUE` assignment.
+///
+/// Returns `None` if the argument is not an assignment.
+fn parse_assignment(arg: &str) -> Option<(String, String)> {
+    let (key, value) = arg.split_once('=')?;
+    (!key.is_empty()).then(|| (key.to_owned(), value.to_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::OsStr;
+
+    use pjsh_core::{Context, Value};
+
+    use crate::utils::{assert_prints_help, file_contents, mock_io, run_builtin};
+
+    use super::*;
+
+    /// Returns a context with an exported variable.
+    fn exported_context() -> Context {
+        let mut ctx = Context::default();
+        ctx.set_var("EXPORTED".into(), Value::Word("value".into()))
+            .unwrap();
+        ctx.export_var("EXPORTED".into()).unwrap();
+        ctx
+    }
+
+    /// Runs the "env" built-in command within a context.
+    ///
+    /// Returns the command result along with the contents written to stdout.
+    fn run(ctx: &mut Context, args: &[&str]) -> (CommandResult, String) {
+        ctx.replace_args(Some(
+            std::iter::once(NAME)
+                .chain(args.iter().copied())
+                .map(ToString::to_string)
+                .collect(),
+        ));
+        let (mut io, mut stdout, _stderr) = mock_io();
+        let result = Env.run(&mut Args::new(ctx, &mut io));
+        (result, file_contents(&mut stdout))
+    }
+
+    #[test]
+    fn it_prints_exported_variables() {
+        let mut ctx = exported_context();
+
+        let (_, stdout) = run(&mut ctx, &[]);
+        assert_eq!(stdout, "EXPORTED=value\n");
+
+        let (_, stdout) = run(&mut ctx, &["-u", "EXPORTED", "B=2", "A=1"]);
+        assert_eq!(stdout, "A=1\nB=2\n");
+
+        let (_, stdout) = run(&mut ctx, &["-i", "A=1"]);
+        assert_eq!(stdout, "A=1\n");
+    }
+
+    #[test]
+    fn it_runs_programs_with_a_modified_environment() {
+        let mut ctx = exported_context();
+        ctx.set_var("PATH".into(), Value::Word("/bin:/usr/bin".into()))
+            .unwrap();
+
+        let (result, _) = run(&mut ctx, &["-i", "A=1", "sh", "-c", "B=2"]);
+        let CommandResult::Process(process) = result else {
+            panic!("env should spawn a process");
+        };
+        let envs: Vec<_> = process.command.get_envs().collect();
+        let args: Vec<_> = process.command.get_args().collect();
+
+        assert_eq!(envs, [(OsStr::new("A"), Some(OsStr::new("1")))]);
+        assert_eq!(args, ["-c", "B=2"]);
+
+        // The shell's own variables should never be modified.
+        assert_eq!(ctx.get_var("A"), None);
+        assert_eq!(ctx.exported_vars().get("EXPORTED"), Some(&"value"));
+    }
+
+    #[test]
+    fn it_requires_existing_programs() {
+        assert_eq!(
+            run_builtin(&Env, &["env", "A=1", "missing-program"]),
+            (
+                status::COMMAND_NOT_FOUND,
+                String::new(),
+                "env: missing-program: command not found\n".into()
+            )
+        );
+    }
+
+    #[test]
+    fn it_prints_help() {
+        assert_prints_help(&Env);
+    }
+}
@@ -0,0 +1,129 @@
+use clap::Parser;
+use pjsh_core::command::{Action, Args, Command, CommandResult};
+use pjsh_parse::parse;
+
+use crate::{status, utils};
+
+/// Command name.
+const NAME: &str = "eval";
+
+/// Execute arguments as shell code in the current shell.
+///
+/// The arguments are joined with spaces before being parsed. Variables and
+/// functions that are defined by the code remain defined afterwards.
+///
+/// This is a built-in shell command.
+#[derive(Parser)]
+#[clap(name = NAME, version)]
+struct EvalOpts {
+    /// Shell code to execute.
+    #[clap(allow_hyphen_values = true, trailing_var_arg = true)]
+    code: Vec<String>,
+}
+
+/// Implementation for the "eval" built-in command.
+#[derive(Clone)]
+pub struct Eval;
+impl Command for Eval {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn description(&self) -> Option<String> {
+        utils::description::<EvalOpts>()
+    }
+
+    fn usage(&self) -> Option<String> {
+        utils::usage::<EvalOpts>(NAME)
+    }
+
+    fn run(&self, args: &mut Args) -> CommandResult {
+        match EvalOpts::try_parse_from(args.context.args()) {
+            Ok(opts) => eval(opts, args),
+            Err(error) => utils::exit_with_parse_error(args.io, error),
+        }
+    }
+}
+
+/// Parses code to be executed within the caller's context.
+///
+/// The code is executed by the shell once the command has returned, so that
+/// positional arguments and control flow statements, such as `return`, refer
+/// to the caller.
+///
+/// Returns the exit code of the last executed statement.
+fn eval(opts: EvalOpts, args: &mut Args) -> CommandResult {
+    let src = opts.code.join(" ");
+    match parse(&src, &args.context.aliases) {
+        Ok(program) => CommandResult::with_actions(status::SUCCESS, vec![Action::Execute(program)]),
+        Err(error) => {
+            let message = error
+                .snippet(&src, false)
+                .unwrap_or_else(|| format!("{error}: {src}"));
+            let _ = writeln!(args.io.stderr, "{NAME}: {message}");
+            CommandResult::code(status::GENERAL_ERROR)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pjsh_core::Context;
+
+    use crate::utils::{assert_prints_help, file_contents, mock_io};
+
+    use super::*;
+
+    /// Runs `eval` with arguments within a context.
+    ///
+    /// Returns the command's result along with the contents written to stderr.
+    fn run_eval(code: &[&str], context: &mut Context) -> (CommandResult, String) {
+        let args = std::iter::once(NAME).chain(code.iter().copied());
+        context.replace_args(Some(args.map(ToString::to_string).collect()));
+        let (mut io, _stdout, mut stderr) = mock_io();
+        let mut args = Args::new(context, &mut io);
+
+        let result = Eval.run(&mut args);
+        (result, file_contents(&mut stderr))
+    }
+
+    #[test]
+    fn it_requests_execution_of_the_joined_arguments() {
+        let mut context = Context::default();
+        context.aliases.insert("greet".into(), "echo hello".into());
+
+        let (result, stderr) = run_eval(&["greet;", "fn f() {}"], &mut context);
+        let CommandResult::Builtin(result) = result else {
+            unreachable!()
+        };
+        assert_eq!((result.code, stderr), (status::SUCCESS, String::new()));
+        assert!(matches!(
+            &result.actions[..],
+            [Action::Execute(program)]
+                if program == &parse("echo hello; fn f() {}", &Default::default()).unwrap()
+        ));
+    }
+
+    #[test]
+    fn it_reports_parse_errors_with_the_source() {
+        let mut context = Context::default();
+
+        let (result, stderr) = run_eval(&["echo", ")"], &mut context);
+        assert!(
+            matches!(result, CommandResult::Builtin(result) if result.code == status::GENERAL_ERROR)
+        );
+        assert!(stderr.starts_with("eval: "), "{stderr}");
+        assert!(stderr.contains("1 | echo )"), "{stderr}");
+
+        let (result, stderr) = run_eval(&["if", "true", "{"], &mut context);
+        assert!(
+            matches!(result, CommandResult::Builtin(result) if result.code == status::GENERAL_ERROR)
+        );
+        assert_eq!(stderr, "eval: incomplete sequence: if true {\n");
+    }
+
+    #[test]
+    fn it_prints_help() {
+        assert_prints_help(&Eval);
+    }
+}
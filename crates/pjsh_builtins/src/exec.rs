@@ -0,0 +1,122 @@
+use clap::Parser;
+use pjsh_core::{
+    command::{Action, Args, Command, CommandResult},
+    find_in_path,
+};
+use pjsh_eval::call_external_program;
+
+use crate::{status, utils};
+
+/// Command name.
+const NAME: &str = "exec";
+
+/// Replace the shell with a command.
+///
+/// The command inherits the shell's exported variables and file descriptors.
+/// If no command is supplied, redirections are instead applied to the shell
+/// itself.
+///
+/// This is a built-in shell command.
+#[derive(Parser)]
+#[clap(name = NAME, version)]
+struct ExecOpts {
+    /// Program to execute.
+    command: Option<String>,
+
+    /// Program arguments.
+    #[clap(allow_hyphen_values = true, trailing_var_arg = true)]
+    args: Vec<String>,
+}
+
+/// Implementation for the "exec" built-in command.
+#[derive(Clone)]
+pub struct Exec;
+impl Command for Exec {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn description(&self) -> Option<String> {
+        utils::description::<ExecOpts>()
+    }
+
+    fn usage(&self) -> Option<String> {
+        utils::usage::<ExecOpts>(NAME)
+    }
+
+    fn run(&self, args: &mut Args) -> CommandResult {
+        match ExecOpts::try_parse_from(args.context.args()) {
+            Ok(ExecOpts {
+                command: Some(command),
+                args: command_args,
+            }) => exec(&command, &command_args, args),
+            Ok(_) => CommandResult::with_actions(status::SUCCESS, vec![Action::KeepRedirects]),
+            Err(error) => utils::exit_with_parse_error(args.io, error),
+        }
+    }
+}
+
+/// Replaces the shell process with a program.
+///
+/// Only returns if the program can't be found or executed, in which case an
+/// error is written to stderr.
+///
+/// Returns an exit code.
+fn exec(name: &str, command_args: &[String], args: &mut Args) -> CommandResult {
+    let Some(program) = find_in_path(name, args.context).filter(|path| path.is_file()) else {
+        let _ = writeln!(args.io.stderr, "{NAME}: {name}: command not found");
+        return CommandResult::code(status::COMMAND_NOT_FOUND);
+    };
+
+    let command = match call_external_program(&program, command_args, args.context) {
+        Ok(command) => command,
+        Err(error) => {
+            let _ = writeln!(args.io.stderr, "{NAME}: {name}: {error}");
+            return CommandResult::code(status::CANNOT_EXECUTE);
+        }
+    };
+
+    let error = args.context.host.lock().exec(command);
+    let _ = writeln!(args.io.stderr, "{NAME}: {name}: {error}");
+    CommandResult::code(status::CANNOT_EXECUTE)
+}
+
+#[cfg(test)]
+mod tests {
+    use pjsh_core::Context;
+
+    use crate::utils::{assert_prints_help, mock_io, run_builtin};
+
+    use super::*;
+
+    #[test]
+    fn it_keeps_redirects_without_a_command() {
+        let mut ctx = Context::default();
+        ctx.replace_args(Some(vec![NAME.into()]));
+        let (mut io, _stdout, _stderr) = mock_io();
+        let mut args = Args::new(&mut ctx, &mut io);
+
+        let CommandResult::Builtin(result) = Exec.run(&mut args) else {
+            unreachable!()
+        };
+        assert_eq!(result.code, status::SUCCESS);
+        assert!(matches!(result.actions[..], [Action::KeepRedirects]));
+    }
+
+    #[test]
+    fn it_returns_an_error_for_missing_programs() {
+        assert_eq!(
+            run_builtin(&Exec, &["exec", "/path/to/missing/program"]),
+            (
+                status::COMMAND_NOT_FOUND,
+                String::new(),
+                "exec: /path/to/missing/program: command not found\n".into()
+            )
+        );
+    }
+
+    #[test]
+    fn it_prints_help() {
+        assert_prints_help(&Exec);
+    }
+}
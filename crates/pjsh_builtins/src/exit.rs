@@ -23,6 +23,14 @@ fn name(&self) -> &str {
         "exit"
     }
 
+    fn description(&self) -> Option<String> {
+        utils::description::<ExitOpts>()
+    }
+
+    fn usage(&self) -> Option<String> {
+        utils::usage::<ExitOpts>("exit")
+    }
+
     fn run(&self, args: &mut Args) -> CommandResult {
         match ExitOpts::try_parse_from(args.context.args()) {
             Ok(opts) => {
@@ -40,7 +48,7 @@ mod tests {
 
     use pjsh_core::{Context, Scope};
 
-    use crate::utils::empty_io;
+    use crate::utils::{assert_prints_help, assert_prints_usage_error, empty_io};
 
     use super::*;
 
@@ -104,4 +112,14 @@ fn it_exits_with_code_2_if_code_argument_is_invalid() {
             unreachable!()
         }
     }
+
+    #[test]
+    fn it_prints_help() {
+        assert_prints_help(&Exit);
+    }
+
+    #[test]
+    fn it_prints_usage_errors() {
+        assert_prints_usage_error(&Exit, &["exit", "1", "2"]);
+    }
 }
@@ -28,6 +28,14 @@ fn name(&self) -> &str {
         NAME
     }
 
+    fn description(&self) -> Option<String> {
+        utils::description::<ExportOpts>()
+    }
+
+    fn usage(&self) -> Option<String> {
+        utils::usage::<ExportOpts>(NAME)
+    }
+
     fn run(&self, args: &mut Args) -> CommandResult {
         match ExportOpts::try_parse_from(args.context.args()) {
             Ok(opts) => export_variables(opts, args),
@@ -60,7 +68,7 @@ fn export_variable(variable: String, context: &mut Context) -> Result<(), String
         Some(separator) => {
             let name = variable[..separator].to_owned();
             let value = variable[separator + 1..].to_owned(); // The separator is not included.
-            context.set_var(name.clone(), Value::Word(value));
+            context.set_var(name.clone(), Value::Word(value))?;
             context.export_var(name)
         }
 
@@ -75,28 +83,13 @@ mod tests {
 
     use pjsh_core::{Context, Scope};
 
-    use crate::utils::empty_io;
+    use crate::utils::{assert_prints_help, assert_prints_usage_error, empty_io};
 
     use super::*;
 
     #[test]
     fn it_prints_help() {
-        let mut ctx = Context::with_scopes(vec![Scope::new(
-            String::new(),
-            Some(vec!["export".into(), "--help".into()]),
-            HashMap::default(),
-            HashMap::default(),
-            HashSet::default(),
-        )]);
-        let mut io = empty_io();
-        let mut args = Args::new(&mut ctx, &mut io);
-
-        let cmd = Export {};
-        let CommandResult::Builtin(result) = cmd.run(&mut args) else {
-            unreachable!();
-        };
-
-        assert_eq!(result.code, 0);
+        assert_prints_help(&Export);
     }
 
     #[test]
@@ -229,4 +222,9 @@ fn it_does_not_export_unknown_variables() {
             "nothing should be exported"
         );
     }
+
+    #[test]
+    fn it_prints_usage_errors() {
+        assert_prints_usage_error(&Export, &["export"]);
+    }
 }
@@ -0,0 +1,187 @@
+use clap::Parser;
+use std::io::ErrorKind;
+
+use pjsh_core::{
+    command::{Args, Command, CommandResult},
+    signal::exit_status_code,
+};
+
+use crate::{status, utils};
+
+/// Command name.
+const NAME: &str = "fg";
+
+/// Move a background job to the foreground.
+///
+/// Resumes the job if it has been stopped, waits for it to exit, and exits
+/// with the job's exit status.
+///
+/// This is a built-in shell command.
+#[derive(Parser)]
+#[clap(name = NAME, version)]
+struct FgOpts {
+    /// Job id, optionally prefixed by "%".
+    ///
+    /// Defaults to the most recently started job.
+    #[clap(value_parser = utils::parse_job_id)]
+    job: Option<usize>,
+}
+
+/// Implementation for the "fg" built-in command.
+#[derive(Clone)]
+pub struct Fg;
+impl Command for Fg {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn description(&self) -> Option<String> {
+        utils::description::<FgOpts>()
+    }
+
+    fn usage(&self) -> Option<String> {
+        utils::usage::<FgOpts>(NAME)
+    }
+
+    fn run(&self, args: &mut Args) -> CommandResult {
+        match FgOpts::try_parse_from(args.context.args()) {
+            Ok(opts) => foreground(opts, args),
+            Err(error) => utils::exit_with_parse_error(args.io, error),
+        }
+    }
+}
+
+/// Resumes a background job and waits for it to exit.
+///
+/// Returns the job's exit code.
+fn foreground(opts: FgOpts, args: &mut Args) -> CommandResult {
+    // The host must not remain locked while waiting for the job.
+    let process = {
+        let mut host = args.context.host.lock();
+        match host.resume_child_process(opts.job) {
+            Ok(id) => host.take_child_process(Some(id)),
+            Err(error) if error.kind() == ErrorKind::Unsupported => {
+                host.take_child_process(opts.job)
+            }
+            Err(error) if error.kind() == ErrorKind::NotFound => None,
+            Err(error) => {
+                let _ = writeln!(args.io.stderr, "{NAME}: {error}");
+                return CommandResult::code(status::GENERAL_ERROR);
+            }
+        }
+    };
+    let Some((_, mut child)) = process else {
+        let _ = writeln!(args.io.stderr, "{NAME}: no such job");
+        return CommandResult::code(status::GENERAL_ERROR);
+    };
+
+    match child.wait() {
+        Ok(exit_status) => CommandResult::code(exit_status_code(exit_status)),
+        Err(error) => {
+            let _ = writeln!(args.io.stderr, "{NAME}: {error}");
+            CommandResult::code(status::GENERAL_ERROR)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::process;
+
+    use pjsh_core::Context;
+
+    use crate::utils::{assert_prints_help, assert_prints_usage_error, empty_io};
+
+    use super::*;
+
+    /// Runs the "fg" built-in within a context.
+    ///
+    /// Returns the exit code.
+    fn run_fg(args: &[&str], ctx: &mut Context) -> i32 {
+        ctx.replace_args(Some(args.iter().map(ToString::to_string).collect()));
+        let mut io = empty_io();
+        let mut args = Args::new(ctx, &mut io);
+
+        let CommandResult::Builtin(result) = Fg.run(&mut args) else {
+            unreachable!()
+        };
+        result.code
+    }
+
+    /// Spawns a process that exits with a specific code.
+    fn spawn_exiting_with(code: i32) -> process::Child {
+        process::Command::new("sh")
+            .args(["-c", &format!("exit {code}")])
+            .spawn()
+            .unwrap()
+    }
+
+    #[test]
+    fn it_waits_for_the_most_recent_job() {
+        let mut ctx = Context::default();
+        ctx.host
+            .lock()
+            .add_child_process(spawn_exiting_with(3), None);
+        ctx.host
+            .lock()
+            .add_child_process(spawn_exiting_with(4), None);
+
+        assert_eq!(run_fg(&["fg"], &mut ctx), 4);
+        assert_eq!(run_fg(&["fg"], &mut ctx), 3);
+        assert_eq!(run_fg(&["fg"], &mut ctx), status::GENERAL_ERROR);
+    }
+
+    #[test]
+    fn it_waits_for_a_specific_job() {
+        let mut ctx = Context::default();
+        ctx.host
+            .lock()
+            .add_child_process(spawn_exiting_with(3), None);
+        ctx.host
+            .lock()
+            .add_child_process(spawn_exiting_with(4), None);
+
+        assert_eq!(run_fg(&["fg", "%1"], &mut ctx), 3);
+        assert_eq!(run_fg(&["fg", "1"], &mut ctx), status::GENERAL_ERROR);
+        assert_eq!(run_fg(&["fg", "2"], &mut ctx), 4);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn it_resumes_stopped_jobs() {
+        let mut ctx = Context::default();
+        let stop = pjsh_core::signal::signal_number("STOP").unwrap();
+        let child = process::Command::new("sh")
+            .args(["-c", "sleep 0.1; exit 3"])
+            .spawn()
+            .unwrap();
+        let id = ctx.host.lock().add_child_process(child, None);
+        ctx.host.lock().kill_child_process(id, stop).unwrap();
+
+        assert_eq!(run_fg(&["fg"], &mut ctx), 3);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn it_exits_with_the_signal_that_terminated_the_job() {
+        let mut ctx = Context::default();
+        let child = process::Command::new("sh")
+            .args(["-c", "kill -TERM $$"])
+            .spawn()
+            .unwrap();
+        ctx.host.lock().add_child_process(child, None);
+
+        assert_eq!(run_fg(&["fg"], &mut ctx), 128 + pjsh_core::signal::SIGTERM);
+    }
+
+    #[test]
+    fn it_prints_help() {
+        assert_prints_help(&Fg);
+    }
+
+    #[test]
+    fn it_prints_usage_errors() {
+        assert_prints_usage_error(&Fg, &["fg", "%first"]);
+        assert_prints_usage_error(&Fg, &["fg", "0"]);
+    }
+}
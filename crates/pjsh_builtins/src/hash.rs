@@ -0,0 +1,162 @@
+use clap::Parser;
+use pjsh_core::{
+    command::{Args, Command, CommandResult},
+    find_in_path,
+    utils::path_to_string,
+};
+
+use crate::{status, utils};
+
+/// Command name.
+const NAME: &str = "hash";
+
+/// Remember or display program paths.
+///
+/// Program paths are remembered when programs are first resolved using $PATH.
+/// Remembered paths are forgotten when $PATH or $PATHEXT changes.
+///
+/// If no names are supplied, all remembered paths are printed.
+///
+/// This is a built-in shell command.
+#[derive(Parser)]
+#[clap(name = NAME, version)]
+struct HashOpts {
+    /// Forget all remembered paths.
+    #[clap(short = 'r')]
+    reset: bool,
+
+    /// Program names to resolve and remember.
+    names: Vec<String>,
+}
+
+/// Implementation for the "hash" built-in command.
+#[derive(Clone)]
+pub struct Hash;
+impl Command for Hash {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn description(&self) -> Option<String> {
+        utils::description::<HashOpts>()
+    }
+
+    fn usage(&self) -> Option<String> {
+        utils::usage::<HashOpts>(NAME)
+    }
+
+    fn run(&self, args: &mut Args) -> CommandResult {
+        match HashOpts::try_parse_from(args.context.args()) {
+            Ok(opts) => hash(opts, args),
+            Err(error) => utils::exit_with_parse_error(args.io, error),
+        }
+    }
+}
+
+/// Updates or prints the remembered program paths.
+///
+/// Returns 0 if all names can be resolved, or 1 if at least one name cannot be
+/// resolved.
+fn hash(opts: HashOpts, args: &mut Args) -> CommandResult {
+    if opts.reset {
+        args.context.path_cache.lock().clear();
+    }
+
+    if opts.names.is_empty() {
+        if !opts.reset {
+            let cache = args.context.path_cache.lock();
+            for (name, path) in cache.entries() {
+                let _ = writeln!(args.io.stdout, "{name}\t{}", path_to_string(path));
+            }
+        }
+        return CommandResult::code(status::SUCCESS);
+    }
+
+    let mut code = status::SUCCESS;
+    for name in &opts.names {
+        // Resolve the program again, even if its path is already remembered.
+        args.context.path_cache.lock().remove(name);
+        if find_in_path(name, args.context).is_none() {
+            let _ = writeln!(args.io.stderr, "{NAME}: {name}: not found");
+            code = status::GENERAL_ERROR;
+        }
+    }
+
+    CommandResult::code(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::{HashMap, HashSet},
+        fs::File,
+    };
+
+    use pjsh_core::{Context, Scope, Value};
+    use tempfile::tempdir;
+
+    use crate::utils::{assert_prints_help, file_contents, mock_io};
+
+    use super::*;
+
+    /// Constructs a context in which programs are resolved using a path.
+    fn context(args: &[&str], path: &std::path::Path) -> Context {
+        Context::with_scopes(vec![Scope::new(
+            String::new(),
+            Some(args.iter().map(ToString::to_string).collect()),
+            HashMap::from([("PATH".into(), Some(Value::Word(path_to_string(path))))]),
+            HashMap::default(),
+            HashSet::default(),
+        )])
+    }
+
+    #[test]
+    fn it_resolves_and_prints_program_paths() {
+        let dir = tempdir().unwrap();
+        let program_path = dir.path().join("program");
+        File::create(&program_path).unwrap();
+
+        let mut ctx = context(&["hash", "program"], dir.path());
+        let (mut io, _stdout, _stderr) = mock_io();
+        let result = Hash.run(&mut Args::new(&mut ctx, &mut io));
+        assert!(matches!(result, CommandResult::Builtin(result) if result.code == 0));
+
+        ctx.replace_args(Some(vec!["hash".into()]));
+        let (mut io, mut stdout, _stderr) = mock_io();
+        let _ = Hash.run(&mut Args::new(&mut ctx, &mut io));
+        assert_eq!(
+            file_contents(&mut stdout),
+            format!("program\t{}\n", path_to_string(program_path))
+        );
+    }
+
+    #[test]
+    fn it_forgets_program_paths() {
+        let dir = tempdir().unwrap();
+        File::create(dir.path().join("program")).unwrap();
+
+        let mut ctx = context(&["hash", "-r"], dir.path());
+        find_in_path("program", &ctx).unwrap();
+        let (mut io, _stdout, _stderr) = mock_io();
+        let _ = Hash.run(&mut Args::new(&mut ctx, &mut io));
+        assert_eq!(ctx.path_cache.lock().entries().count(), 0);
+    }
+
+    #[test]
+    fn it_reports_missing_programs() {
+        let dir = tempdir().unwrap();
+        let mut ctx = context(&["hash", "missing"], dir.path());
+        let (mut io, _stdout, mut stderr) = mock_io();
+        let result = Hash.run(&mut Args::new(&mut ctx, &mut io));
+        assert!(matches!(
+            result,
+            CommandResult::Builtin(result) if result.code == status::GENERAL_ERROR
+        ));
+        assert_eq!(file_contents(&mut stderr), "hash: missing: not found\n");
+    }
+
+    #[test]
+    fn it_prints_help() {
+        assert_prints_help(&Hash);
+    }
+}
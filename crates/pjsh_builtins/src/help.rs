@@ -0,0 +1,184 @@
+use clap::Parser;
+use pjsh_core::command::{Args, Command, CommandResult};
+
+use crate::{status, utils};
+
+/// Command name.
+const NAME: &str = "help";
+
+/// Display information about built-in commands.
+///
+/// Lists all built-in commands along with their descriptions if no names are
+/// given. Use "<name> --help" for a built-in command's full help.
+///
+/// This is a built-in shell command.
+#[derive(Parser)]
+#[clap(name = NAME, version)]
+struct HelpOpts {
+    /// Built-in commands to describe.
+    names: Vec<String>,
+}
+
+/// Implementation for the "help" built-in command.
+#[derive(Clone)]
+pub struct Help;
+impl Command for Help {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn description(&self) -> Option<String> {
+        utils::description::<HelpOpts>()
+    }
+
+    fn usage(&self) -> Option<String> {
+        utils::usage::<HelpOpts>(NAME)
+    }
+
+    fn run(&self, args: &mut Args) -> CommandResult {
+        match HelpOpts::try_parse_from(args.context.args()) {
+            Ok(opts) if opts.names.is_empty() => list_builtins(args),
+            Ok(opts) => describe_builtins(opts.names, args),
+            Err(error) => utils::exit_with_parse_error(args.io, error),
+        }
+    }
+}
+
+/// Prints the names and descriptions of all built-in commands.
+///
+/// Returns an exit code.
+fn list_builtins(args: &mut Args) -> CommandResult {
+    let mut names: Vec<&String> = args.context.builtins.keys().collect();
+    names.sort();
+    let width = names.iter().map(|name| name.len()).max().unwrap_or(0);
+
+    for name in names {
+        let description = args.context.builtins[name]
+            .description()
+            .unwrap_or_default();
+        let line = format!("{name:<width$}  {description}");
+        if let Err(error) = writeln!(args.io.stdout, "{}", line.trim_end()) {
+            return utils::exit_with_write_error(NAME, args.io, error);
+        }
+    }
+
+    CommandResult::code(status::SUCCESS)
+}
+
+/// Prints the usage and description of built-in commands.
+///
+/// Returns an exit code.
+fn describe_builtins(names: Vec<String>, args: &mut Args) -> CommandResult {
+    let mut code = status::SUCCESS;
+
+    for name in names {
+        let Some(builtin) = args.context.get_builtin(&name) else {
+            let _ = writeln!(args.io.stderr, "{NAME}: {name}: no such built-in command");
+            code = status::GENERAL_ERROR;
+            continue;
+        };
+
+        let usage = builtin.usage().unwrap_or_else(|| name.clone());
+        let mut text = format!("{name}: {usage}\n");
+        if let Some(description) = builtin.description() {
+            text += &format!("    {description}\n");
+        }
+
+        if let Err(error) = write!(args.io.stdout, "{text}") {
+            return utils::exit_with_write_error(NAME, args.io, error);
+        }
+    }
+
+    CommandResult::code(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use pjsh_core::Context;
+
+    use crate::{
+        utils::{assert_prints_help, assert_prints_usage_error, file_contents, mock_io},
+        Cd, Echo, True,
+    };
+
+    use super::*;
+
+    /// Runs the "help" built-in command with a few registered built-in commands.
+    ///
+    /// Returns the exit code, stdout and stderr.
+    fn help(args: &[&str]) -> (i32, String, String) {
+        let mut ctx = Context::default();
+        ctx.register_builtin(Box::new(Cd));
+        ctx.register_builtin(Box::new(Echo));
+        ctx.register_builtin(Box::new(Help));
+        ctx.register_builtin(Box::new(True));
+        ctx.replace_args(Some(
+            std::iter::once(NAME)
+                .chain(args.iter().copied())
+                .map(ToString::to_string)
+                .collect(),
+        ));
+        let (mut io, mut stdout, mut stderr) = mock_io();
+
+        match Help.run(&mut Args::new(&mut ctx, &mut io)) {
+            CommandResult::Builtin(result) => (
+                result.code,
+                file_contents(&mut stdout),
+                file_contents(&mut stderr),
+            ),
+            CommandResult::Process(_) => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_lists_builtins() {
+        assert_eq!(
+            help(&[]),
+            (
+                status::SUCCESS,
+                "cd    Change the shell's working directory\n\
+                 echo  Print a line of text\n\
+                 help  Display information about built-in commands\n\
+                 true  Exit with a status indicating success\n"
+                    .into(),
+                String::new()
+            )
+        );
+    }
+
+    #[test]
+    fn it_describes_builtins() {
+        assert_eq!(
+            help(&["cd", "true"]),
+            (
+                status::SUCCESS,
+                "cd: cd [DIRECTORY]\n    Change the shell's working directory\n\
+                 true: true\n    Exit with a status indicating success\n"
+                    .into(),
+                String::new()
+            )
+        );
+    }
+
+    #[test]
+    fn it_reports_unknown_builtins() {
+        assert_eq!(
+            help(&["missing", "true"]),
+            (
+                status::GENERAL_ERROR,
+                "true: true\n    Exit with a status indicating success\n".into(),
+                "help: missing: no such built-in command\n".into()
+            )
+        );
+    }
+
+    #[test]
+    fn it_prints_help() {
+        assert_prints_help(&Help);
+    }
+
+    #[test]
+    fn it_prints_usage_errors() {
+        assert_prints_usage_error(&Help, &["help", "--unknown"]);
+    }
+}
@@ -0,0 +1,215 @@
+use clap::Parser;
+use pjsh_core::command::{Args, Command, CommandResult};
+
+use crate::{status, utils};
+
+/// Command name.
+const NAME: &str = "history";
+
+/// Display or manipulate the command history.
+///
+/// Prints all history entries along with their positions if no options are
+/// given.
+///
+/// This is a built-in shell command.
+#[derive(Parser)]
+#[clap(name = NAME, version)]
+struct HistoryOpts {
+    /// Only print the last COUNT entries.
+    #[clap(conflicts_with_all = ["clear", "delete"])]
+    count: Option<usize>,
+
+    /// Clear the history by removing all entries.
+    ///
+    /// Interactive shells ask for confirmation first.
+    #[clap(short, conflicts_with = "delete")]
+    clear: bool,
+
+    /// Remove the history entry at position OFFSET.
+    #[clap(short, value_name = "OFFSET")]
+    delete: Option<usize>,
+}
+
+/// Implementation for the "history" built-in command.
+#[derive(Clone)]
+pub struct History;
+impl Command for History {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn description(&self) -> Option<String> {
+        utils::description::<HistoryOpts>()
+    }
+
+    fn usage(&self) -> Option<String> {
+        utils::usage::<HistoryOpts>(NAME)
+    }
+
+    fn run(&self, args: &mut Args) -> CommandResult {
+        match HistoryOpts::try_parse_from(args.context.args()) {
+            Ok(opts) => history(opts, args),
+            Err(error) => utils::exit_with_parse_error(args.io, error),
+        }
+    }
+}
+
+/// Displays or manipulates the context's history.
+///
+/// Returns an exit code.
+fn history(opts: HistoryOpts, args: &mut Args) -> CommandResult {
+    if opts.clear {
+        let interactive = utils::is_interactive(args.context);
+        if !utils::confirm(args.io, "history: clear all entries?", interactive) {
+            return CommandResult::code(status::GENERAL_ERROR);
+        }
+
+        args.context.history.lock().clear();
+        return CommandResult::code(status::SUCCESS);
+    }
+
+    if let Some(offset) = opts.delete {
+        // Positions start at 1, while history indices start at 0.
+        let removed = offset
+            .checked_sub(1)
+            .is_some_and(|index| args.context.history.lock().remove(index));
+        if !removed {
+            let _ = writeln!(
+                args.io.stderr,
+                "{NAME}: {offset}: history position out of range"
+            );
+            return CommandResult::code(status::GENERAL_ERROR);
+        }
+        return CommandResult::code(status::SUCCESS);
+    }
+
+    let entries = args.context.history.lock().entries();
+    let skip = opts
+        .count
+        .map_or(0, |count| entries.len().saturating_sub(count));
+
+    for (index, entry) in entries.iter().enumerate().skip(skip) {
+        if let Err(error) = writeln!(args.io.stdout, "{:>5}  {entry}", index + 1) {
+            let _ = writeln!(args.io.stderr, "{NAME}: {error}");
+            return CommandResult::code(status::GENERAL_ERROR);
+        }
+    }
+
+    CommandResult::code(status::SUCCESS)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use parking_lot::Mutex;
+    use pjsh_core::{Context, MemoryHistory};
+
+    use crate::utils::{assert_prints_help, assert_prints_usage_error, file_contents, mock_io};
+
+    use super::*;
+
+    /// Returns a context with a history containing some entries.
+    fn context_with_history(entries: &[&str]) -> Context {
+        let entries = entries.iter().map(ToString::to_string).collect();
+        let mut ctx = Context::default();
+        ctx.history = Arc::new(Mutex::new(MemoryHistory::new(entries)));
+        ctx
+    }
+
+    /// Runs the "history" built-in within a context.
+    ///
+    /// Returns the exit code along with the contents written to stdout and stderr.
+    fn run_history(args: &[&str], ctx: &mut Context) -> (i32, String, String) {
+        ctx.replace_args(Some(args.iter().map(ToString::to_string).collect()));
+        let (mut io, mut stdout, mut stderr) = mock_io();
+        let mut args = Args::new(ctx, &mut io);
+
+        let CommandResult::Builtin(result) = History.run(&mut args) else {
+            unreachable!()
+        };
+        (
+            result.code,
+            file_contents(&mut stdout),
+            file_contents(&mut stderr),
+        )
+    }
+
+    #[test]
+    fn it_prints_numbered_entries() {
+        let mut ctx = context_with_history(&["echo first", "echo second", "ls"]);
+        assert_eq!(
+            run_history(&["history"], &mut ctx),
+            (
+                status::SUCCESS,
+                "    1  echo first\n    2  echo second\n    3  ls\n".into(),
+                String::new()
+            )
+        );
+    }
+
+    #[test]
+    fn it_prints_the_last_entries() {
+        let mut ctx = context_with_history(&["echo first", "echo second", "ls"]);
+        assert_eq!(
+            run_history(&["history", "2"], &mut ctx),
+            (
+                status::SUCCESS,
+                "    2  echo second\n    3  ls\n".into(),
+                String::new()
+            )
+        );
+        assert_eq!(
+            run_history(&["history", "5"], &mut ctx).1,
+            "    1  echo first\n    2  echo second\n    3  ls\n"
+        );
+    }
+
+    #[test]
+    fn it_clears_the_history() {
+        let mut ctx = context_with_history(&["echo first", "ls"]);
+        assert_eq!(
+            run_history(&["history", "-c"], &mut ctx),
+            (status::SUCCESS, String::new(), String::new())
+        );
+        assert!(ctx.history.lock().entries().is_empty());
+    }
+
+    #[test]
+    fn it_deletes_entries() {
+        let mut ctx = context_with_history(&["echo first", "echo second", "ls"]);
+        assert_eq!(
+            run_history(&["history", "-d", "2"], &mut ctx),
+            (status::SUCCESS, String::new(), String::new())
+        );
+        assert_eq!(ctx.history.lock().entries(), vec!["echo first", "ls"]);
+    }
+
+    #[test]
+    fn it_fails_to_delete_missing_entries() {
+        let mut ctx = context_with_history(&["ls"]);
+        for offset in ["0", "2"] {
+            assert_eq!(
+                run_history(&["history", "-d", offset], &mut ctx),
+                (
+                    status::GENERAL_ERROR,
+                    String::new(),
+                    format!("history: {offset}: history position out of range\n")
+                )
+            );
+        }
+        assert_eq!(ctx.history.lock().entries(), vec!["ls"]);
+    }
+
+    #[test]
+    fn it_prints_help() {
+        assert_prints_help(&History);
+    }
+
+    #[test]
+    fn it_prints_usage_errors() {
+        assert_prints_usage_error(&History, &["history", "-c", "-d", "1"]);
+        assert_prints_usage_error(&History, &["history", "-c", "2"]);
+        assert_prints_usage_error(&History, &["history", "last"]);
+    }
+}
@@ -25,6 +25,14 @@ fn name(&self) -> &str {
         NAME
     }
 
+    fn description(&self) -> Option<String> {
+        utils::description::<InterpolateOpts>()
+    }
+
+    fn usage(&self) -> Option<String> {
+        utils::usage::<InterpolateOpts>(NAME)
+    }
+
     fn run(&self, args: &mut Args) -> CommandResult {
         match InterpolateOpts::try_parse_from(args.context.args()) {
             Ok(opts) => interpolate_text_args(opts),
@@ -72,7 +80,7 @@ mod tests {
 
     use pjsh_core::{Context, Scope};
 
-    use crate::utils::empty_io;
+    use crate::utils::{assert_prints_help, assert_prints_usage_error, empty_io};
 
     use super::*;
 
@@ -104,21 +112,11 @@ fn it_interpolates_input() {
 
     #[test]
     fn it_prints_help() {
-        let mut ctx = Context::with_scopes(vec![Scope::new(
-            String::new(),
-            Some(vec!["interpolate".into(), "--help".into()]),
-            HashMap::default(),
-            HashMap::default(),
-            HashSet::default(),
-        )]);
-        let mut io = empty_io();
-        let mut args = Args::new(&mut ctx, &mut io);
-
-        let cmd = Interpolate;
-        let CommandResult::Builtin(result) = cmd.run(&mut args) else {
-            unreachable!();
-        };
+        assert_prints_help(&Interpolate);
+    }
 
-        assert_eq!(result.code, 0);
+    #[test]
+    fn it_prints_usage_errors() {
+        assert_prints_usage_error(&Interpolate, &["interpolate"]);
     }
 }
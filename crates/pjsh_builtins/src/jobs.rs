@@ -0,0 +1,177 @@
+use clap::Parser;
+use pjsh_core::{
+    command::{Args, Command, CommandResult},
+    Job, JobState,
+};
+
+use crate::{status, utils};
+
+/// Command name.
+const NAME: &str = "jobs";
+
+/// List background jobs.
+///
+/// Prints one line per job containing its job id, process id, state, and
+/// command line. Exited jobs are only listed once.
+///
+/// This is a built-in shell command.
+#[derive(Parser)]
+#[clap(name = NAME, version)]
+struct JobsOpts;
+
+/// Implementation for the "jobs" built-in command.
+#[derive(Clone)]
+pub struct Jobs;
+impl Command for Jobs {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn description(&self) -> Option<String> {
+        utils::description::<JobsOpts>()
+    }
+
+    fn usage(&self) -> Option<String> {
+        utils::usage::<JobsOpts>(NAME)
+    }
+
+    fn run(&self, args: &mut Args) -> CommandResult {
+        match JobsOpts::try_parse_from(args.context.args()) {
+            Ok(_) => list_jobs(args),
+            Err(error) => utils::exit_with_parse_error(args.io, error),
+        }
+    }
+}
+
+/// Prints all jobs that are tracked by the context's host to stdout.
+///
+/// Returns an exit code.
+fn list_jobs(args: &mut Args) -> CommandResult {
+    let jobs = args.context.host.lock().jobs();
+
+    for job in jobs {
+        if let Err(error) = writeln!(args.io.stdout, "{}", format_job(&job)) {
+            let _ = writeln!(args.io.stderr, "{NAME}: {error}");
+            return CommandResult::code(status::GENERAL_ERROR);
+        }
+    }
+
+    CommandResult::code(status::SUCCESS)
+}
+
+/// Returns a printable line describing a job.
+fn format_job(job: &Job) -> String {
+    let mut line = format!("[{}]", job.id);
+
+    if let Some(pid) = job.pid {
+        line.push_str(&format!(" {pid}"));
+    }
+
+    match job.state {
+        JobState::Running => line.push_str(" Running"),
+        JobState::Exited(Some(0)) => line.push_str(" Done"),
+        JobState::Exited(Some(code)) => line.push_str(&format!(" Exit {code}")),
+        JobState::Exited(None) => line.push_str(" Terminated"),
+    }
+
+    if let Some(command) = &job.command {
+        line.push_str(&format!(" {command}"));
+    }
+
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+
+    use pjsh_core::Context;
+
+    use crate::utils::{
+        assert_prints_help, assert_prints_usage_error, file_contents, mock_io, run_builtin,
+    };
+
+    use super::*;
+
+    /// Runs the "jobs" built-in within a context.
+    ///
+    /// Returns the contents written to stdout.
+    fn run_jobs(ctx: &mut Context) -> String {
+        ctx.replace_args(Some(vec![NAME.into()]));
+        let (mut io, mut stdout, _) = mock_io();
+        let mut args = Args::new(ctx, &mut io);
+
+        let CommandResult::Builtin(result) = Jobs.run(&mut args) else {
+            unreachable!()
+        };
+        assert_eq!(result.code, status::SUCCESS);
+        file_contents(&mut stdout)
+    }
+
+    #[test]
+    fn it_formats_jobs() {
+        let job = |pid, state, command: Option<&str>| Job {
+            id: 3,
+            pid,
+            state,
+            command: command.map(ToString::to_string),
+        };
+
+        assert_eq!(
+            format_job(&job(Some(42), JobState::Running, Some("sleep 10"))),
+            "[3] 42 Running sleep 10"
+        );
+        assert_eq!(
+            format_job(&job(None, JobState::Exited(Some(0)), None)),
+            "[3] Done"
+        );
+        assert_eq!(
+            format_job(&job(Some(42), JobState::Exited(Some(2)), Some("false"))),
+            "[3] 42 Exit 2 false"
+        );
+        assert_eq!(
+            format_job(&job(Some(42), JobState::Exited(None), None)),
+            "[3] 42 Terminated"
+        );
+    }
+
+    #[test]
+    fn it_lists_exited_jobs_once() {
+        let mut ctx = Context::default();
+        let (sender, receiver) = mpsc::channel::<()>();
+        ctx.host.lock().add_thread(std::thread::spawn(move || {
+            let _ = receiver.recv();
+            0
+        }));
+
+        assert_eq!(run_jobs(&mut ctx), "[1] Running\n");
+
+        drop(sender);
+        let mut output = run_jobs(&mut ctx);
+        while output == "[1] Running\n" {
+            std::thread::yield_now();
+            output = run_jobs(&mut ctx);
+        }
+
+        assert_eq!(output, "[1] Done\n");
+        assert_eq!(run_jobs(&mut ctx), "");
+    }
+
+    #[test]
+    fn it_prints_nothing_without_jobs() {
+        assert_eq!(
+            run_builtin(&Jobs, &["jobs"]),
+            (0, String::new(), String::new())
+        );
+    }
+
+    #[test]
+    fn it_prints_help() {
+        assert_prints_help(&Jobs);
+    }
+
+    #[test]
+    fn it_prints_usage_errors() {
+        assert_prints_usage_error(&Jobs, &["jobs", "--unknown"]);
+    }
+}
@@ -0,0 +1,170 @@
+use clap::Parser;
+use pjsh_core::{
+    command::{Args, Command, CommandResult},
+    signal,
+};
+
+use crate::{
+    status,
+    utils::{self, Target},
+};
+
+/// Command name.
+const NAME: &str = "kill";
+
+/// Send a signal to processes or jobs.
+///
+/// The signal may also be given as "-SIGNAL", such as "-9" or "-KILL". Signals
+/// are only supported on Unix-like platforms. Other platforms always kill the
+/// process.
+///
+/// This is a built-in shell command.
+#[derive(Parser)]
+#[clap(name = NAME, version)]
+struct KillOpts {
+    /// Signal name or number to send.
+    #[clap(short, long, default_value = "TERM", value_parser = parse_signal)]
+    signal: i32,
+
+    /// Process ids, or job ids prefixed by "%".
+    #[clap(required = true, value_parser = utils::parse_target)]
+    targets: Vec<Target>,
+}
+
+/// Implementation for the "kill" built-in command.
+#[derive(Clone)]
+pub struct Kill;
+impl Command for Kill {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn description(&self) -> Option<String> {
+        utils::description::<KillOpts>()
+    }
+
+    fn usage(&self) -> Option<String> {
+        utils::usage::<KillOpts>(NAME)
+    }
+
+    fn run(&self, args: &mut Args) -> CommandResult {
+        let argv = expand_signal_shorthand(args.context.args().to_vec());
+        match KillOpts::try_parse_from(argv) {
+            Ok(opts) => kill(opts, args),
+            Err(error) => utils::exit_with_parse_error(args.io, error),
+        }
+    }
+}
+
+/// Sends a signal to all targets.
+///
+/// Returns an exit code.
+fn kill(opts: KillOpts, args: &mut Args) -> CommandResult {
+    let mut exit_code = status::SUCCESS;
+
+    for target in opts.targets {
+        let result = {
+            let mut host = args.context.host.lock();
+            match target {
+                Target::Job(id) => host.kill_child_process(id, opts.signal),
+                Target::Process(pid) => host.kill_process(pid, opts.signal),
+            }
+        };
+
+        if let Err(error) = result {
+            let _ = writeln!(args.io.stderr, "{NAME}: {target}: {error}");
+            exit_code = status::GENERAL_ERROR;
+        }
+    }
+
+    CommandResult::code(exit_code)
+}
+
+/// Rewrites a leading "-SIGNAL" argument, such as "-9" or "-KILL", as "-s SIGNAL".
+fn expand_signal_shorthand(mut argv: Vec<String>) -> Vec<String> {
+    let signal = argv
+        .get(1)
+        .and_then(|arg| arg.strip_prefix('-'))
+        .filter(|signal| signal::signal_number(signal).is_some())
+        .map(ToOwned::to_owned);
+
+    if let Some(signal) = signal {
+        argv.splice(1..2, ["-s".to_owned(), signal]);
+    }
+
+    argv
+}
+
+/// Parses a signal name or number.
+///
+/// Used as a [`clap`] value parser.
+fn parse_signal(arg: &str) -> Result<i32, String> {
+    signal::signal_number(arg).ok_or_else(|| format!("invalid signal: {arg}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::{assert_prints_help, assert_prints_usage_error, run_builtin};
+
+    use super::*;
+
+    #[test]
+    fn it_expands_signal_shorthands() {
+        let argv = |args: &[&str]| args.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+        assert_eq!(
+            expand_signal_shorthand(argv(&["kill", "-9", "%1"])),
+            argv(&["kill", "-s", "9", "%1"])
+        );
+        assert_eq!(
+            expand_signal_shorthand(argv(&["kill", "-KILL", "%1"])),
+            argv(&["kill", "-s", "KILL", "%1"])
+        );
+        assert_eq!(
+            expand_signal_shorthand(argv(&["kill", "-s", "HUP", "%1"])),
+            argv(&["kill", "-s", "HUP", "%1"])
+        );
+    }
+
+    #[test]
+    fn it_fails_to_kill_unknown_jobs() {
+        assert_eq!(
+            run_builtin(&Kill, &["kill", "%1", "%2"]),
+            (
+                status::GENERAL_ERROR,
+                String::new(),
+                "kill: %1: no such job\nkill: %2: no such job\n".into()
+            )
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn it_kills_processes() {
+        use std::os::unix::process::ExitStatusExt;
+
+        let mut child = std::process::Command::new("sleep")
+            .arg("10")
+            .spawn()
+            .unwrap();
+        let pid = child.id().to_string();
+
+        assert_eq!(
+            run_builtin(&Kill, &["kill", "-KILL", &pid]),
+            (status::SUCCESS, String::new(), String::new())
+        );
+        assert_eq!(child.wait().unwrap().signal(), Some(signal::SIGKILL));
+    }
+
+    #[test]
+    fn it_prints_help() {
+        assert_prints_help(&Kill);
+    }
+
+    #[test]
+    fn it_prints_usage_errors() {
+        assert_prints_usage_error(&Kill, &["kill"]);
+        assert_prints_usage_error(&Kill, &["kill", "%first"]);
+        assert_prints_usage_error(&Kill, &["kill", "-s", "UNKNOWN", "1"]);
+    }
+}
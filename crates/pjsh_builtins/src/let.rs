@@ -0,0 +1,189 @@
+use clap::Parser;
+use pjsh_core::{
+    command::{Args, Command, CommandResult},
+    Value,
+};
+use pjsh_eval::evaluate_arithmetic;
+
+use crate::{status, utils};
+
+/// Command name.
+const NAME: &str = "let";
+
+/// Evaluate an arithmetic expression.
+///
+/// All arguments are joined into a single expression. If the expression starts
+/// with a variable name followed by "=", such as "i = i + 1", the result is
+/// assigned to the variable.
+///
+/// Exits with a non-zero status if the result is zero.
+///
+/// This is a built-in shell command.
+#[derive(Parser)]
+#[clap(name = NAME, version)]
+struct LetOpts {
+    /// Arithmetic expression to evaluate.
+    #[clap(required = true, num_args = 1.., allow_hyphen_values = true)]
+    expression: Vec<String>,
+}
+
+/// Implementation for the "let" built-in command.
+#[derive(Clone)]
+pub struct Let;
+impl Command for Let {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn description(&self) -> Option<String> {
+        utils::description::<LetOpts>()
+    }
+
+    fn usage(&self) -> Option<String> {
+        utils::usage::<LetOpts>(NAME)
+    }
+
+    fn run(&self, args: &mut Args) -> CommandResult {
+        match LetOpts::try_parse_from(args.context.args()) {
+            Ok(opts) => evaluate(&opts.expression.join(" "), args),
+            Err(error) => utils::exit_with_parse_error(args.io, error),
+        }
+    }
+}
+
+/// Evaluates an expression, optionally assigning its result to a variable.
+///
+/// Returns an exit code.
+fn evaluate(expression: &str, args: &mut Args) -> CommandResult {
+    let (name, expression) = split_assignment(expression);
+
+    let number = match evaluate_arithmetic(expression, args.context) {
+        Ok(number) => number,
+        Err(error) => {
+            let _ = writeln!(args.io.stderr, "{NAME}: {error}");
+            return CommandResult::code(status::BUILTIN_ERROR);
+        }
+    };
+
+    if let Some(name) = name {
+        let value = Value::Word(number.to_string());
+        if let Err(err) = args.context.set_var(name.to_owned(), value) {
+            let _ = writeln!(args.io.stderr, "{NAME}: {err}");
+            return CommandResult::code(status::GENERAL_ERROR);
+        }
+    }
+
+    match number.is_zero() {
+        true => CommandResult::code(status::GENERAL_ERROR),
+        false => CommandResult::code(status::SUCCESS),
+    }
+}
+
+/// Splits an expression such as "i = i + 1" into a variable name and the
+/// expression to assign to it.
+///
+/// Comparisons, such as "i == 1", are not considered to be assignments.
+fn split_assignment(expression: &str) -> (Option<&str>, &str) {
+    let trimmed = expression.trim_start();
+    let name_end = trimmed
+        .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .unwrap_or(trimmed.len());
+    let (name, rest) = trimmed.split_at(name_end);
+
+    match rest.trim_start().strip_prefix('=') {
+        Some(value)
+            if !value.starts_with('=')
+                && name.starts_with(|c: char| c.is_alphabetic() || c == '_') =>
+        {
+            (Some(name), value.trim())
+        }
+        _ => (None, expression.trim()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    use pjsh_core::{Context, Scope};
+
+    use crate::utils::{assert_prints_help, assert_prints_usage_error, file_contents, mock_io};
+
+    use super::*;
+
+    /// Runs the built-in in a context, returning its exit code and stderr.
+    fn run(ctx: &mut Context, args: &[&str]) -> (i32, String) {
+        ctx.replace_args(Some(args.iter().map(ToString::to_string).collect()));
+        let (mut io, _stdout, mut stderr) = mock_io();
+        match Let.run(&mut Args::new(ctx, &mut io)) {
+            CommandResult::Builtin(result) => (result.code, file_contents(&mut stderr)),
+            CommandResult::Process(_) => unreachable!(),
+        }
+    }
+
+    fn context() -> Context {
+        Context::with_scopes(vec![Scope::new(
+            String::new(),
+            None,
+            HashMap::from([("i".into(), Some(Value::Word("1".into())))]),
+            HashMap::default(),
+            HashSet::default(),
+        )])
+    }
+
+    #[test]
+    fn it_assigns_results_to_variables() {
+        let mut ctx = context();
+        assert_eq!(
+            run(&mut ctx, &["let", "i", "=", "i", "+", "1"]),
+            (status::SUCCESS, String::new())
+        );
+        assert_eq!(ctx.get_var("i"), Some(&Value::Word("2".into())));
+
+        assert_eq!(run(&mut ctx, &["let", "j=i*3"]).0, status::SUCCESS);
+        assert_eq!(ctx.get_var("j"), Some(&Value::Word("6".into())));
+    }
+
+    #[test]
+    fn it_exits_with_the_truth_of_the_result() {
+        let mut ctx = context();
+        assert_eq!(run(&mut ctx, &["let", "i == 1"]).0, status::SUCCESS);
+        assert_eq!(
+            run(&mut ctx, &["let", "i", "-", "1"]).0,
+            status::GENERAL_ERROR
+        );
+        assert_eq!(run(&mut ctx, &["let", "zero = 0"]).0, status::GENERAL_ERROR);
+        assert_eq!(ctx.get_var("zero"), Some(&Value::Word("0".into())));
+    }
+
+    #[test]
+    fn it_reports_invalid_expressions() {
+        let mut ctx = context();
+        assert_eq!(
+            run(&mut ctx, &["let", "i = i / 0"]),
+            (
+                status::BUILTIN_ERROR,
+                "let: invalid arithmetic expression 'i / 0': division by zero\n".into()
+            )
+        );
+        assert_eq!(ctx.get_var("i"), Some(&Value::Word("1".into())));
+    }
+
+    #[test]
+    fn it_splits_assignments() {
+        assert_eq!(split_assignment("i = i + 1"), (Some("i"), "i + 1"));
+        assert_eq!(split_assignment("i == 1"), (None, "i == 1"));
+        assert_eq!(split_assignment("1 = 1"), (None, "1 = 1"));
+        assert_eq!(split_assignment("i + 1"), (None, "i + 1"));
+    }
+
+    #[test]
+    fn it_prints_help() {
+        assert_prints_help(&Let);
+    }
+
+    #[test]
+    fn it_prints_usage_errors() {
+        assert_prints_usage_error(&Let, &["let"]);
+    }
+}
@@ -1,33 +1,89 @@
 mod alias;
+mod assert_equal;
+mod bg;
+mod bookmark;
 mod cd;
+mod dirs;
+mod disown;
 mod echo;
+mod env;
+mod eval;
+mod exec;
 mod exit;
 mod export;
+mod fg;
+mod hash;
+mod help;
+mod history;
 mod interpolate;
+mod jobs;
+mod kill;
+mod r#let;
 mod logic;
+mod path;
+mod printf;
 mod pwd;
+mod read;
+mod readonly;
+mod registry;
+mod seq;
+mod set;
+mod shift;
 mod sleep;
 mod source;
+mod test;
+mod trap;
 mod r#type;
+mod ulimit;
+mod umask;
 mod unalias;
 mod unset;
+mod wait;
 mod which;
 
 pub(crate) mod status;
 pub(crate) mod utils;
 
 pub use alias::Alias;
+pub use assert_equal::AssertEqual;
+pub use bg::Bg;
+pub use bookmark::Bookmark;
 pub use cd::Cd;
+pub use dirs::{Dirs, Popd, Pushd};
+pub use disown::Disown;
 pub use echo::Echo;
+pub use env::Env;
+pub use eval::Eval;
+pub use exec::Exec;
 pub use exit::Exit;
 pub use export::Export;
+pub use fg::Fg;
+pub use hash::Hash;
+pub use help::Help;
+pub use history::History;
 pub use interpolate::Interpolate;
+pub use jobs::Jobs;
+pub use kill::Kill;
 pub use logic::{False, True};
+pub use path::Path;
+pub use printf::Printf;
 pub use pwd::Pwd;
+pub use r#let::Let;
 pub use r#type::Type;
+pub use read::Read;
+pub use readonly::Readonly;
+pub use registry::standard_builtins;
+pub use seq::Seq;
+pub use set::Set;
+pub use shift::Shift;
 pub use sleep::Sleep;
 pub use source::{Source, SourceShorthand};
+pub use test::{Test, TestBracket};
+pub use trap::Trap;
+pub use ulimit::Ulimit;
+pub use umask::Umask;
 pub use unalias::Unalias;
 pub use unset::Unset;
 pub use utils::exit_with_parse_error;
+pub use wait::Wait;
 pub use which::Which;
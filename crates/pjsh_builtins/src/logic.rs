@@ -12,6 +12,14 @@ fn name(&self) -> &str {
         "true"
     }
 
+    fn description(&self) -> Option<String> {
+        Some("Exit with a status indicating success".into())
+    }
+
+    fn usage(&self) -> Option<String> {
+        Some("true".into())
+    }
+
     fn run(&self, _args: &mut Args) -> CommandResult {
         CommandResult::code(status::SUCCESS)
     }
@@ -27,6 +35,14 @@ fn name(&self) -> &str {
         "false"
     }
 
+    fn description(&self) -> Option<String> {
+        Some("Exit with a status indicating failure".into())
+    }
+
+    fn usage(&self) -> Option<String> {
+        Some("false".into())
+    }
+
     fn run(&self, _args: &mut Args) -> CommandResult {
         CommandResult::code(1) // Any non-zero code is false.
     }
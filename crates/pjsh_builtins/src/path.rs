@@ -0,0 +1,286 @@
+use std::collections::HashSet;
+
+use clap::{builder::NonEmptyStringValueParser, Parser, Subcommand};
+use pjsh_core::{
+    command::{Args, Command, CommandResult},
+    split_path_list,
+    utils::resolve_path,
+    Value, PATH_SEPARATOR,
+};
+
+use crate::{status, utils};
+
+/// Command name.
+const NAME: &str = "path";
+
+/// Modify "$PATH"-like variables as lists of directories.
+///
+/// Directories are separated by ":" on Unix-like platforms, and by ";" on
+/// Windows. Exported variables remain exported after being modified.
+///
+/// This is a built-in shell command.
+#[derive(Parser)]
+#[clap(name = NAME, version)]
+struct PathOpts {
+    /// Variable to modify.
+    #[clap(
+        short = 'v',
+        long = "variable",
+        value_name = "NAME",
+        default_value = "PATH",
+        global = true
+    )]
+    variable: String,
+
+    /// Separator between directories. Defaults to the platform's separator.
+    #[clap(short = 's', long = "separator", value_parser = NonEmptyStringValueParser::new(), global = true)]
+    separator: Option<String>,
+
+    #[clap(subcommand)]
+    action: PathAction,
+}
+
+#[derive(Subcommand)]
+enum PathAction {
+    /// Add directories to the start of the list, moving them if already present.
+    Prepend {
+        /// Directories to add.
+        #[clap(required = true)]
+        directories: Vec<String>,
+    },
+
+    /// Add directories to the end of the list, moving them if already present.
+    Append {
+        /// Directories to add.
+        #[clap(required = true)]
+        directories: Vec<String>,
+    },
+
+    /// Remove directories from the list.
+    Remove {
+        /// Directories to remove.
+        #[clap(required = true)]
+        directories: Vec<String>,
+    },
+
+    /// Remove duplicate and empty entries, keeping the first occurrence.
+    Clean {
+        /// Also remove entries that are not existing directories.
+        #[clap(long = "prune")]
+        prune: bool,
+    },
+}
+
+/// Implementation for the "path" built-in command.
+#[derive(Clone)]
+pub struct Path;
+impl Command for Path {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn description(&self) -> Option<String> {
+        utils::description::<PathOpts>()
+    }
+
+    fn usage(&self) -> Option<String> {
+        utils::usage::<PathOpts>(NAME)
+    }
+
+    fn run(&self, args: &mut Args) -> CommandResult {
+        match PathOpts::try_parse_from(args.context.args()) {
+            Ok(opts) => path(opts, args),
+            Err(error) => utils::exit_with_parse_error(args.io, error),
+        }
+    }
+}
+
+/// Modifies the directories within a variable.
+///
+/// Returns an exit code.
+fn path(opts: PathOpts, args: &mut Args) -> CommandResult {
+    let name = opts.variable;
+    let separator = opts.separator.as_deref().unwrap_or(PATH_SEPARATOR);
+
+    let mut directories = match args.context.get_var(&name) {
+        Some(Value::Word(word)) => split_path_list(word, separator),
+        Some(Value::List(_)) => return exit_with_error(args, &format!("{name}: not a word")),
+        None => Vec::new(),
+    };
+
+    match opts.action {
+        PathAction::Prepend { directories: added } => {
+            directories.retain(|directory| !added.contains(directory));
+            directories.splice(0..0, added);
+        }
+        PathAction::Append { directories: added } => {
+            directories.retain(|directory| !added.contains(directory));
+            directories.extend(added);
+        }
+        PathAction::Remove {
+            directories: removed,
+        } => directories.retain(|directory| !removed.contains(directory)),
+        PathAction::Clean { prune } => {
+            let mut seen = HashSet::new();
+            directories.retain(|directory| seen.insert(directory.clone()));
+            if prune {
+                directories.retain(|directory| resolve_path(args.context, directory).is_dir());
+            }
+        }
+    }
+
+    match args
+        .context
+        .set_var(name, Value::Word(directories.join(separator)))
+    {
+        Ok(()) => CommandResult::code(status::SUCCESS),
+        Err(err) => exit_with_error(args, &err),
+    }
+}
+
+/// Prints an error message to standard error.
+///
+/// Returns an exit code.
+fn exit_with_error(args: &mut Args, message: &str) -> CommandResult {
+    let _ = writeln!(args.io.stderr, "{NAME}: {message}");
+    CommandResult::code(status::GENERAL_ERROR)
+}
+
+#[cfg(test)]
+mod tests {
+    use pjsh_core::{utils::path_to_string, utils::word_var, Context};
+    use tempfile::TempDir;
+
+    use crate::utils::{assert_prints_help, assert_prints_usage_error, file_contents, mock_io};
+
+    use super::*;
+
+    /// Runs the "path" built-in command within a context.
+    ///
+    /// Returns the exit code and stderr.
+    fn run(ctx: &mut Context, args: &[&str]) -> (i32, String) {
+        ctx.replace_args(Some(
+            std::iter::once(NAME)
+                .chain(args.iter().copied())
+                .map(ToString::to_string)
+                .collect(),
+        ));
+        let (mut io, _stdout, mut stderr) = mock_io();
+
+        match Path.run(&mut Args::new(ctx, &mut io)) {
+            CommandResult::Builtin(result) => (result.code, file_contents(&mut stderr)),
+            CommandResult::Process(_) => unreachable!(),
+        }
+    }
+
+    /// Returns a context with a variable.
+    fn context_with_var(name: &str, value: &str) -> Context {
+        let mut ctx = Context::default();
+        ctx.set_var(name.into(), Value::Word(value.into())).unwrap();
+        ctx
+    }
+
+    #[test]
+    fn it_modifies_paths_with_colons() {
+        let mut ctx = context_with_var("LIST", "/b:/c");
+
+        let success = (status::SUCCESS, String::new());
+        assert_eq!(
+            run(&mut ctx, &["-v", "LIST", "-s", ":", "prepend", "/a"]),
+            success
+        );
+        assert_eq!(
+            run(&mut ctx, &["-v", "LIST", "-s", ":", "append", "/b"]),
+            success
+        );
+        assert_eq!(word_var(&ctx, "LIST"), Some("/a:/c:/b"));
+
+        assert_eq!(
+            run(&mut ctx, &["-v", "LIST", "-s", ":", "remove", "/c"]),
+            success
+        );
+        assert_eq!(word_var(&ctx, "LIST"), Some("/a:/b"));
+    }
+
+    #[test]
+    fn it_modifies_paths_with_semicolons() {
+        let mut ctx = context_with_var("LIST", r"C:\b;C:\c");
+
+        let success = (status::SUCCESS, String::new());
+        assert_eq!(
+            run(
+                &mut ctx,
+                &["prepend", r"C:\c", r"C:\a", "-v", "LIST", "-s", ";"]
+            ),
+            success
+        );
+        assert_eq!(word_var(&ctx, "LIST"), Some(r"C:\c;C:\a;C:\b"));
+    }
+
+    #[test]
+    fn it_modifies_path_by_default() {
+        let mut ctx = context_with_var("PATH", "");
+        ctx.export_var("PATH".into()).unwrap();
+
+        assert_eq!(
+            run(&mut ctx, &["append", "/bin"]),
+            (status::SUCCESS, String::new())
+        );
+        assert_eq!(ctx.exported_vars().get("PATH"), Some(&"/bin"));
+    }
+
+    #[test]
+    fn it_cleans_paths() {
+        let dir = TempDir::new().unwrap();
+        let dir_path = path_to_string(dir.path());
+        let missing_path = path_to_string(dir.path().join("missing"));
+        let mut ctx = context_with_var("LIST", &format!("{dir_path}::{missing_path}:{dir_path}"));
+
+        assert_eq!(
+            run(&mut ctx, &["clean", "-v", "LIST", "-s", ":"]),
+            (status::SUCCESS, String::new())
+        );
+        assert_eq!(
+            word_var(&ctx, "LIST"),
+            Some(format!("{dir_path}:{missing_path}").as_str())
+        );
+
+        assert_eq!(
+            run(&mut ctx, &["clean", "--prune", "-v", "LIST", "-s", ":"]),
+            (status::SUCCESS, String::new())
+        );
+        assert_eq!(word_var(&ctx, "LIST"), Some(dir_path.as_str()));
+    }
+
+    #[test]
+    fn it_requires_modifiable_words() {
+        let mut ctx = context_with_var("LIST", "/a");
+        ctx.make_var_readonly("LIST".into()).unwrap();
+        assert_eq!(
+            run(&mut ctx, &["-v", "LIST", "append", "/b"]),
+            (
+                status::GENERAL_ERROR,
+                "path: readonly variable: LIST\n".into()
+            )
+        );
+
+        ctx.set_var("ITEMS".into(), Value::List(vec!["/a".into()]))
+            .unwrap();
+        assert_eq!(
+            run(&mut ctx, &["-v", "ITEMS", "append", "/b"]),
+            (status::GENERAL_ERROR, "path: ITEMS: not a word\n".into())
+        );
+    }
+
+    #[test]
+    fn it_prints_help() {
+        assert_prints_help(&Path);
+    }
+
+    #[test]
+    fn it_prints_usage_errors() {
+        assert_prints_usage_error(&Path, &["path", "rename"]);
+        assert_prints_usage_error(&Path, &["path", "prepend"]);
+        assert_prints_usage_error(&Path, &["path", "-s", "", "clean"]);
+    }
+}
@@ -0,0 +1,470 @@
+use std::{fmt::Display, iter::Peekable, str::Chars};
+
+use clap::Parser;
+use pjsh_core::command::{Args, Command, CommandResult, Io};
+
+use crate::{status, utils};
+
+/// Command name.
+const NAME: &str = "printf";
+
+/// Number of decimals to print for %f conversions without a precision.
+const DEFAULT_FLOAT_PRECISION: usize = 6;
+
+/// Print formatted text.
+///
+/// The format string is reused until all arguments have been consumed.
+///
+/// Supported conversions are %s (string), %d (decimal integer), %x
+/// (hexadecimal integer), %f (floating point number) and %% (a literal percent
+/// sign). Conversions may specify a width, a precision, and the flags "-"
+/// (left-align) and "0" (zero-pad).
+///
+/// The precision is the number of decimals for %f, the minimum number of digits
+/// for %d and %x, and the maximum number of characters for %s.
+///
+/// Supported escape sequences are \n, \r, \t and \\.
+///
+/// This is a built-in shell command.
+#[derive(Parser)]
+#[clap(name = NAME, version)]
+struct PrintfOpts {
+    /// Format string.
+    format: String,
+
+    /// Arguments to format.
+    #[clap(allow_hyphen_values = true)]
+    args: Vec<String>,
+}
+
+/// Implementation for the "printf" built-in command.
+#[derive(Clone)]
+pub struct Printf;
+impl Command for Printf {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn description(&self) -> Option<String> {
+        utils::description::<PrintfOpts>()
+    }
+
+    fn usage(&self) -> Option<String> {
+        utils::usage::<PrintfOpts>(NAME)
+    }
+
+    fn run(&self, args: &mut Args) -> CommandResult {
+        match PrintfOpts::try_parse_from(args.context.args()) {
+            Ok(opts) => print_formatted(opts, args.io),
+            Err(error) => utils::exit_with_parse_error(args.io, error),
+        }
+    }
+}
+
+/// An error in a format string or its arguments.
+#[derive(Debug, PartialEq)]
+enum FormatError {
+    /// A conversion character is not supported.
+    InvalidConversion(char),
+
+    /// An argument cannot be converted to a number.
+    InvalidNumber(String),
+
+    /// A conversion specification is not terminated by a conversion character.
+    UnterminatedConversion,
+}
+
+impl Display for FormatError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FormatError::InvalidConversion(ch) => write!(f, "invalid conversion: %{ch}"),
+            FormatError::InvalidNumber(arg) => write!(f, "invalid number: {arg}"),
+            FormatError::UnterminatedConversion => write!(f, "unterminated conversion"),
+        }
+    }
+}
+
+/// A part of a parsed format string.
+#[derive(Debug, PartialEq)]
+enum Segment {
+    /// Text to print as-is.
+    Literal(String),
+
+    /// A conversion consuming a single argument.
+    Conversion(Spec),
+}
+
+/// A conversion specification such as `%-8s`.
+#[derive(Debug, Default, PartialEq)]
+struct Spec {
+    /// Align the value to the left rather than to the right.
+    left_align: bool,
+
+    /// Pad numbers with zeros rather than spaces.
+    zero_pad: bool,
+
+    /// Minimum width of the converted value.
+    width: usize,
+
+    /// Precision of the converted value.
+    precision: Option<usize>,
+
+    /// Conversion character.
+    conversion: char,
+}
+
+/// Prints formatted text to stdout.
+///
+/// Returns an exit code.
+fn print_formatted(opts: PrintfOpts, io: &mut Io) -> CommandResult {
+    let segments = match parse_format(&opts.format) {
+        Ok(segments) => segments,
+        Err(error) => {
+            let _ = writeln!(io.stderr, "{NAME}: {error}");
+            return CommandResult::code(status::GENERAL_ERROR);
+        }
+    };
+
+    let (output, errors) = format_args(&segments, &opts.args);
+
+    let mut code = status::SUCCESS;
+    if let Err(error) = write!(io.stdout, "{output}").and_then(|_| io.stdout.flush()) {
+        return utils::exit_with_write_error(NAME, io, error);
+    }
+
+    for error in errors {
+        let _ = writeln!(io.stderr, "{NAME}: {error}");
+        code = status::GENERAL_ERROR;
+    }
+
+    CommandResult::code(code)
+}
+
+/// Parses a format string into segments.
+fn parse_format(format: &str) -> Result<Vec<Segment>, FormatError> {
+    let mut segments = Vec::new();
+    let mut literal = String::new();
+    let mut chars = format.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' => match chars.next() {
+                Some('n') => literal.push('\n'),
+                Some('r') => literal.push('\r'),
+                Some('t') => literal.push('\t'),
+                Some('\\') => literal.push('\\'),
+                Some(other) => {
+                    literal.push('\\');
+                    literal.push(other);
+                }
+                None => literal.push('\\'),
+            },
+            '%' if chars.peek() == Some(&'%') => {
+                chars.next();
+                literal.push('%');
+            }
+            '%' => {
+                let mut spec = Spec::default();
+                while let Some(flag) = chars.next_if(|c| matches!(c, '-' | '0')) {
+                    match flag {
+                        '-' => spec.left_align = true,
+                        _ => spec.zero_pad = true,
+                    }
+                }
+                spec.width = take_number(&mut chars);
+                if chars.next_if_eq(&'.').is_some() {
+                    spec.precision = Some(take_number(&mut chars));
+                }
+
+                spec.conversion = match chars.next() {
+                    Some(conversion @ ('s' | 'd' | 'x' | 'f')) => conversion,
+                    Some(other) => return Err(FormatError::InvalidConversion(other)),
+                    None => return Err(FormatError::UnterminatedConversion),
+                };
+
+                if !literal.is_empty() {
+                    segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                }
+                segments.push(Segment::Conversion(spec));
+            }
+            _ => literal.push(ch),
+        }
+    }
+
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(literal));
+    }
+
+    Ok(segments)
+}
+
+/// Consumes a sequence of digits, returning their value.
+///
+/// Returns 0 if there are no digits.
+fn take_number(chars: &mut Peekable<Chars>) -> usize {
+    let mut number = 0usize;
+    while let Some(digit) = chars.next_if(char::is_ascii_digit) {
+        let digit = digit.to_digit(10).unwrap_or(0) as usize;
+        number = number.saturating_mul(10).saturating_add(digit);
+    }
+    number
+}
+
+/// Formats arguments using a parsed format string.
+///
+/// The format is reused until all arguments have been consumed. Missing
+/// arguments are treated as empty strings, or zero for numeric conversions.
+/// Arguments that are not numbers are also converted as zero by numeric
+/// conversions, but result in errors.
+///
+/// Returns the formatted output along with any errors caused by invalid
+/// arguments.
+fn format_args(segments: &[Segment], args: &[String]) -> (String, Vec<FormatError>) {
+    let mut output = String::new();
+    let mut errors = Vec::new();
+    let mut args = args.iter();
+    let has_conversions = segments
+        .iter()
+        .any(|segment| matches!(segment, Segment::Conversion(_)));
+
+    loop {
+        for segment in segments {
+            match segment {
+                Segment::Literal(literal) => output.push_str(literal),
+                Segment::Conversion(spec) => {
+                    let arg = args.next().map_or("", String::as_str);
+                    match convert(spec, arg) {
+                        Ok(value) => output.push_str(&value),
+                        Err(error @ FormatError::InvalidNumber(_)) => {
+                            // Invalid numbers are converted as zero, as in POSIX printf.
+                            output.push_str(&convert(spec, "0").unwrap_or_default());
+                            errors.push(error);
+                        }
+                        Err(error) => errors.push(error),
+                    }
+                }
+            }
+        }
+
+        if !has_conversions || args.len() == 0 {
+            break;
+        }
+    }
+
+    (output, errors)
+}
+
+/// Converts an argument using a conversion specification.
+fn convert(spec: &Spec, arg: &str) -> Result<String, FormatError> {
+    match spec.conversion {
+        's' => {
+            let value = match spec.precision {
+                Some(precision) => arg.chars().take(precision).collect(),
+                None => arg.to_owned(),
+            };
+            Ok(pad(spec, value, false))
+        }
+        'd' => parse_integer(arg).map(|n| pad(spec, with_min_digits(spec, n.to_string()), true)),
+        'x' => parse_integer(arg).map(|n| pad(spec, with_min_digits(spec, format!("{n:x}")), true)),
+        'f' => parse_float(arg).map(|n| {
+            let precision = spec.precision.unwrap_or(DEFAULT_FLOAT_PRECISION);
+            pad(spec, format!("{n:.precision$}"), true)
+        }),
+        other => Err(FormatError::InvalidConversion(other)),
+    }
+}
+
+/// Pads an integer with leading zeros to the precision of a conversion
+/// specification.
+fn with_min_digits(spec: &Spec, value: String) -> String {
+    let Some(precision) = spec.precision else {
+        return value;
+    };
+
+    let (sign, digits) = split_sign(&value);
+    match digits.len() < precision {
+        true => format!("{sign}{}{digits}", "0".repeat(precision - digits.len())),
+        false => value,
+    }
+}
+
+/// Parses an integer argument. Empty arguments are treated as zero.
+fn parse_integer(arg: &str) -> Result<i64, FormatError> {
+    if arg.is_empty() {
+        return Ok(0);
+    }
+
+    arg.trim()
+        .parse()
+        .map_err(|_| FormatError::InvalidNumber(arg.to_owned()))
+}
+
+/// Parses a floating point argument. Empty arguments are treated as zero.
+fn parse_float(arg: &str) -> Result<f64, FormatError> {
+    if arg.is_empty() {
+        return Ok(0.0);
+    }
+
+    arg.trim()
+        .parse()
+        .map_err(|_| FormatError::InvalidNumber(arg.to_owned()))
+}
+
+/// Pads a converted value to the width of a conversion specification.
+fn pad(spec: &Spec, value: String, is_numeric: bool) -> String {
+    let len = value.chars().count();
+    if len >= spec.width {
+        return value;
+    }
+
+    let padding = spec.width - len;
+    if spec.left_align {
+        return value + &" ".repeat(padding);
+    }
+
+    if spec.zero_pad && is_numeric {
+        // Zeros are inserted between the sign and the digits.
+        let (sign, digits) = split_sign(&value);
+        return format!("{sign}{}{digits}", "0".repeat(padding));
+    }
+
+    " ".repeat(padding) + &value
+}
+
+/// Splits a number into its sign and its digits.
+fn split_sign(value: &str) -> (&str, &str) {
+    match value.strip_prefix('-') {
+        Some(digits) => ("-", digits),
+        None => ("", value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::{assert_prints_help, assert_prints_usage_error, run_builtin};
+
+    use super::*;
+
+    /// Runs printf with some arguments and returns its exit code and output.
+    fn printf(args: &[&str]) -> (i32, String, String) {
+        let mut all_args = vec!["printf"];
+        all_args.extend(args);
+        run_builtin(&Printf, &all_args)
+    }
+
+    #[test]
+    fn it_prints_help() {
+        assert_prints_help(&Printf);
+    }
+
+    #[test]
+    fn it_prints_usage_errors() {
+        assert_prints_usage_error(&Printf, &["printf"]);
+    }
+
+    #[test]
+    fn it_formats_strings_and_integers() {
+        assert_eq!(
+            printf(&["%s is %d (0x%x)\\n", "answer", "42", "42"]),
+            (0, "answer is 42 (0x2a)\n".into(), String::new())
+        );
+    }
+
+    #[test]
+    fn it_interprets_escapes() {
+        assert_eq!(
+            printf(&["a\\tb\\\\c%%\\n"]),
+            (0, "a\tb\\c%\n".into(), String::new())
+        );
+    }
+
+    #[test]
+    fn it_pads_values() {
+        assert_eq!(
+            printf(&["[%5s][%-5s][%05d][%04x]", "ab", "cd", "-42", "255"]),
+            (0, "[   ab][cd   ][-0042][00ff]".into(), String::new())
+        );
+    }
+
+    #[test]
+    fn it_formats_floats() {
+        assert_eq!(
+            printf(&[
+                "%f|%.2f|%8.3f|%-6.1f|%.0f",
+                "1.5",
+                "3.14159",
+                "-2",
+                "0.3",
+                "2.7"
+            ]),
+            (0, "1.500000|3.14|  -2.000|0.3   |3".into(), String::new())
+        );
+    }
+
+    #[test]
+    fn it_applies_precision() {
+        assert_eq!(
+            printf(&["[%.3s][%5.1s][%.4d][%.3x]", "abcdef", "xyz", "-7", "10"]),
+            (0, "[abc][    x][-0007][00a]".into(), String::new())
+        );
+    }
+
+    #[test]
+    fn it_formats_tables() {
+        assert_eq!(
+            printf(&["%-6s%5.1f\\n", "apple", "1.5", "kiwi", "10"]),
+            (0, "apple   1.5\nkiwi   10.0\n".into(), String::new())
+        );
+    }
+
+    #[test]
+    fn it_substitutes_missing_arguments() {
+        assert_eq!(
+            printf(&["[%s][%d][%x][%.1f]"]),
+            (0, "[][0][0][0.0]".into(), String::new())
+        );
+    }
+
+    #[test]
+    fn it_reuses_the_format_for_remaining_arguments() {
+        assert_eq!(
+            printf(&["%s=%d\\n", "a", "1", "b", "2", "c"]),
+            (0, "a=1\nb=2\nc=0\n".into(), String::new())
+        );
+    }
+
+    #[test]
+    fn it_prints_formats_without_conversions_once() {
+        assert_eq!(
+            printf(&["text\\n", "unused"]),
+            (0, "text\n".into(), String::new())
+        );
+    }
+
+    #[test]
+    fn it_fails_on_invalid_conversions() {
+        let (code, stdout, stderr) = printf(&["%q", "arg"]);
+        assert_eq!(code, status::GENERAL_ERROR);
+        assert_eq!(stdout, "");
+        assert_eq!(stderr, "printf: invalid conversion: %q\n");
+    }
+
+    #[test]
+    fn it_fails_on_invalid_numbers() {
+        let (code, stdout, stderr) = printf(&["%d\\n", "NaN"]);
+        assert_eq!(code, status::GENERAL_ERROR);
+        assert_eq!(stdout, "0\n");
+        assert_eq!(stderr, "printf: invalid number: NaN\n");
+    }
+
+    #[test]
+    fn it_converts_invalid_numbers_as_zero() {
+        let (code, stdout, stderr) = printf(&["[%03d][%x][%.1f][%s]\\n", "abc", "x", "y", "z"]);
+        assert_eq!(code, status::GENERAL_ERROR);
+        assert_eq!(stdout, "[000][0][0.0][z]\n");
+        assert_eq!(
+            stderr,
+            "printf: invalid number: abc\nprintf: invalid number: x\nprintf: invalid number: y\n"
+        );
+    }
+}
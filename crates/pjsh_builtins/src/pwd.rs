@@ -26,6 +26,14 @@ fn name(&self) -> &str {
         NAME
     }
 
+    fn description(&self) -> Option<String> {
+        utils::description::<PwdOpts>()
+    }
+
+    fn usage(&self) -> Option<String> {
+        utils::usage::<PwdOpts>(NAME)
+    }
+
     fn run(&self, args: &mut Args) -> CommandResult {
         match PwdOpts::try_parse_from(args.context.args()) {
             Ok(opts) => print_working_directory(opts, args),
@@ -40,8 +48,7 @@ fn print_working_directory(_opts: PwdOpts, args: &mut Args) -> CommandResult {
 
     if let Some(dir) = cwd {
         if let Err(error) = writeln!(args.io.stdout, "{}", path_to_string(dir)) {
-            let _ = writeln!(args.io.stderr, "{NAME}: {error}");
-            return CommandResult::code(status::GENERAL_ERROR);
+            return utils::exit_with_write_error(NAME, args.io, error);
         }
 
         return CommandResult::code(status::SUCCESS);
@@ -55,7 +62,7 @@ fn print_working_directory(_opts: PwdOpts, args: &mut Args) -> CommandResult {
 mod tests {
     use pjsh_core::{Context, Value};
 
-    use crate::utils::{file_contents, mock_io};
+    use crate::utils::{assert_prints_help, assert_prints_usage_error, file_contents, mock_io};
 
     use super::*;
 
@@ -64,7 +71,8 @@ fn it_prints_the_current_working_directory() {
         let mut ctx = Context::default();
         let (mut io, mut stdout, mut stderr) = mock_io();
 
-        ctx.set_var("PWD".into(), Value::Word("/current/path".into()));
+        ctx.set_var("PWD".into(), Value::Word("/current/path".into()))
+            .unwrap();
         let pwd = Pwd {};
 
         let mut args = Args::new(&mut ctx, &mut io);
@@ -77,4 +85,14 @@ fn it_prints_the_current_working_directory() {
             unreachable!()
         }
     }
+
+    #[test]
+    fn it_prints_help() {
+        assert_prints_help(&Pwd);
+    }
+
+    #[test]
+    fn it_prints_usage_errors() {
+        assert_prints_usage_error(&Pwd, &["pwd", "extra"]);
+    }
 }
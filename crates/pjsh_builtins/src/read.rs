@@ -0,0 +1,220 @@
+use clap::Parser;
+use pjsh_core::{
+    command::{Args, Command, CommandResult},
+    Value,
+};
+
+use crate::{status, utils};
+
+/// Command name.
+const NAME: &str = "read";
+
+/// Variable name to use if no names are supplied.
+const DEFAULT_NAME: &str = "REPLY";
+
+/// Read a line from standard input.
+///
+/// The line is split into words that are assigned to each name in order. The
+/// final name is assigned all remaining words.
+///
+/// Exits with a non-zero status if the end of input is reached.
+///
+/// This is a built-in shell command.
+#[derive(Parser)]
+#[clap(name = NAME, version)]
+struct ReadOpts {
+    /// Prompt to print to standard error if the shell is interactive.
+    #[clap(short, long)]
+    prompt: Option<String>,
+
+    /// Variable names to assign words to.
+    ///
+    /// If no names are supplied, the line is assigned to $REPLY.
+    names: Vec<String>,
+}
+
+/// Implementation for the "read" built-in command.
+#[derive(Clone)]
+pub struct Read;
+impl Command for Read {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn description(&self) -> Option<String> {
+        utils::description::<ReadOpts>()
+    }
+
+    fn usage(&self) -> Option<String> {
+        utils::usage::<ReadOpts>(NAME)
+    }
+
+    fn run(&self, args: &mut Args) -> CommandResult {
+        match ReadOpts::try_parse_from(args.context.args()) {
+            Ok(opts) => read_line(opts, args),
+            Err(error) => utils::exit_with_parse_error(args.io, error),
+        }
+    }
+}
+
+/// Reads a line from stdin and assigns its words to variables.
+///
+/// Returns an exit code.
+fn read_line(opts: ReadOpts, args: &mut Args) -> CommandResult {
+    if let Some(prompt) = &opts.prompt {
+        if utils::is_interactive(args.context) {
+            let _ = write!(args.io.stderr, "{prompt}");
+            let _ = args.io.stderr.flush();
+        }
+    }
+
+    let line = match utils::take_line(&mut args.io.stdin) {
+        Ok(Some(line)) => line,
+        Ok(None) => return CommandResult::code(status::GENERAL_ERROR),
+        Err(error) => {
+            let _ = writeln!(args.io.stderr, "{NAME}: {error}");
+            return CommandResult::code(status::GENERAL_ERROR);
+        }
+    };
+
+    let names = match opts.names.is_empty() {
+        true => vec![DEFAULT_NAME.to_owned()],
+        false => opts.names,
+    };
+
+    let mut result = CommandResult::code(status::SUCCESS);
+    let words = split_words(&line, names.len());
+    for (name, value) in names.into_iter().zip(words) {
+        if let Err(err) = args.context.set_var(name, Value::Word(value)) {
+            let _ = writeln!(args.io.stderr, "{NAME}: {err}");
+            result = CommandResult::code(status::GENERAL_ERROR);
+        }
+    }
+
+    result
+}
+
+/// Splits a line into exactly `n` words.
+///
+/// The final word contains the remainder of the line. Missing words are empty.
+fn split_words(line: &str, n: usize) -> Vec<String> {
+    let mut words = Vec::with_capacity(n);
+    let mut rest = line.trim();
+
+    while words.len() + 1 < n {
+        let (word, remainder) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+        words.push(word.to_owned());
+        rest = remainder.trim_start();
+    }
+
+    if n > 0 {
+        words.push(rest.to_owned());
+    }
+
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    use pjsh_core::{command::Io, Context, Scope};
+
+    use crate::utils::{assert_prints_help, assert_prints_usage_error, take_line};
+
+    use super::*;
+
+    /// Runs the "read" built-in with some input.
+    fn run_read(args: &[&str], input: &'static str, ctx: &mut Context) -> i32 {
+        ctx.replace_args(Some(args.iter().map(ToString::to_string).collect()));
+        let mut io = Io::new(
+            Box::new(input.as_bytes()),
+            Box::new(std::io::sink()),
+            Box::new(std::io::sink()),
+        );
+        let mut args = Args::new(ctx, &mut io);
+
+        let CommandResult::Builtin(result) = Read.run(&mut args) else {
+            unreachable!()
+        };
+        result.code
+    }
+
+    fn empty_context() -> Context {
+        Context::with_scopes(vec![Scope::new(
+            String::new(),
+            Some(Vec::default()),
+            HashMap::default(),
+            HashMap::default(),
+            HashSet::default(),
+        )])
+    }
+
+    #[test]
+    fn it_reads_a_line_into_reply() {
+        let mut ctx = empty_context();
+
+        assert_eq!(run_read(&["read"], "a line\nnext", &mut ctx), 0);
+        assert_eq!(ctx.get_var("REPLY"), Some(&Value::Word("a line".into())));
+    }
+
+    #[test]
+    fn it_splits_words_between_names() {
+        let mut ctx = empty_context();
+
+        let code = run_read(&["read", "first", "rest"], "  one two  three\n", &mut ctx);
+        assert_eq!(code, 0);
+        assert_eq!(ctx.get_var("first"), Some(&Value::Word("one".into())));
+        assert_eq!(ctx.get_var("rest"), Some(&Value::Word("two  three".into())));
+    }
+
+    #[test]
+    fn it_does_not_assign_readonly_variables() {
+        let mut ctx = empty_context();
+        ctx.set_var("first".into(), Value::Word("value".into()))
+            .unwrap();
+        ctx.make_var_readonly("first".into()).unwrap();
+
+        let code = run_read(&["read", "first", "second"], "one two\n", &mut ctx);
+        assert_eq!(code, 1);
+        assert_eq!(ctx.get_var("first"), Some(&Value::Word("value".into())));
+        assert_eq!(ctx.get_var("second"), Some(&Value::Word("two".into())));
+    }
+
+    #[test]
+    fn it_assigns_empty_words_to_remaining_names() {
+        let mut ctx = empty_context();
+
+        assert_eq!(run_read(&["read", "a", "b", "c"], "one\r\n", &mut ctx), 0);
+        assert_eq!(ctx.get_var("a"), Some(&Value::Word("one".into())));
+        assert_eq!(ctx.get_var("b"), Some(&Value::Word("".into())));
+        assert_eq!(ctx.get_var("c"), Some(&Value::Word("".into())));
+    }
+
+    #[test]
+    fn it_fails_at_end_of_input() {
+        let mut ctx = empty_context();
+
+        assert_eq!(run_read(&["read", "line"], "", &mut ctx), 1);
+        assert_eq!(ctx.get_var("line"), None);
+    }
+
+    #[test]
+    fn it_reads_a_single_line_at_a_time() {
+        let mut input: &[u8] = b"first\nsecond";
+
+        assert_eq!(take_line(&mut input).unwrap(), Some("first".into()));
+        assert_eq!(take_line(&mut input).unwrap(), Some("second".into()));
+        assert_eq!(take_line(&mut input).unwrap(), None);
+    }
+
+    #[test]
+    fn it_prints_help() {
+        assert_prints_help(&Read);
+    }
+
+    #[test]
+    fn it_prints_usage_errors() {
+        assert_prints_usage_error(&Read, &["read", "--unknown"]);
+    }
+}
@@ -0,0 +1,188 @@
+use clap::Parser;
+use pjsh_core::{
+    command::{Args, Command, CommandResult},
+    Context, Value,
+};
+
+use crate::{status, utils};
+
+/// Command name.
+const NAME: &str = "readonly";
+
+/// Mark variables as read-only.
+///
+/// Read-only variables can't be reassigned. Lists all read-only variables if
+/// no variables are given.
+///
+/// This is a built-in shell command.
+#[derive(Parser)]
+#[clap(name = NAME, version)]
+struct ReadonlyOpts {
+    /// Variables to mark as read-only, optionally assigning a value using
+    /// "name=value".
+    variables: Vec<String>,
+}
+
+/// Implementation for the "readonly" built-in command.
+#[derive(Clone)]
+pub struct Readonly;
+impl Command for Readonly {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn description(&self) -> Option<String> {
+        utils::description::<ReadonlyOpts>()
+    }
+
+    fn usage(&self) -> Option<String> {
+        utils::usage::<ReadonlyOpts>(NAME)
+    }
+
+    fn run(&self, args: &mut Args) -> CommandResult {
+        match ReadonlyOpts::try_parse_from(args.context.args()) {
+            Ok(opts) if opts.variables.is_empty() => list_readonly_variables(args),
+            Ok(opts) => make_variables_readonly(opts, args),
+            Err(error) => utils::exit_with_parse_error(args.io, error),
+        }
+    }
+}
+
+/// Prints the names of all read-only variables, one name per line.
+///
+/// Returns an exit code.
+fn list_readonly_variables(args: &mut Args) -> CommandResult {
+    let mut names: Vec<&str> = args.context.readonly_vars().into_iter().collect();
+    names.sort_unstable();
+
+    for name in names {
+        let _ = writeln!(args.io.stdout, "{name}");
+    }
+
+    CommandResult::code(status::SUCCESS)
+}
+
+/// Marks shell variables as read-only.
+///
+/// Returns 0 if all variables can be marked as read-only, or 1 if at least
+/// one argument cannot be marked.
+fn make_variables_readonly(opts: ReadonlyOpts, args: &mut Args) -> CommandResult {
+    let mut result = CommandResult::code(status::SUCCESS);
+
+    for variable in opts.variables {
+        if let Err(err) = make_variable_readonly(variable, args.context) {
+            let _ = writeln!(args.io.stderr, "{NAME}: {err}");
+            result = CommandResult::code(status::GENERAL_ERROR);
+        }
+    }
+
+    result
+}
+
+/// Marks a shell variable as read-only.
+fn make_variable_readonly(variable: String, context: &mut Context) -> Result<(), String> {
+    let Some((name, value)) = variable.split_once('=') else {
+        // If there is no equals sign, the variable must already be known by the shell.
+        return context.make_var_readonly(variable);
+    };
+
+    // If an equals sign is present, the value should be set prior to marking the variable.
+    context.set_var(name.to_owned(), Value::Word(value.to_owned()))?;
+    context.make_var_readonly(name.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    use pjsh_core::Scope;
+
+    use crate::utils::{assert_prints_help, file_contents, mock_io};
+
+    use super::*;
+
+    /// Runs the "readonly" built-in in a context.
+    ///
+    /// Returns the exit code along with the contents written to stdout and stderr.
+    fn run_readonly(args: &[&str], ctx: &mut Context) -> (i32, String, String) {
+        ctx.replace_args(Some(args.iter().map(ToString::to_string).collect()));
+        let (mut io, mut stdout, mut stderr) = mock_io();
+        let mut args = Args::new(ctx, &mut io);
+
+        let CommandResult::Builtin(result) = Readonly.run(&mut args) else {
+            unreachable!()
+        };
+        (
+            result.code,
+            file_contents(&mut stdout),
+            file_contents(&mut stderr),
+        )
+    }
+
+    fn context_with_var(name: &str, value: &str) -> Context {
+        Context::with_scopes(vec![Scope::new(
+            "scope".into(),
+            None,
+            HashMap::from([(name.into(), Some(Value::Word(value.into())))]),
+            HashMap::default(),
+            HashSet::default(),
+        )])
+    }
+
+    #[test]
+    fn it_marks_variables_as_readonly() {
+        let mut ctx = context_with_var("known", "value");
+
+        let (code, _, _) = run_readonly(&["readonly", "known", "new=assigned"], &mut ctx);
+        assert_eq!(code, status::SUCCESS);
+        assert!(ctx.is_var_readonly("known"));
+        assert!(ctx.is_var_readonly("new"));
+        assert_eq!(ctx.get_var("new"), Some(&Value::Word("assigned".into())));
+    }
+
+    #[test]
+    fn it_does_not_reassign_readonly_variables() {
+        let mut ctx = context_with_var("var", "value");
+        run_readonly(&["readonly", "var"], &mut ctx);
+
+        assert_eq!(
+            run_readonly(&["readonly", "var=other"], &mut ctx),
+            (
+                status::GENERAL_ERROR,
+                String::new(),
+                "readonly: readonly variable: var\n".into()
+            )
+        );
+        assert_eq!(ctx.get_var("var"), Some(&Value::Word("value".into())));
+    }
+
+    #[test]
+    fn it_fails_to_mark_unknown_variables() {
+        let mut ctx = context_with_var("var", "value");
+
+        assert_eq!(
+            run_readonly(&["readonly", "unknown"], &mut ctx),
+            (
+                status::GENERAL_ERROR,
+                String::new(),
+                "readonly: unknown variable: unknown\n".into()
+            )
+        );
+    }
+
+    #[test]
+    fn it_lists_readonly_variables() {
+        let mut ctx = context_with_var("var", "value");
+        run_readonly(&["readonly", "var", "b=1", "a=2"], &mut ctx);
+
+        assert_eq!(
+            run_readonly(&["readonly"], &mut ctx),
+            (status::SUCCESS, "a\nb\nvar\n".into(), String::new())
+        );
+    }
+
+    #[test]
+    fn it_prints_help() {
+        assert_prints_help(&Readonly);
+    }
+}
@@ -0,0 +1,55 @@
+use pjsh_core::command::BuiltinRegistry;
+
+use crate::*;
+
+/// Returns a registry containing the standard built-in commands.
+///
+/// Built-in commands that depend on the embedding shell, such as `source` and
+/// `.`, are not included.
+pub fn standard_builtins() -> BuiltinRegistry {
+    BuiltinRegistry::new()
+        .with(Box::new(Alias))
+        .with(Box::new(AssertEqual))
+        .with(Box::new(Bg))
+        .with(Box::new(Bookmark))
+        .with(Box::new(Cd))
+        .with(Box::new(Dirs))
+        .with(Box::new(Disown))
+        .with(Box::new(Echo))
+        .with(Box::new(Env))
+        .with(Box::new(Eval))
+        .with(Box::new(Exec))
+        .with(Box::new(Exit))
+        .with(Box::new(Export))
+        .with(Box::new(False))
+        .with(Box::new(Fg))
+        .with(Box::new(Hash))
+        .with(Box::new(Help))
+        .with(Box::new(History))
+        .with(Box::new(Interpolate))
+        .with(Box::new(Jobs))
+        .with(Box::new(Kill))
+        .with(Box::new(Let))
+        .with(Box::new(Path))
+        .with(Box::new(Popd))
+        .with(Box::new(Printf))
+        .with(Box::new(Pushd))
+        .with(Box::new(Pwd))
+        .with(Box::new(Read))
+        .with(Box::new(Readonly))
+        .with(Box::new(Seq))
+        .with(Box::new(Set))
+        .with(Box::new(Shift))
+        .with(Box::new(Sleep))
+        .with(Box::new(Test))
+        .with(Box::new(TestBracket))
+        .with(Box::new(Trap))
+        .with(Box::new(True))
+        .with(Box::new(Type))
+        .with(Box::new(Ulimit))
+        .with(Box::new(Umask))
+        .with(Box::new(Unalias))
+        .with(Box::new(Unset))
+        .with(Box::new(Wait))
+        .with(Box::new(Which))
+}
@@ -0,0 +1,212 @@
+use std::io::{self, BufWriter, Write};
+
+use clap::Parser;
+use pjsh_core::command::{Args, Command, CommandResult};
+
+use crate::{status, utils};
+
+/// Command name.
+const NAME: &str = "seq";
+
+/// Print a sequence of numbers.
+///
+/// Numbers are printed one per line, starting at FIRST and counting up (or
+/// down) by INCREMENT until LAST is passed. Both FIRST and INCREMENT default
+/// to 1 if omitted.
+///
+/// This is a built-in shell command.
+#[derive(Parser)]
+#[clap(name = NAME, version, allow_negative_numbers = true)]
+struct SeqOpts {
+    /// Pad all numbers with leading zeroes to an equal width.
+    #[clap(short = 'w', long = "equal-width")]
+    equal_width: bool,
+
+    /// [FIRST [INCREMENT]] LAST
+    #[clap(value_name = "NUMBER", num_args = 1..=3, required = true)]
+    numbers: Vec<i64>,
+}
+
+/// Implementation for the "seq" built-in command.
+#[derive(Clone)]
+pub struct Seq;
+impl Command for Seq {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn description(&self) -> Option<String> {
+        utils::description::<SeqOpts>()
+    }
+
+    fn usage(&self) -> Option<String> {
+        utils::usage::<SeqOpts>(NAME)
+    }
+
+    fn run(&self, args: &mut Args) -> CommandResult {
+        match SeqOpts::try_parse_from(args.context.args()) {
+            Ok(opts) => seq(opts, args),
+            Err(error) => utils::exit_with_parse_error(args.io, error),
+        }
+    }
+}
+
+/// Prints a sequence of numbers to stdout.
+///
+/// Returns an exit code.
+fn seq(opts: SeqOpts, args: &mut Args) -> CommandResult {
+    let (first, increment, last) = match opts.numbers[..] {
+        [last] => (1, 1, last),
+        [first, last] => (first, 1, last),
+        [first, increment, last] => (first, increment, last),
+        _ => unreachable!("clap should require 1 to 3 numbers"),
+    };
+
+    if increment == 0 {
+        let _ = writeln!(args.io.stderr, "{NAME}: increment must not be zero");
+        return CommandResult::code(status::BUILTIN_ERROR);
+    }
+
+    let width = match opts.equal_width {
+        true => first.to_string().len().max(last.to_string().len()),
+        false => 0,
+    };
+
+    let numbers = sequence(first, increment, last);
+    if let Err(error) = print_numbers(&mut args.io.stdout, numbers, width) {
+        return utils::exit_with_write_error(NAME, args.io, error);
+    }
+
+    CommandResult::code(status::SUCCESS)
+}
+
+/// Prints numbers one per line, padded with leading zeroes to `width`.
+///
+/// Output is buffered, as sequences may be arbitrarily long.
+fn print_numbers(
+    stdout: &mut impl Write,
+    numbers: impl Iterator<Item = i64>,
+    width: usize,
+) -> io::Result<()> {
+    let mut writer = BufWriter::new(stdout);
+    for number in numbers {
+        writeln!(writer, "{number:0width$}")?;
+    }
+    writer.flush()
+}
+
+/// Returns an iterator over all numbers from `first` to `last`, inclusive, in
+/// steps of a non-zero `increment`.
+fn sequence(first: i64, increment: i64, last: i64) -> impl Iterator<Item = i64> {
+    let in_range = move |number: &i64| match increment > 0 {
+        true => *number <= last,
+        false => *number >= last,
+    };
+
+    std::iter::successors(Some(first), move |number| number.checked_add(increment))
+        .take_while(in_range)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::{assert_prints_help, assert_prints_usage_error, run_builtin};
+
+    use super::*;
+
+    #[test]
+    fn it_prints_sequences() {
+        assert_eq!(
+            run_builtin(&Seq, &["seq", "3"]),
+            (status::SUCCESS, "1\n2\n3\n".into(), String::new())
+        );
+        assert_eq!(
+            run_builtin(&Seq, &["seq", "-1", "1"]),
+            (status::SUCCESS, "-1\n0\n1\n".into(), String::new())
+        );
+        assert_eq!(
+            run_builtin(&Seq, &["seq", "1", "3", "8"]),
+            (status::SUCCESS, "1\n4\n7\n".into(), String::new())
+        );
+        assert_eq!(
+            run_builtin(&Seq, &["seq", "3", "1"]),
+            (status::SUCCESS, String::new(), String::new())
+        );
+    }
+
+    #[test]
+    fn it_prints_decreasing_sequences() {
+        assert_eq!(
+            run_builtin(&Seq, &["seq", "3", "-1", "1"]),
+            (status::SUCCESS, "3\n2\n1\n".into(), String::new())
+        );
+    }
+
+    #[test]
+    fn it_pads_numbers_to_equal_width() {
+        assert_eq!(
+            run_builtin(&Seq, &["seq", "-w", "8", "10"]),
+            (status::SUCCESS, "08\n09\n10\n".into(), String::new())
+        );
+        assert_eq!(
+            run_builtin(&Seq, &["seq", "-w", "-1", "1"]),
+            (status::SUCCESS, "-1\n00\n01\n".into(), String::new())
+        );
+    }
+
+    #[test]
+    fn it_stops_at_the_integer_limit() {
+        let max = i64::MAX.to_string();
+        let (code, stdout, _) = run_builtin(&Seq, &["seq", &max, &max]);
+        assert_eq!((code, stdout), (status::SUCCESS, format!("{max}\n")));
+    }
+
+    #[test]
+    fn it_prints_long_sequences() {
+        let expected: String = (1..=10_000).map(|number| format!("{number}\n")).collect();
+        assert_eq!(
+            run_builtin(&Seq, &["seq", "10000"]),
+            (status::SUCCESS, expected, String::new())
+        );
+    }
+
+    #[test]
+    fn it_reports_broken_pipes() {
+        struct BrokenPipe;
+        impl Write for BrokenPipe {
+            fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+                Err(io::ErrorKind::BrokenPipe.into())
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Err(io::ErrorKind::BrokenPipe.into())
+            }
+        }
+
+        let error = print_numbers(&mut BrokenPipe, 1..=3, 0).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::BrokenPipe);
+    }
+
+    #[test]
+    fn it_requires_non_zero_increments() {
+        assert_eq!(
+            run_builtin(&Seq, &["seq", "1", "0", "3"]),
+            (
+                status::BUILTIN_ERROR,
+                String::new(),
+                "seq: increment must not be zero\n".into()
+            )
+        );
+    }
+
+    #[test]
+    fn it_prints_help() {
+        assert_prints_help(&Seq);
+    }
+
+    #[test]
+    fn it_prints_usage_errors() {
+        assert_prints_usage_error(&Seq, &["seq"]);
+        assert_prints_usage_error(&Seq, &["seq", "1", "2", "3", "4"]);
+        assert_prints_usage_error(&Seq, &["seq", "one"]);
+    }
+}
@@ -0,0 +1,243 @@
+use clap::Parser;
+use pjsh_core::{
+    command::{Args, Command, CommandResult},
+    Options,
+};
+
+use crate::{status, utils};
+
+/// Command name.
+const NAME: &str = "set";
+
+/// Enable or disable shell options.
+///
+/// Options are enabled using `-o OPTION` and disabled using `+o OPTION`. The
+/// short forms -e (errexit), -u (nounset), and -C (noclobber) are also
+/// accepted, as is -f (noglob). Short forms are disabled using + rather than
+/// -. Disabled options are applied before enabled options.
+///
+/// Available options are errexit, nounset, pipefail, noclobber, warnsplit, and
+/// noglob. The strict option refers to all of them except noglob at once.
+///
+/// If no options are supplied, the state of each option is printed.
+///
+/// This is a built-in shell command.
+#[derive(Parser)]
+#[clap(name = NAME, version)]
+struct SetOpts {
+    /// Enable an option.
+    #[clap(short = 'o', value_name = "OPTION")]
+    enable: Vec<String>,
+
+    /// Disable an option. Typically written as `+o OPTION`.
+    #[clap(long = "disable", value_name = "OPTION")]
+    disable: Vec<String>,
+}
+
+/// Implementation for the "set" built-in command.
+#[derive(Clone)]
+pub struct Set;
+impl Command for Set {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn description(&self) -> Option<String> {
+        utils::description::<SetOpts>()
+    }
+
+    fn usage(&self) -> Option<String> {
+        utils::usage::<SetOpts>(NAME)
+    }
+
+    fn run(&self, args: &mut Args) -> CommandResult {
+        match SetOpts::try_parse_from(normalize_args(args.context.args())) {
+            Ok(opts) => set_options(opts, args),
+            Err(error) => utils::exit_with_parse_error(args.io, error),
+        }
+    }
+}
+
+/// Rewrites `+`-prefixed and short form options into options that can be
+/// parsed.
+///
+/// For example, `set -eu +o pipefail` is parsed as
+/// `set -o errexit -o nounset --disable pipefail`.
+fn normalize_args(args: &[String]) -> Vec<String> {
+    let mut normalized = Vec::with_capacity(args.len());
+    for (index, arg) in args.iter().enumerate() {
+        if index == 0 {
+            normalized.push(arg.clone());
+            continue;
+        }
+
+        if arg == "+o" {
+            normalized.push("--disable".into());
+            continue;
+        }
+
+        let (flag, short_names) = match (arg.strip_prefix('-'), arg.strip_prefix('+')) {
+            (Some(short_names), _) => ("-o", short_names),
+            (_, Some(short_names)) => ("--disable", short_names),
+            _ => {
+                normalized.push(arg.clone());
+                continue;
+            }
+        };
+
+        match short_names
+            .chars()
+            .map(option_name)
+            .collect::<Option<Vec<_>>>()
+        {
+            Some(names) if !names.is_empty() => {
+                for name in names {
+                    normalized.push(flag.into());
+                    normalized.push(name.into());
+                }
+            }
+            _ => normalized.push(arg.clone()),
+        }
+    }
+    normalized
+}
+
+/// Returns the name of an option given its short form.
+fn option_name(short_name: char) -> Option<&'static str> {
+    match short_name {
+        'e' => Some("errexit"),
+        'u' => Some("nounset"),
+        'C' => Some("noclobber"),
+        'f' => Some("noglob"),
+        _ => None,
+    }
+}
+
+/// Enables and disables options, or prints the state of each option if no
+/// options are supplied.
+///
+/// Returns an exit code.
+fn set_options(opts: SetOpts, args: &mut Args) -> CommandResult {
+    if opts.enable.is_empty() && opts.disable.is_empty() {
+        for name in Options::NAMES {
+            let sign = match args.context.options.get(name) {
+                Some(true) => '-',
+                _ => '+',
+            };
+            let _ = writeln!(args.io.stdout, "{NAME} {sign}o {name}");
+        }
+        return CommandResult::code(status::SUCCESS);
+    }
+
+    // Validate all options before changing any of them.
+    let mut options = args.context.options;
+    let changes = opts
+        .disable
+        .iter()
+        .map(|name| (name, false))
+        .chain(opts.enable.iter().map(|name| (name, true)));
+    for (name, enabled) in changes {
+        if !options.set(name, enabled) {
+            let _ = writeln!(args.io.stderr, "{NAME}: {name}: invalid option name");
+            return CommandResult::code(status::BUILTIN_ERROR);
+        }
+    }
+
+    args.context.options = options;
+    CommandResult::code(status::SUCCESS)
+}
+
+#[cfg(test)]
+mod tests {
+    use pjsh_core::Context;
+
+    use crate::utils::{assert_prints_help, file_contents, mock_io, run_builtin};
+
+    use super::*;
+
+    /// Runs the built-in in a context, returning its exit code.
+    fn run(ctx: &mut Context, args: &[&str]) -> i32 {
+        ctx.replace_args(Some(args.iter().map(ToString::to_string).collect()));
+        let (mut io, _stdout, _stderr) = mock_io();
+        match Set.run(&mut Args::new(ctx, &mut io)) {
+            CommandResult::Builtin(result) => result.code,
+            CommandResult::Process(_) => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_normalizes_short_and_disabling_options() {
+        let args: Vec<String> = ["set", "-eu", "+C", "+o", "pipefail", "-o", "warnsplit"]
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            normalize_args(&args),
+            vec![
+                "set",
+                "-o",
+                "errexit",
+                "-o",
+                "nounset",
+                "--disable",
+                "noclobber",
+                "--disable",
+                "pipefail",
+                "-o",
+                "warnsplit"
+            ]
+        );
+    }
+
+    #[test]
+    fn it_toggles_options() {
+        let mut ctx = Context::default();
+        assert_eq!(run(&mut ctx, &["set", "-o", "strict"]), status::SUCCESS);
+        assert!(ctx.options.errexit && ctx.options.pipefail && ctx.options.warnsplit);
+
+        // Disabled options are applied first.
+        assert_eq!(
+            run(&mut ctx, &["set", "-o", "nounset", "+u"]),
+            status::SUCCESS
+        );
+        assert!(ctx.options.nounset);
+        assert_eq!(run(&mut ctx, &["set", "+u"]), status::SUCCESS);
+        assert!(!ctx.options.nounset && ctx.options.errexit);
+
+        assert_eq!(run(&mut ctx, &["set", "+o", "strict"]), status::SUCCESS);
+        assert!(!ctx.options.errexit && !ctx.options.pipefail);
+    }
+
+    #[test]
+    fn it_rejects_unknown_options() {
+        let mut ctx = Context::default();
+        assert_eq!(
+            run(&mut ctx, &["set", "-o", "errexit", "-o", "unknown"]),
+            status::BUILTIN_ERROR
+        );
+        assert_eq!(ctx.options, Options::default());
+    }
+
+    #[test]
+    fn it_prints_options() {
+        let mut ctx = Context::default();
+        ctx.options.pipefail = true;
+        ctx.replace_args(Some(vec![NAME.into()]));
+        let (mut io, mut stdout, _stderr) = mock_io();
+        let _ = Set.run(&mut Args::new(&mut ctx, &mut io));
+        assert_eq!(
+            file_contents(&mut stdout),
+            "set +o errexit\nset -o nounset\nset -o pipefail\nset +o noclobber\nset +o warnsplit\nset +o noglob\n"
+        );
+    }
+
+    #[test]
+    fn it_prints_help() {
+        assert_prints_help(&Set);
+    }
+
+    #[test]
+    fn it_reports_unknown_short_options() {
+        assert_eq!(run_builtin(&Set, &["set", "-x"]).0, status::BUILTIN_ERROR);
+    }
+}
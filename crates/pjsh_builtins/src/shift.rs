@@ -0,0 +1,94 @@
+use clap::Parser;
+use pjsh_core::command::{Action, Args, Command, CommandResult};
+
+use crate::{status, utils};
+
+/// Command name.
+const NAME: &str = "shift";
+
+/// Shift positional arguments.
+///
+/// Removes the first positional arguments, so that `$1` refers to the argument
+/// following the removed arguments.
+///
+/// Exits with a non-zero status, without removing any arguments, if there are
+/// fewer positional arguments than requested.
+///
+/// This is a built-in shell command.
+#[derive(Parser)]
+#[clap(name = NAME, version)]
+struct ShiftOpts {
+    /// Number of positional arguments to remove.
+    #[clap(default_value_t = 1)]
+    n: usize,
+}
+
+/// Implementation for the "shift" built-in command.
+#[derive(Clone)]
+pub struct Shift;
+impl Command for Shift {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn description(&self) -> Option<String> {
+        utils::description::<ShiftOpts>()
+    }
+
+    fn usage(&self) -> Option<String> {
+        utils::usage::<ShiftOpts>(NAME)
+    }
+
+    fn run(&self, args: &mut Args) -> CommandResult {
+        match ShiftOpts::try_parse_from(args.context.args()) {
+            // Positional arguments belong to the calling scope, which is
+            // restored once the built-in has been executed.
+            Ok(opts) => {
+                CommandResult::with_actions(status::SUCCESS, vec![Action::ShiftArgs(opts.n)])
+            }
+            Err(error) => utils::exit_with_parse_error(args.io, error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pjsh_core::Context;
+
+    use crate::utils::{assert_prints_help, assert_prints_usage_error, empty_io};
+
+    use super::*;
+
+    fn shifted(args: &[&str]) -> Option<usize> {
+        let mut ctx = Context::default();
+        ctx.replace_args(Some(args.iter().map(ToString::to_string).collect()));
+        let mut io = empty_io();
+        match Shift.run(&mut Args::new(&mut ctx, &mut io)) {
+            CommandResult::Builtin(result) => match result.actions.as_slice() {
+                [Action::ShiftArgs(n)] => Some(*n),
+                _ => None,
+            },
+            CommandResult::Process(_) => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_shifts_one_argument_by_default() {
+        assert_eq!(shifted(&["shift"]), Some(1));
+    }
+
+    #[test]
+    fn it_shifts_multiple_arguments() {
+        assert_eq!(shifted(&["shift", "3"]), Some(3));
+    }
+
+    #[test]
+    fn it_prints_help() {
+        assert_prints_help(&Shift);
+    }
+
+    #[test]
+    fn it_prints_usage_errors() {
+        assert_prints_usage_error(&Shift, &["shift", "-1"]);
+    }
+}
@@ -36,6 +36,14 @@ fn name(&self) -> &str {
         NAME
     }
 
+    fn description(&self) -> Option<String> {
+        utils::description::<SleepOpts>()
+    }
+
+    fn usage(&self) -> Option<String> {
+        utils::usage::<SleepOpts>(NAME)
+    }
+
     fn run(&self, args: &mut Args) -> CommandResult {
         match SleepOpts::try_parse_from(args.context.args()) {
             Ok(opts) => sleep(opts),
@@ -66,3 +74,29 @@ fn parse_duration(args: &SleepOpts) -> std::time::Duration {
         TimeUnit::Hours => std::time::Duration::from_secs(args.duration * 3600),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::{assert_prints_help, assert_prints_usage_error, run_builtin};
+
+    use super::*;
+
+    #[test]
+    fn it_prints_help() {
+        assert_prints_help(&Sleep);
+    }
+
+    #[test]
+    fn it_sleeps() {
+        assert_eq!(
+            run_builtin(&Sleep, &["sleep", "0"]),
+            (status::SUCCESS, String::new(), String::new())
+        );
+    }
+
+    #[test]
+    fn it_prints_usage_errors() {
+        assert_prints_usage_error(&Sleep, &["sleep"]);
+        assert_prints_usage_error(&Sleep, &["sleep", "1", "weeks"]);
+    }
+}
@@ -1,12 +1,13 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use clap::Parser;
+use clap::{CommandFactory, FromArgMatches, Parser};
 use pjsh_core::{
     command::{Args, Command, CommandResult},
-    Context,
+    utils::{path_to_string, word_var},
+    Context, Value,
 };
 
-use crate::utils;
+use crate::{status, utils};
 
 /// Command name.
 const NAME: &str = "source";
@@ -18,6 +19,15 @@
 #[derive(Parser)]
 #[clap(name = NAME, version)]
 struct SourceOpts {
+    /// Undo changes to variables, functions, and aliases if the script fails.
+    #[clap(long)]
+    sandbox: bool,
+
+    /// Resolve the script file relative to the directory of the current script
+    /// rather than the working directory.
+    #[clap(short, long)]
+    relative: bool,
+
     /// Script file to execute.
     file: PathBuf,
 
@@ -53,16 +63,16 @@ fn name(&self) -> &str {
         NAME
     }
 
+    fn description(&self) -> Option<String> {
+        utils::description::<SourceOpts>()
+    }
+
+    fn usage(&self) -> Option<String> {
+        utils::usage::<SourceOpts>(NAME)
+    }
+
     fn run(&self, args: &mut Args) -> CommandResult {
-        match SourceOpts::try_parse_from(args.context.args()) {
-            Ok(opts) => {
-                let old_args = args.context.replace_args(Some(opts.args));
-                (self.source_function)(opts.file, args.context);
-                args.context.replace_args(old_args); // Restore args in context.
-                CommandResult::code(args.context.last_exit())
-            }
-            Err(error) => utils::exit_with_parse_error(args.io, error),
-        }
+        source(NAME, &self.source_function, args)
     }
 }
 
@@ -94,15 +104,252 @@ fn name(&self) -> &str {
         NAME_SHORTHAND
     }
 
+    fn description(&self) -> Option<String> {
+        utils::description::<SourceOpts>()
+    }
+
+    fn usage(&self) -> Option<String> {
+        utils::usage::<SourceOpts>(NAME_SHORTHAND)
+    }
+
     fn run(&self, args: &mut Args) -> CommandResult {
-        match SourceOpts::try_parse_from(args.context.args()) {
-            Ok(opts) => {
-                let old_args = args.context.replace_args(Some(opts.args));
-                (self.source_function)(opts.file, args.context);
-                args.context.replace_args(old_args); // Restore args in context.
-                CommandResult::code(args.context.last_exit())
+        source(NAME_SHORTHAND, &self.source_function, args)
+    }
+}
+
+/// Sources a file using a callback function.
+///
+/// The command name is used when printing help and usage errors.
+///
+/// Returns the last exit code after sourcing the file.
+fn source<F>(name: &'static str, source_function: &F, args: &mut Args) -> CommandResult
+where
+    F: Fn(PathBuf, &mut Context),
+{
+    let opts = SourceOpts::command()
+        .name(name)
+        .try_get_matches_from(args.context.args())
+        .and_then(|matches| SourceOpts::from_arg_matches(&matches));
+
+    match opts {
+        Ok(opts) => {
+            let file = match opts.relative {
+                true => match word_var(args.context, "PJSH_CURRENT_SCRIPT_DIR") {
+                    Some(dir) => Path::new(dir).join(&opts.file),
+                    None => {
+                        let file = path_to_string(&opts.file);
+                        let _ = writeln!(
+                            args.io.stderr,
+                            "{name}: {file}: no current script to resolve the path from"
+                        );
+                        return CommandResult::code(status::GENERAL_ERROR);
+                    }
+                },
+                false => opts.file,
+            };
+
+            let snapshot = opts.sandbox.then(|| args.context.snapshot());
+            let old_args = args.context.replace_args(Some(opts.args));
+            let old_script = replace_current_script(args.context, Some(&file));
+            source_function(file, args.context);
+            replace_current_script(args.context, old_script.as_deref());
+            args.context.replace_args(old_args); // Restore args in context.
+
+            let exit = args.context.last_exit();
+            if let Some(snapshot) = snapshot.filter(|_| exit != 0) {
+                args.context.restore(snapshot);
+            }
+
+            CommandResult::code(exit)
+        }
+        Err(error) => utils::exit_with_parse_error(args.io, error),
+    }
+}
+
+/// Replaces the path of the current script, updating `$PJSH_CURRENT_SCRIPT_PATH`
+/// and `$PJSH_CURRENT_SCRIPT_DIR`.
+///
+/// Returns the path of the replaced script, if any.
+fn replace_current_script(context: &mut Context, script: Option<&Path>) -> Option<PathBuf> {
+    let old_script = word_var(context, "PJSH_CURRENT_SCRIPT_PATH").map(PathBuf::from);
+
+    match script {
+        Some(script) => {
+            let script = script.canonicalize().unwrap_or_else(|_| script.to_owned());
+            let dir = script.parent().map(path_to_string).unwrap_or_default();
+            let path = path_to_string(&script);
+            let _ = context.set_var("PJSH_CURRENT_SCRIPT_PATH".into(), Value::Word(path));
+            let _ = context.set_var("PJSH_CURRENT_SCRIPT_DIR".into(), Value::Word(dir));
+        }
+        None => {
+            let _ = context.unset_var("PJSH_CURRENT_SCRIPT_PATH");
+            let _ = context.unset_var("PJSH_CURRENT_SCRIPT_DIR");
+        }
+    }
+
+    old_script
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use crate::utils::{
+        assert_prints_help, assert_prints_usage_error, file_contents, mock_io, run_builtin,
+    };
+
+    use super::*;
+
+    /// Source function that does nothing.
+    fn noop(_: PathBuf, _: &mut Context) {}
+
+    /// Source function that records the sourced file and the current script
+    /// directory in variables.
+    fn record(file: PathBuf, context: &mut Context) {
+        let dir = word_var(context, "PJSH_CURRENT_SCRIPT_DIR").unwrap_or_default();
+        let dir = Value::Word(dir.to_owned());
+        context
+            .set_var("SOURCED_FILE".into(), Value::Word(path_to_string(file)))
+            .unwrap();
+        context.set_var("SOURCED_DIR".into(), dir).unwrap();
+    }
+
+    /// Runs the "source" built-in within a context.
+    ///
+    /// Returns the exit code along with the contents written to stderr.
+    fn run_source(args: &[&str], context: &mut Context) -> (i32, String) {
+        context.replace_args(Some(args.iter().map(ToString::to_string).collect()));
+        let (mut io, _stdout, mut stderr) = mock_io();
+        let mut args = Args::new(context, &mut io);
+
+        let CommandResult::Builtin(result) = Source::new(record).run(&mut args) else {
+            unreachable!()
+        };
+        (result.code, file_contents(&mut stderr))
+    }
+
+    #[test]
+    fn it_prints_help() {
+        assert_prints_help(&Source::new(noop));
+        assert_prints_help(&SourceShorthand::new(noop));
+    }
+
+    #[test]
+    fn it_sources_files() {
+        assert_eq!(
+            run_builtin(&Source::new(noop), &["source", "file"]),
+            (0, String::new(), String::new())
+        );
+        assert_eq!(
+            run_builtin(&SourceShorthand::new(noop), &[".", "file"]),
+            (0, String::new(), String::new())
+        );
+    }
+
+    #[test]
+    fn it_restores_the_context_if_sandboxed_scripts_fail() {
+        fn failing_script(_: PathBuf, context: &mut Context) {
+            context
+                .set_var("var".into(), Value::Word("changed".into()))
+                .unwrap();
+            context.register_exit(1);
+        }
+
+        for (sandbox, expected) in [(true, "original"), (false, "changed")] {
+            let mut context = Context::default();
+            context
+                .set_var("var".into(), Value::Word("original".into()))
+                .unwrap();
+            let mut source_args = vec!["source", "file"];
+            if sandbox {
+                source_args.insert(1, "--sandbox");
             }
-            Err(error) => utils::exit_with_parse_error(args.io, error),
+            context.replace_args(Some(source_args.iter().map(ToString::to_string).collect()));
+
+            let (mut io, _stdout, _stderr) = mock_io();
+            let mut args = Args::new(&mut context, &mut io);
+            let CommandResult::Builtin(result) = Source::new(failing_script).run(&mut args) else {
+                unreachable!()
+            };
+
+            assert_eq!(result.code, 1);
+            assert_eq!(context.get_var("var"), Some(&Value::Word(expected.into())));
         }
     }
+
+    #[test]
+    fn it_sources_files_relative_to_the_current_script() {
+        let root = TempDir::new().unwrap();
+        let root_path = root.path().canonicalize().unwrap();
+        let (scripts, lib, other) = (
+            root_path.join("scripts"),
+            root_path.join("scripts/lib"),
+            root_path.join("other"),
+        );
+        std::fs::create_dir_all(&lib).unwrap();
+        std::fs::create_dir_all(&other).unwrap();
+        std::fs::write(lib.join("helper.pjsh"), "").unwrap();
+
+        let mut context = Context::default();
+        context
+            .set_var("PWD".into(), Value::Word(path_to_string(&other)))
+            .unwrap();
+        let script = path_to_string(scripts.join("main.pjsh"));
+        context
+            .set_var(
+                "PJSH_CURRENT_SCRIPT_PATH".into(),
+                Value::Word(script.clone()),
+            )
+            .unwrap();
+        let scripts_dir = path_to_string(&scripts);
+        context
+            .set_var(
+                "PJSH_CURRENT_SCRIPT_DIR".into(),
+                Value::Word(scripts_dir.clone()),
+            )
+            .unwrap();
+
+        assert_eq!(
+            run_source(&["source", "-r", "./lib/helper.pjsh"], &mut context),
+            (status::SUCCESS, String::new())
+        );
+        assert_eq!(
+            word_var(&context, "SOURCED_FILE"),
+            Some(path_to_string(scripts.join("./lib/helper.pjsh")).as_str())
+        );
+        assert_eq!(
+            word_var(&context, "SOURCED_DIR"),
+            Some(path_to_string(&lib).as_str())
+        );
+
+        // The current script should be restored once the file has been sourced.
+        assert_eq!(
+            word_var(&context, "PJSH_CURRENT_SCRIPT_PATH"),
+            Some(script.as_str())
+        );
+        assert_eq!(
+            word_var(&context, "PJSH_CURRENT_SCRIPT_DIR"),
+            Some(scripts_dir.as_str())
+        );
+    }
+
+    #[test]
+    fn it_requires_a_current_script_for_relative_files() {
+        let mut context = Context::default();
+
+        assert_eq!(
+            run_source(&["source", "-r", "file.pjsh"], &mut context),
+            (
+                status::GENERAL_ERROR,
+                "source: file.pjsh: no current script to resolve the path from\n".into()
+            )
+        );
+        assert_eq!(word_var(&context, "SOURCED_FILE"), None);
+    }
+
+    #[test]
+    fn it_prints_usage_errors() {
+        assert_prints_usage_error(&Source::new(noop), &["source"]);
+        assert_prints_usage_error(&SourceShorthand::new(noop), &["."]);
+    }
 }
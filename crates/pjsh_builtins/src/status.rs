@@ -6,3 +6,22 @@
 
 /// Exit code for misuse of shell built-ins.
 pub const BUILTIN_ERROR: i32 = 2;
+
+/// Exit code for built-ins that are not supported on the current platform.
+pub const UNSUPPORTED: i32 = 3;
+
+/// Exit code for commands that are found but cannot be executed.
+pub const CANNOT_EXECUTE: i32 = 126;
+
+/// Exit code for commands that cannot be found.
+pub const COMMAND_NOT_FOUND: i32 = 127;
+
+/// Exit code for jobs that are terminated by a signal.
+pub const TERMINATED: i32 = 127;
+
+/// Exit code for built-ins that are interrupted by the user.
+pub const INTERRUPTED: i32 = 130;
+
+/// Exit code for built-ins whose output is closed early, such as when piped
+/// into `head`. Matches programs terminated by `SIGPIPE`.
+pub const BROKEN_PIPE: i32 = 141;
@@ -0,0 +1,171 @@
+use clap::{CommandFactory, FromArgMatches, Parser};
+use pjsh_core::command::{Args, Command, CommandResult};
+use pjsh_eval::eval_condition;
+use pjsh_parse::parse_condition_words;
+
+use crate::{status, utils};
+
+/// Command name.
+const NAME: &str = "test";
+const NAME_BRACKET: &str = "[";
+
+/// Closing argument required by the "[" form.
+const CLOSING_BRACKET: &str = "]";
+
+/// Evaluate a condition.
+///
+/// Conditions use the same syntax as `[[ ... ]]`. Exits with status 0 if the
+/// condition is true, and 1 if it is false or empty.
+///
+/// When invoked as "[", the final argument must be "]".
+///
+/// This is a built-in shell command.
+#[derive(Parser)]
+#[clap(name = NAME, version)]
+struct TestOpts {
+    /// Condition to evaluate.
+    #[clap(allow_hyphen_values = true, trailing_var_arg = true)]
+    expression: Vec<String>,
+}
+
+/// Implementation for the "test" built-in command.
+#[derive(Clone)]
+pub struct Test;
+impl Command for Test {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn description(&self) -> Option<String> {
+        utils::description::<TestOpts>()
+    }
+
+    fn usage(&self) -> Option<String> {
+        utils::usage::<TestOpts>(NAME)
+    }
+
+    fn run(&self, args: &mut Args) -> CommandResult {
+        test(NAME, args)
+    }
+}
+
+/// Implementation for the "[" built-in command.
+#[derive(Clone)]
+pub struct TestBracket;
+impl Command for TestBracket {
+    fn name(&self) -> &str {
+        NAME_BRACKET
+    }
+
+    fn description(&self) -> Option<String> {
+        utils::description::<TestOpts>()
+    }
+
+    fn usage(&self) -> Option<String> {
+        utils::usage::<TestOpts>(NAME_BRACKET)
+    }
+
+    fn run(&self, args: &mut Args) -> CommandResult {
+        test(NAME_BRACKET, args)
+    }
+}
+
+/// Evaluates the condition described by a command's arguments.
+///
+/// The command name is used when printing help and usage errors.
+///
+/// Returns an exit code.
+fn test(name: &'static str, args: &mut Args) -> CommandResult {
+    let opts = TestOpts::command()
+        .name(name)
+        .try_get_matches_from(args.context.args())
+        .and_then(|matches| TestOpts::from_arg_matches(&matches));
+
+    let mut expression = match opts {
+        Ok(opts) => opts.expression,
+        Err(error) => return utils::exit_with_parse_error(args.io, error),
+    };
+
+    if name == NAME_BRACKET && expression.pop().as_deref() != Some(CLOSING_BRACKET) {
+        let _ = writeln!(args.io.stderr, "{name}: missing '{CLOSING_BRACKET}'");
+        return CommandResult::code(status::BUILTIN_ERROR);
+    }
+
+    if expression.is_empty() {
+        return CommandResult::code(status::GENERAL_ERROR);
+    }
+
+    let result = parse_condition_words(&expression)
+        .map_err(|error| error.to_string())
+        .and_then(|condition| {
+            eval_condition(&condition, args.context).map_err(|error| error.to_string())
+        });
+
+    match result {
+        Ok(true) => CommandResult::code(status::SUCCESS),
+        Ok(false) => CommandResult::code(status::GENERAL_ERROR),
+        Err(error) => {
+            let _ = writeln!(args.io.stderr, "{name}: {error}");
+            CommandResult::code(status::BUILTIN_ERROR)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::{tempdir, NamedTempFile};
+
+    use crate::utils::{assert_prints_help, run_builtin};
+
+    use super::*;
+
+    #[test]
+    fn it_prints_help() {
+        assert_prints_help(&Test);
+        assert_prints_help(&TestBracket);
+    }
+
+    #[test]
+    fn it_compares_strings() {
+        assert_eq!(run_builtin(&Test, &["test", "a", "=", "a"]).0, 0);
+        assert_eq!(run_builtin(&Test, &["test", "a", "==", "b"]).0, 1);
+        assert_eq!(run_builtin(&Test, &["test", "a", "!=", "b"]).0, 0);
+        assert_eq!(run_builtin(&Test, &["test", "-z", ""]).0, 0);
+        assert_eq!(run_builtin(&Test, &["test", "-n", ""]).0, 1);
+        assert_eq!(run_builtin(&Test, &["test", "word"]).0, 0);
+        assert_eq!(run_builtin(&Test, &["test"]).0, 1);
+    }
+
+    #[test]
+    fn it_tests_paths() {
+        let file = NamedTempFile::new().unwrap();
+        let file = file.path().to_string_lossy().to_string();
+        let dir = tempdir().unwrap();
+        let dir = dir.path().to_string_lossy().to_string();
+
+        assert_eq!(run_builtin(&Test, &["test", "-f", &file]).0, 0);
+        assert_eq!(run_builtin(&Test, &["test", "-d", &file]).0, 1);
+        assert_eq!(run_builtin(&Test, &["test", "-d", &dir]).0, 0);
+        assert_eq!(run_builtin(&Test, &["test", "!", "-e", &dir]).0, 1);
+    }
+
+    #[test]
+    fn it_requires_a_closing_bracket() {
+        assert_eq!(
+            run_builtin(&TestBracket, &["[", "a", "=", "a", "]"]),
+            (0, String::new(), String::new())
+        );
+        assert_eq!(
+            run_builtin(&TestBracket, &["[", "a", "=", "a"]),
+            (2, String::new(), "[: missing ']'\n".into())
+        );
+    }
+
+    #[test]
+    fn it_fails_on_invalid_conditions() {
+        let (code, stdout, stderr) = run_builtin(&Test, &["test", "a", "b"]);
+        assert_eq!(code, status::BUILTIN_ERROR);
+        assert_eq!(stdout, "");
+        assert!(stderr.starts_with("test: "));
+    }
+}
@@ -0,0 +1,215 @@
+use clap::Parser;
+use pjsh_core::{
+    command::{Args, Command, CommandResult},
+    signal::{self, EXIT},
+};
+
+use crate::{status, utils};
+
+/// Command name.
+const NAME: &str = "trap";
+
+/// Execute commands when the shell receives a signal or exits.
+///
+/// Handlers are executed by the shell between statements. Once the handler for
+/// a terminating signal, such as INT or TERM, has been executed, the shell
+/// exits. The pseudo-signal EXIT refers to the shell exiting.
+///
+/// If the handler is "-", the signals are reset to their default behavior. If
+/// no arguments are supplied, all installed handlers are printed.
+///
+/// Signals can only be trapped on Unix-like platforms.
+///
+/// This is a built-in shell command.
+#[derive(Parser)]
+#[clap(name = NAME, version)]
+struct TrapOpts {
+    /// Print all installed handlers.
+    #[clap(short = 'p', conflicts_with = "handler")]
+    print: bool,
+
+    /// Commands to execute, or "-" to reset the signals.
+    #[clap(requires = "signals")]
+    handler: Option<String>,
+
+    /// Signal names or numbers, or EXIT.
+    #[clap(value_parser = parse_trap_signal)]
+    signals: Vec<i32>,
+}
+
+/// Implementation for the "trap" built-in command.
+#[derive(Clone)]
+pub struct Trap;
+impl Command for Trap {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn description(&self) -> Option<String> {
+        utils::description::<TrapOpts>()
+    }
+
+    fn usage(&self) -> Option<String> {
+        utils::usage::<TrapOpts>(NAME)
+    }
+
+    fn run(&self, args: &mut Args) -> CommandResult {
+        match TrapOpts::try_parse_from(args.context.args()) {
+            Ok(opts) => match opts.handler {
+                Some(handler) if handler == "-" => reset_traps(&opts.signals, args),
+                Some(handler) => set_traps(handler, &opts.signals, args),
+                None => print_traps(args),
+            },
+            Err(error) => utils::exit_with_parse_error(args.io, error),
+        }
+    }
+}
+
+/// Prints all installed handlers in a form that can be used to reinstall them.
+///
+/// Returns an exit code.
+fn print_traps(args: &mut Args) -> CommandResult {
+    for (signal, handler) in &args.context.traps {
+        let handler = handler.replace('\'', "\\'");
+        let _ = writeln!(
+            args.io.stdout,
+            "{NAME} -- '{handler}' {}",
+            trap_name(*signal)
+        );
+    }
+
+    CommandResult::code(status::SUCCESS)
+}
+
+/// Installs a handler for signals.
+///
+/// Returns an exit code.
+fn set_traps(handler: String, signals: &[i32], args: &mut Args) -> CommandResult {
+    for &signal in signals {
+        if signal != EXIT {
+            if let Err(error) = signal::trap_signal(signal) {
+                let _ = writeln!(args.io.stderr, "{NAME}: {}: {error}", trap_name(signal));
+                return match error.kind() {
+                    std::io::ErrorKind::Unsupported => CommandResult::code(status::UNSUPPORTED),
+                    _ => CommandResult::code(status::GENERAL_ERROR),
+                };
+            }
+        }
+
+        args.context.traps.insert(signal, handler.clone());
+    }
+
+    CommandResult::code(status::SUCCESS)
+}
+
+/// Removes the handlers for signals, restoring their default behavior.
+///
+/// Returns an exit code.
+fn reset_traps(signals: &[i32], args: &mut Args) -> CommandResult {
+    let mut exit_code = status::SUCCESS;
+
+    for &signal in signals {
+        if args.context.traps.remove(&signal).is_none() || signal == EXIT {
+            continue;
+        }
+
+        if let Err(error) = signal::reset_signal(signal) {
+            let _ = writeln!(args.io.stderr, "{NAME}: {}: {error}", trap_name(signal));
+            exit_code = status::GENERAL_ERROR;
+        }
+    }
+
+    CommandResult::code(exit_code)
+}
+
+/// Returns the name of a signal, such as "SIGINT" or "EXIT".
+fn trap_name(signal: i32) -> String {
+    match (signal, signal::signal_name(signal)) {
+        (EXIT, _) => "EXIT".to_owned(),
+        (_, Some(name)) => format!("SIG{name}"),
+        (number, None) => number.to_string(),
+    }
+}
+
+/// Parses a signal name or number, or the EXIT pseudo-signal.
+///
+/// Used as a [`clap`] value parser.
+fn parse_trap_signal(arg: &str) -> Result<i32, String> {
+    if arg.eq_ignore_ascii_case("EXIT") {
+        return Ok(EXIT);
+    }
+
+    signal::signal_number(arg).ok_or_else(|| format!("invalid signal: {arg}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use pjsh_core::{signal::SIGTERM, Context};
+
+    use crate::utils::{assert_prints_help, assert_prints_usage_error, file_contents, mock_io};
+
+    use super::*;
+
+    /// Runs the built-in in a context, returning its exit code and stdout.
+    fn run(ctx: &mut Context, args: &[&str]) -> (i32, String) {
+        ctx.replace_args(Some(args.iter().map(ToString::to_string).collect()));
+        let (mut io, mut stdout, _stderr) = mock_io();
+        match Trap.run(&mut Args::new(ctx, &mut io)) {
+            CommandResult::Builtin(result) => (result.code, file_contents(&mut stdout)),
+            CommandResult::Process(_) => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_installs_and_prints_exit_handlers() {
+        let mut ctx = Context::default();
+        assert_eq!(
+            run(&mut ctx, &["trap", "echo 'bye'", "EXIT"]).0,
+            status::SUCCESS
+        );
+        assert_eq!(ctx.traps.get(&EXIT), Some(&"echo 'bye'".to_owned()));
+        assert_eq!(
+            run(&mut ctx, &["trap", "-p"]),
+            (status::SUCCESS, "trap -- 'echo \\'bye\\'' EXIT\n".into())
+        );
+
+        assert_eq!(run(&mut ctx, &["trap", "-", "exit"]).0, status::SUCCESS);
+        assert!(ctx.traps.is_empty());
+        assert_eq!(run(&mut ctx, &["trap"]), (status::SUCCESS, String::new()));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn it_traps_and_resets_signals() {
+        let mut ctx = Context::default();
+        assert_eq!(
+            run(&mut ctx, &["trap", "cleanup", "TERM"]).0,
+            status::SUCCESS
+        );
+        assert_eq!(ctx.traps.get(&SIGTERM), Some(&"cleanup".to_owned()));
+        assert_eq!(run(&mut ctx, &["trap", "-", "SIGTERM"]).0, status::SUCCESS);
+        assert!(ctx.traps.is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn it_reports_signals_that_cannot_be_trapped() {
+        let mut ctx = Context::default();
+        assert_eq!(
+            run(&mut ctx, &["trap", "echo", "KILL"]).0,
+            status::GENERAL_ERROR
+        );
+        assert!(ctx.traps.is_empty());
+    }
+
+    #[test]
+    fn it_prints_help() {
+        assert_prints_help(&Trap);
+    }
+
+    #[test]
+    fn it_prints_usage_errors() {
+        assert_prints_usage_error(&Trap, &["trap", "echo"]);
+        assert_prints_usage_error(&Trap, &["trap", "echo", "UNKNOWN"]);
+    }
+}
@@ -29,6 +29,14 @@ fn name(&self) -> &str {
         NAME
     }
 
+    fn description(&self) -> Option<String> {
+        utils::description::<TypeOpts>()
+    }
+
+    fn usage(&self) -> Option<String> {
+        utils::usage::<TypeOpts>(NAME)
+    }
+
     fn run(&self, args: &mut Args) -> CommandResult {
         match TypeOpts::try_parse_from(args.context.args()) {
             Ok(opts) => resolve_command_types(opts),
@@ -89,3 +97,48 @@ fn print_type(name: String, r#type: CommandType, mut io: Io) -> i32 {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    use pjsh_core::{Context, Scope};
+
+    use crate::utils::{assert_prints_help, assert_prints_usage_error, empty_io};
+
+    use super::*;
+
+    #[test]
+    fn it_prints_help() {
+        assert_prints_help(&Type);
+    }
+
+    #[test]
+    fn it_resolves_each_name() {
+        let mut ctx = Context::with_scopes(vec![Scope::new(
+            String::new(),
+            Some(vec!["type".into(), "first".into(), "second".into()]),
+            HashMap::default(),
+            HashMap::default(),
+            HashSet::default(),
+        )]);
+        let mut io = empty_io();
+        let mut args = Args::new(&mut ctx, &mut io);
+
+        let CommandResult::Builtin(result) = Type.run(&mut args) else {
+            unreachable!()
+        };
+
+        assert_eq!(result.code, status::SUCCESS);
+        assert!(matches!(
+            result.actions.as_slice(),
+            [Action::ResolveCommandType(first, _), Action::ResolveCommandType(second, _)]
+                if first == "first" && second == "second"
+        ));
+    }
+
+    #[test]
+    fn it_prints_usage_errors() {
+        assert_prints_usage_error(&Type, &["type"]);
+    }
+}
@@ -0,0 +1,241 @@
+use clap::{ArgGroup, Parser};
+use pjsh_core::{
+    command::{Args, Command, CommandResult},
+    utils::{resource_limit, set_resource_limit, Resource, ResourceLimit},
+};
+
+use crate::{status, utils};
+
+/// Command name.
+const NAME: &str = "ulimit";
+
+/// Display or set resource limits.
+///
+/// Limits apply to the shell and are inherited by the programs it starts. If
+/// no limit is supplied, the current limit is printed. Sizes are in bytes.
+///
+/// Resource limits are only supported on Unix-like platforms.
+///
+/// This is a built-in shell command.
+#[derive(Parser)]
+#[clap(name = NAME, version)]
+#[clap(group = ArgGroup::new("resource").args(["core_file_size", "file_size", "open_files", "processes"]))]
+struct UlimitOpts {
+    /// Print all current limits.
+    #[clap(short = 'a', conflicts_with_all = ["resource", "limit"])]
+    all: bool,
+
+    /// Use the hard limit.
+    #[clap(short = 'H')]
+    hard: bool,
+
+    /// Use the soft limit. This is the default unless -H is given.
+    #[clap(short = 'S')]
+    soft: bool,
+
+    /// Maximum size of core files.
+    #[clap(short = 'c')]
+    core_file_size: bool,
+
+    /// Maximum size of files written by the shell and its programs. This is
+    /// the default resource.
+    #[clap(short = 'f')]
+    file_size: bool,
+
+    /// Maximum number of open file descriptors.
+    #[clap(short = 'n')]
+    open_files: bool,
+
+    /// Maximum number of processes for the current user.
+    #[clap(short = 'u')]
+    processes: bool,
+
+    /// New limit, either a number or "unlimited".
+    #[clap(value_parser = parse_limit)]
+    limit: Option<ResourceLimit>,
+}
+
+impl UlimitOpts {
+    /// Returns the selected resource.
+    fn resource(&self) -> Resource {
+        if self.core_file_size {
+            Resource::CoreFileSize
+        } else if self.open_files {
+            Resource::OpenFiles
+        } else if self.processes {
+            Resource::Processes
+        } else {
+            Resource::FileSize
+        }
+    }
+}
+
+/// Implementation for the "ulimit" built-in command.
+#[derive(Clone)]
+pub struct Ulimit;
+impl Command for Ulimit {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn description(&self) -> Option<String> {
+        utils::description::<UlimitOpts>()
+    }
+
+    fn usage(&self) -> Option<String> {
+        utils::usage::<UlimitOpts>(NAME)
+    }
+
+    fn run(&self, args: &mut Args) -> CommandResult {
+        match UlimitOpts::try_parse_from(args.context.args()) {
+            Ok(opts) => ulimit(opts, args),
+            Err(error) => utils::exit_with_parse_error(args.io, error),
+        }
+    }
+}
+
+/// Prints or sets resource limits.
+///
+/// Returns an exit code.
+fn ulimit(opts: UlimitOpts, args: &mut Args) -> CommandResult {
+    let resources = match opts.all {
+        true => Resource::ALL.to_vec(),
+        false => vec![opts.resource()],
+    };
+
+    for resource in resources {
+        let (soft, hard) = match resource_limit(resource) {
+            Ok(limits) => limits,
+            Err(error) => return exit_with_error(args, resource, error),
+        };
+
+        if let Some(limit) = opts.limit {
+            // Only the soft limit is changed by default, as an unprivileged
+            // process can never raise its hard limit once lowered.
+            let (soft, hard) = match (opts.soft, opts.hard) {
+                (true, true) => (limit, limit),
+                (false, true) => (soft, limit),
+                _ => (limit, hard),
+            };
+            if let Err(error) = set_resource_limit(resource, soft, hard) {
+                return exit_with_error(args, resource, error);
+            }
+            continue;
+        }
+
+        let limit = match opts.hard && !opts.soft {
+            true => hard,
+            false => soft,
+        };
+        let result = match opts.all {
+            true => writeln!(
+                args.io.stdout,
+                "{:<24}(-{}) {limit}",
+                resource.description(),
+                flag(resource)
+            ),
+            false => writeln!(args.io.stdout, "{limit}"),
+        };
+        if let Err(error) = result {
+            return utils::exit_with_write_error(NAME, args.io, error);
+        }
+    }
+
+    CommandResult::code(status::SUCCESS)
+}
+
+/// Returns the command line flag of a resource.
+fn flag(resource: Resource) -> char {
+    match resource {
+        Resource::CoreFileSize => 'c',
+        Resource::FileSize => 'f',
+        Resource::OpenFiles => 'n',
+        Resource::Processes => 'u',
+    }
+}
+
+/// Prints an error message for a resource to standard error.
+///
+/// Returns an exit code.
+fn exit_with_error(args: &mut Args, resource: Resource, error: std::io::Error) -> CommandResult {
+    let _ = writeln!(
+        args.io.stderr,
+        "{NAME}: {}: {error}",
+        resource.description()
+    );
+    CommandResult::code(status::GENERAL_ERROR)
+}
+
+/// Parses a resource limit, such as `1024` or `unlimited`.
+fn parse_limit(value: &str) -> Result<ResourceLimit, String> {
+    if value == "unlimited" {
+        return Ok(ResourceLimit::Unlimited);
+    }
+
+    value
+        .parse()
+        .map(ResourceLimit::Value)
+        .map_err(|_| format!("invalid limit: {value}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::{assert_prints_help, assert_prints_usage_error, run_builtin};
+
+    use super::*;
+
+    #[test]
+    fn it_parses_limits() {
+        assert_eq!(parse_limit("unlimited"), Ok(ResourceLimit::Unlimited));
+        assert_eq!(parse_limit("1024"), Ok(ResourceLimit::Value(1024)));
+        assert!(parse_limit("-1").is_err());
+        assert!(parse_limit("many").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn it_prints_limits() {
+        let (soft, hard) = resource_limit(Resource::OpenFiles).unwrap();
+        assert_eq!(
+            run_builtin(&Ulimit, &["ulimit", "-n"]),
+            (status::SUCCESS, format!("{soft}\n"), String::new())
+        );
+        assert_eq!(
+            run_builtin(&Ulimit, &["ulimit", "-Hn"]),
+            (status::SUCCESS, format!("{hard}\n"), String::new())
+        );
+
+        let (code, stdout, _) = run_builtin(&Ulimit, &["ulimit", "-a"]);
+        assert_eq!(code, status::SUCCESS);
+        assert_eq!(stdout.lines().count(), Resource::ALL.len());
+        assert!(stdout.contains(&format!("open files              (-n) {soft}\n")));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn it_sets_limits() {
+        // Setting the soft limit to its current value never fails, and avoids
+        // affecting other tests running within the same process.
+        let (soft, hard) = resource_limit(Resource::CoreFileSize).unwrap();
+        assert_eq!(
+            run_builtin(&Ulimit, &["ulimit", "-c", &soft.to_string()]),
+            (status::SUCCESS, String::new(), String::new())
+        );
+        assert_eq!(
+            resource_limit(Resource::CoreFileSize).unwrap(),
+            (soft, hard)
+        );
+    }
+
+    #[test]
+    fn it_prints_help() {
+        assert_prints_help(&Ulimit);
+    }
+
+    #[test]
+    fn it_prints_usage_errors() {
+        assert_prints_usage_error(&Ulimit, &["ulimit", "-n", "-u"]);
+        assert_prints_usage_error(&Ulimit, &["ulimit", "-a", "-n"]);
+        assert_prints_usage_error(&Ulimit, &["ulimit", "-n", "lots"]);
+    }
+}
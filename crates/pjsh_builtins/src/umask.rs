@@ -0,0 +1,140 @@
+use clap::Parser;
+use pjsh_core::{
+    command::{Args, Command, CommandResult},
+    utils::{set_umask, umask},
+};
+
+use crate::{status, utils};
+
+/// Command name.
+const NAME: &str = "umask";
+
+/// Display or set the file mode creation mask.
+///
+/// The mask determines which permissions are removed from files created by
+/// the shell and its child processes. If no mask is supplied, the current mask
+/// is printed.
+///
+/// File mode creation masks are only supported on Unix-like platforms.
+///
+/// This is a built-in shell command.
+#[derive(Parser)]
+#[clap(name = NAME, version)]
+struct UmaskOpts {
+    /// Print the mask in symbolic form, as the permissions that are kept.
+    #[clap(short = 'S')]
+    symbolic: bool,
+
+    /// Octal mask to set, such as 022.
+    #[clap(value_parser = parse_mask)]
+    mask: Option<u32>,
+}
+
+/// Implementation for the "umask" built-in command.
+#[derive(Clone)]
+pub struct Umask;
+impl Command for Umask {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn description(&self) -> Option<String> {
+        utils::description::<UmaskOpts>()
+    }
+
+    fn usage(&self) -> Option<String> {
+        utils::usage::<UmaskOpts>(NAME)
+    }
+
+    fn run(&self, args: &mut Args) -> CommandResult {
+        match UmaskOpts::try_parse_from(args.context.args()) {
+            Ok(opts) => {
+                let result = match opts.mask {
+                    Some(mask) => set_umask(mask).map(|_| ()),
+                    None => umask().map(|mask| {
+                        let _ = match opts.symbolic {
+                            true => writeln!(args.io.stdout, "{}", symbolic_mask(mask)),
+                            false => writeln!(args.io.stdout, "{mask:04o}"),
+                        };
+                    }),
+                };
+
+                match result {
+                    Ok(()) => CommandResult::code(status::SUCCESS),
+                    Err(error) => {
+                        let _ = writeln!(args.io.stderr, "{NAME}: {error}");
+                        match error.kind() {
+                            std::io::ErrorKind::Unsupported => {
+                                CommandResult::code(status::UNSUPPORTED)
+                            }
+                            _ => CommandResult::code(status::GENERAL_ERROR),
+                        }
+                    }
+                }
+            }
+            Err(error) => utils::exit_with_parse_error(args.io, error),
+        }
+    }
+}
+
+/// Parses an octal file mode creation mask.
+fn parse_mask(src: &str) -> Result<u32, String> {
+    match u32::from_str_radix(src, 8) {
+        Ok(mask) if mask <= 0o777 => Ok(mask),
+        _ => Err(format!("invalid octal mask: {src}")),
+    }
+}
+
+/// Returns the permissions that are kept by a mask, such as `u=rwx,g=rx,o=rx`
+/// for the mask `022`.
+fn symbolic_mask(mask: u32) -> String {
+    ["u", "g", "o"]
+        .iter()
+        .enumerate()
+        .map(|(index, class)| {
+            let bits = !mask >> (6 - 3 * index);
+            let permissions: String = [(0o4, 'r'), (0o2, 'w'), (0o1, 'x')]
+                .iter()
+                .filter(|(bit, _)| bits & bit != 0)
+                .map(|(_, permission)| permission)
+                .collect();
+            format!("{class}={permissions}")
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(not(unix))]
+    use crate::utils::run_builtin;
+    use crate::utils::{assert_prints_help, assert_prints_usage_error};
+
+    use super::*;
+
+    #[test]
+    fn it_formats_symbolic_masks() {
+        assert_eq!(symbolic_mask(0o022), "u=rwx,g=rx,o=rx");
+        assert_eq!(symbolic_mask(0o077), "u=rwx,g=,o=");
+        assert_eq!(symbolic_mask(0o754), "u=,g=w,o=wx");
+    }
+
+    #[cfg(not(unix))]
+    #[test]
+    fn it_reports_that_masks_are_unsupported() {
+        let (code, _, stderr) = run_builtin(&Umask, &["umask"]);
+        assert_eq!(code, status::UNSUPPORTED);
+        assert_eq!(stderr, "umask: not supported on this platform\n");
+    }
+
+    #[test]
+    fn it_prints_help() {
+        assert_prints_help(&Umask);
+    }
+
+    #[test]
+    fn it_prints_usage_errors() {
+        assert_prints_usage_error(&Umask, &["umask", "1000"]);
+        assert_prints_usage_error(&Umask, &["umask", "rwx"]);
+    }
+}
@@ -27,6 +27,14 @@ fn name(&self) -> &str {
         NAME
     }
 
+    fn description(&self) -> Option<String> {
+        utils::description::<UnaliasOpts>()
+    }
+
+    fn usage(&self) -> Option<String> {
+        utils::usage::<UnaliasOpts>(NAME)
+    }
+
     fn run(&self, args: &mut Args) -> CommandResult {
         match UnaliasOpts::try_parse_from(args.context.args()) {
             Ok(opts) => remove_aliases(args.context, &opts.names),
@@ -53,7 +61,7 @@ mod tests {
 
     use pjsh_core::{Context, Scope};
 
-    use crate::utils::{file_contents, mock_io};
+    use crate::utils::{assert_prints_help, assert_prints_usage_error, file_contents, mock_io};
 
     use super::*;
 
@@ -107,4 +115,14 @@ fn it_can_print_help() {
             assert_ne!(file_contents(&mut stdout), String::new());
         }
     }
+
+    #[test]
+    fn it_prints_help() {
+        assert_prints_help(&Unalias);
+    }
+
+    #[test]
+    fn it_prints_usage_errors() {
+        assert_prints_usage_error(&Unalias, &["unalias", "--unknown"]);
+    }
 }
@@ -1,8 +1,5 @@
 use clap::Parser;
-use pjsh_core::{
-    command::{Args, Command, CommandResult},
-    Context,
-};
+use pjsh_core::command::{Args, Command, CommandResult};
 
 use crate::{status, utils};
 
@@ -22,13 +19,20 @@ enum UnsetType {
 
 /// Unset shell variables and/or functions.
 ///
+/// Names are treated as variable names unless a type is given. Functions are
+/// only unset if no variable with the same name exists.
+///
 /// This is a built-in shell command.
 #[derive(Parser)]
 #[clap(name = NAME, version)]
 struct UnsetOpts {
     /// Determines whether to treat each name as a function or variable name.
-    #[clap(value_enum, default_value = "variable", short, long)]
-    r#type: UnsetType,
+    #[clap(value_enum, short, long)]
+    r#type: Option<UnsetType>,
+
+    /// Treat each name as a shell function name. Same as "--type function".
+    #[clap(short, conflicts_with = "type")]
+    function: bool,
 
     /// Variable or function names to unset.
     #[clap(required = true, num_args = 1..)]
@@ -43,9 +47,17 @@ fn name(&self) -> &str {
         NAME
     }
 
+    fn description(&self) -> Option<String> {
+        utils::description::<UnsetOpts>()
+    }
+
+    fn usage(&self) -> Option<String> {
+        utils::usage::<UnsetOpts>(NAME)
+    }
+
     fn run(&self, args: &mut Args) -> CommandResult {
         match UnsetOpts::try_parse_from(args.context.args()) {
-            Ok(opts) => unset_names(opts, args.context),
+            Ok(opts) => unset_names(opts, args),
             Err(error) => utils::exit_with_parse_error(args.io, error),
         }
     }
@@ -53,14 +65,31 @@ fn run(&self, args: &mut Args) -> CommandResult {
 
 /// Unsets a collection of names in a context.
 ///
-/// Returns an exit code.
-fn unset_names(opts: UnsetOpts, ctx: &mut Context) -> CommandResult {
-    match opts.r#type {
-        UnsetType::Function => opts.name.iter().for_each(|f| ctx.unregister_function(f)),
-        UnsetType::Variable => opts.name.iter().for_each(|v| ctx.unset_var(v)),
+/// Returns 0 if all names are unset, or 1 if at least one read-only variable
+/// cannot be unset.
+fn unset_names(opts: UnsetOpts, args: &mut Args) -> CommandResult {
+    let r#type = match opts.function {
+        true => Some(UnsetType::Function),
+        false => opts.r#type,
     };
 
-    CommandResult::code(status::SUCCESS)
+    let mut result = CommandResult::code(status::SUCCESS);
+    for name in &opts.name {
+        let is_function = match r#type {
+            Some(UnsetType::Function) => true,
+            Some(UnsetType::Variable) => false,
+            None => !args.context.has_var(name) && args.context.has_function(name),
+        };
+
+        if is_function {
+            args.context.unregister_function(name);
+        } else if let Err(err) = args.context.unset_var(name) {
+            let _ = writeln!(args.io.stderr, "{NAME}: {err}");
+            result = CommandResult::code(status::GENERAL_ERROR);
+        }
+    }
+
+    result
 }
 
 #[cfg(test)]
@@ -68,18 +97,23 @@ mod tests {
     use std::collections::{HashMap, HashSet};
 
     use pjsh_ast::{Block, Function};
-    use pjsh_core::{Scope, Value};
+    use pjsh_core::{Context, Scope, Value};
 
-    use crate::utils::mock_io;
+    use crate::utils::{assert_prints_help, assert_prints_usage_error, file_contents, mock_io};
 
     use super::*;
 
     #[test]
     fn it_prints_help() {
+        assert_prints_help(&Unset);
+    }
+
+    #[test]
+    fn it_unsets_variables() {
         let mut ctx = Context::with_scopes(vec![Scope::new(
             String::new(),
-            Some(vec!["unset".into(), "--help".into()]),
-            HashMap::default(),
+            Some(vec!["unset".into(), "var".into()]),
+            HashMap::from([("var".into(), Some(Value::Word("value".into())))]),
             HashMap::default(),
             HashSet::default(),
         )]);
@@ -92,10 +126,12 @@ fn it_prints_help() {
         };
 
         assert_eq!(result.code, 0);
+        assert!(result.actions.is_empty());
+        assert_eq!(ctx.get_var("var"), None);
     }
 
     #[test]
-    fn it_unsets_variables() {
+    fn it_does_not_unset_readonly_variables() {
         let mut ctx = Context::with_scopes(vec![Scope::new(
             String::new(),
             Some(vec!["unset".into(), "var".into()]),
@@ -103,17 +139,20 @@ fn it_unsets_variables() {
             HashMap::default(),
             HashSet::default(),
         )]);
-        let (mut io, _, _) = mock_io();
+        ctx.make_var_readonly("var".into()).unwrap();
+        let (mut io, _, mut stderr) = mock_io();
         let mut args = Args::new(&mut ctx, &mut io);
 
-        let cmd = Unset {};
-        let CommandResult::Builtin(result) = cmd.run(&mut args) else {
+        let CommandResult::Builtin(result) = Unset.run(&mut args) else {
             unreachable!();
         };
 
-        assert_eq!(result.code, 0);
-        assert!(result.actions.is_empty());
-        assert_eq!(ctx.get_var("var"), None);
+        assert_eq!(result.code, 1);
+        assert_eq!(
+            file_contents(&mut stderr),
+            "unset: readonly variable: var\n"
+        );
+        assert_eq!(ctx.get_var("var"), Some(&Value::Word("value".into())));
     }
 
     #[test]
@@ -151,4 +190,77 @@ fn it_unsets_functions() {
         assert!(result.actions.is_empty());
         assert_eq!(ctx.get_function("func"), None);
     }
+
+    #[test]
+    fn it_unsets_functions_using_the_short_flag() {
+        let mut ctx = Context::with_scopes(vec![Scope::new(
+            String::new(),
+            Some(Vec::default()),
+            HashMap::default(),
+            HashMap::default(),
+            HashSet::default(),
+        )]);
+        let execute = |src: &str, ctx: &mut Context| {
+            let program = pjsh_parse::parse(src, &HashMap::default()).unwrap();
+            program
+                .statements
+                .iter()
+                .try_for_each(|statement| pjsh_eval::execute_statement(statement, ctx))
+        };
+        ctx.builtins.insert(NAME.into(), Box::new(Unset));
+
+        assert!(execute("fn func() { }; func; unset -f func", &mut ctx).is_ok());
+        assert_eq!(ctx.get_function("func"), None);
+        assert!(matches!(
+            execute("func", &mut ctx),
+            Err(pjsh_eval::EvalError::UnknownCommand(command)) if command == "func"
+        ));
+    }
+
+    #[test]
+    fn it_prefers_unsetting_variables() {
+        let function = Function {
+            name: "name".into(),
+            args: Vec::default(),
+            list_arg: None,
+            body: Block::default(),
+        };
+        let context = |args: &[&str]| {
+            Context::with_scopes(vec![Scope::new(
+                String::new(),
+                Some(args.iter().map(ToString::to_string).collect()),
+                HashMap::from([("name".into(), Some(Value::Word("value".into())))]),
+                HashMap::from([("name".into(), Some(function.clone()))]),
+                HashSet::default(),
+            )])
+        };
+        let run = |ctx: &mut Context| {
+            let (mut io, _, _) = mock_io();
+            let CommandResult::Builtin(result) = Unset.run(&mut Args::new(ctx, &mut io)) else {
+                unreachable!();
+            };
+            assert_eq!(result.code, 0);
+        };
+
+        let mut ctx = context(&["unset", "name"]);
+        run(&mut ctx);
+        assert!(!ctx.has_var("name"));
+        assert!(ctx.has_function("name"));
+
+        // Functions are unset once no variable remains.
+        ctx.replace_args(Some(vec!["unset".into(), "name".into()]));
+        run(&mut ctx);
+        assert!(!ctx.has_function("name"));
+
+        let mut ctx = context(&["unset", "-f", "name"]);
+        run(&mut ctx);
+        assert!(ctx.has_var("name"));
+        assert!(!ctx.has_function("name"));
+    }
+
+    #[test]
+    fn it_prints_usage_errors() {
+        assert_prints_usage_error(&Unset, &["unset"]);
+        assert_prints_usage_error(&Unset, &["unset", "-f", "--type=variable", "name"]);
+    }
 }
@@ -1,4 +1,11 @@
-use pjsh_core::{command::CommandResult, command::Io};
+use std::{fmt::Display, io::IsTerminal, path::Path};
+
+use clap::CommandFactory;
+
+use pjsh_core::{
+    command::CommandResult, command::Io, utils::path_to_string, Context, FileDescriptor, Value,
+    FD_STDIN,
+};
 
 use crate::status;
 
@@ -6,7 +13,10 @@
 /// on the error type.
 ///
 /// Clap returns help messages as errors, so this function handles IO writing
-/// accordingly.
+/// accordingly. All built-in commands should use this function in order to
+/// handle `-h`/`--help` and usage errors uniformly:
+///  - Help and version information is printed to stdout with exit code 0.
+///  - Usage errors are printed to stderr with exit code 2.
 ///
 /// Returns an exit code.
 pub fn exit_with_parse_error(io: &mut Io, error: clap::Error) -> CommandResult {
@@ -24,6 +34,170 @@ pub fn exit_with_parse_error(io: &mut Io, error: clap::Error) -> CommandResult {
     CommandResult::code(code)
 }
 
+/// Returns the description of a built-in command, taken from the first
+/// paragraph of its options' documentation.
+pub(crate) fn description<T: CommandFactory>() -> Option<String> {
+    T::command().get_about().map(ToString::to_string)
+}
+
+/// Returns the usage synopsis of a built-in command called by a name.
+pub(crate) fn usage<T: CommandFactory>(name: &'static str) -> Option<String> {
+    let usage = T::command().name(name).render_usage().to_string();
+    Some(usage.trim_start_matches("Usage: ").to_owned())
+}
+
+/// Prints an error that occurred while writing a built-in command's output to
+/// standard error.
+///
+/// Broken pipes, where the reading end of the output has been closed, are not
+/// considered errors. Such commands exit quietly with [`status::BROKEN_PIPE`].
+///
+/// Returns an exit code.
+pub(crate) fn exit_with_write_error(
+    name: &str,
+    io: &mut Io,
+    error: std::io::Error,
+) -> CommandResult {
+    if error.kind() == std::io::ErrorKind::BrokenPipe {
+        return CommandResult::code(status::BROKEN_PIPE);
+    }
+
+    let _ = writeln!(io.stderr, "{name}: {error}");
+    CommandResult::code(status::GENERAL_ERROR)
+}
+
+/// Parses a job id, optionally prefixed by `%`.
+///
+/// Used as a [`clap`] value parser.
+pub(crate) fn parse_job_id(arg: &str) -> Result<usize, String> {
+    let id = arg.strip_prefix('%').unwrap_or(arg);
+    match id.parse::<usize>() {
+        Ok(id) if id > 0 => Ok(id),
+        _ => Err(format!("invalid job id: {arg}")),
+    }
+}
+
+/// A job or process that is targeted by a built-in command.
+#[derive(Clone, Copy)]
+pub(crate) enum Target {
+    /// A job that is tracked by the shell.
+    Job(usize),
+
+    /// A process id (PID).
+    Process(u32),
+}
+
+impl Display for Target {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Target::Job(id) => write!(f, "%{id}"),
+            Target::Process(pid) => write!(f, "{pid}"),
+        }
+    }
+}
+
+/// Parses a process id, or a job id prefixed by `%`.
+///
+/// Used as a [`clap`] value parser.
+pub(crate) fn parse_target(arg: &str) -> Result<Target, String> {
+    if arg.starts_with('%') {
+        return parse_job_id(arg).map(Target::Job);
+    }
+
+    arg.parse()
+        .map(Target::Process)
+        .map_err(|_| format!("invalid process id: {arg}"))
+}
+
+/// Changes the working directory of a context by updating `$PWD`.
+///
+/// The previous working directory is kept in `$OLDPWD`.
+///
+/// Returns the new working directory, or an error if `$PWD` or `$OLDPWD` is
+/// read-only.
+pub(crate) fn set_working_directory(context: &mut Context, path: &Path) -> Result<String, String> {
+    if let Some(pwd) = context.get_var("PWD").map(|pwd| pwd.to_owned()) {
+        context.set_var("OLDPWD".to_owned(), pwd)?;
+    }
+
+    let new_path = path_to_string(path);
+    context.set_var("PWD".to_owned(), Value::Word(new_path.clone()))?;
+    Ok(new_path)
+}
+
+/// Returns `true` if a context reads its input from an interactive terminal.
+pub(crate) fn is_interactive(context: &Context) -> bool {
+    matches!(
+        context.get_file_descriptor(FD_STDIN),
+        Some(FileDescriptor::Stdin)
+    ) && std::io::stdin().is_terminal()
+}
+
+/// Reads a single line of input, excluding its line ending.
+///
+/// Input is read one byte at a time in order to avoid consuming input beyond
+/// the end of the line.
+///
+/// Returns `None` if the end of input is reached before any bytes are read.
+pub(crate) fn take_line(input: &mut impl std::io::Read) -> std::io::Result<Option<String>> {
+    let mut bytes = Vec::new();
+    let mut byte = [0u8; 1];
+
+    loop {
+        match input.read(&mut byte) {
+            Ok(0) if bytes.is_empty() => return Ok(None),
+            Ok(0) => break,
+            Ok(_) if byte[0] == b'\n' => break,
+            Ok(_) => bytes.push(byte[0]),
+            Err(error) if error.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(error) => return Err(error),
+        }
+    }
+
+    if bytes.last() == Some(&b'\r') {
+        bytes.pop();
+    }
+
+    Ok(Some(String::from_utf8_lossy(&bytes).into_owned()))
+}
+
+/// Asks the user to confirm a destructive action by answering a yes/no
+/// question on stdin.
+///
+/// The question is only asked in interactive shells, as there is nobody to
+/// answer it otherwise. Non-interactive shells always confirm the action.
+///
+/// Returns `true` if the action is confirmed.
+pub(crate) fn confirm(io: &mut Io, question: &str, interactive: bool) -> bool {
+    if !interactive {
+        return true;
+    }
+
+    let _ = write!(io.stderr, "{question} [y/N] ");
+    let _ = io.stderr.flush();
+
+    match take_line(&mut io.stdin) {
+        Ok(Some(answer)) => matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"),
+        _ => false,
+    }
+}
+
+/// Constructs a new input/output wrapper whose stdout is a pipe that has
+/// already been closed for reading.
+#[cfg(test)]
+pub(crate) fn broken_pipe_io() -> (Io, std::fs::File) {
+    let (reader, writer) = os_pipe::pipe().unwrap();
+    drop(reader);
+
+    let stderr = tempfile::tempfile().unwrap();
+    let io = Io::new(
+        Box::new(std::io::empty()),
+        Box::new(writer),
+        Box::new(stderr.try_clone().unwrap()),
+    );
+    (io, stderr)
+}
+
 /// Constructs a new no-op input/output wrapper for a command.
 #[cfg(test)]
 pub(crate) fn empty_io() -> Io {
@@ -61,3 +235,99 @@ pub(crate) fn file_contents(file: &mut std::fs::File) -> String {
     let _ = file.read_to_string(&mut string);
     string
 }
+
+/// Runs a built-in command with some arguments in an otherwise empty context.
+///
+/// Returns the exit code along with the contents written to stdout and stderr.
+#[cfg(test)]
+pub(crate) fn run_builtin(
+    command: &dyn pjsh_core::command::Command,
+    args: &[&str],
+) -> (i32, String, String) {
+    use std::collections::{HashMap, HashSet};
+
+    use pjsh_core::{command::Args, Context, Scope};
+
+    let mut ctx = Context::with_scopes(vec![Scope::new(
+        String::new(),
+        Some(args.iter().map(ToString::to_string).collect()),
+        HashMap::default(),
+        HashMap::default(),
+        HashSet::default(),
+    )]);
+    let (mut io, mut stdout, mut stderr) = mock_io();
+    let mut args = Args::new(&mut ctx, &mut io);
+
+    let CommandResult::Builtin(result) = command.run(&mut args) else {
+        unreachable!("built-in commands should not spawn processes")
+    };
+
+    (
+        result.code,
+        file_contents(&mut stdout),
+        file_contents(&mut stderr),
+    )
+}
+
+/// Asserts that a built-in command prints its help, including its description,
+/// to stdout, and exits with a successful status, when called with either `-h`
+/// or `--help`.
+#[cfg(test)]
+pub(crate) fn assert_prints_help(command: &dyn pjsh_core::command::Command) {
+    let name = command.name().to_owned();
+    for flag in ["-h", "--help"] {
+        let (code, stdout, stderr) = run_builtin(command, &[&name, flag]);
+        assert_eq!(code, status::SUCCESS, "{name} {flag}");
+        assert!(stdout.contains(&format!("Usage: {name}")), "{name} {flag}");
+        if let Some(description) = command.description() {
+            assert!(stdout.starts_with(&description), "{name} {flag}");
+        }
+        assert_eq!(stderr, "", "{name} {flag}");
+    }
+}
+
+/// Asserts that a built-in command prints a usage error to stderr, and exits
+/// with a misuse status, when called with invalid arguments.
+#[cfg(test)]
+pub(crate) fn assert_prints_usage_error(command: &dyn pjsh_core::command::Command, args: &[&str]) {
+    let (code, stdout, stderr) = run_builtin(command, args);
+    assert_eq!(code, status::BUILTIN_ERROR, "{args:?}");
+    assert_eq!(stdout, "", "{args:?}");
+    assert!(stderr.starts_with("error: "), "{args:?}");
+    assert!(stderr.contains("--help"), "{args:?}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Asks a question with some input.
+    ///
+    /// Returns the decision along with the contents written to stderr.
+    fn ask(input: &'static str, interactive: bool) -> (bool, String) {
+        let stderr = tempfile::tempfile().unwrap();
+        let mut io = Io::new(
+            Box::new(input.as_bytes()),
+            Box::new(std::io::sink()),
+            Box::new(stderr.try_clone().unwrap()),
+        );
+
+        let confirmed = confirm(&mut io, "delete?", interactive);
+        (confirmed, file_contents(&mut stderr.try_clone().unwrap()))
+    }
+
+    #[test]
+    fn it_confirms_interactively() {
+        assert_eq!(ask("y\n", true), (true, "delete? [y/N] ".into()));
+        assert_eq!(ask("Yes\n", true), (true, "delete? [y/N] ".into()));
+        assert_eq!(ask("n\n", true), (false, "delete? [y/N] ".into()));
+        assert_eq!(ask("\n", true), (false, "delete? [y/N] ".into()));
+        assert_eq!(ask("", true), (false, "delete? [y/N] ".into()));
+    }
+
+    #[test]
+    fn it_confirms_automatically_when_not_interactive() {
+        assert_eq!(ask("n\n", false), (true, String::new()));
+        assert_eq!(ask("", false), (true, String::new()));
+    }
+}
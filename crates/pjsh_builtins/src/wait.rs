@@ -0,0 +1,236 @@
+use std::{io, time::Duration};
+
+use clap::Parser;
+use pjsh_core::{
+    command::{Args, Command, CommandResult},
+    signal::InterruptGuard,
+    Context, JobState,
+};
+
+use crate::{
+    status,
+    utils::{self, Target},
+};
+
+/// Command name.
+const NAME: &str = "wait";
+
+/// Time to wait between checking whether jobs have exited.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Wait for background jobs to exit.
+///
+/// Waits for all jobs and exits with 0 if no jobs are given. Otherwise, exits
+/// with the exit status of the last given job. Waiting can be interrupted by
+/// pressing Ctrl-C.
+///
+/// This is a built-in shell command.
+#[derive(Parser)]
+#[clap(name = NAME, version)]
+struct WaitOpts {
+    /// Process ids, or job ids prefixed by "%".
+    #[clap(value_parser = utils::parse_target)]
+    targets: Vec<Target>,
+}
+
+/// Implementation for the "wait" built-in command.
+#[derive(Clone)]
+pub struct Wait;
+impl Command for Wait {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn description(&self) -> Option<String> {
+        utils::description::<WaitOpts>()
+    }
+
+    fn usage(&self) -> Option<String> {
+        utils::usage::<WaitOpts>(NAME)
+    }
+
+    fn run(&self, args: &mut Args) -> CommandResult {
+        match WaitOpts::try_parse_from(args.context.args()) {
+            Ok(opts) => wait(opts, args),
+            Err(error) => utils::exit_with_parse_error(args.io, error),
+        }
+    }
+}
+
+/// Waits for jobs to exit.
+///
+/// Returns an exit code.
+fn wait(opts: WaitOpts, args: &mut Args) -> CommandResult {
+    let interrupts = InterruptGuard::new();
+
+    if opts.targets.is_empty() {
+        return match wait_for_all_jobs(args.context, &interrupts) {
+            Ok(()) => CommandResult::code(status::SUCCESS),
+            Err(_) => CommandResult::code(status::INTERRUPTED),
+        };
+    }
+
+    let mut exit_code = status::SUCCESS;
+    for target in opts.targets {
+        match wait_for_target(target, args.context, &interrupts) {
+            Ok(code) => exit_code = code,
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => {
+                return CommandResult::code(status::INTERRUPTED);
+            }
+            Err(error) => {
+                let _ = writeln!(args.io.stderr, "{NAME}: {target}: {error}");
+                exit_code = status::GENERAL_ERROR;
+            }
+        }
+    }
+
+    CommandResult::code(exit_code)
+}
+
+/// Waits for all jobs that are tracked by the context's host to exit.
+fn wait_for_all_jobs(context: &Context, interrupts: &InterruptGuard) -> io::Result<()> {
+    poll(interrupts, || {
+        let jobs = context.host.lock().jobs();
+        let done = jobs.iter().all(|job| job.state != JobState::Running);
+        Ok(done.then_some(()))
+    })
+}
+
+/// Waits for a single job to exit.
+///
+/// Returns the job's exit code.
+fn wait_for_target(
+    target: Target,
+    context: &Context,
+    interrupts: &InterruptGuard,
+) -> io::Result<i32> {
+    let id = match target {
+        Target::Job(id) => id,
+        Target::Process(pid) => context.host.lock().job_id(pid).ok_or_else(not_a_child)?,
+    };
+
+    // The host must not remain locked while waiting, as jobs may need to access it.
+    poll(interrupts, || {
+        match context.host.lock().try_wait_job(id)? {
+            JobState::Running => Ok(None),
+            JobState::Exited(code) => Ok(Some(code.unwrap_or(status::TERMINATED))),
+        }
+    })
+}
+
+/// Returns an error indicating that a process is not a job of the shell.
+fn not_a_child() -> io::Error {
+    io::Error::new(io::ErrorKind::NotFound, "not a child of this shell")
+}
+
+/// Repeatedly calls a function until it returns a value.
+///
+/// # Errors
+///
+/// This function will return an error if the function returns an error, or
+/// with [`io::ErrorKind::Interrupted`] if an interrupt is received.
+fn poll<T>(
+    interrupts: &InterruptGuard,
+    mut poll_fn: impl FnMut() -> io::Result<Option<T>>,
+) -> io::Result<T> {
+    loop {
+        if let Some(value) = poll_fn()? {
+            return Ok(value);
+        }
+
+        if interrupts.is_interrupted() {
+            return Err(io::ErrorKind::Interrupted.into());
+        }
+
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::process;
+
+    use crate::utils::{
+        assert_prints_help, assert_prints_usage_error, file_contents, mock_io, run_builtin,
+    };
+
+    use super::*;
+
+    /// Runs the "wait" built-in within a context.
+    ///
+    /// Returns the exit code along with the contents written to stderr.
+    fn run_wait(args: &[&str], ctx: &mut Context) -> (i32, String) {
+        ctx.replace_args(Some(args.iter().map(ToString::to_string).collect()));
+        let (mut io, _, mut stderr) = mock_io();
+        let mut args = Args::new(ctx, &mut io);
+
+        let CommandResult::Builtin(result) = Wait.run(&mut args) else {
+            unreachable!()
+        };
+        (result.code, file_contents(&mut stderr))
+    }
+
+    /// Spawns a process that exits with a specific code.
+    fn spawn_exiting_with(code: i32) -> process::Child {
+        process::Command::new("sh")
+            .args(["-c", &format!("exit {code}")])
+            .spawn()
+            .unwrap()
+    }
+
+    #[test]
+    fn it_waits_for_all_jobs() {
+        let mut ctx = Context::default();
+        ctx.host
+            .lock()
+            .add_child_process(spawn_exiting_with(3), None);
+        ctx.host.lock().add_thread(std::thread::spawn(|| {
+            std::thread::sleep(Duration::from_millis(50));
+            4
+        }));
+
+        assert_eq!(
+            run_wait(&["wait"], &mut ctx),
+            (status::SUCCESS, String::new())
+        );
+        assert_eq!(ctx.host.lock().jobs(), Vec::new());
+    }
+
+    #[test]
+    fn it_waits_for_specific_jobs() {
+        let mut ctx = Context::default();
+        let child = spawn_exiting_with(3);
+        let pid = child.id().to_string();
+        ctx.host.lock().add_child_process(child, None);
+        ctx.host.lock().add_thread(std::thread::spawn(|| 4));
+
+        assert_eq!(run_wait(&["wait", "%2"], &mut ctx), (4, String::new()));
+        assert_eq!(run_wait(&["wait", &pid], &mut ctx), (3, String::new()));
+        assert_eq!(
+            run_wait(&["wait", "%1", &pid], &mut ctx),
+            (
+                status::GENERAL_ERROR,
+                format!("wait: %1: no such job\nwait: {pid}: not a child of this shell\n")
+            )
+        );
+    }
+
+    #[test]
+    fn it_succeeds_without_jobs() {
+        assert_eq!(
+            run_builtin(&Wait, &["wait"]),
+            (status::SUCCESS, String::new(), String::new())
+        );
+    }
+
+    #[test]
+    fn it_prints_help() {
+        assert_prints_help(&Wait);
+    }
+
+    #[test]
+    fn it_prints_usage_errors() {
+        assert_prints_usage_error(&Wait, &["wait", "%first"]);
+        assert_prints_usage_error(&Wait, &["wait", "-1"]);
+    }
+}
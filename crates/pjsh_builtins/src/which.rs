@@ -28,6 +28,14 @@ fn name(&self) -> &str {
         NAME
     }
 
+    fn description(&self) -> Option<String> {
+        utils::description::<WhichOpts>()
+    }
+
+    fn usage(&self) -> Option<String> {
+        utils::usage::<WhichOpts>(NAME)
+    }
+
     fn run(&self, args: &mut Args) -> CommandResult {
         match WhichOpts::try_parse_from(args.context.args()) {
             Ok(opts) => resolve_command_paths(opts),
@@ -68,3 +76,48 @@ fn resolve_command_paths(args: WhichOpts) -> CommandResult {
 
     CommandResult::with_actions(status::SUCCESS, actions)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    use pjsh_core::{Context, Scope};
+
+    use crate::utils::{assert_prints_help, assert_prints_usage_error, empty_io};
+
+    use super::*;
+
+    #[test]
+    fn it_prints_help() {
+        assert_prints_help(&Which);
+    }
+
+    #[test]
+    fn it_resolves_each_name() {
+        let mut ctx = Context::with_scopes(vec![Scope::new(
+            String::new(),
+            Some(vec!["which".into(), "first".into(), "second".into()]),
+            HashMap::default(),
+            HashMap::default(),
+            HashSet::default(),
+        )]);
+        let mut io = empty_io();
+        let mut args = Args::new(&mut ctx, &mut io);
+
+        let CommandResult::Builtin(result) = Which.run(&mut args) else {
+            unreachable!()
+        };
+
+        assert_eq!(result.code, status::SUCCESS);
+        assert!(matches!(
+            result.actions.as_slice(),
+            [Action::ResolveCommandPath(first, _), Action::ResolveCommandPath(second, _)]
+                if first == "first" && second == "second"
+        ));
+    }
+
+    #[test]
+    fn it_prints_usage_errors() {
+        assert_prints_usage_error(&Which, &["which"]);
+    }
+}
@@ -0,0 +1,75 @@
+use pjsh_core::{
+    utils::{bookmarks_file, path_to_string, Bookmarks},
+    Context,
+};
+
+use crate::Replacement;
+
+/// Completes bookmark names, such as `:proj`, as the directory argument of `cd`.
+pub(crate) fn complete_bookmark(
+    prefix: &str,
+    words: &[&str],
+    word_index: usize,
+    context: &Context,
+) -> Option<Vec<Replacement>> {
+    if word_index != 1 || words[0] != "cd" {
+        return None;
+    }
+
+    let name_prefix = prefix.strip_prefix(':')?;
+    let bookmarks = bookmarks_file(context).map(Bookmarks::load)?;
+
+    Some(
+        bookmarks
+            .iter()
+            .filter(|(name, _)| name.starts_with(name_prefix))
+            .map(|(name, path)| Replacement::customized(format!(":{name}"), &path_to_string(path)))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use pjsh_core::Value;
+
+    use super::*;
+
+    #[test]
+    fn it_completes_bookmarks_for_cd() {
+        let home = tempfile::tempdir().unwrap();
+        let mut bookmarks = Bookmarks::default();
+        bookmarks
+            .insert("docs", PathBuf::from("/work/docs"))
+            .unwrap();
+        bookmarks
+            .insert("proj", PathBuf::from("/work/project"))
+            .unwrap();
+        bookmarks
+            .insert("prod", PathBuf::from("/work/prod"))
+            .unwrap();
+        bookmarks.save(home.path().join(".pjsh/bookmarks")).unwrap();
+
+        let mut context = Context::default();
+        context
+            .set_var("HOME".into(), Value::Word(path_to_string(home.path())))
+            .unwrap();
+
+        let contents = |prefix: &str, words: &[&str], word_index: usize| {
+            complete_bookmark(prefix, words, word_index, &context)
+                .map(|replacements| replacements.into_iter().map(|r| r.content).collect())
+        };
+
+        assert_eq!(
+            contents(":pro", &["cd", ":pro"], 1),
+            Some(vec![":prod".to_string(), ":proj".to_string()])
+        );
+        assert_eq!(
+            contents(":", &["cd", ":"], 1).map(|names: Vec<String>| names.len()),
+            Some(3)
+        );
+        assert_eq!(contents("pro", &["cd", "pro"], 1), None);
+        assert_eq!(contents(":pro", &["ls", ":pro"], 1), None);
+    }
+}
@@ -3,19 +3,34 @@
 use pjsh_core::Context;
 
 use crate::{
-    completions::Completion, input::separate_input, known_prefixes::complete_known_prefix,
-    registered_completions::complete_registered, uncontextualized_completions::complete_anything,
+    bookmarks::complete_bookmark,
+    completions::Completion,
+    external_completions::ExternalCompletions,
+    fs::complete_paths,
+    input::{command_start, is_redirect, separate_input},
+    known_prefixes::complete_known_prefix,
+    program_cache::ProgramCache,
+    ranking::rank_replacements,
+    registered_completions::complete_registered,
+    uncontextualized_completions::complete_anything,
     LineCompletion, Replacement,
 };
 
 #[derive(Debug, Default)]
 pub struct Completer {
     completions: HashMap<String, Completion>,
+    external: ExternalCompletions,
+    programs: ProgramCache,
 }
 
 impl Completer {
-    pub fn complete_line(&self, line: &str, pos: usize, context: &Context) -> LineCompletion {
-        let mut words = separate_input(line);
+    pub fn complete_line(&mut self, line: &str, pos: usize, context: &Context) -> LineCompletion {
+        // Unclosed subshells, such as "$(git ch", are completed as separate commands.
+        let offset = command_start(&line[..pos]);
+        let mut words: Vec<_> = separate_input(&line[offset..])
+            .into_iter()
+            .map(|(word, start, end)| (word, start + offset, end + offset))
+            .collect();
 
         // The current position may be inside whitespace following the final word.
         // If this is the case, completions should be provided for a new word with an
@@ -45,16 +60,162 @@ pub fn register_completion(&mut self, program: String, completion: Completion) {
         self.completions.insert(program, completion);
     }
 
+    /// Removes the registered completion for a program.
+    ///
+    /// Returns `true` if a completion was registered.
+    pub fn unregister_completion(&mut self, program: &str) -> bool {
+        self.completions.remove(program).is_some()
+    }
+
+    /// Returns all registered completions, ordered by program name.
+    pub fn registered_completions(&self) -> Vec<(&str, &Completion)> {
+        let mut completions: Vec<_> = self
+            .completions
+            .iter()
+            .map(|(program, completion)| (program.as_str(), completion))
+            .collect();
+        completions.sort_by_key(|(program, _)| *program);
+        completions
+    }
+
     /// Completes a word based on a prefix.
+    ///
+    /// Replacements are ranked so that the most relevant replacement comes first.
     fn complete_word(
-        &self,
+        &mut self,
         prefix: &str,
         words: &[&str],
         word_index: usize,
         context: &Context,
     ) -> Vec<Replacement> {
-        complete_known_prefix(prefix)
+        // Words following redirect operators, such as ">", are file names.
+        if word_index > 0 && is_redirect(words[word_index - 1]) {
+            let mut replacements = complete_paths(prefix, context, |_| true);
+            rank_replacements(prefix, &mut replacements);
+            return replacements;
+        }
+
+        // Words following a prefix command are completed as a command of their own.
+        if word_index > 0 && matches!(self.completions.get(words[0]), Some(Completion::Prefix)) {
+            return self.complete_word(prefix, &words[1..], word_index - 1, context);
+        }
+
+        let mut replacements = complete_known_prefix(prefix)
+            .or_else(|| complete_bookmark(prefix, words, word_index, context))
+            .or_else(|| {
+                self.external
+                    .complete(prefix, words, word_index, context, &self.completions)
+            })
             .or_else(|| complete_registered(prefix, words, word_index, context, &self.completions))
-            .unwrap_or_else(|| complete_anything(prefix, words, word_index, context))
+            .unwrap_or_else(|| {
+                complete_anything(prefix, words, word_index, context, &mut self.programs)
+            });
+        rank_replacements(prefix, &mut replacements);
+        replacements
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn completions(completer: &mut Completer, line: &str, context: &Context) -> Vec<String> {
+        completer
+            .complete_line(line, line.len(), context)
+            .replacements
+            .into_iter()
+            .map(|replacement| replacement.content)
+            .collect()
+    }
+
+    #[test]
+    fn it_completes_commands_after_prefix_commands() {
+        let mut context = Context::default();
+        context.aliases.insert("greet".into(), "echo hello".into());
+
+        let mut completer = Completer::default();
+        completer.register_completion("wsl".into(), Completion::Prefix);
+        completer.register_completion(
+            "git".into(),
+            Completion::Constant(vec!["checkout".into(), "commit".into()]),
+        );
+
+        assert_eq!(
+            completions(&mut completer, "wsl gre", &context),
+            vec!["greet"]
+        );
+        assert_eq!(
+            completions(&mut completer, "wsl wsl git ch", &context),
+            vec!["checkout"]
+        );
+    }
+
+    #[test]
+    fn it_completes_commands_within_substitutions() {
+        let mut context = Context::default();
+        context.aliases.insert("greet".into(), "echo hello".into());
+
+        let mut completer = Completer::default();
+        completer.register_completion(
+            "git".into(),
+            Completion::Constant(vec!["checkout".into(), "commit".into()]),
+        );
+
+        let line = "echo $(git ch";
+        let completion = completer.complete_line(line, line.len(), &context);
+        assert_eq!(completion.line_pos, 11);
+        assert_eq!(completion.replacements, vec![Replacement::from("checkout")]);
+
+        let line = "echo $(gre) after";
+        let completion = completer.complete_line(line, 10, &context);
+        assert_eq!(completion.line_pos, 7);
+        assert_eq!(completion.replacements, vec![Replacement::from("greet")]);
+    }
+
+    #[test]
+    fn it_completes_files_after_redirects() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("file.txt"), "").unwrap();
+        std::fs::create_dir(dir.path().join("files")).unwrap();
+
+        let mut context = Context::default();
+        let pwd = dir.path().to_string_lossy().to_string();
+        context
+            .set_var("PWD".into(), pjsh_core::Value::Word(pwd))
+            .unwrap();
+
+        let mut completer = Completer::default();
+        completer.register_completion("cat".into(), Completion::Constant(vec!["fi".into()]));
+
+        for (line, line_pos) in [("cat >fi", 5), ("cat 2>> fi", 8), ("cat <fi", 5)] {
+            let completion = completer.complete_line(line, line.len(), &context);
+            assert_eq!(completion.line_pos, line_pos, "{line}");
+            assert_eq!(
+                completions(&mut completer, line, &context),
+                vec!["file.txt", "files/"],
+                "{line}"
+            );
+        }
+
+        // Arguments are still completed using registered completions.
+        assert_eq!(completions(&mut completer, "cat fi", &context), vec!["fi"]);
+    }
+
+    #[test]
+    fn it_unregisters_completions() {
+        let mut completer = Completer::default();
+        completer.register_completion("ls".into(), Completion::File);
+        completer.register_completion("cd".into(), Completion::Directory);
+
+        assert!(completer.unregister_completion("ls"));
+        assert!(!completer.unregister_completion("ls"));
+        assert_eq!(
+            completer
+                .registered_completions()
+                .into_iter()
+                .map(|(program, _)| program)
+                .collect::<Vec<_>>(),
+            vec!["cd"]
+        );
     }
 }
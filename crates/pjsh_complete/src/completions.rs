@@ -11,6 +11,12 @@ impl Replacement {
     pub fn new(content: String, display: String) -> Self {
         Self { content, display }
     }
+
+    /// Constructs a replacement that is displayed along with a description.
+    pub fn customized(content: String, description: &str) -> Self {
+        let display = format!("{content} -- {description}");
+        Self::new(content, display)
+    }
 }
 
 impl From<String> for Replacement {
@@ -40,7 +46,7 @@ pub(crate) fn new(line_pos: usize, replacements: Vec<Replacement>) -> Self {
 }
 
 /// A defined shell completion.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Completion {
     /// Complete using a pre-defined list of words.
     Constant(Vec<String>),
@@ -48,9 +54,15 @@ pub enum Completion {
     /// Complete a directory path.
     Directory,
 
+    /// Complete using the output of `<program> --pjsh-complete -- <line> <point>`.
+    External,
+
     /// Complete a file path.
     File,
 
     /// Function to execute in order to retrieve completions.
     Function(String),
+
+    /// Complete the remaining words as a separate command, e.g. for `wsl git`.
+    Prefix,
 }
@@ -0,0 +1,307 @@
+use std::{
+    collections::HashMap,
+    io::Read,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    time::{Duration, Instant, SystemTime},
+};
+
+use pjsh_core::{find_in_path, utils::word_var, Context};
+
+use crate::{completions::Completion, Replacement};
+
+/// Extension of files containing pre-defined completions for a sibling program.
+const COMPLETIONS_FILE_EXTENSION: &str = "pjsh-completions";
+
+/// Argument instructing a program to print completions for a command line.
+const COMPLETE_ARG: &str = "--pjsh-complete";
+
+/// Maximum time that a program may take to print its completions.
+const TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Interval at which programs are polled while waiting for their completions.
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// Cached completions read from a completions file.
+#[derive(Debug)]
+struct CachedFile {
+    /// Modification time of the file when it was read.
+    modified: Option<SystemTime>,
+
+    /// Completions within the file.
+    replacements: Vec<Replacement>,
+}
+
+/// Completions that are provided by programs themselves.
+///
+/// A program `foo` may either be accompanied by a `foo.pjsh-completions` file
+/// in the same directory, or be registered to print its own completions when
+/// invoked as `foo --pjsh-complete -- <line> <point>`. In both cases,
+/// completions are newline-separated and may be followed by a tab-separated
+/// description.
+#[derive(Debug, Default)]
+pub(crate) struct ExternalCompletions {
+    /// Cached completions files, by path.
+    files: HashMap<PathBuf, CachedFile>,
+
+    /// Completions printed by a program for the most recently completed line.
+    last_invocation: Option<(PathBuf, String, Vec<Replacement>)>,
+}
+
+impl ExternalCompletions {
+    /// Completes a word using completions provided by the program named by the
+    /// first word.
+    ///
+    /// Returns `None` if the program does not provide completions.
+    pub(crate) fn complete(
+        &mut self,
+        prefix: &str,
+        words: &[&str],
+        word_index: usize,
+        context: &Context,
+        completions: &HashMap<String, Completion>,
+    ) -> Option<Vec<Replacement>> {
+        if word_index == 0 {
+            return None;
+        }
+
+        let program = find_in_path(words[0], context).filter(|path| path.is_file())?;
+        match completions.get(words[0]) {
+            Some(Completion::External) => {
+                let line = command_line(prefix, &words[..word_index]);
+                Some(self.invoke(&program, &line, context))
+            }
+            Some(_) => None,
+            None => self.read_completions_file(&program).map(|replacements| {
+                replacements
+                    .iter()
+                    .filter(|replacement| replacement.content.starts_with(prefix))
+                    .cloned()
+                    .collect()
+            }),
+        }
+    }
+
+    /// Returns the completions within the completions file of a program.
+    ///
+    /// Files are only read again if they have been modified since last being read.
+    fn read_completions_file(&mut self, program: &Path) -> Option<&Vec<Replacement>> {
+        let stem = program.file_stem()?.to_string_lossy();
+        let path = program.with_file_name(format!("{stem}.{COMPLETIONS_FILE_EXTENSION}"));
+        let Ok(metadata) = std::fs::metadata(&path) else {
+            self.files.remove(&path);
+            return None;
+        };
+
+        let modified = metadata.modified().ok();
+        let is_fresh = self
+            .files
+            .get(&path)
+            .is_some_and(|cached| modified.is_some() && cached.modified == modified);
+        if !is_fresh {
+            let contents = std::fs::read_to_string(&path).ok()?;
+            let replacements = parse_completions(&contents);
+            self.files.insert(
+                path.clone(),
+                CachedFile {
+                    modified,
+                    replacements,
+                },
+            );
+        }
+
+        self.files.get(&path).map(|cached| &cached.replacements)
+    }
+
+    /// Returns the completions that a program prints for a command line.
+    ///
+    /// The completions for the most recent command line are reused when
+    /// completing the same line again.
+    fn invoke(&mut self, program: &Path, line: &str, context: &Context) -> Vec<Replacement> {
+        if let Some((cached_program, cached_line, replacements)) = &self.last_invocation {
+            if cached_program == program && cached_line == line {
+                return replacements.clone();
+            }
+        }
+
+        let replacements = invoke_program(program, line, context)
+            .map(|output| parse_completions(&output))
+            .unwrap_or_default();
+        self.last_invocation = Some((program.to_owned(), line.to_owned(), replacements.clone()));
+        replacements
+    }
+}
+
+/// Returns the command line up to the word being completed.
+fn command_line(prefix: &str, preceding_words: &[&str]) -> String {
+    let mut line = preceding_words.join(" ");
+    line.push(' ');
+    line.push_str(prefix);
+    line
+}
+
+/// Invokes a program to print its completions for a command line, in which the
+/// cursor is at the end of the line.
+///
+/// Returns `None` if the program fails, or if it does not exit in time.
+fn invoke_program(program: &Path, line: &str, context: &Context) -> Option<String> {
+    let mut command = Command::new(program);
+    command
+        .args([COMPLETE_ARG, "--", line, &line.len().to_string()])
+        .env_clear()
+        .envs(context.exported_vars())
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null());
+    if let Some(path) = word_var(context, "PWD") {
+        command.current_dir(path);
+    }
+
+    let mut child = command.spawn().ok()?;
+
+    // Read output while waiting, so that the program is never blocked by a full pipe.
+    let mut stdout = child.stdout.take()?;
+    let reader = std::thread::spawn(move || {
+        let mut output = String::new();
+        stdout.read_to_string(&mut output).map(|_| output)
+    });
+
+    let deadline = Instant::now() + TIMEOUT;
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) if status.success() => break,
+            Ok(None) if Instant::now() < deadline => std::thread::sleep(POLL_INTERVAL),
+            _ => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
+            }
+        }
+    }
+
+    reader.join().ok()?.ok()
+}
+
+/// Parses newline-separated completions, each optionally followed by a
+/// tab-separated description.
+fn parse_completions(output: &str) -> Vec<Replacement> {
+    output
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| match line.split_once('\t') {
+            Some((content, description)) => {
+                Replacement::customized(content.to_owned(), description.trim())
+            }
+            None => Replacement::from(line),
+        })
+        .collect()
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::os::unix::fs::PermissionsExt;
+
+    use pjsh_core::Value;
+    use tempfile::TempDir;
+
+    use super::*;
+
+    /// Writes an executable script to a directory.
+    fn write_script(dir: &Path, name: &str, script: &str) {
+        let path = dir.join(name);
+        std::fs::write(&path, format!("#!/bin/sh\n{script}\n")).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    /// Returns a context in which programs are found in a directory.
+    fn context_with_path(dir: &Path) -> Context {
+        let mut context = Context::default();
+        let path = Value::Word(dir.to_string_lossy().to_string());
+        context.set_var("PATH".into(), path).unwrap();
+        context
+    }
+
+    fn contents(replacements: &[Replacement]) -> Vec<(&str, &str)> {
+        replacements
+            .iter()
+            .map(|replacement| (replacement.content.as_str(), replacement.display.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn it_parses_completions_with_descriptions() {
+        assert_eq!(
+            contents(&parse_completions("build\tBuild the project\nclean\n\n")),
+            vec![("build", "build -- Build the project"), ("clean", "clean")]
+        );
+    }
+
+    #[test]
+    fn it_reads_completions_files() {
+        let dir = TempDir::new().unwrap();
+        write_script(dir.path(), "tool", "exit 0");
+        std::fs::write(
+            dir.path().join("tool.pjsh-completions"),
+            "build\tBuild the project\nbench\ncheck\n",
+        )
+        .unwrap();
+
+        let context = context_with_path(dir.path());
+        let replacements = ExternalCompletions::default()
+            .complete("b", &["tool", "b"], 1, &context, &HashMap::new())
+            .unwrap();
+        assert_eq!(
+            contents(&replacements),
+            vec![("build", "build -- Build the project"), ("bench", "bench")]
+        );
+    }
+
+    #[test]
+    fn it_invokes_registered_programs() {
+        let dir = TempDir::new().unwrap();
+        let calls = dir.path().join("calls");
+        write_script(
+            dir.path(),
+            "tool",
+            &format!(
+                r#"echo "$*" >> {}
+printf 'build\tBuild the project\nclean\n'"#,
+                calls.display()
+            ),
+        );
+
+        let context = context_with_path(dir.path());
+        let completions = HashMap::from([("tool".into(), Completion::External)]);
+        let mut external = ExternalCompletions::default();
+        for _ in 0..2 {
+            let replacements = external
+                .complete("", &["tool", "--flag", ""], 2, &context, &completions)
+                .unwrap();
+            assert_eq!(
+                contents(&replacements),
+                vec![("build", "build -- Build the project"), ("clean", "clean")]
+            );
+        }
+
+        // Completions for the same line are cached.
+        assert_eq!(
+            std::fs::read_to_string(calls).unwrap(),
+            "--pjsh-complete -- tool --flag  12\n"
+        );
+    }
+
+    #[test]
+    fn it_stops_waiting_for_slow_programs() {
+        let dir = TempDir::new().unwrap();
+        write_script(dir.path(), "tool", "sleep 5; echo late");
+
+        let context = context_with_path(dir.path());
+        let completions = HashMap::from([("tool".into(), Completion::External)]);
+        let start = Instant::now();
+        let replacements = ExternalCompletions::default()
+            .complete("", &["tool", ""], 1, &context, &completions)
+            .unwrap();
+        assert!(replacements.is_empty());
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+}
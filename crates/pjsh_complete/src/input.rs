@@ -1,4 +1,44 @@
+/// Returns the position at which the command at the end of a text input starts.
+///
+/// Unclosed subshells and command substitutions, such as `$(git ch`, start a
+/// new command. Otherwise, the command starts at the beginning of the input.
+pub(crate) fn command_start(text: &str) -> usize {
+    let mut starts = Vec::new();
+    let mut quote = None;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((pos, ch)) = chars.next() {
+        match (quote, ch) {
+            (Some(quote_char), ch) if ch == quote_char => quote = None,
+            (Some(_), _) => (),
+            (None, '\\') => {
+                chars.next();
+            }
+            (None, '\'' | '"') => quote = Some(ch),
+            (None, '$') if chars.next_if(|(_, ch)| *ch == '(').is_some() => {
+                starts.push(pos + 2);
+            }
+            (None, '(') => starts.push(pos + 1),
+            (None, ')') => {
+                starts.pop();
+            }
+            _ => (),
+        }
+    }
+
+    starts.last().copied().unwrap_or(0)
+}
+
+/// Returns `true` if a word is a redirect operator, such as `>`, `2>>` or `<`.
+pub(crate) fn is_redirect(word: &str) -> bool {
+    let operator = word.trim_start_matches(|ch: char| ch.is_ascii_digit());
+    matches!(operator, ">" | ">>" | "<")
+}
+
 /// Return all word spans in a text input.
+///
+/// Redirect operators are separate words, even when attached to a file name
+/// such as in `>file`.
 pub(crate) fn separate_input(text: &str) -> Vec<(&str, usize, usize)> {
     let mut words = Vec::new();
     let mut quotes = Vec::with_capacity(16);
@@ -23,6 +63,27 @@ pub(crate) fn separate_input(text: &str) -> Vec<(&str, usize, usize)> {
             continue;
         }
 
+        // Separate redirect operators from surrounding words. File descriptors
+        // preceding an operator, such as in "2>", belong to the operator.
+        if quotes.is_empty() && !ch.is_whitespace() {
+            let word = &text[start.unwrap_or(pos)..pos];
+            let is_operator_char = matches!(ch, '<' | '>');
+            let word_is_operator = word.contains(['<', '>'])
+                && word
+                    .chars()
+                    .all(|c| c.is_ascii_digit() || c == '<' || c == '>');
+            let word_is_fd = word.chars().all(|c| c.is_ascii_digit());
+
+            let ends_word = match is_operator_char {
+                true => !word_is_operator && !word_is_fd,
+                false => word_is_operator,
+            };
+            if let (true, Some(word_start)) = (ends_word, start) {
+                words.push((word, word_start, pos));
+                start = None;
+            }
+        }
+
         // Start words on first non-whitespace.
         if start.is_none() {
             start = Some(pos);
@@ -75,6 +136,43 @@ fn get_input_words_separated_by_whitespace() {
         );
     }
 
+    #[test]
+    fn get_input_words_with_redirects() {
+        assert_eq!(words("cat >file"), vec!["cat", ">", "file"]);
+        assert_eq!(
+            words("cat>>file 2>err"),
+            vec!["cat", ">>", "file", "2>", "err"]
+        );
+        assert_eq!(words("cat <in >"), vec!["cat", "<", "in", ">"]);
+        assert_eq!(words("echo '>quoted'"), vec!["echo", "'>quoted'"]);
+        assert_eq!(
+            separate_input("cat >/tmp/fi"),
+            vec![("cat", 0, 3), (">", 4, 5), ("/tmp/fi", 5, 12)]
+        );
+    }
+
+    #[test]
+    fn it_identifies_redirects() {
+        assert!(is_redirect(">"));
+        assert!(is_redirect(">>"));
+        assert!(is_redirect("<"));
+        assert!(is_redirect("2>"));
+        assert!(!is_redirect(">&"));
+        assert!(!is_redirect("2"));
+        assert!(!is_redirect("file"));
+    }
+
+    #[test]
+    fn it_finds_the_start_of_commands() {
+        assert_eq!(command_start("git ch"), 0);
+        assert_eq!(command_start("echo $(git ch"), 7);
+        assert_eq!(command_start("echo $(a $(b) c"), 7);
+        assert_eq!(command_start("echo $(a) b"), 0);
+        assert_eq!(command_start("(cd dir; ls"), 1);
+        assert_eq!(command_start("echo '$(' \\$\\( b"), 0);
+        assert_eq!(command_start("echo `$(git ch`"), 8);
+    }
+
     #[test]
     fn get_input_words_with_quotes() {
         assert_eq!(
@@ -1,8 +1,12 @@
+mod bookmarks;
 mod completer;
 mod completions;
+mod external_completions;
 mod fs;
 mod input;
 mod known_prefixes;
+mod program_cache;
+mod ranking;
 mod registered_completions;
 mod uncontextualized_completions;
 
@@ -0,0 +1,166 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use is_executable::is_executable;
+use pjsh_core::{paths, utils::word_var, Context};
+
+/// Function for scanning a directory for executable program names.
+type Scanner = Box<dyn Fn(&Path) -> HashSet<String> + Send>;
+
+/// Cached programs within a single directory.
+struct CachedDir {
+    /// Modification time of the directory when it was scanned.
+    modified: Option<SystemTime>,
+
+    /// Executable program names within the directory.
+    programs: HashSet<String>,
+}
+
+/// A cache of executable programs within the directories in `$PATH`.
+///
+/// Directories are only rescanned if `$PATH` changes, or if their modification
+/// time changes.
+pub(crate) struct ProgramCache {
+    /// Value of `$PATH` when the cache was last used.
+    path: Option<String>,
+
+    /// Cached programs per directory.
+    dirs: HashMap<PathBuf, CachedDir>,
+
+    /// Function for scanning a directory.
+    scanner: Scanner,
+}
+
+impl ProgramCache {
+    /// Constructs a new program cache using a custom directory scanner.
+    pub(crate) fn with_scanner(scanner: Scanner) -> Self {
+        Self {
+            path: None,
+            dirs: HashMap::new(),
+            scanner,
+        }
+    }
+
+    /// Returns the names of all executable programs in `$PATH`.
+    ///
+    /// Directories are rescanned if they have changed since last being scanned.
+    pub(crate) fn programs(&mut self, context: &Context) -> HashSet<&str> {
+        let path = word_var(context, "PATH").map(ToString::to_string);
+        if path != self.path {
+            self.dirs.clear();
+            self.path = path;
+        }
+
+        let dirs = paths(context);
+        self.dirs.retain(|dir, _| dirs.contains(dir));
+
+        for dir in &dirs {
+            let modified = std::fs::metadata(dir).and_then(|m| m.modified()).ok();
+            let is_fresh = self
+                .dirs
+                .get(dir)
+                .is_some_and(|cached| modified.is_some() && cached.modified == modified);
+
+            if !is_fresh {
+                let programs = (self.scanner)(dir);
+                self.dirs
+                    .insert(dir.clone(), CachedDir { modified, programs });
+            }
+        }
+
+        self.dirs
+            .values()
+            .flat_map(|cached| cached.programs.iter().map(String::as_str))
+            .collect()
+    }
+}
+
+impl Default for ProgramCache {
+    fn default() -> Self {
+        Self::with_scanner(Box::new(scan_dir))
+    }
+}
+
+impl Debug for ProgramCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProgramCache")
+            .field("path", &self.path)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Returns the names of all executable files within a directory.
+fn scan_dir(dir: &Path) -> HashSet<String> {
+    let Ok(files) = std::fs::read_dir(dir) else {
+        return HashSet::new();
+    };
+
+    files
+        .filter_map(Result::ok)
+        .filter(|file| is_executable(file.path()))
+        .map(|file| file.file_name().to_string_lossy().to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashSet,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+    };
+
+    use pjsh_core::{utils::path_to_string, Scope, Value};
+    use tempfile::TempDir;
+
+    use super::*;
+
+    /// Constructs a cache that counts the number of directory scans.
+    fn counting_cache() -> (ProgramCache, Arc<AtomicUsize>) {
+        let scans = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&scans);
+        let cache = ProgramCache::with_scanner(Box::new(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            HashSet::from(["program".to_owned()])
+        }));
+        (cache, scans)
+    }
+
+    fn context_with_path(path: &Path) -> Context {
+        Context::with_scopes(vec![Scope::new(
+            String::new(),
+            None,
+            HashMap::from([("PATH".into(), Some(Value::Word(path_to_string(path))))]),
+            HashMap::default(),
+            HashSet::default(),
+        )])
+    }
+
+    #[test]
+    fn it_does_not_rescan_unchanged_paths() {
+        let dir = TempDir::new().unwrap();
+        let context = context_with_path(dir.path());
+        let (mut cache, scans) = counting_cache();
+
+        assert_eq!(cache.programs(&context), HashSet::from(["program"]));
+        assert_eq!(cache.programs(&context), HashSet::from(["program"]));
+        assert_eq!(scans.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn it_rescans_when_path_changes() {
+        let first = TempDir::new().unwrap();
+        let second = TempDir::new().unwrap();
+        let (mut cache, scans) = counting_cache();
+
+        cache.programs(&context_with_path(first.path()));
+        cache.programs(&context_with_path(second.path()));
+        assert_eq!(scans.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,95 @@
+use std::cmp::Ordering;
+
+use crate::Replacement;
+
+/// Relevance of a replacement in relation to a prefix.
+///
+/// Variants are ordered from most to least relevant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Relevance {
+    /// The replacement starts with the prefix.
+    Prefix,
+
+    /// The replacement starts with the prefix when ignoring case.
+    CaseInsensitivePrefix,
+
+    /// The replacement contains all characters in the prefix, in order.
+    Fuzzy,
+
+    /// The replacement is unrelated to the prefix.
+    Unrelated,
+}
+
+/// Sorts replacements so that the most relevant replacement for a prefix comes
+/// first.
+///
+/// Replacements of equal relevance are sorted alphabetically.
+pub(crate) fn rank_replacements(prefix: &str, replacements: &mut [Replacement]) {
+    replacements.sort_by(|a, b| compare(prefix, a, b));
+}
+
+/// Compares two replacements based on their relevance for a prefix.
+fn compare(prefix: &str, a: &Replacement, b: &Replacement) -> Ordering {
+    relevance(prefix, &a.content)
+        .cmp(&relevance(prefix, &b.content))
+        .then_with(|| a.content.cmp(&b.content))
+}
+
+/// Returns the relevance of a candidate in relation to a prefix.
+fn relevance(prefix: &str, candidate: &str) -> Relevance {
+    if candidate.starts_with(prefix) {
+        return Relevance::Prefix;
+    }
+
+    if candidate.to_lowercase().starts_with(&prefix.to_lowercase()) {
+        return Relevance::CaseInsensitivePrefix;
+    }
+
+    let mut candidate_chars = candidate.chars();
+    if prefix.chars().all(|ch| candidate_chars.any(|c| c == ch)) {
+        return Relevance::Fuzzy;
+    }
+
+    Relevance::Unrelated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranked(prefix: &str, candidates: &[&str]) -> Vec<String> {
+        let mut replacements: Vec<Replacement> =
+            candidates.iter().map(|c| Replacement::from(*c)).collect();
+        rank_replacements(prefix, &mut replacements);
+        replacements.into_iter().map(|r| r.content).collect()
+    }
+
+    #[test]
+    fn it_ranks_exact_prefixes_before_case_insensitive_prefixes() {
+        assert_eq!(
+            ranked("do", &["Downloads", "docs", "Documents", "dotfiles"]),
+            vec!["docs", "dotfiles", "Documents", "Downloads"]
+        );
+    }
+
+    #[test]
+    fn it_ranks_prefixes_before_fuzzy_matches() {
+        assert_eq!(
+            ranked("gt", &["git", "gtk", "gst", "GTest", "target"]),
+            vec!["gtk", "GTest", "git", "gst", "target"]
+        );
+    }
+
+    #[test]
+    fn it_ranks_unrelated_candidates_last() {
+        assert_eq!(
+            ranked("ab", &["xyz", "ba", "a-b", "abc"]),
+            vec!["abc", "a-b", "ba", "xyz"]
+        );
+    }
+
+    #[test]
+    fn it_sorts_alphabetically_without_prefix() {
+        assert_eq!(ranked("", &["c", "a", "b"]), vec!["a", "b", "c"]);
+    }
+}
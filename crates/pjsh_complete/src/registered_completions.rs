@@ -21,9 +21,7 @@ pub fn complete_registered(
         return None;
     }
 
-    let Some(completion) = completions.get(words[0]) else {
-        return None;
-    };
+    let completion = completions.get(words[0])?;
 
     Some(match completion {
         Completion::Constant(words) => complete_words(prefix, words),
@@ -46,6 +44,7 @@ pub fn complete_registered(
                 .map(|word| Replacement::from(word.to_string()))
                 .collect()
         }
+        Completion::External | Completion::Prefix => return None,
     })
 }
 
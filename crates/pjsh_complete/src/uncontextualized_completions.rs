@@ -1,8 +1,8 @@
-use std::collections::HashSet;
-
 use is_executable::is_executable;
 use itertools::{chain, Itertools};
-use pjsh_core::{paths, Context};
+use pjsh_core::Context;
+
+use crate::program_cache::ProgramCache;
 
 use super::{fs::complete_paths, Replacement};
 
@@ -12,6 +12,7 @@ pub fn complete_anything(
     _words: &[&str],
     word_index: usize,
     context: &Context,
+    programs: &mut ProgramCache,
 ) -> Vec<Replacement> {
     // Complete references to things that may be executable if completing the first
     // word, i.e. the program.
@@ -21,7 +22,7 @@ pub fn complete_anything(
             complete_builtins(prefix, context),
             complete_functions(prefix, context),
             complete_variables(prefix, context),
-            complete_programs(prefix, context),
+            complete_programs(prefix, context, programs),
             complete_paths(prefix, context, |path| path.is_dir() || is_executable(path)),
         )
         .unique()
@@ -53,7 +54,7 @@ fn complete_aliases<'a>(
     prefix: &'a str,
     context: &'a Context,
 ) -> impl Iterator<Item = Replacement> + 'a {
-    context.aliases.iter().filter_map(move |(name, _)| {
+    context.aliases.keys().filter_map(move |name| {
         if name.starts_with(prefix) {
             Some(Replacement::from(name.to_string()))
         } else {
@@ -67,7 +68,7 @@ fn complete_builtins<'a>(
     prefix: &'a str,
     context: &'a Context,
 ) -> impl Iterator<Item = Replacement> + 'a {
-    context.builtins.iter().filter_map(move |(name, _)| {
+    context.builtins.keys().filter_map(move |name| {
         if name.starts_with(prefix) {
             Some(Replacement::from(name.to_string()))
         } else {
@@ -82,34 +83,26 @@ fn complete_functions<'a>(
     context: &'a Context,
 ) -> impl Iterator<Item = Replacement> + 'a {
     context
-        .get_function_names()
+        .function_names()
         .into_iter()
         .filter(move |name| name.starts_with(prefix))
         .map(Replacement::from)
 }
 
 /// Completes a program name.
-fn complete_programs(prefix: &str, context: &Context) -> Vec<Replacement> {
-    let mut programs = HashSet::new();
-    for dir in paths(context) {
-        let Ok(files) = std::fs::read_dir(dir) else {
-            continue
-        };
-
-        for file in files {
-            let Ok(file) = file else {
-                continue
-            };
-
-            let name = file.file_name().to_string_lossy().to_string();
-            if !name.starts_with(prefix) || !is_executable(file.path()) {
-                continue;
-            }
-
-            programs.insert(name);
-        }
-    }
-    programs.into_iter().map(Replacement::from).collect()
+///
+/// Programs are read from a cache of the executables in `$PATH`.
+fn complete_programs(
+    prefix: &str,
+    context: &Context,
+    programs: &mut ProgramCache,
+) -> Vec<Replacement> {
+    programs
+        .programs(context)
+        .into_iter()
+        .filter(|name| name.starts_with(prefix))
+        .map(Replacement::from)
+        .collect()
 }
 
 /// Completes a variable.
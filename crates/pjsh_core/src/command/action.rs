@@ -1,5 +1,7 @@
 use std::path::PathBuf;
 
+use pjsh_ast::Program;
+
 use crate::command::Io;
 
 type ExitCode = i32;
@@ -19,6 +21,13 @@ pub enum Action {
     /// Exit the current scope.
     ExitScope(ExitCode),
 
+    /// Execute a program within the calling scope.
+    ///
+    /// The command's exit code is replaced by that of the program's last
+    /// statement. Control flow statements, such as `return`, apply to the
+    /// calling function or loop.
+    Execute(Program),
+
     /// Interpolate a string and call a function with the interpolated value as
     /// an argument, or an error message if it cannot be interpolated.
     Interpolate(String, Box<InterpolateCallback>),
@@ -30,6 +39,16 @@ pub enum Action {
     /// Resolve the path to a command and call a function with it as an
     /// argument.
     ResolveCommandPath(String, Box<ResolveCommandPathCallback>),
+
+    /// Keep the command's redirections applied to the shell's file descriptors
+    /// once the command has been executed.
+    KeepRedirects,
+
+    /// Remove a number of positional arguments from the calling scope.
+    ///
+    /// The command exits with a non-zero code, and no arguments are removed, if
+    /// there are fewer positional arguments.
+    ShiftArgs(usize),
 }
 
 /// Command types.
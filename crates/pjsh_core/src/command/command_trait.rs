@@ -5,6 +5,17 @@ pub trait Command: CommandClone + Send + Sync {
     /// Returns the command's name.
     fn name(&self) -> &str;
 
+    /// Returns a short description of the command, if available.
+    fn description(&self) -> Option<String> {
+        None
+    }
+
+    /// Returns the command's usage synopsis, such as `cd [DIRECTORY]`, if
+    /// available.
+    fn usage(&self) -> Option<String> {
+        None
+    }
+
     /// Runs the command.
     fn run(&self, args: &mut Args) -> CommandResult;
 }
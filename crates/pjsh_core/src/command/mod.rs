@@ -2,10 +2,12 @@
 mod args;
 mod command_trait;
 mod io;
+mod registry;
 mod result;
 
 pub use action::{Action, CommandType};
 pub use args::Args;
 pub use command_trait::Command;
 pub use io::Io;
+pub use registry::BuiltinRegistry;
 pub use result::{BuiltinCommandResult, CommandResult, ProcessCommandResult};
@@ -0,0 +1,97 @@
+use std::collections::HashMap;
+
+use super::Command;
+
+/// A set of built-in commands keyed by their name.
+///
+/// Used to compose the built-in commands of a [`Context`](crate::Context),
+/// typically by adding or removing commands from a default set.
+#[derive(Clone, Default)]
+pub struct BuiltinRegistry {
+    builtins: HashMap<String, Box<dyn Command>>,
+}
+
+impl BuiltinRegistry {
+    /// Constructs a new empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a built-in command, replacing any built-in command with the same
+    /// name.
+    pub fn with(mut self, builtin: Box<dyn Command>) -> Self {
+        self.builtins.insert(builtin.name().to_owned(), builtin);
+        self
+    }
+
+    /// Removes the built-in command with a name, if it exists.
+    pub fn without(mut self, name: &str) -> Self {
+        self.builtins.remove(name);
+        self
+    }
+
+    /// Returns `true` if the registry contains a built-in command with a name.
+    pub fn contains(&self, name: &str) -> bool {
+        self.builtins.contains_key(name)
+    }
+
+    /// Returns the names of all built-in commands in the registry.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.builtins.keys().map(String::as_str)
+    }
+}
+
+impl From<BuiltinRegistry> for HashMap<String, Box<dyn Command>> {
+    fn from(registry: BuiltinRegistry) -> Self {
+        registry.builtins
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::command::{Args, CommandResult};
+
+    use super::*;
+
+    #[derive(Clone)]
+    struct Named(&'static str, i32);
+    impl Command for Named {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        fn run(&self, _args: &mut Args) -> CommandResult {
+            CommandResult::code(self.1)
+        }
+    }
+
+    #[test]
+    fn it_adds_and_removes_builtins() {
+        let registry = BuiltinRegistry::new()
+            .with(Box::new(Named("a", 0)))
+            .with(Box::new(Named("b", 0)))
+            .without("a")
+            .without("missing");
+
+        assert!(!registry.contains("a"));
+        assert!(registry.contains("b"));
+        assert_eq!(registry.names().collect::<Vec<_>>(), vec!["b"]);
+    }
+
+    #[test]
+    fn it_overrides_builtins() {
+        let registry = BuiltinRegistry::new()
+            .with(Box::new(Named("a", 0)))
+            .with(Box::new(Named("a", 1)));
+
+        let builtins = HashMap::from(registry);
+        let mut context = crate::Context::default();
+        let mut io = crate::command::Io::new(
+            Box::new(std::io::empty()),
+            Box::new(std::io::sink()),
+            Box::new(std::io::sink()),
+        );
+        let result = builtins["a"].run(&mut Args::new(&mut context, &mut io));
+        assert!(matches!(result, CommandResult::Builtin(result) if result.code == 1));
+    }
+}
@@ -1,5 +1,5 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     io::{Read, Write},
     path::PathBuf,
     process::Stdio,
@@ -9,10 +9,11 @@
 use pjsh_ast::Function;
 
 use crate::{
-    command::{Command, Io},
+    command::{BuiltinRegistry, Command, Io},
     file_descriptor::FileDescriptorError,
     utils::word_var,
-    FileDescriptor, Filter, Host, StdHost, FD_STDERR, FD_STDIN, FD_STDOUT,
+    DynamicVars, FileDescriptor, Filter, History, Host, MemoryHistory, Options, PathCache, Profile,
+    ProfileSink, StdHost, FD_STDERR, FD_STDIN, FD_STDOUT,
 };
 
 /// An execution context consisting of a number of execution scopes.
@@ -23,6 +24,14 @@ pub struct Context {
     /// The context's host.
     pub host: Arc<parking_lot::Mutex<dyn Host>>,
 
+    /// The shell's command history.
+    pub history: Arc<parking_lot::Mutex<dyn History>>,
+
+    /// Program paths resolved using `$PATH`.
+    ///
+    /// The cache is shared with cloned contexts.
+    pub path_cache: Arc<parking_lot::Mutex<PathCache>>,
+
     /// Scopes in order of increasing specificity.
     scopes: Vec<Scope>,
 
@@ -31,6 +40,40 @@ pub struct Context {
 
     /// Built-in filters in the context.
     pub filters: HashMap<String, Box<dyn Filter>>,
+
+    /// Variables whose values are computed when read, such as `RANDOM`.
+    ///
+    /// Dynamic variables are shadowed by regular variables with the same name.
+    /// Providers are shared with cloned contexts.
+    pub dynamic_vars: DynamicVars,
+
+    /// Options that change how commands are executed.
+    pub options: Options,
+
+    /// Process ID (PID) of the most recently started asynchronous program.
+    ///
+    /// Available as `$!`.
+    pub last_async_pid: Option<u32>,
+
+    /// Directory stack, excluding the current working directory.
+    ///
+    /// The most recently pushed directory is kept last.
+    pub dir_stack: Vec<PathBuf>,
+
+    /// Commands to execute when a signal is received, keyed by signal number.
+    ///
+    /// Cloned contexts, such as subshells, do not inherit any traps.
+    pub traps: BTreeMap<i32, String>,
+
+    /// Execution profile, if profiling is enabled.
+    ///
+    /// Cloned contexts are never profiled.
+    pub profile: Option<Profile>,
+
+    /// Sink receiving the timing of each executed command, if enabled.
+    ///
+    /// Cloned contexts never record command timings.
+    pub profile_sink: Option<Box<dyn ProfileSink>>,
 }
 
 impl Context {
@@ -44,12 +87,51 @@ pub fn try_clone(&self) -> std::io::Result<Self> {
         Ok(Self {
             aliases: self.aliases.clone(),
             host: Arc::clone(&self.host),
+            history: Arc::clone(&self.history),
+            path_cache: Arc::clone(&self.path_cache),
             scopes,
             builtins: self.builtins.clone(),
             filters: self.filters.clone(),
+            dynamic_vars: self.dynamic_vars.fork(),
+            options: self.options,
+            last_async_pid: self.last_async_pid,
+            dir_stack: self.dir_stack.clone(),
+            traps: BTreeMap::new(),
+            profile: None,
+            profile_sink: None,
         })
     }
 
+    /// Takes a snapshot of the context's aliases, variables, functions, and
+    /// arguments.
+    ///
+    /// The host, built-ins, filters, and file descriptors are not included.
+    /// Snapshots share their data with the context until it is modified.
+    pub fn snapshot(&self) -> ContextSnapshot {
+        ContextSnapshot {
+            aliases: self.aliases.clone(),
+            scopes: self.scopes.iter().map(Scope::snapshot).collect(),
+        }
+    }
+
+    /// Restores the context's aliases, variables, functions, and arguments
+    /// from a snapshot.
+    ///
+    /// Scopes that have been pushed since the snapshot was taken are dropped,
+    /// and scopes that have been popped are recreated without any file
+    /// descriptors.
+    pub fn restore(&mut self, snapshot: ContextSnapshot) {
+        self.aliases = snapshot.aliases;
+        self.scopes.truncate(snapshot.scopes.len());
+
+        for (index, scope_snapshot) in snapshot.scopes.into_iter().enumerate() {
+            match self.scopes.get_mut(index) {
+                Some(scope) => scope.restore(scope_snapshot),
+                None => self.scopes.push(Scope::from(scope_snapshot)),
+            }
+        }
+    }
+
     /// Returns the name of the current scope.
     pub fn name(&self) -> &str {
         self.scopes
@@ -64,9 +146,18 @@ pub fn with_scopes(scopes: Vec<Scope>) -> Self {
         Self {
             aliases: HashMap::default(),
             host: Arc::new(parking_lot::Mutex::new(StdHost::default())),
+            history: Arc::new(parking_lot::Mutex::new(MemoryHistory::default())),
+            path_cache: Arc::default(),
             scopes,
             builtins: HashMap::new(),
             filters: HashMap::new(),
+            dynamic_vars: DynamicVars::standard(),
+            options: Options::default(),
+            last_async_pid: None,
+            dir_stack: Vec::new(),
+            traps: BTreeMap::new(),
+            profile: None,
+            profile_sink: None,
         }
     }
 
@@ -77,11 +168,36 @@ pub fn push_scope(&mut self, scope: Scope) {
 
     /// Removes and returns the innermost scope in the context.
     ///
-    /// # Panics
+    /// Returns `None` if there are no scopes within the context.
+    pub fn pop_scope(&mut self) -> Option<Scope> {
+        self.scopes.pop()
+    }
+
+    /// Calls a function with a scope as the innermost scope of the context.
     ///
-    /// Panics if there are no scopes within the context.
-    pub fn pop_scope(&mut self) -> Scope {
-        self.scopes.remove(self.scopes.len() - 1)
+    /// The scope, along with any scope that the function leaves behind, is
+    /// removed once the function returns. This holds even if the function
+    /// returns early due to an error.
+    pub fn scoped<T>(&mut self, scope: Scope, func: impl FnOnce(&mut Context) -> T) -> T {
+        let depth = self.scopes.len();
+        self.push_scope(scope);
+        let result = func(self);
+        self.scopes.truncate(depth);
+        result
+    }
+
+    /// Returns the number of scopes within the context.
+    pub fn scope_depth(&self) -> usize {
+        self.scopes.len()
+    }
+
+    /// Returns the names of all scopes within the context, from the outermost
+    /// to the innermost scope.
+    pub fn scope_names(&self) -> Vec<&str> {
+        self.scopes
+            .iter()
+            .map(|scope| scope.name.as_str())
+            .collect()
     }
 
     /// Returns the value of a variable within the current scope.
@@ -97,6 +213,20 @@ pub fn get_var<'a>(&'a self, name: &str) -> Option<&'a Value> {
         Some(value)
     }
 
+    /// Returns the value of a variable within the current scope, falling back
+    /// to the value of a dynamic variable with the same name.
+    pub fn get_var_value(&self, name: &str) -> Option<Value> {
+        match self.get_var(name) {
+            Some(value) => Some(value.clone()),
+            None => self.dynamic_vars.get(name),
+        }
+    }
+
+    /// Returns `true` if a variable is set within the current scope.
+    pub fn has_var(&self, name: &str) -> bool {
+        self.get_var(name).is_some()
+    }
+
     /// Returns all variable names within the current scope.
     pub fn get_var_names(&self) -> HashSet<String> {
         let mut variables = HashSet::new();
@@ -110,31 +240,46 @@ pub fn get_var_names(&self) -> HashSet<String> {
 
     /// Sets the value of a variable within the current scope.
     ///
-    /// Parent scopes are not modified.
-    pub fn set_var(&mut self, name: String, value: Value) -> Option<Value> {
-        let Some(scope) = self.scopes.last_mut() else {
-            return None;
-        };
+    /// Parent scopes are not modified. Assignments to assignable dynamic
+    /// variables, such as `SECONDS`, are passed to the dynamic variable instead.
+    ///
+    /// Read-only variables cannot be reassigned.
+    pub fn set_var(&mut self, name: String, value: Value) -> Result<(), String> {
+        if self.is_var_readonly(&name) {
+            return Err(format!("readonly variable: {name}"));
+        }
+
+        if self.get_var(&name).is_none() && self.dynamic_vars.assign(&name, &value) {
+            return Ok(());
+        }
 
-        scope.vars.insert(name, Some(value)).flatten()
+        if let Some(scope) = self.scopes.last_mut() {
+            Arc::make_mut(&mut scope.vars).insert(name, Some(value));
+        }
+        Ok(())
     }
 
-    /// Removes the value of a variable within the current scope. Returns the
-    /// removed value.
+    /// Removes the value of a variable within the current scope.
     ///
-    /// Parent scopes are not modified.
-    pub fn unset_var(&mut self, name: &str) {
+    /// Parent scopes are not modified. Read-only variables cannot be removed.
+    pub fn unset_var(&mut self, name: &str) -> Result<(), String> {
+        if self.is_var_readonly(name) {
+            return Err(format!("readonly variable: {name}"));
+        }
+
         let Some(scope) = self.scopes.last_mut() else {
-            return;
+            return Ok(());
         };
 
         // Remove the function if it is defined in the current scope.
-        if scope.vars.remove(name).is_some() {
-            return;
+        let vars = Arc::make_mut(&mut scope.vars);
+        if vars.remove(name).is_some() {
+            return Ok(());
         }
 
         // Shadow the function if declared in a parent scope.
-        scope.vars.insert(name.to_owned(), None);
+        vars.insert(name.to_owned(), None);
+        Ok(())
     }
 
     /// Exports a variable from the shell's environment, causing the variable to be
@@ -148,29 +293,62 @@ pub fn export_var(&mut self, name: String) -> Result<(), String> {
             _ => (),
         }
 
-        self.scopes
-            .last_mut()
-            .expect("scope exists") // A scope should always exist here.
-            .exported_keys
-            .insert(name);
+        let scope = self.scopes.last_mut().expect("scope exists"); // A scope should always exist here.
+        Arc::make_mut(&mut scope.exported_keys).insert(name);
 
         Ok(())
     }
 
     /// Returns a collection with references to all exported variables within the current scope.
+    ///
+    /// Exported variables that have since been unset, or reassigned to lists, are excluded.
     pub fn exported_vars(&self) -> HashMap<&str, &str> {
         let keys = self
             .scopes
             .iter()
             .flat_map(|scope| scope.exported_keys.iter());
 
-        keys.map(|key| {
-            (
-                key.as_str(),
-                word_var(self, key).expect("only word variables should be exportable"),
-            )
-        })
-        .collect()
+        keys.filter_map(|key| Some((key.as_str(), word_var(self, key)?)))
+            .collect()
+    }
+
+    /// Marks a variable as read-only, preventing it from being reassigned.
+    ///
+    /// The variable name must be known to the shell. Dynamic variables, such as
+    /// `SECONDS`, can also be marked as read-only.
+    ///
+    /// The variable is marked within the scope that defines it, so that it
+    /// remains read-only once the current scope is exited. Dynamic variables are
+    /// marked within the outermost scope.
+    pub fn make_var_readonly(&mut self, name: String) -> Result<(), String> {
+        if self.get_var_value(&name).is_none() {
+            return Err(format!("unknown variable: {name}"));
+        }
+
+        let owner = self
+            .scopes
+            .iter()
+            .rposition(|scope| matches!(scope.vars.get(&name), Some(Some(_))))
+            .unwrap_or(0);
+        let scope = &mut self.scopes[owner]; // A scope should always exist here.
+        Arc::make_mut(&mut scope.readonly_keys).insert(name);
+
+        Ok(())
+    }
+
+    /// Returns `true` if a variable has been marked as read-only in any scope.
+    pub fn is_var_readonly(&self, name: &str) -> bool {
+        self.scopes
+            .iter()
+            .any(|scope| scope.readonly_keys.contains(name))
+    }
+
+    /// Returns the names of all read-only variables within the current scope.
+    pub fn readonly_vars(&self) -> HashSet<&str> {
+        self.scopes
+            .iter()
+            .flat_map(|scope| scope.readonly_keys.iter().map(String::as_str))
+            .collect()
     }
 
     /// Returns a registered function with a specific name within the current scope.
@@ -186,16 +364,29 @@ pub fn get_function<'a>(&'a self, name: &str) -> Option<&'a Function> {
         Some(function)
     }
 
-    /// Returns all registered function names within the current scope.
-    pub fn get_function_names(&self) -> HashSet<String> {
-        let scopes = self.scopes.iter();
-        let mut functions = HashSet::new();
+    /// Returns `true` if a function is registered within the current scope.
+    pub fn has_function(&self, name: &str) -> bool {
+        self.get_function(name).is_some()
+    }
 
-        for scope in scopes {
-            functions.extend(scope.functions.keys().cloned());
-        }
+    /// Returns the names of all functions that are registered within the
+    /// current scope, in alphabetical order.
+    ///
+    /// Functions in inner scopes shadow those in outer scopes, so functions
+    /// that have been unregistered in an inner scope are excluded.
+    pub fn function_names(&self) -> Vec<&str> {
+        let mut seen = HashSet::new();
+        let mut names: Vec<&str> = self
+            .scopes
+            .iter()
+            .rev()
+            .flat_map(|scope| scope.functions.iter())
+            .filter(|(name, _)| seen.insert(name.as_str()))
+            .filter_map(|(name, function)| function.as_ref().map(|_| name.as_str()))
+            .collect();
 
-        functions
+        names.sort_unstable();
+        names
     }
 
     /// Registers a function within the current scope.
@@ -205,7 +396,7 @@ pub fn register_function(&mut self, function: Function) {
         };
 
         let name = function.name.clone();
-        scope.functions.insert(name, Some(function));
+        Arc::make_mut(&mut scope.functions).insert(name, Some(function));
     }
 
     /// Unregisters a function within the current scope.
@@ -215,12 +406,13 @@ pub fn unregister_function(&mut self, name: &str) {
         };
 
         // Remove the function if it is defined in the current scope.
-        if scope.functions.remove(name).is_some() {
+        let functions = Arc::make_mut(&mut scope.functions);
+        if functions.remove(name).is_some() {
             return;
         }
 
         // Shadow the function if declared in a parent scope.
-        scope.functions.insert(name.to_owned(), None);
+        functions.insert(name.to_owned(), None);
     }
 
     /// Returns a built-in command matching a name.
@@ -233,6 +425,13 @@ pub fn register_builtin(&mut self, builtin: Box<dyn Command>) {
         self.builtins.insert(builtin.name().to_owned(), builtin);
     }
 
+    /// Replaces all built-in commands in the context with the commands in a
+    /// registry.
+    pub fn with_builtins_from(mut self, registry: BuiltinRegistry) -> Self {
+        self.builtins = HashMap::from(registry);
+        self
+    }
+
     /// Registers a temporary file within the current scope.
     pub fn register_temporary_file(&mut self, path: PathBuf) {
         if let Some(scope) = self.scopes.last_mut() {
@@ -246,6 +445,29 @@ pub fn replace_args(&mut self, args: Option<Vec<String>>) -> Option<Vec<String>>
         std::mem::replace(&mut scope.args, args)
     }
 
+    /// Removes the first `n` positional arguments within the current scope.
+    ///
+    /// The first argument, containing the name of the current script or
+    /// function, is never removed. Returns `false`, without removing any
+    /// arguments, if there are fewer than `n` positional arguments.
+    pub fn shift_args(&mut self, n: usize) -> bool {
+        let Some(args) = self
+            .scopes
+            .iter_mut()
+            .rev()
+            .find_map(|scope| scope.args.as_mut())
+        else {
+            return n == 0;
+        };
+
+        if args.len().saturating_sub(1) < n {
+            return false;
+        }
+
+        args.drain(1..=n);
+        true
+    }
+
     /// Returns a slice containing all positional arguments within the current scope.
     pub fn args(&self) -> &[String] {
         self.scopes
@@ -281,6 +503,22 @@ pub fn set_file_descriptor(&mut self, index: usize, file_descriptor: FileDescrip
         }
     }
 
+    /// Replaces a file descriptor within the current scope and returns its old
+    /// value. The file descriptor is removed from the current scope if `None`.
+    ///
+    /// Parent scopes are not modified.
+    pub fn replace_file_descriptor(
+        &mut self,
+        index: usize,
+        file_descriptor: Option<FileDescriptor>,
+    ) -> Option<FileDescriptor> {
+        let scope = self.scopes.last_mut()?;
+        match file_descriptor {
+            Some(file_descriptor) => scope.file_descriptors.insert(index, file_descriptor),
+            None => scope.file_descriptors.remove(&index),
+        }
+    }
+
     pub fn input(&mut self, index: usize) -> Option<Result<Stdio, FileDescriptorError>> {
         for scope in self.scopes.iter_mut().rev() {
             if let Some(file_descriptor) = scope.file_descriptors.get_mut(&index) {
@@ -348,6 +586,8 @@ fn default() -> Self {
         Self {
             aliases: Default::default(),
             host: Arc::new(parking_lot::Mutex::new(StdHost::default())),
+            history: Arc::new(parking_lot::Mutex::new(MemoryHistory::default())),
+            path_cache: Arc::default(),
             scopes: vec![Scope::new(
                 "global".to_owned(),
                 Some(Vec::default()),
@@ -357,6 +597,13 @@ fn default() -> Self {
             )],
             builtins: Default::default(),
             filters: Default::default(),
+            dynamic_vars: DynamicVars::standard(),
+            options: Default::default(),
+            last_async_pid: None,
+            dir_stack: Default::default(),
+            traps: Default::default(),
+            profile: None,
+            profile_sink: None,
         }
     }
 }
@@ -374,15 +621,19 @@ pub struct Scope {
 
     /// A hash map containing variables that have been registered within this scope. More variables
     /// can be available through the [`Context`] itself.
-    vars: HashMap<String, Option<Value>>,
+    vars: Arc<HashMap<String, Option<Value>>>,
 
     /// A hash map containing functions that have been registered within this scope. More functions
     /// can be available through the [`Context`] itself.
-    functions: HashMap<String, Option<Function>>,
+    functions: Arc<HashMap<String, Option<Function>>>,
 
     /// A hash set containing the names of all variables that this scope exports. More variables
     /// can be available through the [`Context`] itself.
-    exported_keys: HashSet<String>,
+    exported_keys: Arc<HashSet<String>>,
+
+    /// A hash set containing the names of all variables that have been marked as read-only within
+    /// this scope. More variables can be read-only through the [`Context`] itself.
+    readonly_keys: Arc<HashSet<String>>,
 
     /// The exit code reported by the shell.
     last_exit: i32,
@@ -406,9 +657,10 @@ pub fn new(
         Self {
             name,
             args,
-            vars,
-            functions,
-            exported_keys,
+            vars: Arc::new(vars),
+            functions: Arc::new(functions),
+            exported_keys: Arc::new(exported_keys),
+            readonly_keys: Arc::default(),
             last_exit: 0,
             file_descriptors: Default::default(),
             temporary_files: Vec::new(),
@@ -428,6 +680,7 @@ pub fn try_clone(&self) -> std::io::Result<Self> {
             vars: self.vars.clone(),
             functions: self.functions.clone(),
             exported_keys: self.exported_keys.clone(),
+            readonly_keys: self.readonly_keys.clone(),
             last_exit: self.last_exit,
             file_descriptors,
             temporary_files: self.temporary_files.clone(),
@@ -435,6 +688,48 @@ pub fn try_clone(&self) -> std::io::Result<Self> {
     }
 }
 
+impl Scope {
+    /// Takes a snapshot of the scope, excluding file descriptors and temporary files.
+    fn snapshot(&self) -> ScopeSnapshot {
+        ScopeSnapshot {
+            name: self.name.clone(),
+            args: self.args.clone(),
+            vars: Arc::clone(&self.vars),
+            functions: Arc::clone(&self.functions),
+            exported_keys: Arc::clone(&self.exported_keys),
+            readonly_keys: Arc::clone(&self.readonly_keys),
+            last_exit: self.last_exit,
+        }
+    }
+
+    /// Restores the scope from a snapshot, keeping its file descriptors and temporary files.
+    fn restore(&mut self, snapshot: ScopeSnapshot) {
+        self.name = snapshot.name;
+        self.args = snapshot.args;
+        self.vars = snapshot.vars;
+        self.functions = snapshot.functions;
+        self.exported_keys = snapshot.exported_keys;
+        self.readonly_keys = snapshot.readonly_keys;
+        self.last_exit = snapshot.last_exit;
+    }
+}
+
+impl From<ScopeSnapshot> for Scope {
+    fn from(snapshot: ScopeSnapshot) -> Self {
+        Self {
+            name: snapshot.name,
+            args: snapshot.args,
+            vars: snapshot.vars,
+            functions: snapshot.functions,
+            exported_keys: snapshot.exported_keys,
+            readonly_keys: snapshot.readonly_keys,
+            last_exit: snapshot.last_exit,
+            file_descriptors: Default::default(),
+            temporary_files: Vec::new(),
+        }
+    }
+}
+
 impl Drop for Scope {
     fn drop(&mut self) {
         // Remove all temporary files registered within the scope.
@@ -446,6 +741,30 @@ fn drop(&mut self) {
     }
 }
 
+/// A snapshot of the state of a [`Context`].
+///
+/// Created by [`Context::snapshot`] and restored by [`Context::restore`].
+pub struct ContextSnapshot {
+    /// Registered aliases keyed by their name.
+    aliases: HashMap<String, String>,
+
+    /// Snapshots of the context's scopes in order of increasing specificity.
+    scopes: Vec<ScopeSnapshot>,
+}
+
+/// A snapshot of the state of a [`Scope`].
+///
+/// Maps and sets are shared with the scope until either of them is modified.
+struct ScopeSnapshot {
+    name: String,
+    args: Option<Vec<String>>,
+    vars: Arc<HashMap<String, Option<Value>>>,
+    functions: Arc<HashMap<String, Option<Function>>>,
+    exported_keys: Arc<HashSet<String>>,
+    readonly_keys: Arc<HashSet<String>>,
+    last_exit: i32,
+}
+
 /// A single value.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Value {
@@ -470,12 +789,13 @@ fn get_var() {
             Scope {
                 name: "outer".to_owned(),
                 args: None,
-                vars: HashMap::from([
+                vars: Arc::new(HashMap::from([
                     ("outer".to_owned(), Some(Value::Word("outer".to_owned()))),
                     ("both".to_owned(), Some(Value::Word("outer".to_owned()))),
-                ]),
-                functions: HashMap::default(),
-                exported_keys: HashSet::default(),
+                ])),
+                functions: Arc::default(),
+                exported_keys: Arc::default(),
+                readonly_keys: Arc::default(),
                 last_exit: 0,
                 file_descriptors: HashMap::default(),
                 temporary_files: vec![],
@@ -483,12 +803,13 @@ fn get_var() {
             Scope {
                 name: "inner".to_owned(),
                 args: None,
-                vars: HashMap::from([
+                vars: Arc::new(HashMap::from([
                     ("inner".to_owned(), Some(Value::Word("inner".to_owned()))),
                     ("both".to_owned(), Some(Value::Word("inner".to_owned()))),
-                ]),
-                functions: HashMap::default(),
-                exported_keys: HashSet::default(),
+                ])),
+                functions: Arc::default(),
+                exported_keys: Arc::default(),
+                readonly_keys: Arc::default(),
                 last_exit: 0,
                 file_descriptors: HashMap::default(),
                 temporary_files: vec![],
@@ -501,6 +822,35 @@ fn get_var() {
         assert_eq!(context.get_var("both"), Some(&Value::Word("inner".into())));
     }
 
+    #[test]
+    fn it_shifts_its_args() {
+        let mut context = Context::with_scopes(vec![
+            Scope::new(
+                "outer".into(),
+                Some(vec!["script".into(), "a".into(), "b".into(), "c".into()]),
+                HashMap::default(),
+                HashMap::default(),
+                HashSet::default(),
+            ),
+            Scope::new(
+                "inner".into(),
+                None,
+                HashMap::default(),
+                HashMap::default(),
+                HashSet::default(),
+            ),
+        ]);
+
+        assert!(context.shift_args(1));
+        assert_eq!(context.args(), &["script", "b", "c"]);
+        assert!(!context.shift_args(3));
+        assert_eq!(context.args(), &["script", "b", "c"]);
+        assert!(context.shift_args(2));
+        assert_eq!(context.args(), &["script"]);
+        assert!(context.shift_args(0));
+        assert!(!context.shift_args(1));
+    }
+
     #[test]
     fn it_replaces_its_args() {
         let new_args = vec!["replaced".to_owned(), "args".to_owned()];
@@ -539,6 +889,49 @@ fn it_deletes_temporary_files_when_their_scope_is_dropped() {
         );
     }
 
+    #[test]
+    fn it_pops_scopes_without_underflowing() {
+        let mut context = Context::with_scopes(vec![Scope::new(
+            "scope".into(),
+            None,
+            HashMap::default(),
+            HashMap::default(),
+            HashSet::default(),
+        )]);
+
+        assert_eq!(
+            context.pop_scope().map(|scope| scope.name.clone()),
+            Some("scope".into())
+        );
+        assert!(context.pop_scope().is_none());
+        assert_eq!(context.scope_depth(), 0);
+    }
+
+    #[test]
+    fn it_removes_scoped_scopes() {
+        let mut context = Context::default();
+        let depth = context.scope_depth();
+        let new_scope = |name: &str| {
+            Scope::new(
+                name.into(),
+                None,
+                HashMap::default(),
+                HashMap::default(),
+                HashSet::default(),
+            )
+        };
+
+        let result: Result<(), String> = context.scoped(new_scope("inner"), |context| {
+            assert_eq!(context.name(), "inner");
+            context.push_scope(new_scope("leaked"));
+            assert_eq!(context.scope_names()[depth..], ["inner", "leaked"]);
+            Err("failed".into())
+        });
+
+        assert_eq!(result, Err("failed".into()));
+        assert_eq!(context.scope_depth(), depth);
+    }
+
     #[test]
     fn it_unregisters_functions() {
         let outer_fn = Function {
@@ -571,11 +964,15 @@ fn it_unregisters_functions() {
             ),
         ]);
 
+        assert!(context.has_function("outer"));
+        assert!(context.has_function("inner"));
+
         context.unregister_function("outer");
         context.unregister_function("inner");
 
         assert_eq!(context.get_function("outer"), None);
         assert_eq!(context.get_function("inner"), None);
+        assert!(!context.has_function("outer"));
 
         context.pop_scope();
         assert_eq!(
@@ -585,6 +982,46 @@ fn it_unregisters_functions() {
         );
     }
 
+    #[test]
+    fn it_lists_function_names() {
+        let function = |name: &str| Function {
+            name: name.into(),
+            args: Vec::default(),
+            list_arg: None,
+            body: Block::default(),
+        };
+
+        let mut context = Context::with_scopes(vec![
+            Scope::new(
+                "outer".into(),
+                None,
+                HashMap::default(),
+                HashMap::from([
+                    ("shared".to_string(), Some(function("shared"))),
+                    ("outer".to_string(), Some(function("outer"))),
+                    ("hidden".to_string(), Some(function("hidden"))),
+                ]),
+                HashSet::default(),
+            ),
+            Scope::new(
+                "inner".into(),
+                None,
+                HashMap::default(),
+                HashMap::from([
+                    ("shared".to_string(), Some(function("shared"))),
+                    ("inner".to_string(), Some(function("inner"))),
+                ]),
+                HashSet::default(),
+            ),
+        ]);
+        context.unregister_function("hidden");
+
+        assert_eq!(context.function_names(), ["inner", "outer", "shared"]);
+
+        context.pop_scope();
+        assert_eq!(context.function_names(), ["hidden", "outer", "shared"]);
+    }
+
     #[test]
     fn it_unsets_vars() {
         let mut context = Context::with_scopes(vec![
@@ -604,11 +1041,15 @@ fn it_unsets_vars() {
             ),
         ]);
 
-        context.unset_var("outer");
-        context.unset_var("inner");
+        assert!(context.has_var("outer"));
+        assert!(context.has_var("inner"));
+
+        context.unset_var("outer").unwrap();
+        context.unset_var("inner").unwrap();
 
         assert_eq!(context.get_var("outer"), None);
         assert_eq!(context.get_var("inner"), None);
+        assert!(!context.has_var("outer"));
 
         context.pop_scope();
         assert_eq!(
@@ -617,4 +1058,215 @@ fn it_unsets_vars() {
             "the var should not be dropped from the outer scope"
         );
     }
+
+    #[test]
+    fn it_makes_vars_readonly_in_nested_scopes() {
+        let mut context = Context::with_scopes(vec![Scope::new(
+            "outer".into(),
+            None,
+            HashMap::from([("var".to_string(), Some(Value::Word("value".into())))]),
+            HashMap::default(),
+            HashSet::default(),
+        )]);
+
+        assert!(context.make_var_readonly("unknown".into()).is_err());
+        assert_eq!(context.make_var_readonly("var".into()), Ok(()));
+        context.push_scope(Scope::new(
+            "inner".into(),
+            None,
+            HashMap::default(),
+            HashMap::default(),
+            HashSet::default(),
+        ));
+
+        assert!(context.is_var_readonly("var"));
+        assert_eq!(context.readonly_vars(), HashSet::from(["var"]));
+
+        context.pop_scope();
+        assert!(context.is_var_readonly("var"));
+    }
+
+    #[test]
+    fn it_keeps_vars_readonly_after_exiting_the_marking_scope() {
+        let mut context = Context::with_scopes(vec![Scope::new(
+            "outer".into(),
+            None,
+            HashMap::from([("var".to_string(), Some(Value::Word("value".into())))]),
+            HashMap::default(),
+            HashSet::default(),
+        )]);
+        context.push_scope(Scope::new(
+            "inner".into(),
+            None,
+            HashMap::default(),
+            HashMap::default(),
+            HashSet::default(),
+        ));
+
+        context.make_var_readonly("var".into()).unwrap();
+        context.make_var_readonly("SECONDS".into()).unwrap();
+        context.pop_scope();
+
+        assert_eq!(
+            context.set_var("var".into(), Value::Word("new".into())),
+            Err("readonly variable: var".into())
+        );
+        assert!(context.is_var_readonly("SECONDS"));
+    }
+
+    #[test]
+    fn it_does_not_modify_readonly_vars() {
+        let mut context = Context::with_scopes(vec![Scope::new(
+            "outer".into(),
+            None,
+            HashMap::from([("var".to_string(), Some(Value::Word("value".into())))]),
+            HashMap::default(),
+            HashSet::default(),
+        )]);
+        context.make_var_readonly("var".into()).unwrap();
+        context.push_scope(Scope::new(
+            "inner".into(),
+            None,
+            HashMap::default(),
+            HashMap::default(),
+            HashSet::default(),
+        ));
+
+        let error = Err("readonly variable: var".into());
+        assert_eq!(
+            context.set_var("var".into(), Value::Word("new".into())),
+            error
+        );
+        assert_eq!(context.unset_var("var"), error);
+        assert_eq!(context.get_var("var"), Some(&Value::Word("value".into())));
+    }
+
+    #[test]
+    fn it_does_not_share_seconds_with_clones() {
+        let mut context = Context::default();
+        context
+            .set_var("SECONDS".into(), Value::Word("1000".into()))
+            .unwrap();
+
+        let mut clone = context.try_clone().unwrap();
+        clone
+            .set_var("SECONDS".into(), Value::Word("5".into()))
+            .unwrap();
+        assert_eq!(
+            clone.get_var_value("SECONDS"),
+            Some(Value::Word("5".into()))
+        );
+        assert_eq!(
+            context.get_var_value("SECONDS"),
+            Some(Value::Word("1000".into()))
+        );
+
+        context.make_var_readonly("SECONDS".into()).unwrap();
+        assert_eq!(
+            context.set_var("SECONDS".into(), Value::Word("0".into())),
+            Err("readonly variable: SECONDS".into())
+        );
+    }
+
+    #[test]
+    fn it_restores_snapshots() {
+        let function = Function {
+            name: "function".into(),
+            args: Vec::default(),
+            list_arg: None,
+            body: Block::default(),
+        };
+        let mut context = Context::with_scopes(vec![Scope::new(
+            "outer".into(),
+            Some(vec!["outer".into()]),
+            HashMap::from([("var".into(), Some(Value::Word("outer".into())))]),
+            HashMap::default(),
+            HashSet::default(),
+        )]);
+        context.aliases.insert("ll".into(), "ls -l".into());
+        context.push_scope(Scope::new(
+            "inner".into(),
+            Some(vec!["inner".into()]),
+            HashMap::default(),
+            HashMap::default(),
+            HashSet::default(),
+        ));
+        context
+            .set_var("inner".into(), Value::Word("inner".into()))
+            .unwrap();
+
+        let snapshot = context.snapshot();
+
+        context
+            .set_var("var".into(), Value::Word("changed".into()))
+            .unwrap();
+        context.unset_var("inner").unwrap();
+        context.export_var("var".into()).unwrap();
+        context.make_var_readonly("var".into()).unwrap();
+        context.register_function(function);
+        context.replace_args(Some(vec!["changed".into()]));
+        context.register_exit(1);
+        context.aliases.clear();
+        context.pop_scope();
+        context.push_scope(Scope::new(
+            "extra".into(),
+            None,
+            HashMap::default(),
+            HashMap::default(),
+            HashSet::default(),
+        ));
+        context.push_scope(Scope::new(
+            "extra".into(),
+            None,
+            HashMap::default(),
+            HashMap::default(),
+            HashSet::default(),
+        ));
+
+        context.restore(snapshot);
+
+        assert_eq!(context.scopes.len(), 2);
+        assert_eq!(context.name(), "inner");
+        assert_eq!(context.args(), &["inner".to_string()]);
+        assert_eq!(context.last_exit(), 0);
+        assert_eq!(context.get_var("var"), Some(&Value::Word("outer".into())));
+        assert_eq!(context.get_var("inner"), Some(&Value::Word("inner".into())));
+        assert!(!context.is_var_readonly("var"));
+        assert!(!context.exported_vars().contains_key("var"));
+        assert!(context.get_function("function").is_none());
+        assert_eq!(context.aliases.get("ll"), Some(&"ls -l".to_string()));
+
+        context.pop_scope();
+        assert_eq!(context.name(), "outer");
+        assert_eq!(context.args(), &["outer".to_string()]);
+    }
+
+    #[test]
+    fn it_shares_unmodified_state_with_snapshots() {
+        let mut context = Context::with_scopes(vec![Scope::new(
+            "scope".into(),
+            None,
+            HashMap::from([("var".into(), Some(Value::Word("value".into())))]),
+            HashMap::default(),
+            HashSet::default(),
+        )]);
+
+        let snapshot = context.snapshot();
+        assert!(Arc::ptr_eq(
+            &snapshot.scopes[0].vars,
+            &context.scopes[0].vars
+        ));
+
+        context
+            .set_var("var".into(), Value::Word("changed".into()))
+            .unwrap();
+        assert!(!Arc::ptr_eq(
+            &snapshot.scopes[0].vars,
+            &context.scopes[0].vars
+        ));
+        assert_eq!(
+            snapshot.scopes[0].vars.get("var"),
+            Some(&Some(Value::Word("value".into())))
+        );
+    }
 }
@@ -0,0 +1,260 @@
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+use rand::Rng;
+
+use super::context::Value;
+
+/// A function providing the current value of a dynamic variable.
+pub type DynamicVarGetter = Arc<dyn Fn() -> Value + Send + Sync>;
+
+/// A function handling assignments to a dynamic variable.
+pub type DynamicVarSetter = Arc<dyn Fn(&Value) + Send + Sync>;
+
+/// A function creating an independent copy of a stateful dynamic variable.
+type DynamicVarForker = Arc<dyn Fn() -> DynamicVar + Send + Sync>;
+
+/// A variable whose value is computed each time it is read.
+#[derive(Clone)]
+struct DynamicVar {
+    /// Provides the variable's value.
+    get: DynamicVarGetter,
+
+    /// Handles assignments to the variable, if it is assignable.
+    set: Option<DynamicVarSetter>,
+
+    /// Copies the variable's state, if it has any.
+    fork: Option<DynamicVarForker>,
+}
+
+/// A registry of dynamic variables keyed by their name.
+///
+/// Providers are shared with cloned registries. Use [`DynamicVars::fork`] to
+/// obtain a registry whose assignments do not affect the original.
+#[derive(Clone, Default)]
+pub struct DynamicVars {
+    vars: HashMap<String, DynamicVar>,
+}
+
+impl DynamicVars {
+    /// Constructs a registry containing the shell's standard dynamic variables:
+    ///
+    /// - `RANDOM`: a random integer between 0 and 32767.
+    /// - `SECONDS`: the number of seconds since the registry was constructed.
+    ///   Assigning an integer to it makes it count from that integer instead.
+    /// - `EPOCHSECONDS`: the number of seconds since the Unix epoch.
+    pub fn standard() -> Self {
+        let mut vars = Self::default();
+
+        vars.register("RANDOM", || {
+            Value::Word(rand::thread_rng().gen_range(0..=32767).to_string())
+        });
+
+        vars.vars
+            .insert("SECONDS".into(), seconds_var(Instant::now(), 0));
+
+        vars.register("EPOCHSECONDS", || {
+            let epoch = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |duration| duration.as_secs());
+            Value::Word(epoch.to_string())
+        });
+
+        vars
+    }
+
+    /// Registers a read-only dynamic variable, replacing any previous variable
+    /// with the same name.
+    ///
+    /// Assignments to the variable create a regular variable that shadows it.
+    pub fn register<G>(&mut self, name: impl Into<String>, get: G)
+    where
+        G: Fn() -> Value + Send + Sync + 'static,
+    {
+        let var = DynamicVar {
+            get: Arc::new(get),
+            set: None,
+            fork: None,
+        };
+        self.vars.insert(name.into(), var);
+    }
+
+    /// Registers an assignable dynamic variable, replacing any previous
+    /// variable with the same name.
+    ///
+    /// Assignments to the variable are passed to `set` instead of creating a
+    /// regular variable.
+    pub fn register_assignable<G, S>(&mut self, name: impl Into<String>, get: G, set: S)
+    where
+        G: Fn() -> Value + Send + Sync + 'static,
+        S: Fn(&Value) + Send + Sync + 'static,
+    {
+        let var = DynamicVar {
+            get: Arc::new(get),
+            set: Some(Arc::new(set)),
+            fork: None,
+        };
+        self.vars.insert(name.into(), var);
+    }
+
+    /// Removes a dynamic variable.
+    pub fn unregister(&mut self, name: &str) {
+        self.vars.remove(name);
+    }
+
+    /// Returns the current value of a dynamic variable.
+    pub fn get(&self, name: &str) -> Option<Value> {
+        self.vars.get(name).map(|var| (var.get)())
+    }
+
+    /// Assigns a value to a dynamic variable.
+    ///
+    /// Returns `false` if there is no assignable dynamic variable with the
+    /// given name.
+    pub fn assign(&self, name: &str, value: &Value) -> bool {
+        match self.vars.get(name).and_then(|var| var.set.as_ref()) {
+            Some(set) => {
+                set(value);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the names of all dynamic variables.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.vars.keys().map(String::as_str)
+    }
+
+    /// Returns a copy of the registry where stateful variables, such as
+    /// `SECONDS`, have their own state.
+    ///
+    /// Assignments to the copy do not affect the original registry, and vice
+    /// versa.
+    pub fn fork(&self) -> Self {
+        let vars = self
+            .vars
+            .iter()
+            .map(|(name, var)| {
+                let var = var.fork.as_ref().map_or_else(|| var.clone(), |fork| fork());
+                (name.clone(), var)
+            })
+            .collect();
+        Self { vars }
+    }
+}
+
+/// Constructs a `SECONDS` variable counting from `offset` at `start`.
+fn seconds_var(start: Instant, offset: u64) -> DynamicVar {
+    let base = Arc::new(parking_lot::Mutex::new((start, offset)));
+    let get_base = Arc::clone(&base);
+    let fork_base = Arc::clone(&base);
+
+    DynamicVar {
+        get: Arc::new(move || {
+            let (start, offset) = *get_base.lock();
+            Value::Word((offset + start.elapsed().as_secs()).to_string())
+        }),
+        set: Some(Arc::new(move |value| {
+            let offset = match value {
+                Value::Word(word) => word.trim().parse().unwrap_or_default(),
+                Value::List(_) => 0,
+            };
+            *base.lock() = (Instant::now(), offset);
+        })),
+        fork: Some(Arc::new(move || {
+            let (start, offset) = *fork_base.lock();
+            seconds_var(start, offset)
+        })),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{thread::sleep, time::Duration};
+
+    use super::*;
+
+    fn seconds(vars: &DynamicVars) -> u64 {
+        match vars.get("SECONDS") {
+            Some(Value::Word(word)) => word.parse().expect("SECONDS should be numeric"),
+            value => panic!("unexpected SECONDS value: {value:?}"),
+        }
+    }
+
+    #[test]
+    fn it_returns_random_numbers() {
+        let vars = DynamicVars::standard();
+        let numbers: Vec<_> = (0..10).filter_map(|_| vars.get("RANDOM")).collect();
+
+        for number in &numbers {
+            let Value::Word(word) = number else {
+                panic!("RANDOM should be a word");
+            };
+            assert!((0..=32767).contains(&word.parse::<u32>().unwrap()));
+        }
+
+        // Ten identical random numbers are practically impossible.
+        assert!(numbers.iter().any(|number| number != &numbers[0]));
+    }
+
+    #[test]
+    fn it_counts_seconds() {
+        let vars = DynamicVars::standard();
+        let before = seconds(&vars);
+        sleep(Duration::from_millis(1100));
+        assert!(seconds(&vars) > before);
+    }
+
+    #[test]
+    fn it_rebases_seconds_on_assignment() {
+        let vars = DynamicVars::standard();
+        assert!(vars.assign("SECONDS", &Value::Word("100".into())));
+        assert!((100..102).contains(&seconds(&vars)));
+
+        assert!(vars.assign("SECONDS", &Value::Word("invalid".into())));
+        assert!(seconds(&vars) < 2);
+    }
+
+    #[test]
+    fn it_forks_seconds() {
+        let vars = DynamicVars::standard();
+        assert!(vars.assign("SECONDS", &Value::Word("1000".into())));
+
+        let forked = vars.fork();
+        assert!((1000..1002).contains(&seconds(&forked)));
+        assert!(forked.assign("SECONDS", &Value::Word("5".into())));
+        assert!((5..7).contains(&seconds(&forked)));
+        assert!((1000..1002).contains(&seconds(&vars)));
+
+        // Clones share their state.
+        let cloned = vars.clone();
+        assert!(cloned.assign("SECONDS", &Value::Word("10".into())));
+        assert!((10..12).contains(&seconds(&vars)));
+    }
+
+    #[test]
+    fn it_returns_epoch_seconds() {
+        let vars = DynamicVars::standard();
+        let Some(Value::Word(epoch)) = vars.get("EPOCHSECONDS") else {
+            panic!("EPOCHSECONDS should be a word");
+        };
+        assert!(epoch.parse::<u64>().unwrap() > 1_600_000_000);
+    }
+
+    #[test]
+    fn it_registers_custom_providers() {
+        let mut vars = DynamicVars::default();
+        vars.register("ANSWER", || Value::Word("42".into()));
+
+        assert_eq!(vars.get("ANSWER"), Some(Value::Word("42".into())));
+        assert!(!vars.assign("ANSWER", &Value::Word("0".into())));
+        assert_eq!(vars.names().collect::<Vec<_>>(), vec!["ANSWER"]);
+
+        vars.unregister("ANSWER");
+        assert_eq!(vars.get("ANSWER"), None);
+    }
+}
@@ -0,0 +1,67 @@
+/// A shell's command history.
+///
+/// The history is shared between the shell's line editor and its context,
+/// allowing built-in commands to inspect and modify it.
+pub trait History: Send {
+    /// Returns all history entries, ordered from oldest to newest.
+    fn entries(&self) -> Vec<String>;
+
+    /// Removes all history entries.
+    fn clear(&mut self);
+
+    /// Removes a history entry given its index, starting from 0 for the oldest
+    /// entry.
+    ///
+    /// Returns `false` if there is no entry at the index.
+    fn remove(&mut self, index: usize) -> bool;
+}
+
+/// A command history that is only kept in memory.
+#[derive(Default)]
+pub struct MemoryHistory {
+    /// History entries, ordered from oldest to newest.
+    entries: Vec<String>,
+}
+
+impl MemoryHistory {
+    /// Constructs a new history containing some entries.
+    pub fn new(entries: Vec<String>) -> Self {
+        Self { entries }
+    }
+}
+
+impl History for MemoryHistory {
+    fn entries(&self) -> Vec<String> {
+        self.entries.clone()
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    fn remove(&mut self, index: usize) -> bool {
+        if index >= self.entries.len() {
+            return false;
+        }
+
+        self.entries.remove(index);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_removes_entries() {
+        let mut history = MemoryHistory::new(vec!["a".into(), "b".into(), "c".into()]);
+
+        assert!(history.remove(1));
+        assert!(!history.remove(2));
+        assert_eq!(history.entries(), vec!["a", "c"]);
+
+        history.clear();
+        assert!(history.entries().is_empty());
+    }
+}
@@ -1,4 +1,8 @@
-use std::{collections::HashSet, process::Child, thread::JoinHandle};
+use std::{
+    collections::HashSet,
+    process::{Child, Command},
+    thread::JoinHandle,
+};
 
 /// A host is a shell's representation of its current environment.
 ///
@@ -8,14 +12,19 @@ pub trait Host: Send {
     /// Registers a child process in the host.
     ///
     /// The child process should originate from the shell, meaning that the shell
-    /// should have spawned it.
-    fn add_child_process(&mut self, child: Child);
+    /// should have spawned it. The command line that spawned the process is
+    /// used when listing jobs.
+    ///
+    /// Returns the job id of the child process.
+    fn add_child_process(&mut self, child: Child, command: Option<String>) -> usize;
 
     /// Registers a thread in the host.
     ///
     /// The thread should originate from the shell, meaning that the shell should
     /// have spawned it.
-    fn add_thread(&mut self, thread: JoinHandle<i32>);
+    ///
+    /// Returns the job id of the thread.
+    fn add_thread(&mut self, thread: JoinHandle<i32>) -> usize;
 
     /// Kills all registered child processes.
     fn kill_all_processes(&mut self);
@@ -25,5 +34,134 @@ pub trait Host: Send {
 
     /// Return a list of all exited processes that have been spawned by the host,
     /// removing them from the list of tracked child processes.
+    ///
+    /// The processes can still be waited for using [`Host::try_wait_job`] until
+    /// the next time that exited jobs are reported.
     fn take_exited_child_processes(&mut self) -> HashSet<u32>;
+
+    /// Removes a child process from the host, returning ownership of it along
+    /// with its job id.
+    ///
+    /// The most recently added child process is taken if no job id is given.
+    /// Returns `None` if there is no matching child process.
+    fn take_child_process(&mut self, id: Option<usize>) -> Option<(usize, Child)>;
+
+    /// Resumes a stopped child process.
+    ///
+    /// The most recently added child process is resumed if no job id is given.
+    ///
+    /// Returns the job id of the resumed child process.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if there is no matching child process,
+    /// if the process can't be resumed, or if the platform does not support
+    /// resuming processes.
+    fn resume_child_process(&mut self, id: Option<usize>) -> std::io::Result<usize>;
+
+    /// Sends a signal to a child process given its job id.
+    ///
+    /// The child process is removed from the host unless the signal only stops
+    /// or resumes it. Processes are killed forcefully on platforms without
+    /// signals.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if there is no matching child process,
+    /// or if the signal can't be sent.
+    fn kill_child_process(&mut self, id: usize, signal: i32) -> std::io::Result<()>;
+
+    /// Sends a signal to a process given its process id (PID).
+    ///
+    /// Processes that are tracked by the host are handled as in
+    /// [`Host::kill_child_process`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the signal can't be sent, or if the
+    /// platform does not support sending signals to untracked processes.
+    fn kill_process(&mut self, pid: u32, signal: i32) -> std::io::Result<()>;
+
+    /// Returns the job id of a tracked child process given its process id (PID).
+    ///
+    /// Exited child processes are only matched until the next time that exited
+    /// jobs are reported, as their PIDs may be reused.
+    fn job_id(&self, pid: u32) -> Option<usize>;
+
+    /// Checks whether a job has exited without blocking.
+    ///
+    /// Exited jobs are reaped and removed from the host. Jobs that have already
+    /// been reaped, e.g. when listing jobs, are reported as exited with their
+    /// exit code once, until the next time that exited jobs are reported.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if there is no matching job, or if
+    /// the state of the job can't be determined.
+    fn try_wait_job(&mut self, id: usize) -> std::io::Result<JobState>;
+
+    /// Returns all jobs that are tracked by the host, ordered by job id.
+    ///
+    /// Exited jobs are removed from the host once they have been returned, but
+    /// can still be waited for using [`Host::try_wait_job`] until the next time
+    /// that exited jobs are reported.
+    fn jobs(&mut self) -> Vec<Job>;
+
+    /// Replaces the current process with a command.
+    ///
+    /// On platforms that can't replace processes, the command is executed as a
+    /// child process, and the current process exits with its exit code once it
+    /// has exited.
+    ///
+    /// Only returns if the command can't be executed, in which case the error
+    /// is returned.
+    fn exec(&mut self, command: Command) -> std::io::Error;
+
+    /// Returns the platform that the shell is running on.
+    fn platform(&self) -> Platform {
+        Platform {
+            os: std::env::consts::OS,
+            arch: std::env::consts::ARCH,
+        }
+    }
+}
+
+/// A platform that the shell can run on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Platform {
+    /// Operating system, such as `linux` or `windows`.
+    pub os: &'static str,
+
+    /// CPU architecture, such as `x86_64` or `aarch64`.
+    pub arch: &'static str,
+}
+
+/// A child process or thread that has been spawned by the shell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Job {
+    /// Job id.
+    ///
+    /// Job ids are never reused by a host.
+    pub id: usize,
+
+    /// Process id (PID) of the job, if it is a process.
+    pub pid: Option<u32>,
+
+    /// Current state of the job.
+    pub state: JobState,
+
+    /// Command line that started the job, if known.
+    pub command: Option<String>,
+}
+
+/// State of a [`Job`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobState {
+    /// The job is still running.
+    Running,
+
+    /// The job has exited with an optional exit code.
+    ///
+    /// Jobs that are terminated by a signal, or that panic, have no exit code.
+    Exited(Option<i32>),
 }
@@ -1,3 +1,6 @@
 pub(crate) mod context;
+pub(crate) mod dynamic;
+pub(crate) mod history;
 pub(crate) mod host;
+pub(crate) mod options;
 pub(crate) mod std_host;
@@ -0,0 +1,127 @@
+/// Name of the option bundle that enables all options that make scripts stricter.
+pub const STRICT: &str = "strict";
+
+/// Options that change how a shell executes commands.
+///
+/// Options can be toggled individually, or all at once using the `strict` bundle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// Exit when a command fails.
+    pub errexit: bool,
+
+    /// Treat undefined variables as errors rather than as empty words.
+    pub nounset: bool,
+
+    /// Use the last non-zero exit code in a pipeline as the pipeline's exit code.
+    pub pipefail: bool,
+
+    /// Refuse to overwrite existing files using `>` redirects.
+    pub noclobber: bool,
+
+    /// Warn when a variable containing whitespace is passed as a single
+    /// argument, where other shells would split it into multiple arguments.
+    pub warnsplit: bool,
+
+    /// Keep glob patterns, such as `*`, as literal words rather than expanding them.
+    pub noglob: bool,
+}
+
+impl Options {
+    /// Names of all individual options.
+    pub const NAMES: [&'static str; 6] = [
+        "errexit",
+        "nounset",
+        "pipefail",
+        "noclobber",
+        "warnsplit",
+        "noglob",
+    ];
+
+    /// Names of the options that are part of the `strict` bundle.
+    pub const STRICT_NAMES: [&'static str; 5] =
+        ["errexit", "nounset", "pipefail", "noclobber", "warnsplit"];
+
+    /// Returns whether or not an option is enabled.
+    ///
+    /// Returns `None` if there is no option with the name.
+    pub fn get(&self, name: &str) -> Option<bool> {
+        match name {
+            "errexit" => Some(self.errexit),
+            "nounset" => Some(self.nounset),
+            "pipefail" => Some(self.pipefail),
+            "noclobber" => Some(self.noclobber),
+            "warnsplit" => Some(self.warnsplit),
+            "noglob" => Some(self.noglob),
+            _ => None,
+        }
+    }
+
+    /// Enables or disables an option, or all options in the `strict` bundle if
+    /// the name is `strict`.
+    ///
+    /// Returns `false` if there is no option with the name.
+    pub fn set(&mut self, name: &str, enabled: bool) -> bool {
+        let flag = match name {
+            STRICT => {
+                for name in Self::STRICT_NAMES {
+                    self.set(name, enabled);
+                }
+                return true;
+            }
+            "errexit" => &mut self.errexit,
+            "nounset" => &mut self.nounset,
+            "pipefail" => &mut self.pipefail,
+            "noclobber" => &mut self.noclobber,
+            "warnsplit" => &mut self.warnsplit,
+            "noglob" => &mut self.noglob,
+            _ => return false,
+        };
+
+        *flag = enabled;
+        true
+    }
+}
+
+impl Default for Options {
+    /// Returns the default options.
+    ///
+    /// Undefined variables are always treated as errors by default.
+    fn default() -> Self {
+        Self {
+            errexit: false,
+            nounset: true,
+            pipefail: false,
+            noclobber: false,
+            warnsplit: false,
+            noglob: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_toggles_the_strict_bundle() {
+        let mut options = Options::default();
+        assert!(options.set(STRICT, true));
+        assert!(Options::STRICT_NAMES
+            .iter()
+            .all(|name| options.get(name) == Some(true)));
+        assert_eq!(options.get("noglob"), Some(false));
+
+        // Options in the bundle can still be toggled individually.
+        assert!(options.set("errexit", false));
+        assert_eq!(options.get("errexit"), Some(false));
+        assert_eq!(options.get("pipefail"), Some(true));
+    }
+
+    #[test]
+    fn it_rejects_unknown_options() {
+        let mut options = Options::default();
+        assert!(!options.set("unknown", true));
+        assert_eq!(options.get("unknown"), None);
+        assert_eq!(options, Options::default());
+    }
+}
@@ -1,51 +1,472 @@
-use std::{collections::HashSet, process::Child, thread::JoinHandle};
+use std::{
+    collections::HashSet,
+    process::{Child, Command},
+    thread::JoinHandle,
+};
 
-use super::host::Host;
+use crate::signal;
+
+use super::host::{Host, Job, JobState};
+
+/// A child process that has been spawned by the host.
+struct ChildProcess {
+    /// Job id.
+    id: usize,
+
+    /// The running process.
+    child: Child,
+
+    /// Command line that spawned the process.
+    command: Option<String>,
+}
+
+/// A thread that has been spawned by the host.
+struct Thread {
+    /// Job id.
+    id: usize,
+
+    /// Handle to the running thread.
+    handle: JoinHandle<i32>,
+}
+
+/// A job that has exited without being waited for.
+struct ExitedJob {
+    /// Job id.
+    id: usize,
+
+    /// Process id (PID) of the job, if it was a process.
+    pid: Option<u32>,
+
+    /// Exit code of the job, if any.
+    code: Option<i32>,
+}
 
 /// A host wrapping the Rust standard library.
 #[derive(Default)]
 pub struct StdHost {
     /// Child processes that the host has spawned.
-    child_processes: Vec<Child>,
+    child_processes: Vec<ChildProcess>,
 
     /// Threads that the host has spawned.
-    threads: Vec<JoinHandle<i32>>,
+    threads: Vec<Thread>,
+
+    /// Jobs that have been reaped, but not waited for, along with their exit codes.
+    ///
+    /// Only jobs from the most recent report of exited jobs are kept.
+    exited_jobs: Vec<ExitedJob>,
+
+    /// Most recently assigned job id.
+    last_job_id: usize,
+}
+
+impl StdHost {
+    /// Returns a new unique job id.
+    fn next_job_id(&mut self) -> usize {
+        self.last_job_id += 1;
+        self.last_job_id
+    }
+
+    /// Returns the index of a child process given its job id.
+    ///
+    /// The most recently added child process is used if no job id is given.
+    fn child_process_index(&self, id: Option<usize>) -> Option<usize> {
+        match id {
+            Some(id) => self
+                .child_processes
+                .iter()
+                .position(|process| process.id == id),
+            None => self.child_processes.len().checked_sub(1),
+        }
+    }
+}
+
+/// Returns an error indicating that a job does not exist.
+fn no_such_job() -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::NotFound, "no such job")
+}
+
+/// Resumes a stopped process by sending `SIGCONT` to it.
+#[cfg(unix)]
+fn resume_process(pid: u32) -> std::io::Result<()> {
+    signal::send_signal(pid, libc::SIGCONT)
+}
+
+/// Resuming processes is not supported on this platform.
+#[cfg(not(unix))]
+fn resume_process(_pid: u32) -> std::io::Result<()> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "resuming processes is not supported on this platform",
+    ))
+}
+
+/// Sends a signal to a child process.
+#[cfg(unix)]
+fn signal_child(child: &mut Child, signal: i32) -> std::io::Result<()> {
+    signal::send_signal(child.id(), signal)
+}
+
+/// Kills a child process, as signals are not supported on this platform.
+#[cfg(not(unix))]
+fn signal_child(child: &mut Child, _signal: i32) -> std::io::Result<()> {
+    child.kill()
+}
+
+/// Replaces the current process with a command.
+#[cfg(unix)]
+fn replace_process(mut command: Command) -> std::io::Error {
+    use std::os::unix::process::CommandExt;
+    command.exec()
+}
+
+/// Executes a command and exits with its exit code, as replacing the current
+/// process is not supported on this platform.
+#[cfg(not(unix))]
+fn replace_process(mut command: Command) -> std::io::Error {
+    match command.status() {
+        Ok(status) => std::process::exit(status.code().unwrap_or(1)),
+        Err(error) => error,
+    }
 }
 
 impl Host for StdHost {
-    fn add_child_process(&mut self, child: std::process::Child) {
-        self.child_processes.push(child);
+    fn add_child_process(&mut self, child: std::process::Child, command: Option<String>) -> usize {
+        let id = self.next_job_id();
+        self.child_processes
+            .push(ChildProcess { id, child, command });
+        id
     }
 
-    fn add_thread(&mut self, thread: std::thread::JoinHandle<i32>) {
-        self.threads.push(thread);
+    fn add_thread(&mut self, thread: std::thread::JoinHandle<i32>) -> usize {
+        let id = self.next_job_id();
+        self.threads.push(Thread { id, handle: thread });
+        id
     }
 
     fn kill_all_processes(&mut self) {
-        for mut child in std::mem::take(&mut self.child_processes) {
-            let _ = child.kill(); // Results are safe to ignore.
+        for mut process in std::mem::take(&mut self.child_processes) {
+            let _ = process.child.kill(); // Results are safe to ignore.
         }
     }
 
     fn join_all_threads(&mut self) {
         for thread in std::mem::take(&mut self.threads) {
-            let _ = thread.join(); // Results are safe to ignore.
+            let _ = thread.handle.join(); // Results are safe to ignore.
         }
     }
 
     fn take_exited_child_processes(&mut self) -> HashSet<u32> {
+        self.exited_jobs.clear(); // Previously reported jobs are forgotten.
+
         let mut exited = HashSet::new();
-        for child in &mut self.child_processes {
-            if !matches!(child.try_wait(), Ok(None)) {
-                exited.insert(child.id());
-                let _ = child.wait(); // Ensure that stdin is dropped.
+        for process in &mut self.child_processes {
+            if !matches!(process.child.try_wait(), Ok(None)) {
+                exited.insert(process.child.id());
+                let status = process.child.wait(); // Ensure that stdin is dropped.
+                self.exited_jobs.push(ExitedJob {
+                    id: process.id,
+                    pid: Some(process.child.id()),
+                    code: status.ok().and_then(|status| status.code()),
+                });
             }
         }
 
         // Remove exited processes from the internal data structure.
         self.child_processes
-            .retain(|child| !exited.contains(&child.id()));
+            .retain(|process| !exited.contains(&process.child.id()));
 
         exited
     }
+
+    fn take_child_process(&mut self, id: Option<usize>) -> Option<(usize, Child)> {
+        let index = self.child_process_index(id)?;
+        let process = self.child_processes.remove(index);
+        Some((process.id, process.child))
+    }
+
+    fn resume_child_process(&mut self, id: Option<usize>) -> std::io::Result<usize> {
+        let index = self.child_process_index(id).ok_or_else(no_such_job)?;
+        let process = &self.child_processes[index];
+        resume_process(process.child.id())?;
+        Ok(process.id)
+    }
+
+    fn kill_child_process(&mut self, id: usize, signal: i32) -> std::io::Result<()> {
+        let index = self.child_process_index(Some(id)).ok_or_else(no_such_job)?;
+        signal_child(&mut self.child_processes[index].child, signal)?;
+
+        if cfg!(not(unix)) || signal::is_terminating(signal) {
+            let mut process = self.child_processes.remove(index);
+
+            // Reap the process in the background, as it may take some time to exit.
+            std::thread::spawn(move || process.child.wait());
+        }
+
+        Ok(())
+    }
+
+    fn kill_process(&mut self, pid: u32, signal: i32) -> std::io::Result<()> {
+        match self.job_id(pid) {
+            Some(id) => self.kill_child_process(id, signal),
+            None => signal::send_signal(pid, signal),
+        }
+    }
+
+    fn job_id(&self, pid: u32) -> Option<usize> {
+        let process = self
+            .child_processes
+            .iter()
+            .find(|process| process.child.id() == pid)
+            .map(|process| process.id);
+        process.or_else(|| {
+            self.exited_jobs
+                .iter()
+                .find(|job| job.pid == Some(pid))
+                .map(|job| job.id)
+        })
+    }
+
+    fn try_wait_job(&mut self, id: usize) -> std::io::Result<JobState> {
+        if let Some(index) = self.child_process_index(Some(id)) {
+            let state = match self.child_processes[index].child.try_wait()? {
+                Some(status) => JobState::Exited(status.code()),
+                None => return Ok(JobState::Running),
+            };
+            self.child_processes.remove(index);
+            return Ok(state);
+        }
+
+        // Jobs that have already been reaped can be waited for once.
+        if let Some(index) = self.exited_jobs.iter().position(|job| job.id == id) {
+            return Ok(JobState::Exited(self.exited_jobs.remove(index).code));
+        }
+
+        let index = self
+            .threads
+            .iter()
+            .position(|thread| thread.id == id)
+            .ok_or_else(no_such_job)?;
+        if !self.threads[index].handle.is_finished() {
+            return Ok(JobState::Running);
+        }
+
+        let thread = self.threads.remove(index);
+        Ok(JobState::Exited(thread.handle.join().ok()))
+    }
+
+    fn jobs(&mut self) -> Vec<Job> {
+        let mut jobs = Vec::with_capacity(self.child_processes.len() + self.threads.len());
+
+        for process in &mut self.child_processes {
+            let state = match process.child.try_wait() {
+                Ok(None) => JobState::Running,
+                Ok(Some(status)) => JobState::Exited(status.code()),
+                Err(_) => JobState::Exited(None),
+            };
+
+            jobs.push(Job {
+                id: process.id,
+                pid: Some(process.child.id()),
+                state,
+                command: process.command.clone(),
+            });
+        }
+
+        let (finished, running) = std::mem::take(&mut self.threads)
+            .into_iter()
+            .partition::<Vec<_>, _>(|thread| thread.handle.is_finished());
+        self.threads = running;
+
+        for thread in &self.threads {
+            jobs.push(Job {
+                id: thread.id,
+                pid: None,
+                state: JobState::Running,
+                command: None,
+            });
+        }
+
+        for thread in finished {
+            jobs.push(Job {
+                id: thread.id,
+                pid: None,
+                state: JobState::Exited(thread.handle.join().ok()),
+                command: None,
+            });
+        }
+
+        // Exited jobs are only reported once, but can still be waited for until
+        // the next time exited jobs are reported.
+        self.exited_jobs.clear();
+        for job in &jobs {
+            if let JobState::Exited(code) = job.state {
+                self.exited_jobs.push(ExitedJob {
+                    id: job.id,
+                    pid: job.pid,
+                    code,
+                });
+            }
+        }
+        let exited: HashSet<usize> = self.exited_jobs.iter().map(|job| job.id).collect();
+        self.child_processes
+            .retain(|process| !exited.contains(&process.id));
+
+        jobs.sort_by_key(|job| job.id);
+        jobs
+    }
+
+    fn exec(&mut self, command: Command) -> std::io::Error {
+        replace_process(command)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::process::Command;
+
+    use super::*;
+
+    #[test]
+    fn it_reports_exited_jobs_once() {
+        let mut host = StdHost::default();
+        let first = host.add_thread(std::thread::spawn(|| 3));
+        let second = host.add_thread(std::thread::spawn(|| 4));
+        assert_eq!((first, second), (1, 2));
+
+        while host.threads.iter().any(|t| !t.handle.is_finished()) {
+            std::thread::yield_now();
+        }
+
+        let jobs = host.jobs();
+        assert_eq!(
+            jobs.iter()
+                .map(|job| (job.id, job.state))
+                .collect::<Vec<_>>(),
+            vec![
+                (1, JobState::Exited(Some(3))),
+                (2, JobState::Exited(Some(4)))
+            ]
+        );
+        assert_eq!(host.jobs(), Vec::new());
+    }
+
+    #[test]
+    fn it_takes_the_most_recent_child_process() {
+        let mut host = StdHost::default();
+        for _ in 0..3 {
+            let child = Command::new("true").spawn().unwrap();
+            host.add_child_process(child, None);
+        }
+
+        let (id, mut child) = host.take_child_process(None).unwrap();
+        assert_eq!(id, 3);
+        let _ = child.wait();
+
+        let (id, mut child) = host.take_child_process(Some(1)).unwrap();
+        assert_eq!(id, 1);
+        let _ = child.wait();
+
+        assert!(host.take_child_process(Some(3)).is_none());
+        assert_eq!(host.take_child_process(None).map(|(id, _)| id), Some(2));
+        assert!(host.take_child_process(None).is_none());
+    }
+
+    #[test]
+    fn it_fails_to_resume_missing_child_processes() {
+        let mut host = StdHost::default();
+        assert_eq!(
+            host.resume_child_process(None).unwrap_err().kind(),
+            std::io::ErrorKind::NotFound
+        );
+    }
+
+    #[test]
+    fn it_removes_killed_child_processes() {
+        let mut host = StdHost::default();
+        let child = Command::new("sleep").arg("10").spawn().unwrap();
+        let id = host.add_child_process(child, None);
+
+        host.kill_child_process(id, signal::SIGKILL).unwrap();
+        assert!(host.take_child_process(Some(id)).is_none());
+        assert_eq!(
+            host.kill_child_process(id, signal::SIGKILL)
+                .unwrap_err()
+                .kind(),
+            std::io::ErrorKind::NotFound
+        );
+    }
+
+    #[test]
+    fn it_reaps_waited_jobs() {
+        let mut host = StdHost::default();
+        let child = Command::new("sh").args(["-c", "exit 3"]).spawn().unwrap();
+        let pid = child.id();
+        let process = host.add_child_process(child, None);
+        let thread = host.add_thread(std::thread::spawn(|| 4));
+        assert_eq!(host.job_id(pid), Some(process));
+
+        for (id, code) in [(process, 3), (thread, 4)] {
+            let mut state = host.try_wait_job(id).unwrap();
+            while state == JobState::Running {
+                std::thread::yield_now();
+                state = host.try_wait_job(id).unwrap();
+            }
+            assert_eq!(state, JobState::Exited(Some(code)));
+            assert_eq!(
+                host.try_wait_job(id).unwrap_err().kind(),
+                std::io::ErrorKind::NotFound
+            );
+        }
+        assert_eq!(host.job_id(pid), None);
+    }
+
+    #[test]
+    fn it_waits_for_reaped_jobs_once() {
+        let mut host = StdHost::default();
+        let id = host.add_thread(std::thread::spawn(|| 3));
+        while host.threads.iter().any(|t| !t.handle.is_finished()) {
+            std::thread::yield_now();
+        }
+
+        assert_eq!(host.jobs().len(), 1);
+        assert_eq!(host.try_wait_job(id).unwrap(), JobState::Exited(Some(3)));
+        assert_eq!(
+            host.try_wait_job(id).unwrap_err().kind(),
+            std::io::ErrorKind::NotFound
+        );
+    }
+
+    #[test]
+    fn it_forgets_exited_jobs_once_reported_again() {
+        let mut host = StdHost::default();
+        let mut child = Command::new("true").spawn().unwrap();
+        while child.try_wait().unwrap().is_none() {
+            std::thread::yield_now();
+        }
+        let pid = child.id();
+        let id = host.add_child_process(child, None);
+
+        assert_eq!(host.take_exited_child_processes(), HashSet::from([pid]));
+        assert_eq!(host.job_id(pid), Some(id));
+
+        assert_eq!(host.jobs(), Vec::new());
+        assert!(host.exited_jobs.is_empty());
+        assert_eq!(host.job_id(pid), None);
+        assert_eq!(
+            host.try_wait_job(id).unwrap_err().kind(),
+            std::io::ErrorKind::NotFound
+        );
+    }
+
+    #[test]
+    fn it_does_not_reuse_job_ids() {
+        let mut host = StdHost::default();
+        let child = Command::new("true").spawn().unwrap();
+        let id = host.add_child_process(child, Some("true".into()));
+        host.join_all_threads();
+        host.kill_all_processes();
+
+        let thread = host.add_thread(std::thread::spawn(|| 0));
+        assert!(thread > id);
+    }
 }
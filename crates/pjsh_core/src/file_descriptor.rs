@@ -82,8 +82,10 @@ pub fn try_clone(&self) -> std::io::Result<Self> {
     pub fn output(&mut self) -> Result<Stdio, FileDescriptorError> {
         match self {
             FileDescriptor::Stdin => Err(FileDescriptorError::UnusableForOutput),
-            FileDescriptor::Stdout => Ok(Stdio::inherit()),
-            FileDescriptor::Stderr => Ok(Stdio::inherit()),
+            // Not inherited, as the stream may have been duplicated onto another
+            // file descriptor, such as stderr in "2>&1".
+            FileDescriptor::Stdout => Ok(Stdio::from(io::stdout())),
+            FileDescriptor::Stderr => Ok(Stdio::from(io::stderr())),
             FileDescriptor::Pipe((_, writer)) => Ok(Stdio::from(writer.try_clone().unwrap())),
             FileDescriptor::FileHandle(file) => Ok(Stdio::from(file.try_clone().unwrap())),
             FileDescriptor::File(path) => match File::create(&path) {
@@ -120,7 +122,7 @@ pub fn input(&mut self) -> Result<Stdio, FileDescriptorError> {
                 }
                 Err(error) => Err(FileDescriptorError::FileNotReadable(path.clone(), error)),
             },
-            FileDescriptor::AppendFile(_) => unreachable!(),
+            FileDescriptor::AppendFile(_) => Err(FileDescriptorError::UnusableForInput),
             FileDescriptor::Null => Ok(Stdio::null()),
             _ => self.output(),
         }
@@ -141,7 +143,7 @@ pub fn reader(&mut self) -> Result<Box<dyn Read + Send>, FileDescriptorError> {
                 Err(error) => Err(FileDescriptorError::FileNotReadable(path.clone(), error)),
             },
             FileDescriptor::FileHandle(file) => Ok(Box::new(file.try_clone().unwrap())),
-            FileDescriptor::AppendFile(_) => unreachable!(),
+            FileDescriptor::AppendFile(_) => Err(FileDescriptorError::UnusableForInput),
             FileDescriptor::Null => Ok(Box::new(io::empty())),
         }
     }
@@ -174,3 +176,21 @@ pub fn writer(&mut self) -> Result<Box<dyn Write + Send>, FileDescriptorError> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_rejects_append_files_as_input() {
+        let mut fd = FileDescriptor::AppendFile(PathBuf::from("file"));
+        assert!(matches!(
+            fd.input(),
+            Err(FileDescriptorError::UnusableForInput)
+        ));
+        assert!(matches!(
+            fd.reader(),
+            Err(FileDescriptorError::UnusableForInput)
+        ));
+    }
+}
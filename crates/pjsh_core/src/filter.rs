@@ -8,6 +8,9 @@ pub enum FilterError {
     /// The filter cannot be applied using the provided arguments.
     InvalidArgs(String),
 
+    /// The filter cannot be applied to the input value.
+    InvalidInput(String),
+
     /// The filter cannot be applied to lists.
     InvalidListFilter,
 
@@ -52,6 +55,7 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             FilterError::InvalidArgs(msg) => {
                 write!(f, "invalid arguments for filter: {msg}")
             }
+            FilterError::InvalidInput(msg) => write!(f, "invalid input for filter: {msg}"),
             FilterError::InvalidListFilter => {
                 write!(f, "the filter cannot be applied to lists")
             }
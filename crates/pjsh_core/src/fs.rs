@@ -1,23 +1,103 @@
-use std::path::PathBuf;
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
 
 use crate::{
     utils::{resolve_path, word_var},
     Context,
 };
 
+/// A cache of program paths resolved using `$PATH` and `$PATHEXT`.
+///
+/// Cached paths are discarded whenever `$PATH` or `$PATHEXT` changes.
+#[derive(Debug, Default)]
+pub struct PathCache {
+    /// Values of `$PATH` and `$PATHEXT` that the cached paths were resolved using.
+    key: (String, String),
+
+    /// Resolved program paths keyed by program name.
+    entries: BTreeMap<String, PathBuf>,
+}
+
+impl PathCache {
+    /// Returns all cached program paths, ordered by program name.
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &Path)> {
+        self.entries
+            .iter()
+            .map(|(name, path)| (name.as_str(), path.as_path()))
+    }
+
+    /// Removes all cached program paths.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Removes the cached path of a program.
+    pub fn remove(&mut self, name: &str) {
+        self.entries.remove(name);
+    }
+
+    /// Discards all cached paths if they were resolved using other values of
+    /// `$PATH` or `$PATHEXT` than those in the context.
+    fn invalidate_outdated(&mut self, context: &Context) {
+        let path = word_var(context, "PATH").unwrap_or_default();
+        let pathext = word_var(context, "PATHEXT").unwrap_or_default();
+        if self.key.0 != path || self.key.1 != pathext {
+            self.key = (path.to_owned(), pathext.to_owned());
+            self.entries.clear();
+        }
+    }
+}
+
 /// Find a program by searching for its name in the paths present in `$PATH`.
 ///
 /// Optionally, extensions present in the semicolon-separated `$PATHEXT` are used when searching.
 /// Note that `$PATHEXT` is typically only present on Windows systems. If the environment variable
 /// is undefined, only the name is matched.
 ///
+/// Resolved paths are cached in the context's [`PathCache`]. Cached paths that no longer exist are
+/// resolved again.
+///
 /// Also note that file system case-insensitivity may be in effect.
 pub fn find_in_path(name: &str, context: &Context) -> Option<PathBuf> {
+    find_in_path_with(name, context, &mut |path| path.exists())
+}
+
+/// Find a program by searching for its name in the paths present in `$PATH`, using a function to
+/// determine whether or not paths exist.
+fn find_in_path_with(
+    name: &str,
+    context: &Context,
+    exists: &mut dyn FnMut(&Path) -> bool,
+) -> Option<PathBuf> {
     // Match an exact program path.
     if name.contains('/') {
         return Some(resolve_path(context, name));
     }
 
+    let mut cache = context.path_cache.lock();
+    cache.invalidate_outdated(context);
+    if let Some(path) = cache.entries.get(name) {
+        if exists(path) {
+            return Some(path.clone());
+        }
+    }
+
+    let path = search_path(name, context, exists);
+    match &path {
+        Some(path) => cache.entries.insert(name.to_owned(), path.clone()),
+        None => cache.entries.remove(name),
+    };
+    path
+}
+
+/// Searches for a program in all paths present in `$PATH`, bypassing the cache.
+fn search_path(
+    name: &str,
+    context: &Context,
+    exists: &mut dyn FnMut(&Path) -> bool,
+) -> Option<PathBuf> {
     // Define all possible file extensions that can be matched implicitly.
     let mut extensions = vec![String::new()]; // Empty string = no file extension.
     if let Some(ext_env) = word_var(context, "PATHEXT") {
@@ -37,16 +117,32 @@ pub fn find_in_path(name: &str, context: &Context) -> Option<PathBuf> {
     // Search through all possible paths for a matching file.
     possible_paths
         .into_iter()
-        .find(|path| path.exists())
+        .find(|path| exists(path))
         .map(|path| path.canonicalize().unwrap_or(path))
 }
 
+/// Separator between paths in `$PATH`-like variables on the current platform.
+pub const PATH_SEPARATOR: &str = if cfg!(windows) { ";" } else { ":" };
+
 /// Returns a list of all paths in `$PATH` separated by ':' on Unix systems, and
 /// by ';' on Windows.
 pub fn paths(context: &Context) -> Vec<PathBuf> {
-    let separator = if cfg!(windows) { ';' } else { ':' };
     let path_string = word_var(context, "PATH").unwrap_or_default();
-    path_string.split(separator).map(PathBuf::from).collect()
+    path_string
+        .split(PATH_SEPARATOR)
+        .map(PathBuf::from)
+        .collect()
+}
+
+/// Splits a `$PATH`-like value into a list of paths.
+///
+/// Empty paths are skipped.
+pub fn split_path_list(value: &str, separator: &str) -> Vec<String> {
+    value
+        .split(separator)
+        .filter(|path| !path.is_empty())
+        .map(str::to_owned)
+        .collect()
 }
 
 #[cfg(test)]
@@ -65,7 +161,9 @@ fn it_finds_programs_in_path() -> std::io::Result<()> {
         let non_program_path = dir.path().join("non-program");
         let program_path = dir.path().join("program");
         let mut context = Context::default();
-        context.set_var("PATH".into(), Value::Word(path_to_string(dir.path())));
+        context
+            .set_var("PATH".into(), Value::Word(path_to_string(dir.path())))
+            .unwrap();
 
         File::create(program_path.clone())?;
         File::create(non_program_path)?;
@@ -80,8 +178,12 @@ fn it_finds_programs_in_path_with_pathext() -> std::io::Result<()> {
         let non_program_path = dir.path().join("non-program");
         let program_path = dir.path().join("program.exe");
         let mut context = Context::default();
-        context.set_var("PATH".into(), Value::Word(path_to_string(dir.path())));
-        context.set_var("PATHEXT".into(), Value::Word(".exe".into()));
+        context
+            .set_var("PATH".into(), Value::Word(path_to_string(dir.path())))
+            .unwrap();
+        context
+            .set_var("PATHEXT".into(), Value::Word(".exe".into()))
+            .unwrap();
 
         File::create(program_path.clone())?;
         File::create(non_program_path)?;
@@ -96,7 +198,9 @@ fn it_resolves_programs_in_path() -> std::io::Result<()> {
         let dir = tempdir()?;
         let program_path = dir.path().join("program");
         let mut context = Context::default();
-        context.set_var("PATH".into(), Value::Word("".into())); // No reference to dir.
+        context
+            .set_var("PATH".into(), Value::Word("".into()))
+            .unwrap(); // No reference to dir.
 
         File::create(program_path.clone())?;
 
@@ -108,16 +212,107 @@ fn it_resolves_programs_in_path() -> std::io::Result<()> {
     }
 
     #[test]
-    fn it_splits_paths() {
+    fn it_caches_program_paths() -> std::io::Result<()> {
+        let dir = tempdir()?;
+        let program_path = dir.path().join("program");
+        let mut context = Context::default();
         let separator = if cfg!(windows) { ';' } else { ':' };
+        let path = format!(
+            "/path/to/missing/a{separator}/path/to/missing/b{separator}{}",
+            path_to_string(dir.path())
+        );
+        context.set_var("PATH".into(), Value::Word(path)).unwrap();
+        File::create(program_path.clone())?;
+
+        let mut probes = 0;
+        let mut exists = |path: &Path| {
+            probes += 1;
+            path.exists()
+        };
+        for _ in 0..100 {
+            assert_eq!(
+                find_in_path_with("program", &context, &mut exists),
+                Some(program_path.clone())
+            );
+        }
+
+        // Only the first lookup walks $PATH. Later lookups probe the cached path.
+        assert_eq!(probes, 3 + 99);
+        assert_eq!(
+            context.path_cache.lock().entries().collect::<Vec<_>>(),
+            vec![("program", program_path.as_path())]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn it_resolves_removed_programs_again() -> std::io::Result<()> {
+        let first_dir = tempdir()?;
+        let second_dir = tempdir()?;
+        let first_path = first_dir.path().join("program");
+        let second_path = second_dir.path().join("program");
         let mut context = Context::default();
-        context.set_var(
-            "PATH".into(),
-            Value::Word(format!("/tmp/a{separator}/var/tmp/b")),
+        let separator = if cfg!(windows) { ';' } else { ':' };
+        let path = format!(
+            "{}{separator}{}",
+            path_to_string(first_dir.path()),
+            path_to_string(second_dir.path())
         );
+        context.set_var("PATH".into(), Value::Word(path)).unwrap();
+        File::create(first_path.clone())?;
+        File::create(second_path.clone())?;
+
+        assert_eq!(find_in_path("program", &context), Some(first_path.clone()));
+        std::fs::remove_file(first_path)?;
+        assert_eq!(find_in_path("program", &context), Some(second_path));
+        Ok(())
+    }
+
+    #[test]
+    fn it_invalidates_cached_paths_when_path_changes() -> std::io::Result<()> {
+        let first_dir = tempdir()?;
+        let second_dir = tempdir()?;
+        let first_path = first_dir.path().join("program");
+        let second_path = second_dir.path().join("program");
+        let mut context = Context::default();
+        File::create(first_path.clone())?;
+        File::create(second_path.clone())?;
+
+        context
+            .set_var("PATH".into(), Value::Word(path_to_string(first_dir.path())))
+            .unwrap();
+        assert_eq!(find_in_path("program", &context), Some(first_path));
+
+        context
+            .set_var(
+                "PATH".into(),
+                Value::Word(path_to_string(second_dir.path())),
+            )
+            .unwrap();
+        assert_eq!(find_in_path("program", &context), Some(second_path));
+        Ok(())
+    }
+
+    #[test]
+    fn it_splits_paths() {
+        let separator = if cfg!(windows) { ';' } else { ':' };
+        let mut context = Context::default();
+        context
+            .set_var(
+                "PATH".into(),
+                Value::Word(format!("/tmp/a{separator}/var/tmp/b")),
+            )
+            .unwrap();
         assert_eq!(
             paths(&context),
             vec![PathBuf::from("/tmp/a"), PathBuf::from("/var/tmp/b")]
         );
     }
+
+    #[test]
+    fn it_splits_path_lists() {
+        assert_eq!(split_path_list("/a:/b", ":"), vec!["/a", "/b"]);
+        assert_eq!(split_path_list(r"C:\a;;C:\b;", ";"), vec![r"C:\a", r"C:\b"]);
+        assert_eq!(split_path_list("", ":"), Vec::<String>::new());
+    }
 }
@@ -3,10 +3,22 @@
 mod file_descriptor;
 mod filter;
 mod fs;
+mod profile;
+pub mod signal;
 pub mod utils;
 
 pub use env::std_host::StdHost;
-pub use env::{context::Context, context::Scope, context::Value, host::Host};
+pub use env::{
+    context::Context,
+    context::ContextSnapshot,
+    context::Scope,
+    context::Value,
+    dynamic::{DynamicVarGetter, DynamicVarSetter, DynamicVars},
+    history::{History, MemoryHistory},
+    host::{Host, Job, JobState, Platform},
+    options::{Options, STRICT},
+};
 pub use file_descriptor::{FileDescriptor, FileDescriptorError, FD_STDERR, FD_STDIN, FD_STDOUT};
 pub use filter::{Filter, FilterError, FilterResult};
-pub use fs::{find_in_path, paths};
+pub use fs::{find_in_path, paths, split_path_list, PathCache, PATH_SEPARATOR};
+pub use profile::{Profile, ProfileSink, Timing};
@@ -0,0 +1,125 @@
+use std::{collections::HashMap, time::Duration};
+
+use pjsh_ast::Statement;
+
+/// Accumulated wall-time of something that has been executed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Timing {
+    /// Total time spent executing.
+    pub duration: Duration,
+
+    /// Number of times executed.
+    pub count: usize,
+}
+
+impl Timing {
+    /// Adds a single execution to the timing.
+    fn record(&mut self, duration: Duration) {
+        self.duration += duration;
+        self.count += 1;
+    }
+}
+
+/// Wall-time measurements of executed statements and function calls.
+///
+/// Statements are identified by their address. Measurements of a statement
+/// can therefore only be looked up as long as the statement is kept alive.
+#[derive(Debug, Default)]
+pub struct Profile {
+    /// Timings of statements keyed by their address.
+    statements: HashMap<usize, Timing>,
+
+    /// Timings of function calls keyed by the function name.
+    functions: HashMap<String, Timing>,
+}
+
+impl Profile {
+    /// Records the execution of a statement.
+    pub fn record_statement(&mut self, statement: &Statement, duration: Duration) {
+        self.statements
+            .entry(statement_key(statement))
+            .or_default()
+            .record(duration);
+    }
+
+    /// Records a call to a function.
+    pub fn record_function(&mut self, name: &str, duration: Duration) {
+        self.functions
+            .entry(name.to_owned())
+            .or_default()
+            .record(duration);
+    }
+
+    /// Returns the timing of a statement, if it has been executed.
+    pub fn statement(&self, statement: &Statement) -> Option<Timing> {
+        self.statements.get(&statement_key(statement)).copied()
+    }
+
+    /// Returns the timings of all called functions.
+    pub fn functions(&self) -> impl Iterator<Item = (&str, Timing)> {
+        self.functions
+            .iter()
+            .map(|(name, timing)| (name.as_str(), *timing))
+    }
+}
+
+/// A destination for the timings of individual command executions.
+pub trait ProfileSink: Send {
+    /// Records a single execution of a command.
+    fn record_command(&mut self, name: &str, duration: Duration);
+}
+
+/// Returns a key identifying a statement.
+fn statement_key(statement: &Statement) -> usize {
+    std::ptr::from_ref(statement) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use pjsh_ast::{Assignment, Value, Word};
+
+    use super::*;
+
+    fn assignment() -> Statement {
+        Statement::Assignment(Assignment::new(
+            Word::Literal("key".into()),
+            Value::Word(Word::Literal("value".into())),
+        ))
+    }
+
+    #[test]
+    fn it_records_statements() {
+        let (first, second) = (assignment(), assignment());
+        let mut profile = Profile::default();
+
+        profile.record_statement(&first, Duration::from_millis(2));
+        profile.record_statement(&first, Duration::from_millis(3));
+
+        assert_eq!(
+            profile.statement(&first),
+            Some(Timing {
+                duration: Duration::from_millis(5),
+                count: 2
+            })
+        );
+        assert_eq!(profile.statement(&second), None);
+    }
+
+    #[test]
+    fn it_records_functions() {
+        let mut profile = Profile::default();
+        profile.record_function("f", Duration::from_millis(1));
+        profile.record_function("f", Duration::from_millis(1));
+
+        assert_eq!(
+            profile.functions().collect::<Vec<_>>(),
+            vec![(
+                "f",
+                Timing {
+                    duration: Duration::from_millis(2),
+                    count: 2
+                }
+            )]
+        );
+    }
+}
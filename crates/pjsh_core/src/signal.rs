@@ -0,0 +1,328 @@
+use std::{
+    process::ExitStatus,
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+};
+
+/// Pseudo-signal for handlers that are run when the shell exits.
+pub const EXIT: i32 = 0;
+
+/// Signal that requests a process to terminate.
+pub const SIGTERM: i32 = 15;
+
+/// Signal that forcefully kills a process.
+pub const SIGKILL: i32 = 9;
+
+/// Signals that can be sent to processes, by name.
+#[cfg(unix)]
+const SIGNALS: &[(&str, i32)] = &[
+    ("HUP", libc::SIGHUP),
+    ("INT", libc::SIGINT),
+    ("QUIT", libc::SIGQUIT),
+    ("KILL", libc::SIGKILL),
+    ("USR1", libc::SIGUSR1),
+    ("USR2", libc::SIGUSR2),
+    ("PIPE", libc::SIGPIPE),
+    ("ALRM", libc::SIGALRM),
+    ("TERM", libc::SIGTERM),
+    ("CONT", libc::SIGCONT),
+    ("STOP", libc::SIGSTOP),
+    ("TSTP", libc::SIGTSTP),
+    ("TTIN", libc::SIGTTIN),
+    ("TTOU", libc::SIGTTOU),
+];
+
+/// Signals that can be sent to processes, by name.
+///
+/// Processes are always killed forcefully on this platform.
+#[cfg(not(unix))]
+const SIGNALS: &[(&str, i32)] = &[
+    ("HUP", 1),
+    ("INT", 2),
+    ("QUIT", 3),
+    ("KILL", SIGKILL),
+    ("TERM", SIGTERM),
+];
+
+/// Returns the number of a signal given its name or number.
+///
+/// Names are case-insensitive and may be prefixed by "SIG".
+pub fn signal_number(name: &str) -> Option<i32> {
+    if let Ok(number) = name.parse::<i32>() {
+        return (number >= 0).then_some(number);
+    }
+
+    let name = name.to_ascii_uppercase();
+    let name = name.strip_prefix("SIG").unwrap_or(&name);
+    SIGNALS
+        .iter()
+        .find(|(signal_name, _)| *signal_name == name)
+        .map(|(_, number)| *number)
+}
+
+/// Returns the name of a signal, without a "SIG" prefix, given its number.
+pub fn signal_name(number: i32) -> Option<&'static str> {
+    SIGNALS
+        .iter()
+        .find(|(_, signal_number)| *signal_number == number)
+        .map(|(name, _)| *name)
+}
+
+/// Returns `true` if a signal is expected to end the process receiving it.
+///
+/// Signals that only stop or resume processes, and the null signal, are not
+/// considered to be terminating.
+pub fn is_terminating(signal: i32) -> bool {
+    #[cfg(unix)]
+    let non_terminating = [
+        0,
+        libc::SIGCONT,
+        libc::SIGSTOP,
+        libc::SIGTSTP,
+        libc::SIGTTIN,
+        libc::SIGTTOU,
+    ];
+    #[cfg(not(unix))]
+    let non_terminating = [0];
+
+    !non_terminating.contains(&signal)
+}
+
+/// Returns the exit code of a terminated process.
+///
+/// Processes that are terminated by a signal on Unix, such as `SIGPIPE` when
+/// writing to a closed pipe, exit with `128` plus the signal number.
+pub fn exit_status_code(exit_status: ExitStatus) -> i32 {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        if let Some(signal) = exit_status.signal() {
+            return 128 + signal;
+        }
+    }
+
+    exit_status.code().unwrap_or(127)
+}
+
+/// Sends a signal to a process.
+#[cfg(unix)]
+pub(crate) fn send_signal(pid: u32, signal: i32) -> std::io::Result<()> {
+    let pid = libc::pid_t::try_from(pid)
+        .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidInput, error))?;
+
+    // SAFETY: Sending a signal does not access any memory owned by the shell.
+    match unsafe { libc::kill(pid, signal) } {
+        0 => Ok(()),
+        _ => Err(std::io::Error::last_os_error()),
+    }
+}
+
+/// Sending signals to processes is not supported on this platform.
+#[cfg(not(unix))]
+pub(crate) fn send_signal(_pid: u32, _signal: i32) -> std::io::Result<()> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "sending signals is not supported on this platform",
+    ))
+}
+
+/// Set when an interrupt is received while interrupts are caught.
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+/// Catches interrupts (`SIGINT`) until an [`InterruptGuard`] is dropped.
+///
+/// Interrupts do not terminate the shell while they are caught. Instead, they
+/// are recorded and can be checked using [`InterruptGuard::is_interrupted`].
+/// This allows blocking built-ins to stop waiting when the user presses Ctrl-C.
+pub struct InterruptGuard {
+    /// Interrupt handler to restore once the guard is dropped.
+    #[cfg(unix)]
+    previous_handler: libc::sighandler_t,
+}
+
+impl InterruptGuard {
+    /// Starts catching interrupts.
+    pub fn new() -> Self {
+        INTERRUPTED.store(false, Ordering::SeqCst);
+
+        // SAFETY: The handler only stores to an atomic, which is async-signal-safe.
+        #[cfg(unix)]
+        let previous_handler = unsafe {
+            libc::signal(
+                libc::SIGINT,
+                on_interrupt as *const () as libc::sighandler_t,
+            )
+        };
+
+        Self {
+            #[cfg(unix)]
+            previous_handler,
+        }
+    }
+
+    /// Returns `true` if an interrupt has been received since the guard was created.
+    pub fn is_interrupted(&self) -> bool {
+        INTERRUPTED.load(Ordering::SeqCst)
+    }
+}
+
+impl Default for InterruptGuard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for InterruptGuard {
+    fn drop(&mut self) {
+        // SAFETY: The previous handler was returned by libc::signal.
+        #[cfg(unix)]
+        unsafe {
+            libc::signal(libc::SIGINT, self.previous_handler);
+        }
+    }
+}
+
+/// Records that an interrupt has been received.
+#[cfg(unix)]
+extern "C" fn on_interrupt(_signal: libc::c_int) {
+    INTERRUPTED.store(true, Ordering::SeqCst);
+}
+
+/// Trapped signals that have been received, as a set of bits indexed by signal number.
+static PENDING_SIGNALS: AtomicU64 = AtomicU64::new(0);
+
+/// Records a signal rather than performing its default action.
+///
+/// Received signals can be taken using [`take_pending_signals`]. Signals that
+/// cannot be caught, such as `SIGKILL`, result in an error.
+#[cfg(unix)]
+pub fn trap_signal(signal: i32) -> std::io::Result<()> {
+    if !(1..64).contains(&signal) {
+        return Err(std::io::Error::from(std::io::ErrorKind::InvalidInput));
+    }
+
+    // SAFETY: The handler only updates an atomic, which is async-signal-safe.
+    let previous_handler =
+        unsafe { libc::signal(signal, on_trapped_signal as *const () as libc::sighandler_t) };
+
+    match previous_handler {
+        libc::SIG_ERR => Err(std::io::Error::last_os_error()),
+        _ => Ok(()),
+    }
+}
+
+/// Trapping signals is not supported on this platform.
+#[cfg(not(unix))]
+pub fn trap_signal(_signal: i32) -> std::io::Result<()> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "trapping signals is not supported on this platform",
+    ))
+}
+
+/// Restores the default action of a signal, discarding it if it is pending.
+#[cfg(unix)]
+pub fn reset_signal(signal: i32) -> std::io::Result<()> {
+    if !(1..64).contains(&signal) {
+        return Err(std::io::Error::from(std::io::ErrorKind::InvalidInput));
+    }
+
+    // SAFETY: Restoring the default action does not access any memory owned by the shell.
+    if unsafe { libc::signal(signal, libc::SIG_DFL) } == libc::SIG_ERR {
+        return Err(std::io::Error::last_os_error());
+    }
+
+    PENDING_SIGNALS.fetch_and(!(1 << signal), Ordering::SeqCst);
+    Ok(())
+}
+
+/// Signals always use their default action on this platform.
+#[cfg(not(unix))]
+pub fn reset_signal(_signal: i32) -> std::io::Result<()> {
+    Ok(())
+}
+
+/// Returns the trapped signals that have been received since the last call,
+/// in increasing order.
+pub fn take_pending_signals() -> Vec<i32> {
+    let pending = PENDING_SIGNALS.swap(0, Ordering::SeqCst);
+    (1..64)
+        .filter(|signal| pending & (1 << signal) != 0)
+        .collect()
+}
+
+/// Records that a trapped signal has been received.
+#[cfg(unix)]
+extern "C" fn on_trapped_signal(signal: libc::c_int) {
+    if let Some(bit) = 1u64.checked_shl(signal as u32) {
+        PENDING_SIGNALS.fetch_or(bit, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_signal_names() {
+        assert_eq!(signal_number("KILL"), Some(SIGKILL));
+        assert_eq!(signal_number("sigterm"), Some(SIGTERM));
+        assert_eq!(signal_number("SIGTERM"), Some(SIGTERM));
+        assert_eq!(signal_number("9"), Some(9));
+        assert_eq!(signal_number("-1"), None);
+        assert_eq!(signal_number("UNKNOWN"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn it_catches_interrupts() {
+        let guard = InterruptGuard::new();
+        assert!(!guard.is_interrupted());
+
+        send_signal(std::process::id(), libc::SIGINT).unwrap();
+        while !guard.is_interrupted() {
+            std::thread::yield_now();
+        }
+    }
+
+    #[test]
+    fn it_names_signals() {
+        assert_eq!(signal_name(SIGTERM), Some("TERM"));
+        assert_eq!(signal_name(-1), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn it_records_trapped_signals() {
+        trap_signal(libc::SIGUSR2).unwrap();
+
+        send_signal(std::process::id(), libc::SIGUSR2).unwrap();
+        let mut pending = take_pending_signals();
+        while pending.is_empty() {
+            std::thread::yield_now();
+            pending = take_pending_signals();
+        }
+        assert_eq!(pending, vec![libc::SIGUSR2]);
+
+        reset_signal(libc::SIGUSR2).unwrap();
+        assert!(trap_signal(SIGKILL).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn it_maps_signal_deaths_to_exit_codes() {
+        use std::os::unix::process::ExitStatusExt;
+
+        assert_eq!(exit_status_code(ExitStatus::from_raw(3 << 8)), 3);
+        assert_eq!(
+            exit_status_code(ExitStatus::from_raw(SIGTERM)),
+            128 + SIGTERM
+        );
+    }
+
+    #[test]
+    fn it_detects_terminating_signals() {
+        assert!(is_terminating(SIGTERM));
+        assert!(is_terminating(SIGKILL));
+        assert!(!is_terminating(0));
+    }
+}
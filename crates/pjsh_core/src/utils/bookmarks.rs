@@ -0,0 +1,179 @@
+use std::{
+    collections::BTreeMap,
+    fs::{File, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use crate::Context;
+
+use super::word_var;
+
+/// Path to the bookmarks file, relative to the user's home directory.
+const BOOKMARKS_FILE_NAME: &str = ".pjsh/bookmarks";
+
+/// Named directory bookmarks, such as `proj` for `cd :proj`.
+///
+/// Bookmarks are stored one per line as a name and a path separated by a tab.
+/// Shells modifying the bookmarks should hold a [`BookmarksLock`] while loading
+/// and saving them, so that concurrent changes are not lost.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Bookmarks {
+    entries: BTreeMap<String, PathBuf>,
+}
+
+impl Bookmarks {
+    /// Loads bookmarks from a file.
+    ///
+    /// A missing file contains no bookmarks. Malformed lines are ignored, so that
+    /// a corrupt file does not prevent the remaining bookmarks from being used.
+    pub fn load<P: AsRef<Path>>(path: P) -> Self {
+        let Ok(bytes) = std::fs::read(path) else {
+            return Self::default();
+        };
+
+        let entries = String::from_utf8_lossy(&bytes)
+            .lines()
+            .filter_map(|line| {
+                let (name, path) = line.split_once('\t')?;
+                (is_valid_name(name) && !path.is_empty())
+                    .then(|| (name.to_owned(), PathBuf::from(path)))
+            })
+            .collect();
+
+        Self { entries }
+    }
+
+    /// Saves bookmarks to a file, creating its parent directory if needed.
+    ///
+    /// The file is replaced atomically, so that concurrent shells never observe
+    /// a partially written file.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+
+        let mut temp_name = path.as_os_str().to_owned();
+        temp_name.push(format!(".{}.tmp", std::process::id()));
+        let temp_path = PathBuf::from(temp_name);
+
+        let result = std::fs::File::create(&temp_path).and_then(|mut file| {
+            for (name, path) in &self.entries {
+                writeln!(file, "{name}\t{}", path.to_string_lossy())?;
+            }
+            file.sync_all()
+        });
+
+        match result.and_then(|_| std::fs::rename(&temp_path, path)) {
+            Ok(()) => Ok(()),
+            Err(error) => {
+                let _ = std::fs::remove_file(&temp_path);
+                Err(error)
+            }
+        }
+    }
+
+    /// Returns the path of a bookmark.
+    pub fn get(&self, name: &str) -> Option<&Path> {
+        self.entries.get(name).map(PathBuf::as_path)
+    }
+
+    /// Adds a bookmark, replacing any bookmark with the same name.
+    ///
+    /// Returns an error if the name is not a valid bookmark name, or if the path
+    /// cannot be stored on a single line.
+    pub fn insert(&mut self, name: &str, path: PathBuf) -> Result<(), String> {
+        if !is_valid_name(name) {
+            return Err(format!("{name}: invalid bookmark name"));
+        }
+
+        let path_str = path.to_string_lossy();
+        if path_str.contains(['\n', '\r']) {
+            return Err(format!("{path_str}: paths cannot contain line breaks"));
+        }
+
+        self.entries.insert(name.to_owned(), path);
+        Ok(())
+    }
+
+    /// Removes a bookmark, returning its path if it existed.
+    pub fn remove(&mut self, name: &str) -> Option<PathBuf> {
+        self.entries.remove(name)
+    }
+
+    /// Returns all bookmarks, ordered by name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Path)> {
+        self.entries
+            .iter()
+            .map(|(name, path)| (name.as_str(), path.as_path()))
+    }
+}
+
+/// An exclusive lock on a bookmarks file, which is released when dropped.
+///
+/// The lock is held on a separate `.lock` file, as saving replaces the
+/// bookmarks file itself.
+pub struct BookmarksLock {
+    _file: File,
+}
+
+impl BookmarksLock {
+    /// Locks a bookmarks file, waiting for other shells to release it.
+    pub fn acquire<P: AsRef<Path>>(path: P) -> std::io::Result<Self> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+
+        let mut lock_name = path.as_os_str().to_owned();
+        lock_name.push(".lock");
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(PathBuf::from(lock_name))?;
+
+        lock_exclusive(&file)?;
+        Ok(Self { _file: file })
+    }
+}
+
+/// Places an exclusive advisory lock on a file until it is closed.
+#[cfg(unix)]
+fn lock_exclusive(file: &File) -> std::io::Result<()> {
+    use std::os::unix::io::AsRawFd;
+
+    loop {
+        // SAFETY: The file descriptor is valid for as long as the file is open.
+        if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX) } == 0 {
+            return Ok(());
+        }
+
+        let error = std::io::Error::last_os_error();
+        if error.kind() != std::io::ErrorKind::Interrupted {
+            return Err(error);
+        }
+    }
+}
+
+/// Places an exclusive advisory lock on a file until it is closed.
+///
+/// Advisory locks are only supported on Unix-like platforms.
+#[cfg(not(unix))]
+fn lock_exclusive(_file: &File) -> std::io::Result<()> {
+    Ok(())
+}
+
+/// Returns the path to the bookmarks file of a context, based on `$HOME`.
+pub fn bookmarks_file(context: &Context) -> Option<PathBuf> {
+    word_var(context, "HOME").map(|home| Path::new(home).join(BOOKMARKS_FILE_NAME))
+}
+
+/// Returns `true` if a bookmark name can be stored and referenced as `:name`.
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|ch| ch.is_alphanumeric() || matches!(ch, '-' | '_' | '.'))
+}
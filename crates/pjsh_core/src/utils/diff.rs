@@ -0,0 +1,204 @@
+/// A change between two sequences.
+#[derive(Debug, PartialEq, Eq)]
+enum Change<'a, T> {
+    Equal(&'a T),
+    Delete(&'a T),
+    Insert(&'a T),
+}
+
+/// Returns a line-based diff between two texts, with one line per item.
+///
+/// Unchanged lines are prefixed by a space, deleted lines by `-` and inserted
+/// lines by `+`. When a number of lines are replaced by the same number of
+/// lines, changed words are highlighted as `[-deleted-]` and `{+inserted+}`.
+///
+/// Returns no lines if the texts are identical.
+pub fn diff_lines<S: AsRef<str>>(old: &[S], new: &[S]) -> Vec<String> {
+    let old: Vec<&str> = old.iter().map(AsRef::as_ref).collect();
+    let new: Vec<&str> = new.iter().map(AsRef::as_ref).collect();
+    let changes = lcs_diff(&old, &new);
+
+    if changes
+        .iter()
+        .all(|change| matches!(change, Change::Equal(_)))
+    {
+        return Vec::new();
+    }
+
+    let mut lines = Vec::with_capacity(changes.len());
+    let mut index = 0;
+    while index < changes.len() {
+        if let Change::Equal(line) = changes[index] {
+            lines.push(format!(" {line}"));
+            index += 1;
+            continue;
+        }
+
+        // Collect a block of deletions followed by a block of insertions.
+        let deleted: Vec<&str> = changes[index..]
+            .iter()
+            .map_while(|change| match change {
+                Change::Delete(line) => Some(**line),
+                _ => None,
+            })
+            .collect();
+        index += deleted.len();
+        let inserted: Vec<&str> = changes[index..]
+            .iter()
+            .map_while(|change| match change {
+                Change::Insert(line) => Some(**line),
+                _ => None,
+            })
+            .collect();
+        index += inserted.len();
+
+        let (deleted, inserted): (Vec<String>, Vec<String>) = if deleted.len() == inserted.len() {
+            deleted
+                .iter()
+                .zip(&inserted)
+                .map(|(old, new)| highlight_words(old, new))
+                .unzip()
+        } else {
+            (
+                deleted.into_iter().map(str::to_owned).collect(),
+                inserted.into_iter().map(str::to_owned).collect(),
+            )
+        };
+        lines.extend(deleted.into_iter().map(|line| format!("-{line}")));
+        lines.extend(inserted.into_iter().map(|line| format!("+{line}")));
+    }
+
+    lines
+}
+
+/// Highlights the words that differ between two lines.
+///
+/// Returns the old line with deleted words marked as `[-word-]` and the new
+/// line with inserted words marked as `{+word+}`.
+fn highlight_words(old: &str, new: &str) -> (String, String) {
+    let old_words: Vec<&str> = old.split(' ').collect();
+    let new_words: Vec<&str> = new.split(' ').collect();
+
+    let mut old_line = Vec::with_capacity(old_words.len());
+    let mut new_line = Vec::with_capacity(new_words.len());
+    for change in lcs_diff(&old_words, &new_words) {
+        match change {
+            Change::Equal(word) => {
+                old_line.push(word.to_string());
+                new_line.push(word.to_string());
+            }
+            Change::Delete(word) => old_line.push(format!("[-{word}-]")),
+            Change::Insert(word) => new_line.push(format!("{{+{word}+}}")),
+        }
+    }
+
+    (old_line.join(" "), new_line.join(" "))
+}
+
+/// Computes the changes between two sequences using their longest common
+/// subsequence.
+///
+/// Deletions are placed before insertions in each block of changes.
+fn lcs_diff<'a, T: PartialEq>(old: &'a [T], new: &'a [T]) -> Vec<Change<'a, T>> {
+    // Common prefixes and suffixes are unchanged and need not be searched.
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut changes = Vec::with_capacity(old.len().max(new.len()));
+    changes.extend(old[..prefix].iter().map(Change::Equal));
+    changes.extend(shortest_edit_script(
+        &old[prefix..old.len() - suffix],
+        &new[prefix..new.len() - suffix],
+    ));
+    changes.extend(old[old.len() - suffix..].iter().map(Change::Equal));
+
+    let mut start = 0;
+    for end in 0..=changes.len() {
+        if end == changes.len() || matches!(changes[end], Change::Equal(_)) {
+            changes[start..end].sort_by_key(|change| matches!(change, Change::Insert(_)));
+            start = end + 1;
+        }
+    }
+
+    changes
+}
+
+/// Computes the shortest sequence of changes that turns one sequence into
+/// another using Myers' algorithm.
+///
+/// Runs in `O((n + m) * d)` time and `O(d * d)` space, where `n` and `m` are
+/// the lengths of the sequences and `d` is the number of changes.
+fn shortest_edit_script<'a, T: PartialEq>(old: &'a [T], new: &'a [T]) -> Vec<Change<'a, T>> {
+    let (n, m) = (old.len() as isize, new.len() as isize);
+    let max = old.len() + new.len();
+
+    // furthest[k + offset] is the furthest x reached on diagonal k = x - y.
+    let offset = max as isize + 1;
+    let mut furthest = vec![0isize; 2 * max + 3];
+
+    // trace[d] holds the furthest x on diagonals -d..=d before step d.
+    let mut trace = Vec::new();
+    'search: for d in 0..=max as isize {
+        trace.push(furthest[(offset - d) as usize..=(offset + d) as usize].to_vec());
+        for k in (-d..=d).step_by(2) {
+            let index = (k + offset) as usize;
+            let mut x = if k == -d || (k != d && furthest[index - 1] < furthest[index + 1]) {
+                furthest[index + 1] // Insertion.
+            } else {
+                furthest[index - 1] + 1 // Deletion.
+            };
+            let mut y = x - k;
+            while x < n && y < m && old[x as usize] == new[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            furthest[index] = x;
+
+            if x >= n && y >= m {
+                break 'search;
+            }
+        }
+    }
+
+    // Backtrack from the end of both sequences.
+    let mut changes = Vec::with_capacity(old.len().max(new.len()));
+    let (mut x, mut y) = (n, m);
+    for (d, furthest) in trace.iter().enumerate().skip(1).rev() {
+        let d = d as isize;
+        let k = x - y;
+        let furthest_on = |k: isize| furthest[(k + d) as usize];
+        let previous_k = if k == -d || (k != d && furthest_on(k - 1) < furthest_on(k + 1)) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let previous_x = furthest_on(previous_k);
+        let previous_y = previous_x - previous_k;
+
+        while x > previous_x && y > previous_y {
+            x -= 1;
+            y -= 1;
+            changes.push(Change::Equal(&old[x as usize]));
+        }
+        if x == previous_x {
+            y -= 1;
+            changes.push(Change::Insert(&new[y as usize]));
+        } else {
+            x -= 1;
+            changes.push(Change::Delete(&old[x as usize]));
+        }
+    }
+    while x > 0 && y > 0 {
+        x -= 1;
+        y -= 1;
+        changes.push(Change::Equal(&old[x as usize]));
+    }
+
+    changes.reverse();
+    changes
+}
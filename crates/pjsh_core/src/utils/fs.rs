@@ -51,3 +51,114 @@ pub fn resolve_path<P: AsRef<Path>>(context: &Context, path: P) -> PathBuf {
 
     canonical_path!(word_var(context, "PWD").unwrap_or("/"), path)
 }
+
+/// Returns the home directory of a user, as listed in the user database.
+#[cfg(unix)]
+pub fn user_home_dir(user: &str) -> Option<PathBuf> {
+    use std::ffi::{CStr, CString, OsStr};
+    use std::os::unix::ffi::OsStrExt;
+
+    let name = CString::new(user).ok()?;
+    let mut buffer = vec![0 as libc::c_char; 4096];
+    let mut entry = std::mem::MaybeUninit::<libc::passwd>::uninit();
+    let mut result = std::ptr::null_mut();
+
+    // SAFETY: All pointers are valid for the duration of the call, and the
+    // entry is only read if the call succeeds with a non-null result.
+    let status = unsafe {
+        libc::getpwnam_r(
+            name.as_ptr(),
+            entry.as_mut_ptr(),
+            buffer.as_mut_ptr(),
+            buffer.len(),
+            &mut result,
+        )
+    };
+    if status != 0 || result.is_null() {
+        return None;
+    }
+
+    // SAFETY: The entry was initialized by getpwnam_r, and its fields point into
+    // the buffer, which is still alive.
+    let dir = unsafe { CStr::from_ptr((*result).pw_dir) };
+    Some(PathBuf::from(OsStr::from_bytes(dir.to_bytes())))
+}
+
+/// Looking up the home directories of other users is not supported on this platform.
+#[cfg(not(unix))]
+pub fn user_home_dir(_user: &str) -> Option<PathBuf> {
+    None
+}
+
+/// Returns `true` if the shell's effective user may read a path.
+#[cfg(unix)]
+pub fn is_readable(path: &Path) -> bool {
+    is_accessible(path, libc::R_OK)
+}
+
+/// Returns `true` if a path exists.
+///
+/// Windows has no read permissions, so all existing paths are considered to be
+/// readable.
+#[cfg(not(unix))]
+pub fn is_readable(path: &Path) -> bool {
+    path.metadata().is_ok()
+}
+
+/// Returns `true` if the shell's effective user may write to a path.
+#[cfg(unix)]
+pub fn is_writable(path: &Path) -> bool {
+    is_accessible(path, libc::W_OK)
+}
+
+/// Returns `true` if a path exists and is not read-only.
+#[cfg(not(unix))]
+pub fn is_writable(path: &Path) -> bool {
+    path.metadata()
+        .is_ok_and(|metadata| !metadata.permissions().readonly())
+}
+
+/// Returns `true` if the shell's effective user may access a path in a mode,
+/// such as [`libc::R_OK`].
+///
+/// Unlike checking permission bits, this takes file ownership, group
+/// membership, and privileged users into account.
+#[cfg(unix)]
+fn is_accessible(path: &Path, mode: libc::c_int) -> bool {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let Ok(path) = CString::new(path.as_os_str().as_bytes()) else {
+        return false;
+    };
+
+    // SAFETY: The path is a valid NUL-terminated string for the duration of the call.
+    unsafe { libc::faccessat(libc::AT_FDCWD, path.as_ptr(), mode, libc::AT_EACCESS) == 0 }
+}
+
+/// Sets the file mode creation mask of the shell process, returning the previous mask.
+///
+/// The mask applies to files created by the shell, and is inherited by child processes.
+#[cfg(unix)]
+pub fn set_umask(mask: u32) -> std::io::Result<u32> {
+    // SAFETY: umask always succeeds, and only affects the current process.
+    let previous = unsafe { libc::umask(mask as libc::mode_t) };
+    Ok(previous as u32)
+}
+
+/// File mode creation masks are not supported on this platform.
+#[cfg(not(unix))]
+pub fn set_umask(_mask: u32) -> std::io::Result<u32> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "not supported on this platform",
+    ))
+}
+
+/// Returns the file mode creation mask of the shell process.
+pub fn umask() -> std::io::Result<u32> {
+    // The mask can only be read by replacing it.
+    let mask = set_umask(0o022)?;
+    set_umask(mask)?;
+    Ok(mask)
+}
@@ -0,0 +1,44 @@
+use std::path::{Path, PathBuf};
+
+/// Returns the name of the git branch that is checked out in a directory.
+///
+/// The directory and its ancestors are searched for a `.git` directory, or a
+/// `.git` file pointing to one, and the branch is read from its `HEAD` file.
+/// Git itself is never executed.
+///
+/// If `HEAD` is detached, its abbreviated commit hash is returned instead.
+///
+/// Returns `None` if the directory is not within a git repository.
+pub fn git_branch<P: AsRef<Path>>(dir: P) -> Option<String> {
+    let git_dir = find_git_dir(dir.as_ref())?;
+    let head = std::fs::read_to_string(git_dir.join("HEAD")).ok()?;
+    let head = head.trim();
+
+    if let Some(reference) = head.strip_prefix("ref:") {
+        let reference = reference.trim();
+        let branch = reference.strip_prefix("refs/heads/").unwrap_or(reference);
+        return Some(branch.to_owned());
+    }
+
+    head.get(..7).map(ToString::to_string)
+}
+
+/// Finds the git directory for a directory by searching its ancestors.
+fn find_git_dir(dir: &Path) -> Option<PathBuf> {
+    for ancestor in dir.ancestors() {
+        let git = ancestor.join(".git");
+
+        if git.is_dir() {
+            return Some(git);
+        }
+
+        // Worktrees and submodules use a file pointing to the actual git directory.
+        if git.is_file() {
+            let contents = std::fs::read_to_string(&git).ok()?;
+            let git_dir = PathBuf::from(contents.trim().strip_prefix("gitdir:")?.trim());
+            return Some(ancestor.join(git_dir));
+        }
+    }
+
+    None
+}
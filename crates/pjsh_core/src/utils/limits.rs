@@ -0,0 +1,158 @@
+use std::fmt::Display;
+
+/// A system resource whose usage can be limited for the shell process and its
+/// child processes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resource {
+    /// Maximum size of core files, in bytes.
+    CoreFileSize,
+
+    /// Maximum size of files written by a process, in bytes.
+    FileSize,
+
+    /// Maximum number of open file descriptors.
+    OpenFiles,
+
+    /// Maximum number of processes for the current user.
+    Processes,
+}
+
+impl Resource {
+    /// All resources, ordered by their flag.
+    pub const ALL: [Resource; 4] = [
+        Resource::CoreFileSize,
+        Resource::FileSize,
+        Resource::OpenFiles,
+        Resource::Processes,
+    ];
+
+    /// Returns a short human-readable description of the resource.
+    pub fn description(&self) -> &'static str {
+        match self {
+            Resource::CoreFileSize => "core file size",
+            Resource::FileSize => "file size",
+            Resource::OpenFiles => "open files",
+            Resource::Processes => "max user processes",
+        }
+    }
+}
+
+/// The limit of a resource.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceLimit {
+    /// The resource may be used without limits.
+    Unlimited,
+
+    /// The resource may be used up to a value.
+    Value(u64),
+}
+
+impl Display for ResourceLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ResourceLimit::Unlimited => write!(f, "unlimited"),
+            ResourceLimit::Value(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+/// Returns the soft and hard limits of a resource for the shell process.
+#[cfg(unix)]
+pub fn resource_limit(resource: Resource) -> std::io::Result<(ResourceLimit, ResourceLimit)> {
+    let mut limit = libc::rlimit {
+        rlim_cur: 0,
+        rlim_max: 0,
+    };
+
+    // SAFETY: getrlimit only writes to the supplied struct.
+    if unsafe { libc::getrlimit(rlimit_resource(resource), &mut limit) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+
+    Ok((from_rlim(limit.rlim_cur), from_rlim(limit.rlim_max)))
+}
+
+/// Sets the soft and hard limits of a resource for the shell process.
+///
+/// The limits are inherited by processes that are spawned by the shell.
+#[cfg(unix)]
+pub fn set_resource_limit(
+    resource: Resource,
+    soft: ResourceLimit,
+    hard: ResourceLimit,
+) -> std::io::Result<()> {
+    let limit = libc::rlimit {
+        rlim_cur: to_rlim(soft),
+        rlim_max: to_rlim(hard),
+    };
+
+    // SAFETY: setrlimit only reads the supplied struct, and only affects the current process.
+    if unsafe { libc::setrlimit(rlimit_resource(resource), &limit) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+
+    Ok(())
+}
+
+/// Resource limits are not supported on this platform.
+#[cfg(not(unix))]
+pub fn resource_limit(_resource: Resource) -> std::io::Result<(ResourceLimit, ResourceLimit)> {
+    Err(unsupported())
+}
+
+/// Resource limits are not supported on this platform.
+#[cfg(not(unix))]
+pub fn set_resource_limit(
+    _resource: Resource,
+    _soft: ResourceLimit,
+    _hard: ResourceLimit,
+) -> std::io::Result<()> {
+    Err(unsupported())
+}
+
+/// Returns an error for resource limits on unsupported platforms.
+#[cfg(not(unix))]
+fn unsupported() -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "not supported on this platform",
+    )
+}
+
+/// Returns the identifier of a resource for `getrlimit` and `setrlimit`.
+#[cfg(unix)]
+fn rlimit_resource(resource: Resource) -> RlimitResource {
+    match resource {
+        Resource::CoreFileSize => libc::RLIMIT_CORE,
+        Resource::FileSize => libc::RLIMIT_FSIZE,
+        Resource::OpenFiles => libc::RLIMIT_NOFILE,
+        Resource::Processes => libc::RLIMIT_NPROC,
+    }
+}
+
+/// Type of resource identifiers accepted by `getrlimit` and `setrlimit`.
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+type RlimitResource = libc::__rlimit_resource_t;
+
+/// Type of resource identifiers accepted by `getrlimit` and `setrlimit`.
+#[cfg(all(unix, not(all(target_os = "linux", target_env = "gnu"))))]
+type RlimitResource = libc::c_int;
+
+/// Converts a raw limit into a [`ResourceLimit`].
+#[cfg(unix)]
+#[allow(clippy::unnecessary_cast)] // `rlim_t` is not 64 bits wide on all platforms.
+fn from_rlim(value: libc::rlim_t) -> ResourceLimit {
+    match value {
+        libc::RLIM_INFINITY => ResourceLimit::Unlimited,
+        value => ResourceLimit::Value(value as u64),
+    }
+}
+
+/// Converts a [`ResourceLimit`] into a raw limit.
+#[cfg(unix)]
+fn to_rlim(limit: ResourceLimit) -> libc::rlim_t {
+    match limit {
+        ResourceLimit::Unlimited => libc::RLIM_INFINITY,
+        ResourceLimit::Value(value) => libc::rlim_t::try_from(value).unwrap_or(libc::RLIM_INFINITY),
+    }
+}
@@ -1,9 +1,19 @@
+mod bookmarks;
+mod diff;
 mod fs;
+mod git;
+mod limits;
 
 #[cfg(test)]
 mod tests;
 
-pub use fs::{path_to_string, resolve_path};
+pub use bookmarks::{bookmarks_file, Bookmarks, BookmarksLock};
+pub use diff::diff_lines;
+pub use fs::{
+    is_readable, is_writable, path_to_string, resolve_path, set_umask, umask, user_home_dir,
+};
+pub use git::git_branch;
+pub use limits::{resource_limit, set_resource_limit, Resource, ResourceLimit};
 
 use crate::{env::context::Value, Context};
 
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use super::*;
 use crate::{env::context::Value, Context};
@@ -26,7 +26,172 @@ fn test_resolve_path_with_empty_context() {
 #[test]
 fn test_resolve_path_with_linux_pwd_context() {
     let mut ctx = Context::default();
-    ctx.set_var("PWD".into(), Value::Word("/base".into()));
+    ctx.set_var("PWD".into(), Value::Word("/base".into()))
+        .unwrap();
     assert_eq!(resolve_path(&ctx, "child"), PathBuf::from("/base/child"));
     assert_eq!(resolve_path(&ctx, "/absolute"), PathBuf::from("/absolute"));
 }
+
+#[test]
+fn test_git_branch() {
+    let repo = tempfile::tempdir().unwrap();
+    let nested = repo.path().join("nested/dir");
+    std::fs::create_dir_all(repo.path().join(".git")).unwrap();
+    std::fs::create_dir_all(&nested).unwrap();
+
+    std::fs::write(repo.path().join(".git/HEAD"), "ref: refs/heads/main\n").unwrap();
+    assert_eq!(git_branch(repo.path()), Some("main".into()));
+    assert_eq!(git_branch(&nested), Some("main".into()));
+
+    // Detached heads are represented by abbreviated commit hashes.
+    std::fs::write(
+        repo.path().join(".git/HEAD"),
+        "0123456789abcdef0123456789abcdef01234567\n",
+    )
+    .unwrap();
+    assert_eq!(git_branch(&nested), Some("0123456".into()));
+}
+
+#[test]
+fn test_git_branch_outside_repository() {
+    let dir = tempfile::tempdir().unwrap();
+    assert_eq!(git_branch(dir.path()), None);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_user_home_dir() {
+    assert!(user_home_dir("root").is_some());
+    assert_eq!(user_home_dir("pjsh-missing-user"), None);
+}
+
+#[test]
+fn test_bookmarks_roundtrip() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join(".pjsh/bookmarks");
+
+    let mut bookmarks = Bookmarks::load(&file);
+    assert_eq!(bookmarks, Bookmarks::default());
+    assert!(bookmarks
+        .insert("proj", PathBuf::from("/work/project"))
+        .is_ok());
+    assert!(bookmarks
+        .insert("docs", PathBuf::from("/work/docs"))
+        .is_ok());
+    assert_eq!(
+        bookmarks.insert("not valid", PathBuf::from("/invalid")),
+        Err("not valid: invalid bookmark name".into())
+    );
+    assert_eq!(
+        bookmarks.insert("lines", PathBuf::from("/work\nproj\t/etc")),
+        Err("/work\nproj\t/etc: paths cannot contain line breaks".into())
+    );
+    bookmarks.save(&file).unwrap();
+
+    let loaded = Bookmarks::load(&file);
+    assert_eq!(loaded.get("proj"), Some(Path::new("/work/project")));
+    assert_eq!(
+        loaded.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+        vec!["docs", "proj"]
+    );
+}
+
+#[test]
+fn test_bookmarks_lock_concurrent_updates() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join(".pjsh/bookmarks");
+
+    let threads: Vec<_> = (0..8)
+        .map(|i| {
+            let file = file.clone();
+            std::thread::spawn(move || {
+                let _lock = BookmarksLock::acquire(&file).unwrap();
+                let mut bookmarks = Bookmarks::load(&file);
+                std::thread::sleep(std::time::Duration::from_millis(10));
+                bookmarks
+                    .insert(&format!("b{i}"), PathBuf::from("/work"))
+                    .unwrap();
+                bookmarks.save(&file).unwrap();
+            })
+        })
+        .collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+
+    assert_eq!(Bookmarks::load(&file).iter().count(), 8);
+}
+
+#[test]
+fn test_bookmarks_ignore_corrupt_lines() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("bookmarks");
+    std::fs::write(
+        &file,
+        b"proj\t/work/project\ngarbage\n\t/empty\n\xff\xfe\tbad\n",
+    )
+    .unwrap();
+
+    let bookmarks = Bookmarks::load(&file);
+    assert_eq!(
+        bookmarks.iter().collect::<Vec<_>>(),
+        vec![("proj", Path::new("/work/project"))]
+    );
+}
+
+#[test]
+fn test_diff_lines_of_identical_inputs() {
+    assert!(diff_lines(&["same", "lines"], &["same", "lines"]).is_empty());
+    assert!(diff_lines::<&str>(&[], &[]).is_empty());
+}
+
+#[test]
+fn test_diff_lines_with_insertions_and_deletions() {
+    assert_eq!(
+        diff_lines(&["a", "b", "c"], &["a", "c", "d"]),
+        vec![" a", "-b", " c", "+d"]
+    );
+    assert_eq!(diff_lines(&[], &["new"]), vec!["+new"]);
+    assert_eq!(diff_lines(&["old"], &[]), vec!["-old"]);
+}
+
+#[test]
+fn test_diff_lines_with_modifications() {
+    assert_eq!(
+        diff_lines(
+            &["first", "the quick fox", "last"],
+            &["first", "the slow fox", "last"]
+        ),
+        vec![" first", "-the [-quick-] fox", "+the {+slow+} fox", " last"]
+    );
+}
+
+#[test]
+fn test_diff_lines_with_minimal_changes() {
+    let lines = diff_lines(
+        &["a", "b", "c", "a", "b", "b", "a"],
+        &["c", "b", "a", "b", "a", "c"],
+    );
+    assert_eq!(
+        lines.iter().filter(|line| !line.starts_with(' ')).count(),
+        5
+    );
+}
+
+#[test]
+fn test_diff_lines_of_large_inputs() {
+    let old: Vec<String> = (0..50_000).map(|line| line.to_string()).collect();
+    let mut new = old.clone();
+    new[10] = "changed".into();
+    new.remove(40_000);
+    new.push("appended".into());
+
+    let changes: Vec<String> = diff_lines(&old, &new)
+        .into_iter()
+        .filter(|line| !line.starts_with(' '))
+        .collect();
+    assert_eq!(
+        changes,
+        vec!["-[-10-]", "+{+changed+}", "-40000", "+appended"]
+    );
+}
@@ -1,27 +1,72 @@
 use pjsh_core::{
-    command::{Action, CommandType},
+    command::{Action, BuiltinCommandResult, CommandResult, CommandType},
     find_in_path, Context,
 };
 use pjsh_parse::parse_interpolation;
 
 use crate::{
+    execute_statements,
+    flow::Flow,
     interpolate_word,
     resolve::{resolve_command, ResolvedCommand},
-    EvalResult,
+    EvalError, EvalResult,
 };
 
+/// Handles the actions requested by a built-in command.
+///
+/// Returns the command's result without any actions, using an exit code that
+/// has been replaced by the last handled action, if any.
+///
+/// # Errors
+///
+/// Returns an error if an action cannot be handled, or if it results in control
+/// flow that should be propagated to the calling scope.
+pub(crate) fn handle_actions(
+    result: BuiltinCommandResult,
+    context: &mut Context,
+) -> EvalResult<CommandResult> {
+    let mut exit_code = result.code;
+    for action in &result.actions {
+        if let Some(code) = handle_action(action, context)? {
+            exit_code = code;
+        }
+    }
+    Ok(CommandResult::code(exit_code))
+}
+
 /// Handles an action.
-pub(crate) fn handle_action(action: &Action, context: &mut Context) -> EvalResult<()> {
+///
+/// Returns an exit code that replaces the command's own exit code, if any.
+///
+/// # Errors
+///
+/// Returns [`EvalError::Exit`] if the current scope should be exited. The exit
+/// code is registered before returning.
+fn handle_action(action: &Action, context: &mut Context) -> EvalResult<Option<i32>> {
     match action {
-        Action::ExitScope(_code) => todo!(),
+        Action::ExitScope(code) => {
+            context.register_exit(*code);
+            Err(EvalError::Exit(*code))
+        }
+        Action::Execute(program) => {
+            context.register_exit(0);
+            match execute_statements(&program.statements, context)? {
+                Flow::Proceed => Ok(Some(context.last_exit())),
+                flow => Err(EvalError::ControlFlow(flow)),
+            }
+        }
         Action::Interpolate(word, callback) => {
             let result = parse_interpolation(word)
-                .map_err(|error| format!("{error}"))
+                .map_err(|error| {
+                    error
+                        .snippet(word, false)
+                        .unwrap_or_else(|| error.to_string())
+                })
                 .and_then(|word| {
                     interpolate_word(&word, context).map_err(|error| format!("{error}"))
                 });
             callback(context.io(), result);
-            Ok(())
+            Ok(None)
         }
         Action::ResolveCommandType(name, callback) => {
             let command_type = if let Some(alias) = context.aliases.get(name) {
@@ -36,12 +81,17 @@ pub(crate) fn handle_action(action: &Action, context: &mut Context) -> EvalResul
             };
 
             callback(context.io(), name.clone(), command_type);
-            Ok(())
+            Ok(None)
         }
         Action::ResolveCommandPath(name, callback) => {
             let path = find_in_path(name, context);
             callback(name.clone(), context.io(), path.as_ref());
-            Ok(())
+            Ok(None)
         }
+        Action::KeepRedirects => Ok(None), // Handled when executing the command.
+        Action::ShiftArgs(n) => match context.shift_args(*n) {
+            true => Ok(None),
+            false => Ok(Some(1)),
+        },
     }
 }
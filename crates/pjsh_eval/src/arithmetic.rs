@@ -0,0 +1,399 @@
+use std::{fmt::Display, iter::Peekable, num::IntErrorKind, str::Chars};
+
+use pjsh_core::{Context, Value};
+
+use crate::error::{EvalError, EvalResult};
+
+/// A number resulting from evaluating an arithmetic expression.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Number {
+    /// An integer.
+    Integer(i64),
+
+    /// A floating point number.
+    Float(f64),
+}
+
+impl Number {
+    /// Returns `true` if the number is zero.
+    pub fn is_zero(&self) -> bool {
+        match self {
+            Number::Integer(integer) => *integer == 0,
+            Number::Float(float) => *float == 0.0,
+        }
+    }
+
+    /// Returns the number as a floating point number.
+    fn as_float(self) -> f64 {
+        match self {
+            Number::Integer(integer) => integer as f64,
+            Number::Float(float) => float,
+        }
+    }
+}
+
+impl Display for Number {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Number::Integer(integer) => write!(f, "{integer}"),
+            Number::Float(float) => write!(f, "{float}"),
+        }
+    }
+}
+
+/// Evaluates an arithmetic expression, such as `(i + 1) * 2`, within a context.
+///
+/// Supported operators are `+`, `-`, `*`, `/`, `%`, and the comparison
+/// operators `==`, `!=`, `<`, `<=`, `>`, and `>=`, which evaluate to `1` if
+/// true and `0` if false. Parentheses can be used for grouping.
+///
+/// Variables can be referenced by name, with or without a leading `$`. Unset
+/// variables are errors, or evaluate to `0` if the `nounset` option is unset.
+///
+/// Integers are used unless at least one operand is a floating point number.
+pub fn evaluate_arithmetic(expression: &str, context: &Context) -> EvalResult<Number> {
+    let mut parser = ArithmeticParser {
+        chars: expression.chars().peekable(),
+        context,
+    };
+
+    parser
+        .comparison()
+        .and_then(|number| match parser.peek() {
+            None => Ok(number),
+            Some(ch) => Err(format!("unexpected character '{ch}'")),
+        })
+        .map_err(|message| EvalError::InvalidArithmetic(expression.to_owned(), message))
+}
+
+/// A recursive descent parser that evaluates arithmetic expressions while parsing them.
+struct ArithmeticParser<'a> {
+    /// Remaining characters of the expression.
+    chars: Peekable<Chars<'a>>,
+
+    /// Context in which variables are resolved.
+    context: &'a Context,
+}
+
+impl ArithmeticParser<'_> {
+    /// Evaluates a comparison between two sums.
+    fn comparison(&mut self) -> Result<Number, String> {
+        let left = self.sum()?;
+
+        let operator = match self.peek() {
+            Some(ch @ ('<' | '>')) => {
+                self.chars.next();
+                match self.chars.next_if_eq(&'=') {
+                    Some(_) => format!("{ch}="),
+                    None => ch.to_string(),
+                }
+            }
+            Some(ch @ ('=' | '!')) => {
+                self.chars.next();
+                if self.chars.next_if_eq(&'=').is_none() {
+                    return Err(format!("unexpected character '{ch}'"));
+                }
+                format!("{ch}=")
+            }
+            _ => return Ok(left),
+        };
+
+        let right = self.sum()?;
+        let result = match (left, right) {
+            (Number::Integer(left), Number::Integer(right)) => compare(&operator, left, right),
+            (left, right) => compare(&operator, left.as_float(), right.as_float()),
+        };
+
+        Ok(Number::Integer(i64::from(result)))
+    }
+
+    /// Evaluates a sequence of terms separated by `+` or `-`.
+    fn sum(&mut self) -> Result<Number, String> {
+        let mut result = self.term()?;
+
+        while let Some(operator @ ('+' | '-')) = self.peek() {
+            self.chars.next();
+            let right = self.term()?;
+            result = apply(operator, result, right)?;
+        }
+
+        Ok(result)
+    }
+
+    /// Evaluates a sequence of factors separated by `*`, `/`, or `%`.
+    fn term(&mut self) -> Result<Number, String> {
+        let mut result = self.factor()?;
+
+        while let Some(operator @ ('*' | '/' | '%')) = self.peek() {
+            self.chars.next();
+            let right = self.factor()?;
+            result = apply(operator, result, right)?;
+        }
+
+        Ok(result)
+    }
+
+    /// Evaluates a number, a variable, or a parenthesized expression, along
+    /// with any leading signs.
+    fn factor(&mut self) -> Result<Number, String> {
+        match self.peek() {
+            Some('-') => {
+                self.chars.next();
+                apply('-', Number::Integer(0), self.factor()?)
+            }
+            Some('+') => {
+                self.chars.next();
+                self.factor()
+            }
+            Some('(') => {
+                self.chars.next();
+                let result = self.comparison()?;
+                match self.peek() {
+                    Some(')') => {
+                        self.chars.next();
+                        Ok(result)
+                    }
+                    Some(ch) => Err(format!("unexpected character '{ch}'")),
+                    None => Err("missing closing parenthesis".to_owned()),
+                }
+            }
+            Some(ch) if ch.is_ascii_digit() || ch == '.' => {
+                let literal = self.take_while(|c| c.is_ascii_alphanumeric() || c == '.');
+                parse_number(&literal).map_err(|error| match error {
+                    NumberError::Invalid => format!("invalid number '{literal}'"),
+                    NumberError::Overflow => format!("integer overflow in '{literal}'"),
+                })
+            }
+            Some(ch) if ch.is_alphabetic() || ch == '_' || ch == '$' => {
+                self.chars.next_if_eq(&'$');
+                let name = self.take_while(|c| c.is_alphanumeric() || c == '_');
+                self.variable(&name)
+            }
+            Some(ch) => Err(format!("unexpected character '{ch}'")),
+            None => Err("unexpected end of expression".to_owned()),
+        }
+    }
+
+    /// Resolves the numeric value of a variable.
+    fn variable(&self, name: &str) -> Result<Number, String> {
+        match self.context.get_var_value(name) {
+            Some(Value::Word(value)) => parse_number(value.trim()).map_err(|error| match error {
+                NumberError::Invalid => format!("variable '{name}' is not a number: '{value}'"),
+                NumberError::Overflow => {
+                    format!("integer overflow in variable '{name}': '{value}'")
+                }
+            }),
+            Some(Value::List(_)) => Err(format!("variable '{name}' is a list")),
+            None if name.is_empty() => Err("missing variable name".to_owned()),
+            None if self.context.options.nounset => Err(format!("undefined variable '{name}'")),
+            None => Ok(Number::Integer(0)),
+        }
+    }
+
+    /// Returns the next non-whitespace character without consuming it.
+    fn peek(&mut self) -> Option<char> {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+        self.chars.peek().copied()
+    }
+
+    /// Consumes characters while they match a predicate.
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> String {
+        let mut taken = String::new();
+        while let Some(ch) = self.chars.next_if(|c| predicate(*c)) {
+            taken.push(ch);
+        }
+        taken
+    }
+}
+
+/// Reasons for a literal not being a number.
+enum NumberError {
+    /// The literal is not a number.
+    Invalid,
+
+    /// The literal is an integer that does not fit in 64 bits.
+    Overflow,
+}
+
+/// Parses an integer or a floating point number.
+///
+/// Integers that are too large are not converted into floating point numbers.
+fn parse_number(literal: &str) -> Result<Number, NumberError> {
+    let error = match literal.parse() {
+        Ok(integer) => return Ok(Number::Integer(integer)),
+        Err(error) => error,
+    };
+
+    let overflows = [IntErrorKind::PosOverflow, IntErrorKind::NegOverflow];
+    if overflows.contains(error.kind()) {
+        return Err(NumberError::Overflow);
+    }
+
+    let unsigned = literal.strip_prefix('-').unwrap_or(literal);
+    match literal.parse::<f64>() {
+        Ok(float) if unsigned.chars().all(|c| c.is_ascii_digit() || c == '.') => {
+            Ok(Number::Float(float))
+        }
+        _ => Err(NumberError::Invalid),
+    }
+}
+
+/// Applies a binary arithmetic operator.
+fn apply(operator: char, left: Number, right: Number) -> Result<Number, String> {
+    if matches!(operator, '/' | '%') && right.is_zero() {
+        return Err("division by zero".to_owned());
+    }
+
+    match (left, right) {
+        (Number::Integer(left), Number::Integer(right)) => {
+            let result = match operator {
+                '+' => left.checked_add(right),
+                '-' => left.checked_sub(right),
+                '*' => left.checked_mul(right),
+                '/' => left.checked_div(right),
+                _ => left.checked_rem(right),
+            };
+            result
+                .map(Number::Integer)
+                .ok_or_else(|| "integer overflow".to_owned())
+        }
+        (left, right) => {
+            let (left, right) = (left.as_float(), right.as_float());
+            Ok(Number::Float(match operator {
+                '+' => left + right,
+                '-' => left - right,
+                '*' => left * right,
+                '/' => left / right,
+                _ => left % right,
+            }))
+        }
+    }
+}
+
+/// Compares two values using a comparison operator.
+fn compare<T: PartialOrd>(operator: &str, left: T, right: T) -> bool {
+    match operator {
+        "<" => left < right,
+        "<=" => left <= right,
+        ">" => left > right,
+        ">=" => left >= right,
+        "==" => left == right,
+        _ => left != right,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    use pjsh_core::{Scope, Value};
+
+    use super::*;
+
+    fn context() -> Context {
+        Context::with_scopes(vec![Scope::new(
+            String::new(),
+            None,
+            HashMap::from([
+                ("i".into(), Some(Value::Word("4".into()))),
+                ("half".into(), Some(Value::Word("0.5".into()))),
+                ("negative".into(), Some(Value::Word("-1.5".into()))),
+                ("text".into(), Some(Value::Word("abc".into()))),
+                (
+                    "huge".into(),
+                    Some(Value::Word("-99999999999999999999".into())),
+                ),
+            ]),
+            HashMap::default(),
+            HashSet::default(),
+        )])
+    }
+
+    fn eval(expression: &str) -> Result<Number, String> {
+        evaluate_arithmetic(expression, &context()).map_err(|error| error.to_string())
+    }
+
+    #[test]
+    fn it_evaluates_integer_arithmetic() {
+        assert_eq!(eval("1 + 2 * 3"), Ok(Number::Integer(7)));
+        assert_eq!(eval("(1 + 2) * 3"), Ok(Number::Integer(9)));
+        assert_eq!(eval("7 / 2"), Ok(Number::Integer(3)));
+        assert_eq!(eval("7 % 4 - -1"), Ok(Number::Integer(4)));
+        assert_eq!(eval("i + $i"), Ok(Number::Integer(8)));
+
+        let mut context = context();
+        context.options.nounset = false;
+        assert_eq!(
+            evaluate_arithmetic("undefined + 1", &context).ok(),
+            Some(Number::Integer(1))
+        );
+    }
+
+    #[test]
+    fn it_evaluates_float_arithmetic() {
+        assert_eq!(eval("7.0 / 2"), Ok(Number::Float(3.5)));
+        assert_eq!(eval("i * half"), Ok(Number::Float(2.0)));
+        assert_eq!(eval("negative * 2"), Ok(Number::Float(-3.0)));
+        assert_eq!(Number::Float(3.5).to_string(), "3.5");
+    }
+
+    #[test]
+    fn it_evaluates_comparisons() {
+        assert_eq!(eval("i > 3"), Ok(Number::Integer(1)));
+        assert_eq!(eval("i <= 3"), Ok(Number::Integer(0)));
+        assert_eq!(eval("i == 2 * 2"), Ok(Number::Integer(1)));
+        assert_eq!(eval("half != 0.5"), Ok(Number::Integer(0)));
+    }
+
+    #[test]
+    fn it_rejects_invalid_expressions() {
+        assert_eq!(
+            eval("i / (2 - 2)"),
+            Err("invalid arithmetic expression 'i / (2 - 2)': division by zero".into())
+        );
+        assert_eq!(
+            eval("1 +"),
+            Err("invalid arithmetic expression '1 +': unexpected end of expression".into())
+        );
+        assert_eq!(
+            eval("(1"),
+            Err("invalid arithmetic expression '(1': missing closing parenthesis".into())
+        );
+        assert_eq!(
+            eval("1 2"),
+            Err("invalid arithmetic expression '1 2': unexpected character '2'".into())
+        );
+        assert_eq!(
+            eval("undefined + 1"),
+            Err(
+                "invalid arithmetic expression 'undefined + 1': undefined variable 'undefined'"
+                    .into()
+            )
+        );
+        assert_eq!(
+            eval("text + 1"),
+            Err(
+                "invalid arithmetic expression 'text + 1': variable 'text' is not a number: 'abc'"
+                    .into()
+            )
+        );
+        assert_eq!(
+            eval("9223372036854775807 + 1"),
+            Err("invalid arithmetic expression '9223372036854775807 + 1': integer overflow".into())
+        );
+        assert_eq!(
+            eval("9223372036854775808 + 1"),
+            Err(
+                "invalid arithmetic expression '9223372036854775808 + 1': integer overflow in '9223372036854775808'"
+                    .into()
+            )
+        );
+        assert_eq!(
+            eval("huge"),
+            Err(
+                "invalid arithmetic expression 'huge': integer overflow in variable 'huge': '-99999999999999999999'"
+                    .into()
+            )
+        );
+    }
+}
@@ -2,6 +2,7 @@
     collections::{HashMap, HashSet},
     path::Path,
     process,
+    time::Instant,
 };
 
 use pjsh_ast::Function;
@@ -14,6 +15,7 @@
 use crate::{
     error::{EvalError, EvalResult},
     execute_statements,
+    flow::Flow,
 };
 
 /// Calls a built-in command.
@@ -36,7 +38,10 @@ pub fn call_external_program<P: AsRef<Path>>(
     args: &[String],
     context: &mut Context,
 ) -> EvalResult<process::Command> {
+    // The new process should only inherit the context's exported variables, which
+    // include the shell's own environment unless it has been unset.
     let mut cmd = process::Command::new(program.as_ref());
+    cmd.env_clear();
     cmd.envs(context.exported_vars());
     cmd.args(args);
 
@@ -71,7 +76,7 @@ pub fn call_function(
     // Ensure that values are provided for all named arguments.
     if function_args.len() < function.args.len() {
         return Err(EvalError::UndefinedFunctionArguments(
-            function.args[args.len()..].to_vec(),
+            function.args[function_args.len()..].to_vec(),
         ));
     }
 
@@ -87,30 +92,43 @@ pub fn call_function(
             .args
             .iter()
             .cloned()
-            .zip(args.iter().cloned().map(Value::Word).map(Some)),
+            .zip(function_args.iter().cloned().map(Value::Word).map(Some)),
     );
 
     if let Some(list_arg_name) = &function.list_arg {
-        let list_args = &args[function.args.len()..];
+        let list_args = &function_args[function.args.len()..];
         vars.insert(
             list_arg_name.clone(),
             Some(Value::List(Vec::from(list_args))),
         );
     }
 
-    context.push_scope(Scope::new(
+    let scope = Scope::new(
         function.name.clone(),
         Some(Vec::from(args)),
         vars,
         HashMap::new(),
         HashSet::new(),
-    ));
-
-    let result = execute_statements(&function.body.statements, context);
+    );
 
-    context.pop_scope();
+    let start = context.profile.is_some().then(Instant::now);
+    let (result, code) = context.scoped(scope, |context| {
+        let result =
+            execute_statements(&function.body.statements, context).and_then(Flow::exit_function);
+
+        // The function's exit code is that of its last statement unless returned explicitly.
+        let code = match &result {
+            Ok(Some(code)) => *code,
+            _ => context.last_exit(),
+        };
+        (result, code)
+    });
+
+    if let (Some(start), Some(profile)) = (start, &mut context.profile) {
+        profile.record_function(&function.name, start.elapsed());
+    }
 
-    result.map(|_| CommandResult::code(0))
+    result.map(|_| CommandResult::code(code))
 }
 
 #[cfg(test)]
@@ -148,9 +166,10 @@ fn test_call_builtin_command() -> EvalResult<()> {
         let command = MyBuiltin;
 
         let CommandResult::Builtin(result) =
-            call_builtin_command(&command, &["mybuiltin".into()], &mut context)? else {
-                unreachable!()
-            };
+            call_builtin_command(&command, &["mybuiltin".into()], &mut context)?
+        else {
+            unreachable!()
+        };
         assert_eq!(result.code, 0);
         Ok(())
     }
@@ -1,7 +1,11 @@
-use std::path::PathBuf;
+use std::{path::PathBuf, time::SystemTime};
 
+use is_executable::is_executable;
 use pjsh_ast::{Condition, Word};
-use pjsh_core::{utils::resolve_path, Context};
+use pjsh_core::{
+    utils::{is_readable, is_writable, resolve_path},
+    Context,
+};
 use regex::RegexBuilder;
 
 use crate::{error::EvalResult, interpolate_word, EvalError};
@@ -19,6 +23,14 @@ pub fn eval_condition(condition: &Condition, context: &Context) -> EvalResult<bo
         Condition::IsDirectory(path) => if_path(path, context, |p| p.is_dir()),
         Condition::IsFile(path) => if_path(path, context, |p| p.is_file()),
         Condition::IsPath(path) => if_path(path, context, |p| p.exists()),
+        Condition::IsReadable(path) => if_path(path, context, |p| is_readable(&p)),
+        Condition::IsWritable(path) => if_path(path, context, |p| is_writable(&p)),
+        Condition::IsExecutable(path) => if_path(path, context, is_executable),
+        Condition::IsNonEmpty(path) => if_path(path, context, |p| {
+            p.metadata().is_ok_and(|metadata| metadata.len() > 0)
+        }),
+        Condition::IsNewer(a, b) => if_modified(a, b, context, |a, b| a > b),
+        Condition::IsOlder(a, b) => if_modified(a, b, context, |a, b| a < b),
         Condition::Empty(word) => Ok(interpolate_word(word, context)?.is_empty()),
         Condition::NotEmpty(word) => Ok(!interpolate_word(word, context)?.is_empty()),
         Condition::Eq(a, b) => if_compare(a, b, context, |a, b| a == b),
@@ -56,6 +68,35 @@ fn if_path<F: Fn(PathBuf) -> bool>(path: &Word, context: &Context, func: F) -> E
     Ok(func(path))
 }
 
+/// Returns the result of a boolean function comparing the modification times
+/// of two paths after interpolating and resolving them.
+///
+/// Returns `false` if either path does not exist.
+///
+/// # Errors
+///
+/// This function will return an error if any of the given words cannot be
+/// interpolated.
+fn if_modified<F: Fn(SystemTime, SystemTime) -> bool>(
+    a: &Word,
+    b: &Word,
+    context: &Context,
+    func: F,
+) -> EvalResult<bool> {
+    let modified = |word| -> EvalResult<Option<SystemTime>> {
+        let path = resolve_path(context, interpolate_word(word, context)?);
+        Ok(path
+            .metadata()
+            .and_then(|metadata| metadata.modified())
+            .ok())
+    };
+
+    match (modified(a)?, modified(b)?) {
+        (Some(a), Some(b)) => Ok(func(a, b)),
+        _ => Ok(false),
+    }
+}
+
 /// Returns `true` if a word matches a regex pattern.
 ///
 /// # Errors
@@ -126,6 +167,114 @@ fn test_is_path() {
         });
     }
 
+    #[test]
+    fn test_is_readable_and_writable() {
+        in_temp_fs(|file, dir| {
+            let context = Context::default();
+            assert!(eval_condition(&Condition::IsReadable(file.clone()), &context).unwrap());
+            assert!(eval_condition(&Condition::IsWritable(file), &context).unwrap());
+            assert!(eval_condition(&Condition::IsReadable(dir.clone()), &context).unwrap());
+            assert!(eval_condition(&Condition::IsWritable(dir), &context).unwrap());
+
+            let missing = Word::Literal("/path/to/missing/file".into());
+            assert!(!eval_condition(&Condition::IsReadable(missing.clone()), &context).unwrap());
+            assert!(!eval_condition(&Condition::IsWritable(missing), &context).unwrap());
+        });
+    }
+
+    #[test]
+    fn test_is_writable_read_only() {
+        let file = NamedTempFile::new().expect("Temporary file can be created");
+        let mut permissions = file.path().metadata().unwrap().permissions();
+        permissions.set_readonly(true);
+        std::fs::set_permissions(file.path(), permissions).unwrap();
+
+        let word = Word::Literal(file.path().to_string_lossy().to_string());
+        let context = Context::default();
+        assert!(eval_condition(&Condition::IsReadable(word.clone()), &context).unwrap());
+
+        // Privileged users may write to read-only files.
+        let can_write = std::fs::OpenOptions::new()
+            .write(true)
+            .open(file.path())
+            .is_ok();
+        assert_eq!(
+            eval_condition(&Condition::IsWritable(word), &context).unwrap(),
+            can_write
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_is_readable_without_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let file = NamedTempFile::new().expect("Temporary file can be created");
+        std::fs::set_permissions(file.path(), std::fs::Permissions::from_mode(0o000)).unwrap();
+
+        // Privileged users may read files without read permissions.
+        let can_read = std::fs::File::open(file.path()).is_ok();
+        let word = Word::Literal(file.path().to_string_lossy().to_string());
+        let context = Context::default();
+        assert_eq!(
+            eval_condition(&Condition::IsReadable(word), &context).unwrap(),
+            can_read
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_is_executable() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let file = NamedTempFile::new().expect("Temporary file can be created");
+        let word = Word::Literal(file.path().to_string_lossy().to_string());
+        let context = Context::default();
+        assert!(!eval_condition(&Condition::IsExecutable(word.clone()), &context).unwrap());
+
+        std::fs::set_permissions(file.path(), std::fs::Permissions::from_mode(0o755)).unwrap();
+        assert!(eval_condition(&Condition::IsExecutable(word), &context).unwrap());
+    }
+
+    #[test]
+    fn test_is_non_empty() {
+        let mut file = NamedTempFile::new().expect("Temporary file can be created");
+        let word = Word::Literal(file.path().to_string_lossy().to_string());
+        let context = Context::default();
+        assert!(!eval_condition(&Condition::IsNonEmpty(word.clone()), &context).unwrap());
+
+        std::io::Write::write_all(&mut file, b"contents").unwrap();
+        assert!(eval_condition(&Condition::IsNonEmpty(word), &context).unwrap());
+
+        let missing = Word::Literal("/path/to/missing/file".into());
+        assert!(!eval_condition(&Condition::IsNonEmpty(missing), &context).unwrap());
+    }
+
+    #[test]
+    fn test_is_newer_and_older() {
+        let old = NamedTempFile::new().expect("Temporary file can be created");
+        let new = NamedTempFile::new().expect("Temporary file can be created");
+        let now = SystemTime::now();
+        old.as_file()
+            .set_modified(now - std::time::Duration::from_secs(60))
+            .unwrap();
+        new.as_file().set_modified(now).unwrap();
+
+        let old = Word::Literal(old.path().to_string_lossy().to_string());
+        let new = Word::Literal(new.path().to_string_lossy().to_string());
+        let missing = Word::Literal("/path/to/missing/file".into());
+        let context = Context::default();
+        let eval = |condition| eval_condition(&condition, &context).unwrap();
+
+        assert!(eval(Condition::IsNewer(new.clone(), old.clone())));
+        assert!(!eval(Condition::IsNewer(old.clone(), new.clone())));
+        assert!(eval(Condition::IsOlder(old.clone(), new.clone())));
+        assert!(!eval(Condition::IsOlder(new.clone(), old.clone())));
+
+        assert!(!eval(Condition::IsNewer(new.clone(), missing.clone())));
+        assert!(!eval(Condition::IsOlder(missing, new)));
+    }
+
     #[test]
     fn test_empty() {
         let empty = Word::Literal(String::new());
@@ -1,7 +1,9 @@
-use std::fmt::Display;
+use std::{fmt::Display, path::PathBuf};
 
 use pjsh_core::{FileDescriptorError, FilterError};
 
+use crate::flow::Flow;
+
 pub type EvalResult<T> = Result<T, EvalError>;
 
 #[derive(Debug)]
@@ -9,19 +11,31 @@ pub enum EvalError {
     FileDescriptorError(usize, FileDescriptorError),
     FilterError(String, FilterError),
     ChildSpawnFailed(std::io::Error),
+    CommandFailed(i32), // Contains the exit code.
     ContextCloneFailed(std::io::Error),
+    ControlFlow(Flow), // Control flow from code executed by a command, such as `eval`.
     CreatePipeFailed(std::io::Error),
+    Exit(i32), // Contains the exit code.
+    FileExists(PathBuf),
+    InvalidArithmetic(String, String), // Contains the expression and an error message.
+    InvalidExitCode(String),           // Contains the invalid exit code.
     InvalidIndex,
     InvalidListInterpolation(String),
-    InvalidRegex(String),         // Contains an error message.
-    InvalidValuePipeline(String), // Contains an error message.
+    InvalidPipelineTimeout(String), // Contains the invalid timeout.
+    InvalidRegex(String),           // Contains an error message.
+    InvalidValuePipeline(String),   // Contains an error message.
     InvalidVariableType {
         variable: String,
         expected_type: String,
         actual_type: String,
     },
-    IoError(std::io::Error), // General IO catch-all error.
+    IoError(std::io::Error),        // General IO catch-all error.
+    LoopControlOutsideLoop(String), // Contains the statement keyword.
+    MisplacedFallthrough,
     PipelineFailed(Vec<std::io::Error>),
+    ReadFileFailed(PathBuf, std::io::Error),
+    ReadonlyVariable(String),
+    ReturnOutsideFunction,
     UnboundFunctionArguments(Vec<String>),
     UndefinedFileDescriptor(usize),
     UndefinedFunctionArguments(Vec<String>),
@@ -49,12 +63,28 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             },
             EvalError::FilterError(filter, error) => write!(f, "{filter}: {error}"),
             EvalError::ChildSpawnFailed(err) => write!(f, "failed to spawn child process: {err}"),
+            EvalError::CommandFailed(code) => write!(f, "command failed with exit code {code}"),
             EvalError::ContextCloneFailed(err) => write!(f, "failed to clone context: {err}"),
+            EvalError::ControlFlow(flow) => match flow.outside_loop() {
+                Ok(()) => write!(f, "unexpected control flow"),
+                Err(error) => write!(f, "{error}"),
+            },
             EvalError::CreatePipeFailed(err) => write!(f, "failed to create pipe: {err}"),
+            EvalError::Exit(code) => write!(f, "exited with code {code}"),
+            EvalError::FileExists(path) => {
+                write!(f, "cannot overwrite existing file '{}'", path.display())
+            }
+            EvalError::InvalidArithmetic(expression, message) => {
+                write!(f, "invalid arithmetic expression '{expression}': {message}")
+            }
+            EvalError::InvalidExitCode(code) => write!(f, "invalid exit code: {code}"),
             EvalError::InvalidIndex => write!(f, "invalid index"),
             EvalError::InvalidListInterpolation(var) => {
                 write!(f, "invalid list interpolation: {var}")
             }
+            EvalError::InvalidPipelineTimeout(timeout) => {
+                write!(f, "invalid pipeline timeout: {timeout}")
+            }
             EvalError::InvalidRegex(msg) => write!(f, "invalid regex: {msg}"),
             EvalError::InvalidValuePipeline(msg) => write!(f, "invalid value pipeline: {msg}"),
             EvalError::InvalidVariableType {
@@ -66,7 +96,23 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
                 "{variable}: invalid type (expected {expected_type}), found {actual_type}"
             ),
             EvalError::IoError(err) => write!(f, "input/output error: {err}"),
+            EvalError::LoopControlOutsideLoop(keyword) => {
+                write!(f, "{keyword}: only meaningful within a loop")
+            }
+            EvalError::MisplacedFallthrough => {
+                write!(
+                    f,
+                    "fallthrough: only meaningful at the end of a switch branch"
+                )
+            }
             EvalError::PipelineFailed(errors) => write!(f, "pipeline failed: {:?}", errors),
+            EvalError::ReadFileFailed(path, err) => {
+                write!(f, "failed to read file '{}': {err}", path.display())
+            }
+            EvalError::ReadonlyVariable(variable) => write!(f, "readonly variable: {variable}"),
+            EvalError::ReturnOutsideFunction => {
+                write!(f, "return: only meaningful within a function")
+            }
             EvalError::UnboundFunctionArguments(args) => {
                 write!(f, "unbound function arguments: {}", args.join(", "))
             }
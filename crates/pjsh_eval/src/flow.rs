@@ -0,0 +1,106 @@
+use crate::{EvalError, EvalResult};
+
+/// Control flow resulting from the execution of statements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flow {
+    /// Proceed to the next statement.
+    Proceed,
+
+    /// Exit the `n`-th enclosing loop.
+    Break(usize),
+
+    /// Skip to the next iteration of the `n`-th enclosing loop.
+    Continue(usize),
+
+    /// Exit the enclosing function with an exit code.
+    Return(i32),
+}
+
+impl Flow {
+    /// Resolves the control flow at the end of a loop iteration.
+    ///
+    /// Returns the flow to propagate out of the loop if the loop should be
+    /// exited, or `None` if the loop should proceed with its next iteration.
+    pub(crate) fn exit_loop(self) -> Option<Flow> {
+        match self {
+            Flow::Proceed | Flow::Continue(1) => None,
+            Flow::Break(1) => Some(Flow::Proceed),
+            Flow::Break(n) => Some(Flow::Break(n - 1)),
+            Flow::Continue(n) => Some(Flow::Continue(n - 1)),
+            Flow::Return(_) => Some(self),
+        }
+    }
+
+    /// Resolves the control flow at the end of a function body.
+    ///
+    /// Returns the function's exit code if it has returned explicitly.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the flow is a loop control
+    /// statement without a matching loop.
+    pub(crate) fn exit_function(self) -> EvalResult<Option<i32>> {
+        match self {
+            Flow::Return(code) => Ok(Some(code)),
+            flow => flow.outside_loop().map(|_| None),
+        }
+    }
+
+    /// Ensures that the control flow does not cross a boundary that loop
+    /// control statements cannot pass, such as a function or a subshell.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the flow is a loop control
+    /// statement without a matching loop.
+    pub(crate) fn outside_loop(self) -> EvalResult<()> {
+        match self {
+            Flow::Proceed => Ok(()),
+            Flow::Break(_) => Err(EvalError::LoopControlOutsideLoop("break".into())),
+            Flow::Continue(_) => Err(EvalError::LoopControlOutsideLoop("continue".into())),
+            Flow::Return(_) => Err(EvalError::ReturnOutsideFunction),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_exits_loops() {
+        assert_eq!(Flow::Proceed.exit_loop(), None);
+        assert_eq!(Flow::Continue(1).exit_loop(), None);
+        assert_eq!(Flow::Break(1).exit_loop(), Some(Flow::Proceed));
+        assert_eq!(Flow::Break(3).exit_loop(), Some(Flow::Break(2)));
+        assert_eq!(Flow::Continue(2).exit_loop(), Some(Flow::Continue(1)));
+        assert_eq!(Flow::Return(3).exit_loop(), Some(Flow::Return(3)));
+    }
+
+    #[test]
+    fn it_exits_functions() {
+        assert_eq!(Flow::Proceed.exit_function().ok(), Some(None));
+        assert_eq!(Flow::Return(3).exit_function().ok(), Some(Some(3)));
+        assert!(matches!(
+            Flow::Break(1).exit_function(),
+            Err(EvalError::LoopControlOutsideLoop(_))
+        ));
+    }
+
+    #[test]
+    fn it_rejects_loop_control_outside_loops() {
+        assert!(Flow::Proceed.outside_loop().is_ok());
+        assert!(matches!(
+            Flow::Break(1).outside_loop(),
+            Err(EvalError::LoopControlOutsideLoop(_))
+        ));
+        assert!(matches!(
+            Flow::Continue(1).outside_loop(),
+            Err(EvalError::LoopControlOutsideLoop(_))
+        ));
+        assert!(matches!(
+            Flow::Return(0).outside_loop(),
+            Err(EvalError::ReturnOutsideFunction)
+        ));
+    }
+}
@@ -0,0 +1,166 @@
+use std::{
+    collections::VecDeque,
+    fs::File,
+    io::{BufRead, BufReader},
+    path::PathBuf,
+};
+
+use pjsh_ast::{Iterable, IterationRule, Word};
+
+use crate::{EvalError, EvalResult};
+
+/// Items that a for-loop iterates over.
+pub(crate) enum LoopItems {
+    /// Words in an iterable.
+    Words(Iterable),
+
+    /// Items that are read from a file as they are iterated over.
+    File(FileItems),
+}
+
+impl Iterator for LoopItems {
+    type Item = EvalResult<Word>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            LoopItems::Words(iterable) => iterable.next().map(Ok),
+            LoopItems::File(items) => items.next(),
+        }
+    }
+}
+
+/// Items in a file, extracted using an iteration rule.
+///
+/// The file is read one line at a time, meaning that only the current line is
+/// kept in memory.
+pub(crate) struct FileItems {
+    /// Path to the file.
+    path: PathBuf,
+
+    /// Reader for the file's contents.
+    reader: BufReader<File>,
+
+    /// Rule for extracting items from the file's contents.
+    rule: IterationRule,
+
+    /// Items that have been read, but that have not yet been iterated over.
+    pending: VecDeque<String>,
+}
+
+impl FileItems {
+    /// Opens a file for iteration.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file cannot be opened.
+    pub(crate) fn open(path: PathBuf, rule: IterationRule) -> EvalResult<Self> {
+        let file =
+            File::open(&path).map_err(|error| EvalError::ReadFileFailed(path.clone(), error))?;
+        Ok(Self {
+            path,
+            reader: BufReader::new(file),
+            rule,
+            pending: VecDeque::new(),
+        })
+    }
+}
+
+impl Iterator for FileItems {
+    type Item = EvalResult<Word>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.pending.pop_front() {
+                return Some(Ok(Word::Literal(item)));
+            }
+
+            // Lines are read including their line endings, as chars may include
+            // line breaks.
+            let mut line = String::new();
+            match self.reader.read_line(&mut line) {
+                Ok(0) => return None,
+                Ok(_) => self.pending.extend(split_items(&line, &self.rule)),
+                Err(error) => {
+                    return Some(Err(EvalError::ReadFileFailed(self.path.clone(), error)))
+                }
+            }
+        }
+    }
+}
+
+/// Extracts iterable items from a text using an iteration rule.
+pub(crate) fn split_items(text: &str, rule: &IterationRule) -> Vec<String> {
+    match rule {
+        IterationRule::Chars => text.chars().map(|c| c.to_string()).collect(),
+        IterationRule::Lines => text.lines().map(|l| l.to_string()).collect(),
+        IterationRule::Words => text.split_whitespace().map(|w| w.to_string()).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufWriter, Write};
+
+    use tempfile::NamedTempFile;
+
+    use super::*;
+
+    /// Writes text to a temporary file.
+    fn file_with_contents(contents: &str) -> NamedTempFile {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(contents.as_bytes()).unwrap();
+        file
+    }
+
+    /// Returns all items in a file.
+    fn items(file: &NamedTempFile, rule: IterationRule) -> Vec<String> {
+        FileItems::open(file.path().to_path_buf(), rule)
+            .unwrap()
+            .map(|item| match item {
+                Ok(Word::Literal(item)) => item,
+                other => panic!("unexpected item: {other:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn it_extracts_file_items_like_values() {
+        let contents = "first line\r\n\n  second  line\nlast";
+        let file = file_with_contents(contents);
+
+        for rule in [
+            IterationRule::Chars,
+            IterationRule::Lines,
+            IterationRule::Words,
+        ] {
+            assert_eq!(items(&file, rule.clone()), split_items(contents, &rule));
+        }
+    }
+
+    #[test]
+    fn it_streams_large_files() {
+        const LINES: usize = 200_000;
+
+        let file = NamedTempFile::new().unwrap();
+        let mut writer = BufWriter::new(file.as_file());
+        for i in 0..LINES {
+            writeln!(writer, "line number {i} in a large generated file").unwrap();
+        }
+        drop(writer);
+        assert!(file.as_file().metadata().unwrap().len() > 8 * 1024 * 1024);
+
+        let items = FileItems::open(file.path().to_path_buf(), IterationRule::Lines).unwrap();
+        assert_eq!(items.inspect(|item| assert!(item.is_ok())).count(), LINES);
+    }
+
+    #[test]
+    fn it_fails_to_open_missing_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("missing.txt");
+
+        assert!(matches!(
+            FileItems::open(path.clone(), IterationRule::Lines),
+            Err(EvalError::ReadFileFailed(error_path, _)) if error_path == path
+        ));
+    }
+}
@@ -1,56 +1,166 @@
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{HashMap, HashSet},
+    fs::File,
+    io::{Seek, Write},
+    path::Path,
+    process::{Child, ExitStatus},
+    time::{Duration, Instant},
+};
 
-use actions::handle_action;
-use call::{call_builtin_command, call_external_program, call_function};
-use condition::eval_condition;
+use actions::handle_actions;
+pub use arithmetic::{evaluate_arithmetic, Number};
+pub use call::call_external_program;
+use call::{call_builtin_command, call_function};
+pub use condition::eval_condition;
 pub use error::{EvalError, EvalResult};
+use flow::Flow;
+use iterate::{split_items, FileItems, LoopItems};
+use not_found::handle_unknown_command;
+use pattern::matches_glob;
 use pjsh_ast::{
-    AndOr, AndOrOp, Assignment, Command, ConditionalChain, ConditionalLoop, ForIterableLoop,
-    ForOfIterableLoop, Iterable, IterationRule, Pipeline, Program, Redirect, Statement, Switch,
-    Value, Word,
+    AndOr, AndOrOp, Assignment, Block, Command, ConditionalChain, ConditionalLoop,
+    ForOfIterableLoop, InterpolationUnit, Iterable, IterationSource, Pipeline, PipelineSegment,
+    Program, Redirect, Statement, Switch, SwitchBranch, Value, Word,
+};
+use pjsh_core::{
+    command::{Action, CommandResult},
+    signal::exit_status_code,
+    utils::{resolve_path, word_var},
+    Context, FileDescriptor, Scope, FD_STDIN, FD_STDOUT,
 };
-use pjsh_core::{command::CommandResult, utils::resolve_path, Context, FileDescriptor, Scope};
 use resolve::resolve_command;
-use words::{expand_words, interpolate_list};
+use rewrite::rewrite_command;
+use tempfile::tempfile;
+use words::{expand_words, here_string, interpolate_list, positional_args};
 pub use words::{interpolate_function_call, interpolate_word};
 
 mod actions;
+mod arithmetic;
 mod call;
 mod condition;
 mod error;
 mod filter;
+mod flow;
+mod iterate;
+mod not_found;
+mod pattern;
 mod resolve;
+mod rewrite;
 mod words;
 
+/// Variable containing the maximum number of seconds that each pipeline
+/// segment may execute for.
+const PIPELINE_TIMEOUT_VAR: &str = "PJSH_PIPELINE_TIMEOUT";
+
+/// Exit code of pipelines containing segments that have timed out.
+const TIMEOUT_EXIT_CODE: i32 = 124;
+
+/// Interval at which processes are polled while waiting for a deadline.
+const TIMEOUT_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
 /// Executes a [`Vec<Statement>`].
-fn execute_statements(statements: &[Statement], context: &mut Context) -> EvalResult<()> {
+///
+/// Execution stops at the first statement resulting in a loop or function
+/// control flow.
+fn execute_statements(statements: &[Statement], context: &mut Context) -> EvalResult<Flow> {
     for statement in statements {
-        execute_statement(statement, context)?;
+        let flow = execute_statement_flow(statement, context)?;
+        if flow != Flow::Proceed {
+            return Ok(flow);
+        }
     }
-    Ok(())
+    Ok(Flow::Proceed)
 }
 
 /// Executes a statement within a context.
+///
+/// # Errors
+///
+/// This function will return an error if the statement cannot be executed, or
+/// if it is a loop control statement outside of a loop.
 pub fn execute_statement(statement: &Statement, context: &mut Context) -> EvalResult<()> {
+    let start = context.profile.is_some().then(Instant::now);
+    let result = execute_statement_flow(statement, context).and_then(Flow::outside_loop);
+
+    if let (Some(start), Some(profile)) = (start, &mut context.profile) {
+        profile.record_statement(statement, start.elapsed());
+    }
+
+    result
+}
+
+/// Executes a statement within a context, returning the resulting control flow.
+fn execute_statement_flow(statement: &Statement, context: &mut Context) -> EvalResult<Flow> {
+    // Control flow from code executed by commands, such as `eval`, applies to
+    // the statement containing the command.
+    match execute_statement_kind(statement, context) {
+        Err(EvalError::ControlFlow(flow)) => Ok(flow),
+        result => result,
+    }
+}
+
+/// Executes a statement within a context based on its kind, returning the
+/// resulting control flow.
+fn execute_statement_kind(statement: &Statement, context: &mut Context) -> EvalResult<Flow> {
     match statement {
-        Statement::AndOr(and_or) => execute_and_or(and_or, context).map(|_| Ok(()))?,
-        Statement::Assignment(assignment) => execute_assignment(assignment, context),
-        Statement::ForIn(for_iterable) => execute_for_iterable_loop(for_iterable.clone(), context),
+        Statement::AndOr(and_or) => execute_and_or_statement(and_or, context),
+        Statement::Assignment(assignment) => {
+            execute_assignment(assignment, context).map(|_| Flow::Proceed)
+        }
+        Statement::ForIn(for_iterable) => {
+            let items = resolve_iterable(for_iterable.iterable.clone(), context)?;
+            execute_for_iterable_loop(&for_iterable.variable, items, &for_iterable.body, context)
+        }
         Statement::ForOfIn(for_of_iterable) => {
-            let for_iterable = contextualize_loop(for_of_iterable.clone(), context)?;
-            execute_for_iterable_loop(for_iterable, context)
+            let items = contextualize_loop(for_of_iterable, context)?;
+            execute_for_iterable_loop(
+                &for_of_iterable.variable,
+                items,
+                &for_of_iterable.body,
+                context,
+            )
         }
         Statement::Function(function) => {
             context.register_function(function.clone());
-            Ok(())
+            Ok(Flow::Proceed)
         }
         Statement::If(conditionals) => execute_conditional_chain(conditionals, context),
         Statement::While(conditional) => execute_conditional_loop(conditional, context),
         Statement::Switch(switch) => execute_switch(switch, context),
         Statement::Subshell(subshell) => {
             let inner_context = context.try_clone().map_err(EvalError::ContextCloneFailed)?;
-            execute_subshell(subshell, inner_context)
+            // Subshells exiting due to errexit still determine the exit code.
+            let code = match execute_subshell(subshell, inner_context) {
+                Ok(code) | Err(EvalError::CommandFailed(code)) => code,
+                Err(error) => return Err(error),
+            };
+
+            context.register_exit(code);
+            match code != 0 && context.options.errexit {
+                true => Err(EvalError::CommandFailed(code)),
+                false => Ok(Flow::Proceed),
+            }
         }
+        Statement::Break(levels) => Ok(Flow::Break(*levels)),
+        Statement::Continue(levels) => Ok(Flow::Continue(*levels)),
+        Statement::Return(code) => execute_return(code.as_ref(), context),
+        Statement::Fallthrough => Err(EvalError::MisplacedFallthrough),
+        Statement::Commented(statement, _) => execute_statement_flow(statement, context),
+    }
+}
+
+/// Executes a return statement, resolving the exit code of the enclosing function.
+///
+/// The last exit code is used if no exit code is given.
+fn execute_return(code: Option<&Word>, context: &mut Context) -> EvalResult<Flow> {
+    let Some(code) = code else {
+        return Ok(Flow::Return(context.last_exit()));
+    };
+
+    let code = interpolate_word(code, context)?;
+    match code.parse() {
+        Ok(code) => Ok(Flow::Return(code)),
+        Err(_) => Err(EvalError::InvalidExitCode(code)),
     }
 }
 
@@ -61,20 +171,38 @@ fn execute_assignment(assignment: &Assignment, context: &mut Context) -> EvalRes
         Value::List(list) => pjsh_core::Value::List(interpolate_list(list, context)?),
         Value::Word(word) => pjsh_core::Value::Word(interpolate_word(word, context)?),
     };
-    context.set_var(key, value);
-    Ok(())
+    assign_var(key, value, context)
+}
+
+/// Sets the value of a variable within the current scope.
+///
+/// # Errors
+///
+/// This function will return an error if the variable is read-only.
+fn assign_var(name: String, value: pjsh_core::Value, context: &mut Context) -> EvalResult<()> {
+    context
+        .set_var(name.clone(), value)
+        .map_err(|_| EvalError::ReadonlyVariable(name))
 }
 
 /// Executes a subshell program within its own context.
-pub(crate) fn execute_subshell(subshell: &Program, mut context: Context) -> EvalResult<()> {
-    execute_statements(&subshell.statements, &mut context)
+///
+/// Returns the exit code of the subshell's last executed statement, or the exit
+/// code given to `exit` if the subshell is exited early.
+pub(crate) fn execute_subshell(subshell: &Program, mut context: Context) -> EvalResult<i32> {
+    match execute_statements(&subshell.statements, &mut context) {
+        Ok(flow) => flow.outside_loop()?,
+        Err(EvalError::Exit(code)) => return Ok(code),
+        Err(error) => return Err(error),
+    }
+    Ok(context.last_exit())
 }
 
 /// Executes a conditional chain.
 fn execute_conditional_chain(
     conditionals: &ConditionalChain,
     context: &mut Context,
-) -> EvalResult<()> {
+) -> EvalResult<Flow> {
     assert!(
         conditionals.branches.len() == conditionals.conditions.len()
             || conditionals.branches.len() == conditionals.conditions.len() + 1
@@ -84,7 +212,7 @@ fn execute_conditional_chain(
 
     for condition in conditionals.conditions.iter() {
         let branch = branches.next().expect("branch exists");
-        execute_and_or(condition, context)?;
+        without_errexit(context, |context| execute_and_or(condition, context))?;
 
         // Skip to the next condition in the chain if the current condition is not met
         // (the condition exits with a non 0 code).
@@ -104,277 +232,903 @@ fn execute_conditional_chain(
         return execute_statements(&branch.statements, context);
     }
 
-    Ok(())
+    Ok(Flow::Proceed)
 }
 
 /// Executes a switch statement.
-fn execute_switch(switch: &Switch, context: &mut Context) -> EvalResult<()> {
+fn execute_switch(switch: &Switch, context: &mut Context) -> EvalResult<Flow> {
     let input = interpolate_word(&switch.input, context)?;
-    let mut branches = HashMap::with_capacity(switch.branches.len());
-    for (key, branch) in &switch.branches {
-        branches.insert(interpolate_word(key, context)?, branch.clone());
+
+    // Take the first matching branch if there is one, or the default branch otherwise.
+    let mut first = None;
+    for (index, branch) in switch.branches.iter().enumerate() {
+        if matches_switch_branch(branch, &input, context)? {
+            first = Some(index);
+            break;
+        }
     }
+    let first = first.or_else(|| {
+        switch
+            .branches
+            .iter()
+            .position(|branch| branch.keys.is_empty())
+    });
 
-    // Take the matching branch if there is one.
-    if let Some(branch) = branches.get(&input) {
-        return execute_statements(&branch.statements, context);
+    // Switch statements without a matching branch are successful.
+    let Some(first) = first else {
+        context.register_exit(0);
+        return Ok(Flow::Proceed);
     };
 
-    Ok(())
+    // Fall through to subsequent branches without matching their keys.
+    for branch in &switch.branches[first..] {
+        let flow = execute_switch_branch(branch, &input, context)?;
+        if flow != Flow::Proceed || !branch.fallthrough {
+            return Ok(flow);
+        }
+    }
+
+    Ok(Flow::Proceed)
+}
+
+/// Returns `true` if any of a switch branch's keys match some input.
+///
+/// Literal keys are matched as glob patterns.
+fn matches_switch_branch(
+    branch: &SwitchBranch,
+    input: &str,
+    context: &mut Context,
+) -> EvalResult<bool> {
+    for key in &branch.keys {
+        let is_pattern = matches!(key, Word::Literal(_));
+        let key = interpolate_word(key, context)?;
+        if key == input || (is_pattern && matches_glob(&key, input)) {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+/// Executes the body of a switch branch.
+///
+/// The input is bound to the branch's variable, if any, within a new scope.
+fn execute_switch_branch(
+    branch: &SwitchBranch,
+    input: &str,
+    context: &mut Context,
+) -> EvalResult<Flow> {
+    let Some(binding) = &branch.binding else {
+        return execute_statements(&branch.body.statements, context);
+    };
+
+    let scope = Scope::new(
+        format!("{} switch", context.name()),
+        None,
+        HashMap::from([(
+            binding.clone(),
+            Some(pjsh_core::Value::Word(input.to_owned())),
+        )]),
+        HashMap::default(),
+        HashSet::default(),
+    );
+    context.scoped(scope, |context| {
+        execute_statements(&branch.body.statements, context)
+    })
 }
 
 /// Executes a conditional loop.
 fn execute_conditional_loop(
     conditional: &ConditionalLoop,
     context: &mut Context,
-) -> EvalResult<()> {
+) -> EvalResult<Flow> {
     loop {
         // Evaluate the condition and break the loop if it is not met (the condition
         // exits with a non 0 code).
-        if execute_and_or(&conditional.condition, context)? != 0 {
+        if without_errexit(context, |context| {
+            execute_and_or(&conditional.condition, context)
+        })? != 0
+        {
             break;
         }
 
-        execute_statements(&conditional.body.statements, context)?;
+        let flow = execute_statements(&conditional.body.statements, context)?;
+        if let Some(flow) = flow.exit_loop() {
+            return Ok(flow);
+        }
     }
-    Ok(())
+    Ok(Flow::Proceed)
 }
 
-/// Executes a for-in iterable loop, consuming the iterable in the process.
-fn execute_for_iterable_loop(
-    mut for_iterable: ForIterableLoop,
-    context: &mut Context,
-) -> EvalResult<()> {
-    if let Iterable::Variable(var) = for_iterable.iterable {
-        // Resolve variable iterables.
-        match context.get_var(&var) {
-            Some(pjsh_core::Value::List(items)) => {
-                let words: Vec<Word> = items.iter().cloned().map(Word::Literal).collect();
-                for_iterable.iterable = Iterable::from(words);
-            }
-            Some(pjsh_core::Value::Word(_)) => {
-                return Err(EvalError::InvalidVariableType {
-                    variable: var,
-                    expected_type: "list".to_string(),
-                    actual_type: "word".to_string(),
-                })
-            }
-            None => return Err(EvalError::UndefinedVariable(var)),
+/// Resolves the items of an iterable.
+///
+/// # Errors
+///
+/// This function will return an error if the iterable is a variable that is
+/// not a defined list.
+fn resolve_iterable(iterable: Iterable, context: &Context) -> EvalResult<LoopItems> {
+    let Iterable::Variable(var) = iterable else {
+        return Ok(LoopItems::Words(iterable));
+    };
+
+    if var == "@" {
+        let words: Vec<Word> = positional_args(context)
+            .iter()
+            .cloned()
+            .map(Word::Literal)
+            .collect();
+        return Ok(LoopItems::Words(Iterable::from(words)));
+    }
+
+    match context.get_var(&var) {
+        Some(pjsh_core::Value::List(items)) => {
+            let words: Vec<Word> = items.iter().cloned().map(Word::Literal).collect();
+            Ok(LoopItems::Words(Iterable::from(words)))
         }
+        Some(pjsh_core::Value::Word(_)) => Err(EvalError::InvalidVariableType {
+            variable: var,
+            expected_type: "list".to_string(),
+            actual_type: "word".to_string(),
+        }),
+        None => Err(EvalError::UndefinedVariable(var)),
     }
+}
 
-    context.push_scope(Scope::new(
+/// Executes a for-in loop body once for each item, consuming the items in the
+/// process.
+///
+/// Items are only read as they are iterated over.
+fn execute_for_iterable_loop(
+    variable: &str,
+    items: LoopItems,
+    body: &Block,
+    context: &mut Context,
+) -> EvalResult<Flow> {
+    let scope = Scope::new(
         format!("{} for-in", context.name()),
         None,
         HashMap::default(),
         HashMap::default(),
         HashSet::default(),
-    ));
+    );
+    context.scoped(scope, |context| {
+        for word in items {
+            let value = interpolate_word(&word?, context)?;
+            assign_var(variable.to_owned(), pjsh_core::Value::Word(value), context)?;
 
-    let mut result = Ok(());
-    for word in for_iterable.iterable {
-        match interpolate_word(&word, context) {
-            Ok(value) => {
-                context.set_var(for_iterable.variable.clone(), pjsh_core::Value::Word(value))
-            }
-            Err(err) => {
-                result = Err(err);
-                break;
+            if let Some(flow) = execute_statements(&body.statements, context)?.exit_loop() {
+                return Ok(flow);
             }
-        };
-
-        if let Err(err) = execute_statements(&for_iterable.body.statements, context) {
-            result = Err(err);
-            break;
         }
+        Ok(Flow::Proceed)
+    })
+}
+
+/// Executes a sequence of and/or logic as a statement.
+///
+/// If `errexit` is enabled, an error is returned if the last pipeline fails.
+/// Failures of other pipelines are part of the logic, and are thus ignored.
+fn execute_and_or_statement(and_or: &AndOr, context: &mut Context) -> EvalResult<Flow> {
+    let (exit_status, is_last_executed) = execute_and_or_pipelines(and_or, context)?;
+    if exit_status != 0 && is_last_executed && context.options.errexit {
+        return Err(EvalError::CommandFailed(exit_status));
     }
-    context.pop_scope();
-    result
+
+    Ok(Flow::Proceed)
 }
 
 /// Executes a sequence of and/or logic.
 fn execute_and_or(and_or: &AndOr, context: &mut Context) -> EvalResult<i32> {
+    execute_and_or_pipelines(and_or, context).map(|(exit_status, _)| exit_status)
+}
+
+/// Executes a sequence of and/or logic.
+///
+/// Returns the exit status along with whether or not the last pipeline was
+/// executed.
+fn execute_and_or_pipelines(and_or: &AndOr, context: &mut Context) -> EvalResult<(i32, bool)> {
     assert_eq!(and_or.operators.len(), and_or.pipelines.len() - 1);
     let mut operators = and_or.operators.iter();
     let mut exit_status = 0;
     let mut operator = &AndOrOp::And;
+    let last = and_or.pipelines.len() - 1;
 
-    for pipeline in &and_or.pipelines {
+    for (index, pipeline) in and_or.pipelines.iter().enumerate() {
         let is_accepting_segment = match operator {
             AndOrOp::And => exit_status == 0,
             AndOrOp::Or => exit_status != 0,
         };
 
         if !is_accepting_segment {
-            break;
+            context.register_exit(exit_status);
+            return Ok((exit_status, false));
         }
 
-        exit_status = execute_pipeline(pipeline, context)?;
+        exit_status = match index < last {
+            true => without_errexit(context, |context| execute_pipeline(pipeline, context))?,
+            false => execute_pipeline(pipeline, context)?,
+        };
         operator = operators.next().unwrap_or(&AndOrOp::And); // There are n-1 operators.
     }
 
     context.register_exit(exit_status);
-    Ok(exit_status)
+    Ok((exit_status, true))
+}
+
+/// Executes a function with `errexit` disabled.
+///
+/// Used for commands that are expected to fail, such as conditions.
+fn without_errexit<T>(context: &mut Context, func: impl FnOnce(&mut Context) -> T) -> T {
+    let errexit = std::mem::replace(&mut context.options.errexit, false);
+    let result = func(context);
+    context.options.errexit = errexit;
+    result
+}
+
+/// Returns a printable command line for a process command.
+fn command_line(command: &std::process::Command) -> String {
+    std::iter::once(command.get_program())
+        .chain(command.get_args())
+        .map(|arg| arg.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join(" ")
 }
 
 /// Executes a pipeline.
+///
+/// All segments are prepared before any of them are executed. Programs are
+/// then started, after which built-in commands and functions are executed in
+/// order while the programs run. This allows data to stream between segments.
 fn execute_pipeline(pipeline: &Pipeline, context: &mut Context) -> EvalResult<i32> {
     if pipeline.segments.is_empty() {
         return Ok(0); // Empty pipelines cannot fail.
     }
 
+    // Connect adjacent segments using pipes.
+    let timeout = pipeline_timeout(context)?;
+    let last = pipeline.segments.len() - 1;
+    let mut connections = Vec::with_capacity(last);
+    for _ in 0..last {
+        let (reader, writer) = os_pipe::pipe().map_err(EvalError::CreatePipeFailed)?;
+        connections.push(Connection {
+            reader: Some(pipe_file(reader)),
+            writer: Some(pipe_file(writer)),
+            is_buffered: false,
+        });
+    }
+
     // Prepare commands.
-    let mut commands = Vec::with_capacity(pipeline.segments.len());
-    for segment in &pipeline.segments {
+    // Programs are connected to their pipes while being prepared. Other
+    // commands are connected once they are executed.
+    let mut segments = Vec::with_capacity(pipeline.segments.len());
+    for (i, segment) in pipeline.segments.iter().enumerate() {
+        let input = i
+            .checked_sub(1)
+            .and_then(|j| connections[j].reader.as_ref());
+        let output = connections.get(i).and_then(|c| c.writer.as_ref());
+        let segment = with_segment_io(input, output, context, |ctx| prepare_segment(segment, ctx))?;
+        segments.push(segment);
+    }
+
+    // Segments that are executed within the shell run one after the other, and
+    // are therefore connected to each other using temporary files rather than
+    // pipes. Unused pipe ends are closed so that programs are notified once the
+    // other end of their pipes is no longer in use.
+    let is_internal: Vec<bool> = segments
+        .iter()
+        .map(|segment| !matches!(segment, PreparedSegment::Program(_)))
+        .collect();
+    for (i, connection) in connections.iter_mut().enumerate() {
+        if is_internal[i] && is_internal[i + 1] {
+            let file = tempfile().map_err(EvalError::IoError)?;
+            connection.reader = Some(file.try_clone().map_err(EvalError::IoError)?);
+            connection.writer = Some(file);
+            connection.is_buffered = true;
+        } else if is_internal[i] {
+            connection.reader = None;
+        } else if is_internal[i + 1] {
+            connection.writer = None;
+        }
+    }
+
+    // Start the child processes.
+    let mut state = PipelineState {
+        codes: vec![None; segments.len()],
+        processes: Vec::with_capacity(segments.len()),
+        io_errors: Vec::new(),
+        deadline: timeout.map(|timeout| Instant::now() + timeout),
+        is_timed_out: false,
+    };
+    let mut internal_segments = Vec::new();
+    for (i, segment) in segments.into_iter().enumerate() {
         match segment {
-            pjsh_ast::PipelineSegment::Command(command) => {
-                commands.push(execute_command(command, context)?);
-            }
-            pjsh_ast::PipelineSegment::Condition(condition) => {
-                let result = eval_condition(condition, context)?;
-                if result {
-                    commands.push(CommandResult::code(0));
-                } else {
-                    commands.push(CommandResult::code(1));
+            PreparedSegment::Program(command) => {
+                if let Err(error) = state.spawn(i, command) {
+                    state.io_errors.push(error);
+                    break;
                 }
             }
+            segment => internal_segments.push((i, segment)),
         }
     }
 
-    // Override stdin and stdout of all relevant segments.
-    for i in 0..(pipeline.segments.len() - 1) {
-        let (reader, writer) = os_pipe::pipe().map_err(EvalError::CreatePipeFailed)?;
-        if let CommandResult::Process(process) = &mut commands[i] {
-            process.command.stdout(writer);
+    // Execute the remaining segments within the shell.
+    let result = match state.io_errors.is_empty() {
+        true => execute_internal_segments(internal_segments, connections, &mut state, context),
+        false => Ok(()),
+    };
+
+    // Wait for synchronous processes to terminate.
+    // Register asynchronous processes in the shell.
+    // Register and return all pipeline errors.
+    if pipeline.is_async && state.io_errors.is_empty() {
+        if let Some(process) = state.processes.last() {
+            context.last_async_pid = Some(process.child.id());
         }
-        if let CommandResult::Process(process) = &mut commands[i + 1] {
-            process.command.stdin(reader);
+
+        // The shell does not wait for asynchronous processes, so only the time
+        // taken to start them is profiled.
+        let mut host = context.host.lock();
+        for process in state.processes {
+            if let Some(sink) = &mut context.profile_sink {
+                sink.record_command(&process.name, process.start.elapsed());
+            }
+            host.add_child_process(process.child, Some(process.command_line));
         }
+        return result.map(|_| 0);
     }
 
-    // Start the child processes.
-    let mut exit_code = 0;
-    let mut processes = Vec::with_capacity(commands.len());
-    let mut io_errors = Vec::new();
-    for command in commands {
-        match command {
+    for process in std::mem::take(&mut state.processes) {
+        state.wait(process, context);
+    }
+
+    result?;
+    if !state.io_errors.is_empty() {
+        return Err(EvalError::PipelineFailed(state.io_errors));
+    }
+
+    let exit_code = state.codes[last].unwrap_or(TIMEOUT_EXIT_CODE);
+    let failed_exit_code = state.codes.iter().rev().flatten().find(|code| **code != 0);
+    match (state.is_timed_out, failed_exit_code) {
+        (true, _) => Ok(TIMEOUT_EXIT_CODE),
+        (false, Some(code)) if context.options.pipefail => Ok(*code),
+        (false, _) => Ok(exit_code),
+    }
+}
+
+/// Executes the pipeline segments that run within the shell, in order.
+///
+/// Each segment is connected to the pipe or temporary file that has been set
+/// up for it. Temporary files are read from the start by the next segment.
+fn execute_internal_segments(
+    segments: Vec<(usize, PreparedSegment)>,
+    mut connections: Vec<Connection>,
+    state: &mut PipelineState,
+    context: &mut Context,
+) -> EvalResult<()> {
+    for (i, segment) in segments {
+        let mut input = None;
+        if let Some(connection) = i.checked_sub(1).map(|j| &mut connections[j]) {
+            input = connection.reader.take();
+            if let Some(file) = input.as_mut().filter(|_| connection.is_buffered) {
+                file.rewind().map_err(EvalError::IoError)?;
+            }
+        }
+        let output = connections.get_mut(i).and_then(|c| c.writer.take());
+
+        let result = match segment {
+            PreparedSegment::Program(command) => CommandResult::from(command),
+            PreparedSegment::Internal(command) => {
+                with_segment_io(input.as_ref(), output.as_ref(), context, |ctx| {
+                    command.execute(ctx)
+                })?
+            }
+            PreparedSegment::Finished(result) => result,
+        };
+
+        match result {
             CommandResult::Builtin(builtin) => {
-                exit_code = builtin.code;
-                for action in &builtin.actions {
-                    handle_action(action, context)?;
-                }
+                state.codes[i] = Some(builtin.code);
             }
-            CommandResult::Process(mut process) => match process.command.spawn() {
-                Ok(process) => processes.push(process),
-                Err(error) => {
-                    io_errors.push(error);
-                    break;
+            CommandResult::Process(process) => {
+                if let Err(error) = state.spawn(i, process.command) {
+                    state.io_errors.push(error);
+                    return Ok(());
                 }
-            },
+
+                // The next segment must be able to read all of the output.
+                if connections.get(i).is_some_and(|c| c.is_buffered) {
+                    if let Some(process) = state.processes.pop() {
+                        state.wait(process, context);
+                    }
+                }
+            }
         }
     }
 
-    // Wait for synchronous processes to terminate.
-    // Register asynchronous processes in the shell.
-    // Register and return all pipeline errors.
-    if pipeline.is_async && io_errors.is_empty() {
-        let mut host = context.host.lock();
-        for process in processes {
-            host.add_child_process(process);
-        }
-        Ok(0)
-    } else {
-        for mut process in processes {
-            match process.wait() {
-                Ok(exit_status) => match exit_status.code() {
-                    Some(code) => exit_code = code,
-                    None => exit_code = 127,
-                },
-                Err(error) => io_errors.push(error),
+    Ok(())
+}
+
+/// Connects the output of a pipeline segment to the input of the next segment.
+struct Connection {
+    /// Input of the next segment.
+    reader: Option<File>,
+
+    /// Output of the segment.
+    writer: Option<File>,
+
+    /// Whether the output is buffered in a temporary file, rather than being
+    /// streamed through a pipe, because both segments execute within the shell.
+    is_buffered: bool,
+}
+
+/// A pipeline segment that is ready to be executed.
+enum PreparedSegment<'a> {
+    /// A program that has yet to be started.
+    Program(std::process::Command),
+
+    /// A command that is executed within the shell, such as a built-in command
+    /// or a function.
+    Internal(InternalCommand<'a>),
+
+    /// A segment that has already been executed, such as a condition.
+    Finished(CommandResult),
+}
+
+/// A command that is executed within the shell.
+struct InternalCommand<'a> {
+    /// Expanded arguments, starting with the command name.
+    args: Vec<String>,
+
+    /// The resolved command.
+    resolved: resolve::ResolvedCommand,
+
+    /// Redirects that are applied while executing the command.
+    redirects: &'a [Redirect],
+}
+
+impl InternalCommand<'_> {
+    /// Executes the command.
+    ///
+    /// Actions requested by built-in commands are handled while the command's
+    /// redirections are still applied. Redirections are undone once the command
+    /// has been executed, unless the command requests that they are kept.
+    fn execute(self, context: &mut Context) -> EvalResult<CommandResult> {
+        let replaced = redirect_file_descriptors(self.redirects, context)?;
+        let args = self.args;
+        let result = match self.resolved {
+            resolve::ResolvedCommand::Builtin(builtin) => {
+                profile_command(&args[0], context, |ctx| {
+                    call_builtin_command(builtin.as_ref(), &args, ctx)
+                })
+            }
+            resolve::ResolvedCommand::Function(func) => {
+                profile_command(&args[0], context, |ctx| call_function(&func, &args, ctx))
+            }
+            resolve::ResolvedCommand::Program(program) => {
+                call_external_program(&program, &args[1..], context).map(CommandResult::from)
+            }
+            resolve::ResolvedCommand::Unknown => handle_unknown_command(&args, context),
+        };
+
+        let keep_redirects = matches!(&result, Ok(CommandResult::Builtin(builtin))
+            if builtin.actions.iter().any(|action| matches!(action, Action::KeepRedirects)));
+        let result = result.and_then(|result| match result {
+            CommandResult::Builtin(builtin) => handle_actions(builtin, context),
+            process => Ok(process),
+        });
+        if !keep_redirects {
+            restore_file_descriptors(replaced, context);
+        }
+
+        result
+    }
+}
+
+/// A process that has been started by a pipeline.
+struct PipelineProcess {
+    /// Index of the pipeline segment that started the process.
+    segment: usize,
+
+    /// The running process.
+    child: Child,
+
+    /// Printable command line.
+    command_line: String,
+
+    /// Program name, for use in profiles.
+    name: String,
+
+    /// Point in time at which the process was started.
+    start: Instant,
+}
+
+/// Execution state of a pipeline.
+struct PipelineState {
+    /// Exit code of each segment, once known.
+    codes: Vec<Option<i32>>,
+
+    /// Processes that have been started, but not yet waited for.
+    processes: Vec<PipelineProcess>,
+
+    /// Errors from starting or waiting for processes.
+    io_errors: Vec<std::io::Error>,
+
+    /// Point in time at which processes are killed.
+    deadline: Option<Instant>,
+
+    /// Whether any process has been killed for exceeding the deadline.
+    is_timed_out: bool,
+}
+
+impl PipelineState {
+    /// Starts a process for a pipeline segment.
+    fn spawn(&mut self, segment: usize, mut command: std::process::Command) -> std::io::Result<()> {
+        let start = Instant::now();
+        let child = command.spawn()?;
+        self.processes.push(PipelineProcess {
+            segment,
+            child,
+            command_line: command_line(&command),
+            name: program_name(&command),
+            start,
+        });
+        Ok(())
+    }
+
+    /// Waits for a process to terminate, and records its exit code.
+    fn wait(&mut self, mut process: PipelineProcess, context: &mut Context) {
+        let result = wait_until(&mut process.child, self.deadline);
+        if let Some(sink) = &mut context.profile_sink {
+            sink.record_command(&process.name, process.start.elapsed());
+        }
+
+        match result {
+            Ok(Some(exit_status)) => {
+                self.codes[process.segment] = Some(exit_status_code(exit_status));
             }
+            Ok(None) => self.is_timed_out = true,
+            Err(error) => self.io_errors.push(error),
         }
+    }
+}
+
+/// Returns the maximum amount of time that the programs within a pipeline may
+/// execute for, as configured by `$PJSH_PIPELINE_TIMEOUT` in seconds.
+///
+/// Built-in commands and functions are executed within the shell and cannot be
+/// killed. Thus, they are not limited by the timeout.
+fn pipeline_timeout(context: &Context) -> EvalResult<Option<Duration>> {
+    let Some(timeout) = word_var(context, PIPELINE_TIMEOUT_VAR) else {
+        return Ok(None);
+    };
+
+    match timeout.parse::<f64>().map(Duration::try_from_secs_f64) {
+        Ok(Ok(timeout)) => Ok(Some(timeout)),
+        _ => Err(EvalError::InvalidPipelineTimeout(timeout.to_owned())),
+    }
+}
+
+/// Waits for a child process to exit.
+///
+/// The process is killed if it has not exited before an optional deadline, in
+/// which case `None` is returned.
+fn wait_until(
+    process: &mut Child,
+    deadline: Option<Instant>,
+) -> std::io::Result<Option<ExitStatus>> {
+    let Some(deadline) = deadline else {
+        return process.wait().map(Some);
+    };
 
-        if !io_errors.is_empty() {
-            return Err(EvalError::PipelineFailed(io_errors));
+    loop {
+        if let Some(exit_status) = process.try_wait()? {
+            return Ok(Some(exit_status));
+        }
+
+        if Instant::now() >= deadline {
+            process.kill()?;
+            process.wait()?;
+            return Ok(None);
         }
 
-        Ok(exit_code)
+        std::thread::sleep(TIMEOUT_POLL_INTERVAL);
+    }
+}
+
+/// Prepares a pipeline segment for execution.
+fn prepare_segment<'a>(
+    segment: &'a PipelineSegment,
+    context: &mut Context,
+) -> EvalResult<PreparedSegment<'a>> {
+    match segment {
+        PipelineSegment::Command(command) => prepare_command(command, context),
+        PipelineSegment::Condition(condition) => match eval_condition(condition, context)? {
+            true => Ok(PreparedSegment::Finished(CommandResult::code(0))),
+            false => Ok(PreparedSegment::Finished(CommandResult::code(1))),
+        },
     }
 }
 
-/// Executes a command.
-fn execute_command(command: &Command, context: &mut Context) -> EvalResult<CommandResult> {
-    redirect_file_descriptors(&command.redirects, context)?;
+/// Prepares a pipeline segment using temporary file descriptors for its stdin
+/// and stdout.
+///
+/// The original file descriptors are restored before returning.
+fn with_segment_io<T>(
+    input: Option<&File>,
+    output: Option<&File>,
+    context: &mut Context,
+    func: impl FnOnce(&mut Context) -> EvalResult<T>,
+) -> EvalResult<T> {
+    let input = input
+        .map(File::try_clone)
+        .transpose()
+        .map_err(EvalError::IoError)?;
+    let output = output
+        .map(File::try_clone)
+        .transpose()
+        .map_err(EvalError::IoError)?;
+
+    let old_stdin = input.map(|file| {
+        context.replace_file_descriptor(FD_STDIN, Some(FileDescriptor::FileHandle(file)))
+    });
+    let old_stdout = output.map(|file| {
+        context.replace_file_descriptor(FD_STDOUT, Some(FileDescriptor::FileHandle(file)))
+    });
+
+    let result = func(context);
+
+    if let Some(old_stdin) = old_stdin {
+        context.replace_file_descriptor(FD_STDIN, old_stdin);
+    }
+    if let Some(old_stdout) = old_stdout {
+        context.replace_file_descriptor(FD_STDOUT, old_stdout);
+    }
+
+    result
+}
+
+/// Converts one end of a pipe into a file handle.
+#[cfg(unix)]
+fn pipe_file(pipe: impl Into<std::os::fd::OwnedFd>) -> File {
+    File::from(pipe.into())
+}
+
+/// Converts one end of a pipe into a file handle.
+#[cfg(windows)]
+fn pipe_file(pipe: impl Into<std::os::windows::io::OwnedHandle>) -> File {
+    File::from(pipe.into())
+}
+
+/// Prepares a command for execution.
+///
+/// Programs are redirected as they are prepared. Other commands are redirected
+/// once they are executed.
+fn prepare_command<'a>(
+    command: &'a Command,
+    context: &mut Context,
+) -> EvalResult<PreparedSegment<'a>> {
+    if context.options.warnsplit {
+        warn_about_unsplit_words(&command.arguments, context);
+    }
+    warn_about_backtick_substitution(command, context);
+
     let args = expand_words(&command.arguments, context)?;
+    if args.is_empty() {
+        // Commands whose words all expand to nothing, such as an unquoted "$@"
+        // without arguments, do nothing and succeed.
+        return Ok(PreparedSegment::Finished(CommandResult::code(0)));
+    }
+
+    let args = rewrite_command(args, context)?;
 
     match resolve_command(&args[0], context) {
-        resolve::ResolvedCommand::Builtin(builtin) => {
-            call_builtin_command(builtin.as_ref(), &args, context)
-        }
-        resolve::ResolvedCommand::Function(func) => call_function(&func, &args, context),
         resolve::ResolvedCommand::Program(program) => {
-            call_external_program(&program, &args[1..], context).map(CommandResult::from)
+            let replaced = redirect_file_descriptors(&command.redirects, context)?;
+            let result = call_external_program(&program, &args[1..], context);
+            restore_file_descriptors(replaced, context);
+            result.map(PreparedSegment::Program)
         }
-        resolve::ResolvedCommand::Unknown => Err(EvalError::UnknownCommand(args[0].to_owned())),
+        resolved => Ok(PreparedSegment::Internal(InternalCommand {
+            args,
+            resolved,
+            redirects: &command.redirects,
+        })),
+    }
+}
+
+/// Writes a warning to stderr for each variable argument containing whitespace.
+///
+/// Other shells split such variables into multiple arguments, whereas they are
+/// always passed as a single argument here.
+fn warn_about_unsplit_words(words: &[Word], context: &mut Context) {
+    let names: Vec<&str> = words
+        .iter()
+        .filter_map(|word| match word {
+            Word::Variable(name) => Some(name.as_str()),
+            _ => None,
+        })
+        .filter(|name| {
+            word_var(context, name).is_some_and(|value| value.contains(char::is_whitespace))
+        })
+        .collect();
+
+    let mut io = context.io();
+    for name in names {
+        let _ = writeln!(
+            io.stderr,
+            "pjsh: warning: ${name} contains whitespace but is passed as a single argument"
+        );
     }
 }
 
-/// Redirects file descriptors.
-fn redirect_file_descriptors(redirects: &[Redirect], context: &mut Context) -> EvalResult<()> {
+/// Writes a warning to stderr if a command is named by an interpolated word
+/// that only contains literal text starting with a command, such as `` `date` ``.
+///
+/// Other shells substitute such words with the output of the command, whereas
+/// they are used as-is here.
+fn warn_about_backtick_substitution(command: &Command, context: &mut Context) {
+    let Some(Word::Interpolation(units)) = command.arguments.first() else {
+        return;
+    };
+
+    let mut content = String::new();
+    for unit in units {
+        match unit {
+            InterpolationUnit::Literal(literal) => content.push_str(literal),
+            _ => return,
+        }
+    }
+
+    let content = content.trim();
+    let Some(name) = content.split_whitespace().next() else {
+        return;
+    };
+    if matches!(
+        resolve_command(name, context),
+        resolve::ResolvedCommand::Unknown
+    ) {
+        return;
+    }
+
+    let mut io = context.io();
+    let _ = writeln!(
+        io.stderr,
+        "pjsh: warning: backticks create interpolated words, not command substitutions: use $({content}) to capture a command's output"
+    );
+}
+
+/// Executes a command, recording its duration in the context's profile sink if
+/// one is enabled.
+///
+/// External programs are recorded once they exit rather than here.
+fn profile_command<T>(
+    name: &str,
+    context: &mut Context,
+    func: impl FnOnce(&mut Context) -> EvalResult<T>,
+) -> EvalResult<T> {
+    let start = context.profile_sink.is_some().then(Instant::now);
+    let result = func(context);
+
+    if let (Some(start), Some(sink)) = (start, &mut context.profile_sink) {
+        sink.record_command(name, start.elapsed());
+    }
+
+    result
+}
+
+/// Returns the file name of a program, for use in profiles.
+fn program_name(command: &std::process::Command) -> String {
+    let program = Path::new(command.get_program());
+    let name = program.file_name().unwrap_or(program.as_os_str());
+    name.to_string_lossy().into_owned()
+}
+
+/// Redirects file descriptors in order.
+///
+/// Returns the replaced file descriptors of the current scope, which should be
+/// restored once the redirected command has been prepared. Redirects that have
+/// already been applied are restored if a later redirect fails.
+fn redirect_file_descriptors(
+    redirects: &[Redirect],
+    context: &mut Context,
+) -> EvalResult<Vec<(usize, Option<FileDescriptor>)>> {
+    let mut replaced = Vec::with_capacity(redirects.len());
     for redirect in redirects {
-        redirect_file_descriptor(redirect, context)?;
+        match redirect_file_descriptor(redirect, context) {
+            Ok(replacement) => replaced.push(replacement),
+            Err(error) => {
+                restore_file_descriptors(replaced, context);
+                return Err(error);
+            }
+        }
+    }
+    Ok(replaced)
+}
+
+/// Restores file descriptors replaced by [`redirect_file_descriptors`].
+///
+/// Replacements are undone in reverse order so that a file descriptor that is
+/// redirected several times regains its original value.
+fn restore_file_descriptors(replaced: Vec<(usize, Option<FileDescriptor>)>, context: &mut Context) {
+    for (index, file_descriptor) in replaced.into_iter().rev() {
+        context.replace_file_descriptor(index, file_descriptor);
     }
-    Ok(())
 }
 
 /// Redirects a file descriptor.
-fn redirect_file_descriptor(redirect: &Redirect, context: &mut Context) -> EvalResult<()> {
-    match (&redirect.source, &redirect.target) {
+///
+/// Output files are opened immediately so that file descriptors duplicated by
+/// later redirects, such as `2>&1` in `>file 2>&1`, share the same file handle.
+///
+/// Returns the redirected file descriptor along with its replaced value.
+fn redirect_file_descriptor(
+    redirect: &Redirect,
+    context: &mut Context,
+) -> EvalResult<(usize, Option<FileDescriptor>)> {
+    let (index, file_descriptor) = match (&redirect.source, &redirect.target) {
         (pjsh_ast::FileDescriptor::Number(source), pjsh_ast::FileDescriptor::Number(target)) => {
-            if let Some(file_descriptor) = context.get_file_descriptor(*target) {
-                context.set_file_descriptor(*source, file_descriptor.try_clone().unwrap());
-            } else {
+            let Some(file_descriptor) = context.get_file_descriptor(*target) else {
                 return Err(EvalError::UndefinedFileDescriptor(*target));
-            }
+            };
+            let file_descriptor = file_descriptor.try_clone().map_err(EvalError::IoError)?;
+            (*source, file_descriptor)
         }
         (pjsh_ast::FileDescriptor::Number(source), pjsh_ast::FileDescriptor::File(file_path)) => {
             let path = resolve_path(context, interpolate_word(file_path, context)?);
-            let file_descriptor = match redirect.mode {
-                pjsh_ast::RedirectMode::Write => FileDescriptor::File(path),
-                pjsh_ast::RedirectMode::Append => FileDescriptor::File(path),
+            if redirect.mode == pjsh_ast::RedirectMode::Write
+                && context.options.noclobber
+                && path.is_file()
+            {
+                return Err(EvalError::FileExists(path));
+            }
+            let mut file_descriptor = match redirect.mode {
+                pjsh_ast::RedirectMode::Read | pjsh_ast::RedirectMode::Write => {
+                    FileDescriptor::File(path)
+                }
+                pjsh_ast::RedirectMode::Append => FileDescriptor::AppendFile(path),
             };
-            context.set_file_descriptor(*source, file_descriptor);
+            file_descriptor
+                .output()
+                .map_err(|error| EvalError::FileDescriptorError(*source, error))?;
+            (*source, file_descriptor)
         }
         (pjsh_ast::FileDescriptor::File(file_path), pjsh_ast::FileDescriptor::Number(target)) => {
             let path = resolve_path(context, interpolate_word(file_path, context)?);
-            context.set_file_descriptor(*target, FileDescriptor::File(path));
+            (*target, FileDescriptor::File(path))
+        }
+        (pjsh_ast::FileDescriptor::Value(word), pjsh_ast::FileDescriptor::Number(target)) => {
+            let file = here_string(word, context)?;
+            (*target, FileDescriptor::FileHandle(file))
+        }
+        (_, pjsh_ast::FileDescriptor::File(_) | pjsh_ast::FileDescriptor::Value(_)) => {
+            unreachable!()
         }
-        (pjsh_ast::FileDescriptor::File(_), pjsh_ast::FileDescriptor::File(_)) => unreachable!(),
     };
 
-    Ok(())
+    let replaced = context.replace_file_descriptor(index, Some(file_descriptor));
+    Ok((index, replaced))
 }
 
-/// Contextualizes a abstract loop, coercing it to a concrete loop.
+/// Contextualizes an abstract loop, resolving the items to iterate over.
+///
+/// Files are opened, but their contents are only read as the items are
+/// iterated over.
 fn contextualize_loop(
-    for_of_iterable: ForOfIterableLoop,
+    for_of_iterable: &ForOfIterableLoop,
     context: &mut Context,
-) -> EvalResult<ForIterableLoop> {
+) -> EvalResult<LoopItems> {
     let word = interpolate_word(&for_of_iterable.iterable, context)?;
+    let rule = for_of_iterable.iteration_rule.clone();
 
-    // Extract iterable items from the interpolated word using the pre-defined
-    // iteration rule.
-    let items: Vec<String> = match for_of_iterable.iteration_rule {
-        IterationRule::Chars => word.chars().map(|c| c.to_string()).collect(),
-        IterationRule::Lines => word.lines().map(|l| l.to_string()).collect(),
-        IterationRule::Words => word.split_whitespace().map(|w| w.to_string()).collect(),
-    };
-
-    let words: Vec<Word> = items.into_iter().map(Word::Literal).collect();
-
-    Ok(ForIterableLoop {
-        variable: for_of_iterable.variable,
-        iterable: Iterable::from(words),
-        body: for_of_iterable.body,
-    })
+    match for_of_iterable.source {
+        IterationSource::Value => {
+            let words = split_items(&word, &rule).into_iter().map(Word::Literal);
+            Ok(LoopItems::Words(Iterable::from(words.collect::<Vec<_>>())))
+        }
+        IterationSource::File => {
+            let path = resolve_path(context, word);
+            FileItems::open(path, rule).map(LoopItems::File)
+        }
+    }
 }
@@ -0,0 +1,43 @@
+use std::collections::{HashMap, HashSet};
+
+use pjsh_core::{command::CommandResult, Context, Scope};
+
+use crate::{
+    call::call_function,
+    error::{EvalError, EvalResult},
+};
+
+/// Name of a function that is called when a command cannot be resolved.
+const COMMAND_NOT_FOUND_HANDLER: &str = "command_not_found_handle";
+
+/// Handles a command that cannot be resolved.
+///
+/// The function named `command_not_found_handle` is called with the command's
+/// name and arguments if it is defined, and its exit code is used as that of
+/// the command. An [`EvalError::UnknownCommand`] is returned otherwise.
+///
+/// The handler is hidden from commands executed within it, so unknown commands
+/// within the handler are never handled recursively.
+pub(crate) fn handle_unknown_command(
+    args: &[String],
+    context: &mut Context,
+) -> EvalResult<CommandResult> {
+    let Some(handler) = context.get_function(COMMAND_NOT_FOUND_HANDLER).cloned() else {
+        return Err(EvalError::UnknownCommand(args[0].to_owned()));
+    };
+
+    let scope = Scope::new(
+        COMMAND_NOT_FOUND_HANDLER.to_owned(),
+        None,
+        HashMap::new(),
+        HashMap::from([(COMMAND_NOT_FOUND_HANDLER.to_owned(), None)]),
+        HashSet::new(),
+    );
+
+    let handler_args: Vec<String> = std::iter::once(handler.name.clone())
+        .chain(args.iter().cloned())
+        .collect();
+    context.scoped(scope, |context| {
+        call_function(&handler, &handler_args, context)
+    })
+}
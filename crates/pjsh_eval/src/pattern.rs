@@ -0,0 +1,74 @@
+/// Returns `true` if some input matches a glob pattern.
+///
+/// Within the pattern, `*` matches any sequence of characters, and `?` matches
+/// any single character. All other characters match themselves.
+pub(crate) fn matches_glob(pattern: &str, input: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let input: Vec<char> = input.chars().collect();
+
+    let (mut p, mut i) = (0, 0);
+
+    // Position of the most recent `*` in the pattern, and the input position
+    // that it is currently matched up to.
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while i < input.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, i));
+                p += 1;
+            }
+            Some('?') => {
+                p += 1;
+                i += 1;
+            }
+            Some(ch) if *ch == input[i] => {
+                p += 1;
+                i += 1;
+            }
+            _ => match backtrack {
+                // Let the most recent `*` match one more character.
+                Some((star, matched)) => {
+                    backtrack = Some((star, matched + 1));
+                    p = star + 1;
+                    i = matched + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|ch| *ch == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_matches_literals() {
+        assert!(matches_glob("file.txt", "file.txt"));
+        assert!(!matches_glob("file.txt", "file.md"));
+        assert!(matches_glob("", ""));
+        assert!(!matches_glob("", "a"));
+    }
+
+    #[test]
+    fn it_matches_wildcards() {
+        assert!(matches_glob("*.txt", "file.txt"));
+        assert!(matches_glob("*.txt", ".txt"));
+        assert!(!matches_glob("*.txt", "file.md"));
+        assert!(matches_glob("a*b*c", "aXXbYYc"));
+        assert!(matches_glob("a*b*c", "abbbc"));
+        assert!(!matches_glob("a*b*c", "acb"));
+        assert!(matches_glob("*", ""));
+        assert!(matches_glob("**", "anything"));
+    }
+
+    #[test]
+    fn it_matches_single_characters() {
+        assert!(matches_glob("file?.txt", "file1.txt"));
+        assert!(!matches_glob("file?.txt", "file.txt"));
+        assert!(matches_glob("?*", "ä"));
+    }
+}
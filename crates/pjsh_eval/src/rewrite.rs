@@ -0,0 +1,51 @@
+use std::collections::{HashMap, HashSet};
+
+use pjsh_core::{utils::word_var, Context, Scope};
+
+use crate::{
+    error::{EvalError, EvalResult},
+    words::capture_function_call,
+};
+
+/// Variable containing the name of a function that may rewrite commands before
+/// they are resolved.
+const COMMAND_REWRITE_VAR: &str = "PJSH_COMMAND_REWRITE";
+
+/// Rewrites the expanded arguments of a command using the function named by
+/// `$PJSH_COMMAND_REWRITE`.
+///
+/// The function is called with the arguments and its output, one argument per
+/// line, replaces them if it exits successfully. The original arguments are
+/// kept if the variable is unset, the function is undefined, or the function
+/// exits with a non-zero code.
+///
+/// Commands within the rewrite function are never rewritten, so a command is
+/// rewritten at most once.
+pub(crate) fn rewrite_command(args: Vec<String>, context: &Context) -> EvalResult<Vec<String>> {
+    let Some(function) =
+        word_var(context, COMMAND_REWRITE_VAR).and_then(|name| context.get_function(name))
+    else {
+        return Ok(args);
+    };
+
+    // Hide the rewrite function from commands executed within it.
+    let mut inner_context = context.try_clone().map_err(EvalError::ContextCloneFailed)?;
+    inner_context.push_scope(Scope::new(
+        COMMAND_REWRITE_VAR.to_owned(),
+        None,
+        HashMap::from([(COMMAND_REWRITE_VAR.to_owned(), None)]),
+        HashMap::new(),
+        HashSet::new(),
+    ));
+
+    let function_args: Vec<String> = std::iter::once(function.name.clone())
+        .chain(args.iter().cloned())
+        .collect();
+    let (code, output) = capture_function_call(function, &function_args, &inner_context)?;
+
+    if code != 0 || output.is_empty() {
+        return Ok(args);
+    }
+
+    Ok(output.lines().map(ToString::to_string).collect())
+}
@@ -1,37 +1,40 @@
 use std::{
     collections::VecDeque,
     env::temp_dir,
-    io::{BufReader, Read, Seek},
+    fs::File,
+    io::{BufReader, Read, Seek, Write},
     path::PathBuf,
 };
 
 use dirs::home_dir;
 use pjsh_ast::{Function, InterpolationUnit, List, Program, ValuePipeline, Word};
 use pjsh_core::{
-    utils::{path_to_string, word_var},
+    command::CommandResult,
+    utils::{git_branch, path_to_string, user_home_dir, word_var},
     Context, FileDescriptor, Value, FD_STDOUT,
 };
 use rand::Rng;
 use tempfile::tempfile;
 
 use crate::{
+    arithmetic::evaluate_arithmetic,
     call::call_function,
     error::{EvalError, EvalResult},
     execute_subshell,
     filter::apply_filter,
 };
 
+/// Maximum number of words that a numeric brace range, such as `{1..3}`, may
+/// expand into.
+const MAX_BRACE_RANGE_LEN: u64 = 100_000;
+
 /// Expands words.
 pub fn expand_words(words: &[Word], context: &Context) -> EvalResult<Vec<String>> {
     if words.is_empty() {
         return Ok(Vec::new());
     }
 
-    let is_aliasable = matches!(words[0], Word::Literal(_) | Word::Variable(_));
     let mut words = interpolate_words(words, context)?;
-
-    if is_aliasable {}
-
     Ok(Vec::from(std::mem::take(&mut words.make_contiguous())))
 }
 
@@ -39,11 +42,19 @@ pub fn expand_words(words: &[Word], context: &Context) -> EvalResult<Vec<String>
 fn interpolate_words(words: &[Word], context: &Context) -> EvalResult<VecDeque<String>> {
     let mut interpolated_words = VecDeque::with_capacity(words.len());
     for word in words {
+        if matches!(word, Word::Variable(name) if name == "@") {
+            interpolated_words.extend(positional_args(context).iter().cloned());
+            continue;
+        }
+
         let is_globbable = matches!(word, Word::Literal(_));
         let word = interpolate_word(word, context)?;
 
         if is_globbable {
-            interpolated_words.extend(expand_globs(word, context));
+            let word = expand_tilde(&word, context);
+            for word in expand_braces(&word) {
+                interpolated_words.extend(expand_globs(word, context));
+            }
         } else {
             interpolated_words.push_back(word);
         }
@@ -51,9 +62,95 @@ fn interpolate_words(words: &[Word], context: &Context) -> EvalResult<VecDeque<S
     Ok(interpolated_words)
 }
 
+/// Expands brace groups, such as `{a,b}` and `{1..3}`, into multiple words.
+///
+/// Words containing multiple groups are expanded into the cartesian product of
+/// the groups. Groups without a top-level comma or a numeric range are kept.
+fn expand_braces(word: &str) -> Vec<String> {
+    for (start, _) in word.match_indices('{') {
+        let Some(end) = closing_brace(word, start) else {
+            continue;
+        };
+        let Some(alternatives) = brace_alternatives(&word[start + 1..end]) else {
+            continue;
+        };
+
+        let prefix = &word[..start];
+        let suffixes = expand_braces(&word[end + 1..]);
+        let mut words = Vec::with_capacity(alternatives.len() * suffixes.len());
+        for alternative in alternatives {
+            for expanded in expand_braces(&alternative) {
+                for suffix in &suffixes {
+                    words.push(format!("{prefix}{expanded}{suffix}"));
+                }
+            }
+        }
+        return words;
+    }
+
+    vec![word.to_owned()]
+}
+
+/// Returns the index of the brace that closes the brace opened at `start`.
+fn closing_brace(word: &str, start: usize) -> Option<usize> {
+    let mut depth = 0;
+    for (index, ch) in word[start..].char_indices() {
+        match ch {
+            '{' => depth += 1,
+            '}' if depth == 1 => return Some(start + index),
+            '}' => depth -= 1,
+            _ => (),
+        }
+    }
+    None
+}
+
+/// Returns the alternatives within a brace group, given the group's contents.
+///
+/// Alternatives are either separated by top-level commas, or given as an
+/// inclusive numeric range, such as `1..3` or `3..1`. Ranges of more
+/// than [`MAX_BRACE_RANGE_LEN`] numbers are not expanded.
+fn brace_alternatives(group: &str) -> Option<Vec<String>> {
+    let mut alternatives = Vec::new();
+    let (mut depth, mut start) = (0, 0);
+    for (index, ch) in group.char_indices() {
+        match ch {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            ',' if depth == 0 => {
+                alternatives.push(group[start..index].to_owned());
+                start = index + 1;
+            }
+            _ => (),
+        }
+    }
+
+    if !alternatives.is_empty() {
+        alternatives.push(group[start..].to_owned());
+        return Some(alternatives);
+    }
+
+    let (first, last) = group.split_once("..")?;
+    let (first, last): (i64, i64) = (first.parse().ok()?, last.parse().ok()?);
+    if first.abs_diff(last) >= MAX_BRACE_RANGE_LEN {
+        return None;
+    }
+
+    let numbers: Vec<String> = match first <= last {
+        true => (first..=last).map(|n| n.to_string()).collect(),
+        false => (last..=first).rev().map(|n| n.to_string()).collect(),
+    };
+    Some(numbers)
+}
+
 /// Expands globs.
-fn expand_globs(mut word: String, context: &Context) -> VecDeque<String> {
-    expand_tilde(&mut word, context);
+///
+/// Words are kept as-is if globbing is disabled using the `noglob` option.
+fn expand_globs(word: String, context: &Context) -> VecDeque<String> {
+    if context.options.noglob {
+        return VecDeque::from([word]);
+    }
+
     expand_asterisk(word, context)
 }
 
@@ -93,13 +190,30 @@ fn expand_asterisk(word: String, context: &Context) -> VecDeque<String> {
     words
 }
 
-/// Expands the tilde (`~`) symbol.
-fn expand_tilde(word: &mut String, context: &Context) {
-    if word.starts_with('~') {
-        let home = word_var(context, "HOME").unwrap_or("/");
+/// Expands a leading tilde (`~`) symbol into a home directory.
+///
+/// `~` expands to the current user's home directory, and `~user` expands to the
+/// home directory of `user`. Words are kept as-is if the home directory is unknown.
+fn expand_tilde(word: &str, context: &Context) -> String {
+    let Some(tilde_prefix) = word.strip_prefix('~') else {
+        return word.to_owned();
+    };
+
+    let (user, path) = match tilde_prefix.find('/') {
+        Some(index) => tilde_prefix.split_at(index),
+        None => (tilde_prefix, ""),
+    };
+
+    let home = match user {
+        "" => word_var(context, "HOME")
+            .map(PathBuf::from)
+            .or_else(home_dir),
+        user => user_home_dir(user),
+    };
 
-        // Replace the word.
-        *word = word.replacen('~', home, 1);
+    match home {
+        Some(home) => path_to_string(home) + path,
+        None => word.to_owned(),
     }
 }
 
@@ -135,6 +249,16 @@ fn interpolate_units(units: &[InterpolationUnit], context: &Context) -> EvalResu
             pjsh_ast::InterpolationUnit::Variable(name) => {
                 output.push_str(&interpolate_variable(name, context)?);
             }
+            pjsh_ast::InterpolationUnit::VariableLength(name) => {
+                output.push_str(&interpolate_variable_length(name, context)?);
+            }
+            pjsh_ast::InterpolationUnit::Arithmetic(expression) => {
+                output.push_str(&evaluate_arithmetic(expression, context)?.to_string());
+            }
+            pjsh_ast::InterpolationUnit::Substring(name, offset, length) => {
+                let value = interpolate_variable(name, context)?;
+                output.push_str(&substring(&value, *offset, *length));
+            }
             pjsh_ast::InterpolationUnit::Subshell(subshell) => {
                 output.push_str(&interpolate_subshell(subshell, context)?);
             }
@@ -147,9 +271,33 @@ fn interpolate_units(units: &[InterpolationUnit], context: &Context) -> EvalResu
     Ok(output)
 }
 
+/// Interpolates a word into a value.
+///
+/// Variables and value pipelines may result in lists. All other words are
+/// interpolated into single words.
+pub(crate) fn interpolate_value(word: &Word, context: &Context) -> EvalResult<Value> {
+    match word {
+        Word::Variable(name) if name == "@" => Ok(Value::List(positional_args(context).to_vec())),
+        Word::Variable(name) => match context.get_var_value(name) {
+            Some(Value::List(list)) => Ok(Value::List(list)),
+            _ => interpolate_variable(name, context).map(Value::Word),
+        },
+        Word::ValuePipeline(pipeline) => evaluate_value_pipeline(pipeline, context),
+        word => interpolate_word(word, context).map(Value::Word),
+    }
+}
+
 /// Interpolates a value pipeline.
 fn interpolate_value_pipeline(pipeline: &ValuePipeline, context: &Context) -> EvalResult<String> {
-    let Some(mut value) = context.get_var(&pipeline.base).cloned() else {
+    match evaluate_value_pipeline(pipeline, context)? {
+        Value::Word(word) => Ok(word),
+        Value::List(_) => Err(EvalError::InvalidListInterpolation(pipeline.base.clone())),
+    }
+}
+
+/// Evaluates a value pipeline by applying all filters to its base value.
+fn evaluate_value_pipeline(pipeline: &ValuePipeline, context: &Context) -> EvalResult<Value> {
+    let Some(mut value) = context.get_var_value(&pipeline.base) else {
         return Err(EvalError::UndefinedVariable(pipeline.base.clone()));
     };
 
@@ -157,15 +305,29 @@ fn interpolate_value_pipeline(pipeline: &ValuePipeline, context: &Context) -> Ev
         value = apply_filter(filter, value, context)?;
     }
 
-    match value {
-        Value::Word(word) => Ok(word),
-        Value::List(_) => Err(EvalError::InvalidListInterpolation(pipeline.base.clone())),
-    }
+    Ok(value)
+}
+
+/// Returns a file containing the contents of a here-string.
+///
+/// Words are written as a single line, and lists are written with one item per
+/// line. The returned file is rewound to its start.
+pub(crate) fn here_string(word: &Word, context: &Context) -> EvalResult<File> {
+    let contents: String = match interpolate_value(word, context)? {
+        Value::Word(word) => word + "\n",
+        Value::List(items) => items.into_iter().map(|item| item + "\n").collect(),
+    };
+
+    let mut file = tempfile().map_err(EvalError::IoError)?;
+    file.write_all(contents.as_bytes())
+        .and_then(|_| file.rewind())
+        .map_err(EvalError::IoError)?;
+    Ok(file)
 }
 
 /// Interpolates a subshell.
 fn interpolate_subshell(subshell: &Program, context: &Context) -> EvalResult<String> {
-    interpolate(context, |context| execute_subshell(subshell, context))
+    interpolate(context, |context| execute_subshell(subshell, context)).map(|(_, output)| output)
 }
 
 /// Interpolates a function call.
@@ -177,17 +339,37 @@ pub fn interpolate_function_call(
     interpolate(context, |mut context| {
         call_function(function, args, &mut context).map(|_| ())
     })
+    .map(|(_, output)| output)
+}
+
+/// Calls a function, capturing its exit code along with its stdout.
+pub(crate) fn capture_function_call(
+    function: &Function,
+    args: &[String],
+    context: &Context,
+) -> EvalResult<(i32, String)> {
+    interpolate(context, |mut context| {
+        match call_function(function, args, &mut context) {
+            Ok(CommandResult::Builtin(result)) => Ok(result.code),
+            Ok(CommandResult::Process(_)) => unreachable!("functions are called within the shell"),
+            Err(EvalError::Exit(code)) => Ok(code),
+            Err(error) => Err(error),
+        }
+    })
 }
 
-/// Returns the interpolated stdout of a function.
-fn interpolate(context: &Context, func: impl Fn(Context) -> EvalResult<()>) -> EvalResult<String> {
+/// Returns the interpolated stdout of a function along with its result.
+fn interpolate<T>(
+    context: &Context,
+    func: impl Fn(Context) -> EvalResult<T>,
+) -> EvalResult<(T, String)> {
     let mut inner_context = context.try_clone().map_err(EvalError::ContextCloneFailed)?;
 
     let stdout = tempfile().map_err(EvalError::IoError)?;
     let stdout_fd = FileDescriptor::FileHandle(stdout.try_clone().map_err(EvalError::IoError)?);
     inner_context.set_file_descriptor(FD_STDOUT, stdout_fd);
 
-    func(inner_context)?;
+    let value = func(inner_context)?;
 
     let read_file = |mut file: std::fs::File| {
         let _ = file.rewind();
@@ -206,7 +388,7 @@ fn interpolate(context: &Context, func: impl Fn(Context) -> EvalResult<()>) -> E
         contents
     };
 
-    Ok(read_file(stdout))
+    Ok((value, read_file(stdout)))
 }
 
 /// Interpolates a variable within a context.
@@ -214,24 +396,90 @@ fn interpolate_variable(variable_name: &str, context: &Context) -> EvalResult<St
     match variable_name {
         "$" => Ok(std::process::id().to_string()),
         "?" => Ok(context.last_exit().to_string()),
+        index if !index.is_empty() && index.chars().all(|c| c.is_ascii_digit()) => {
+            let arg = index
+                .parse()
+                .ok()
+                .and_then(|i: usize| context.args().get(i));
+            match arg {
+                Some(arg) => Ok(arg.to_owned()),
+                None if context.options.nounset => {
+                    Err(EvalError::UndefinedVariable(variable_name.to_owned()))
+                }
+                None => Ok(String::new()),
+            }
+        }
+        "@" | "*" => Ok(positional_args(context).join(" ")),
+        "#" => Ok(positional_args(context).len().to_string()),
+        "!" => match context.last_async_pid {
+            Some(pid) => Ok(pid.to_string()),
+            None if context.options.nounset => Err(EvalError::UndefinedVariable("!".to_owned())),
+            None => Ok(String::new()),
+        },
         "HOME" => home_dir().map_or_else(
             || Err(EvalError::UndefinedVariable("HOME".to_owned())),
             |path| Ok(path_to_string(path)),
         ),
+        "PJSH_GIT_BRANCH" => Ok(word_var(context, "PWD")
+            .and_then(git_branch)
+            .unwrap_or_default()),
         "SHELL" => std::env::current_exe().map_or_else(
             |err| Err(EvalError::IoError(err)),
             |path| Ok(path_to_string(path)),
         ),
-        _ => match context.get_var(variable_name) {
-            Some(Value::Word(word)) => Ok(word.to_owned()),
+        _ => match context.get_var_value(variable_name) {
+            Some(Value::Word(word)) => Ok(word),
             Some(Value::List(_)) => Err(EvalError::InvalidListInterpolation(
                 variable_name.to_owned(),
             )),
-            None => Err(EvalError::UndefinedVariable(variable_name.to_owned())),
+            None if context.options.nounset => {
+                Err(EvalError::UndefinedVariable(variable_name.to_owned()))
+            }
+            None => Ok(String::new()),
         },
     }
 }
 
+/// Returns the positional arguments of a context, excluding `$0`.
+pub(crate) fn positional_args(context: &Context) -> &[String] {
+    context.args().get(1..).unwrap_or_default()
+}
+
+/// Interpolates the length of a variable's value, in characters, within a context.
+///
+/// Undefined variables have a length of `0`.
+fn interpolate_variable_length(variable_name: &str, context: &Context) -> EvalResult<String> {
+    match context.get_var_value(variable_name) {
+        Some(Value::Word(word)) => Ok(word.chars().count().to_string()),
+        Some(Value::List(_)) => Err(EvalError::InvalidListInterpolation(
+            variable_name.to_owned(),
+        )),
+        None => Ok(String::from("0")),
+    }
+}
+
+/// Returns the substring of a value starting at a character offset, keeping at
+/// most `length` characters, or all remaining characters if no length is given.
+///
+/// Negative offsets count from the end of the value. Offsets outside of the
+/// value result in an empty substring.
+fn substring(value: &str, offset: isize, length: Option<usize>) -> String {
+    let char_count = value.chars().count();
+    let start = match offset {
+        offset if offset < 0 => char_count.checked_sub(offset.unsigned_abs()),
+        offset => Some(offset.unsigned_abs()).filter(|start| *start <= char_count),
+    };
+
+    match start {
+        Some(start) => value
+            .chars()
+            .skip(start)
+            .take(length.unwrap_or(usize::MAX))
+            .collect(),
+        None => String::new(),
+    }
+}
+
 /// Substitutes a process/program definition with a path to a file containing
 /// the contents of the process' standard output file descriptor.
 fn substitute_process(process: &Program, context: &Context) -> EvalResult<String> {
@@ -268,6 +516,72 @@ fn it_expands_empty_words() {
         );
     }
 
+    #[test]
+    fn it_expands_braces() {
+        let expand = |word| expand_words(&[Word::Literal(word)], &Context::default()).unwrap();
+
+        assert_eq!(
+            expand("file-{a,b,c}.txt".into()),
+            vec!["file-a.txt", "file-b.txt", "file-c.txt"]
+        );
+        assert_eq!(expand("{a,b}{1,2}".into()), vec!["a1", "a2", "b1", "b2"]);
+        assert_eq!(expand("{a,b{1,2}}".into()), vec!["a", "b1", "b2"]);
+        assert_eq!(expand("{x{a,b}}".into()), vec!["{xa}", "{xb}"]);
+        assert_eq!(expand("{a,}".into()), vec!["a", ""]);
+    }
+
+    #[test]
+    fn it_expands_numeric_ranges() {
+        let expand = |word| expand_words(&[Word::Literal(word)], &Context::default()).unwrap();
+
+        assert_eq!(expand("{1..3}".into()), vec!["1", "2", "3"]);
+        assert_eq!(expand("{5..1}".into()), vec!["5", "4", "3", "2", "1"]);
+        assert_eq!(expand("{-1..1}".into()), vec!["-1", "0", "1"]);
+        assert_eq!(
+            expand("v{1..2}{a,b}".into()),
+            vec!["v1a", "v1b", "v2a", "v2b"]
+        );
+    }
+
+    #[test]
+    fn it_keeps_unexpandable_braces() {
+        let expand = |word| expand_words(&[Word::Literal(word)], &Context::default()).unwrap();
+
+        assert_eq!(expand("{literal}".into()), vec!["{literal}"]);
+        assert_eq!(expand("{a..z}".into()), vec!["{a..z}"]);
+        assert_eq!(expand("{1..1000000000}".into()), vec!["{1..1000000000}"]);
+        assert_eq!(expand("{a,b".into()), vec!["{a,b"]);
+        assert_eq!(
+            expand_words(&[Word::Quoted("{a,b}".into())], &Context::default()).unwrap(),
+            vec!["{a,b}"]
+        );
+    }
+
+    #[test]
+    fn it_expands_tildes() {
+        let mut context = Context::default();
+        context
+            .set_var("HOME".into(), Value::Word("/home/user".into()))
+            .unwrap();
+        let expand = |word: &str| expand_words(&[Word::Literal(word.into())], &context).unwrap();
+
+        assert_eq!(expand("~"), vec!["/home/user"]);
+        assert_eq!(expand("~/sub"), vec!["/home/user/sub"]);
+        assert_eq!(expand("a~b"), vec!["a~b"]);
+        assert_eq!(
+            expand("~pjsh-missing-user/sub"),
+            vec!["~pjsh-missing-user/sub"]
+        );
+        assert_eq!(
+            expand_words(&[Word::Quoted("~".into())], &context).unwrap(),
+            vec!["~"]
+        );
+        assert_eq!(
+            interpolate_word(&Word::Literal("~".into()), &context).unwrap(),
+            "~"
+        );
+    }
+
     #[test]
     fn it_interpolates_words() {
         let context = Context::with_scopes(vec![Scope::new(
@@ -290,4 +604,80 @@ fn it_interpolates_words() {
             "val",
         );
     }
+
+    #[test]
+    fn it_interpolates_variable_lengths() {
+        let context = Context::with_scopes(vec![Scope::new(
+            "scope".into(),
+            Some(Vec::default()),
+            HashMap::from([("var".into(), Some(Value::Word("åäö €".into())))]),
+            HashMap::default(),
+            HashSet::default(),
+        )]);
+        let length = |name: &str| {
+            interpolate_word(
+                &Word::Interpolation(vec![InterpolationUnit::VariableLength(name.into())]),
+                &context,
+            )
+            .unwrap_or("ERROR".into())
+        };
+        assert_eq!(length("var"), "5");
+        assert_eq!(length("undefined"), "0");
+    }
+
+    #[test]
+    fn it_interpolates_arithmetic_expressions() {
+        let context = Context::with_scopes(vec![Scope::new(
+            "scope".into(),
+            Some(Vec::default()),
+            HashMap::from([("i".into(), Some(Value::Word("41".into())))]),
+            HashMap::default(),
+            HashSet::default(),
+        )]);
+        let word = Word::Interpolation(vec![
+            InterpolationUnit::Literal("answer: ".into()),
+            InterpolationUnit::Arithmetic("i + 1".into()),
+        ]);
+        assert_eq!(
+            interpolate_word(&word, &context).unwrap_or("ERROR".into()),
+            "answer: 42"
+        );
+
+        let word = Word::Interpolation(vec![InterpolationUnit::Arithmetic("i / 0".into())]);
+        assert!(matches!(
+            interpolate_word(&word, &context),
+            Err(EvalError::InvalidArithmetic(expression, _)) if expression == "i / 0"
+        ));
+    }
+
+    #[test]
+    fn it_interpolates_substrings() {
+        let context = Context::with_scopes(vec![Scope::new(
+            "scope".into(),
+            Some(Vec::default()),
+            HashMap::from([("version".into(), Some(Value::Word("v1.2.3-ä".into())))]),
+            HashMap::default(),
+            HashSet::default(),
+        )]);
+        let substring = |offset: isize, length: Option<usize>| {
+            interpolate_word(
+                &Word::Interpolation(vec![InterpolationUnit::Substring(
+                    "version".into(),
+                    offset,
+                    length,
+                )]),
+                &context,
+            )
+            .unwrap_or("ERROR".into())
+        };
+
+        assert_eq!(substring(1, None), "1.2.3-ä");
+        assert_eq!(substring(1, Some(3)), "1.2");
+        assert_eq!(substring(-3, None), "3-ä");
+        assert_eq!(substring(-3, Some(1)), "3");
+        assert_eq!(substring(6, Some(10)), "-ä");
+        assert_eq!(substring(8, None), "");
+        assert_eq!(substring(9, None), "");
+        assert_eq!(substring(-9, None), "");
+    }
 }
@@ -1,8 +1,13 @@
 use std::collections::{HashMap, HashSet};
 
 use pjsh_ast::{AndOr, Assignment, Command, Pipeline, PipelineSegment, Statement, Value, Word};
-use pjsh_core::{Context, Scope};
-use pjsh_eval::{execute_statement, EvalResult};
+use pjsh_core::{
+    utils::{path_to_string, word_var},
+    Context, FileDescriptor, Scope, FD_STDOUT,
+};
+use pjsh_eval::{execute_statement, EvalError, EvalResult};
+use pjsh_parse::parse;
+use tempfile::TempDir;
 
 #[derive(Clone)]
 struct TrueCommand;
@@ -16,6 +21,22 @@ fn run(&self, _: &mut pjsh_core::command::Args) -> pjsh_core::command::CommandRe
     }
 }
 
+#[derive(Clone)]
+struct ExitCommand;
+impl pjsh_core::command::Command for ExitCommand {
+    fn name(&self) -> &str {
+        "exit"
+    }
+
+    fn run(&self, args: &mut pjsh_core::command::Args) -> pjsh_core::command::CommandResult {
+        let code = args.context.args()[1].parse().unwrap();
+        pjsh_core::command::CommandResult::with_actions(
+            code,
+            vec![pjsh_core::command::Action::ExitScope(code)],
+        )
+    }
+}
+
 #[test]
 fn it_assigns_variables() {
     let mut context = Context::with_scopes(vec![Scope::new(
@@ -66,3 +87,671 @@ fn it_works() -> EvalResult<()> {
     assert_eq!(context.last_exit(), 0);
     Ok(())
 }
+
+/// Constructs a context containing the current process' `PATH` and a set of
+/// variables.
+fn context_with_vars(vars: &[(&str, String)]) -> Context {
+    let mut vars: HashMap<String, Option<pjsh_core::Value>> = vars
+        .iter()
+        .map(|(key, value)| (key.to_string(), Some(pjsh_core::Value::Word(value.clone()))))
+        .collect();
+    if let Ok(path) = std::env::var("PATH") {
+        vars.insert("PATH".into(), Some(pjsh_core::Value::Word(path)));
+    }
+
+    Context::with_scopes(vec![Scope::new(
+        "scope".into(),
+        Some(Vec::default()),
+        vars,
+        HashMap::default(),
+        HashSet::default(),
+    )])
+}
+
+/// Parses and executes a program within a context.
+fn execute_src(src: &str, context: &mut Context) -> EvalResult<()> {
+    let program = parse(src, &HashMap::default()).expect("source should be valid");
+    for statement in &program.statements {
+        execute_statement(statement, context)?;
+    }
+    Ok(())
+}
+
+#[test]
+fn it_reads_input_from_a_variable_file_name() -> EvalResult<()> {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("input.txt");
+    let output = dir.path().join("output.txt");
+    std::fs::write(&input, "first\nsecond\n").unwrap();
+
+    let mut context = context_with_vars(&[
+        ("input", path_to_string(&input)),
+        ("output", path_to_string(&output)),
+    ]);
+    execute_src("wc -l < $input > $output", &mut context)?;
+
+    assert_eq!(std::fs::read_to_string(output).unwrap().trim(), "2");
+    Ok(())
+}
+
+#[test]
+fn it_reads_input_from_a_tilde_file_name() -> EvalResult<()> {
+    let home = TempDir::new().unwrap();
+    let output = home.path().join("output.txt");
+    std::fs::write(home.path().join("input.txt"), "first\nsecond\nthird\n").unwrap();
+
+    let mut context = context_with_vars(&[
+        ("HOME", path_to_string(home.path())),
+        ("output", path_to_string(&output)),
+    ]);
+    execute_src("wc -l < ~/input.txt > $output", &mut context)?;
+
+    assert_eq!(std::fs::read_to_string(output).unwrap().trim(), "3");
+    Ok(())
+}
+
+#[test]
+fn it_appends_output_to_files() -> EvalResult<()> {
+    let dir = TempDir::new().unwrap();
+    let output = dir.path().join("output.txt");
+
+    let mut context = context_with_vars(&[("output", path_to_string(&output))]);
+    execute_src(
+        "echo first >> $output; echo second >> $output",
+        &mut context,
+    )?;
+
+    assert_eq!(std::fs::read_to_string(output).unwrap(), "first\nsecond\n");
+    Ok(())
+}
+
+#[test]
+fn it_duplicates_file_descriptors_after_redirecting_to_files() -> EvalResult<()> {
+    let dir = TempDir::new().unwrap();
+    let output = dir.path().join("output.txt");
+
+    let mut context = context_with_vars(&[("output", path_to_string(&output))]);
+    execute_src(
+        "sh -c 'echo out; echo err >&2' > $output 2>&1",
+        &mut context,
+    )?;
+
+    assert_eq!(std::fs::read_to_string(output).unwrap(), "out\nerr\n");
+    Ok(())
+}
+
+#[test]
+fn it_duplicates_file_descriptors_before_redirecting_to_files() -> EvalResult<()> {
+    let dir = TempDir::new().unwrap();
+    let output = dir.path().join("output.txt");
+    let terminal = dir.path().join("terminal.txt");
+
+    let mut context = context_with_vars(&[("output", path_to_string(&output))]);
+    context.set_file_descriptor(
+        FD_STDOUT,
+        FileDescriptor::FileHandle(std::fs::File::create(&terminal).unwrap()),
+    );
+    execute_src(
+        "sh -c 'echo out; echo err >&2' 2>&1 > $output",
+        &mut context,
+    )?;
+
+    assert_eq!(std::fs::read_to_string(output).unwrap(), "out\n");
+    assert_eq!(std::fs::read_to_string(terminal).unwrap(), "err\n");
+    Ok(())
+}
+
+#[test]
+fn it_restores_redirected_file_descriptors() -> EvalResult<()> {
+    let dir = TempDir::new().unwrap();
+    let output = dir.path().join("output.txt");
+    let terminal = dir.path().join("terminal.txt");
+
+    let mut context = context_with_vars(&[("output", path_to_string(&output))]);
+    context.set_file_descriptor(
+        FD_STDOUT,
+        FileDescriptor::FileHandle(std::fs::File::create(&terminal).unwrap()),
+    );
+    execute_src("echo a > $output; echo b", &mut context)?;
+
+    assert_eq!(std::fs::read_to_string(output).unwrap(), "a\n");
+    assert_eq!(std::fs::read_to_string(terminal).unwrap(), "b\n");
+    Ok(())
+}
+
+#[test]
+fn it_reads_list_here_strings_as_lines() -> EvalResult<()> {
+    let dir = TempDir::new().unwrap();
+    let output = dir.path().join("output.txt");
+
+    let mut context = context_with_vars(&[("output", path_to_string(&output))]);
+    execute_src("list := [a b c]\nwc -l <<< $list > $output", &mut context)?;
+
+    assert_eq!(std::fs::read_to_string(output).unwrap().trim(), "3");
+    Ok(())
+}
+
+#[test]
+fn it_breaks_loops() -> EvalResult<()> {
+    let mut context = context_with_vars(&[]);
+    execute_src(
+        "while true { state := inside; break; state := after }",
+        &mut context,
+    )?;
+    assert_eq!(
+        context.get_var("state"),
+        Some(&pjsh_core::Value::Word("inside".into()))
+    );
+
+    execute_src(
+        "for i in [1 2] { break; pjsh-missing-command }",
+        &mut context,
+    )
+}
+
+#[test]
+fn it_continues_loops() -> EvalResult<()> {
+    let mut context = context_with_vars(&[]);
+    execute_src(
+        "for i in [1 2] { continue; pjsh-missing-command }",
+        &mut context,
+    )
+}
+
+#[test]
+fn it_breaks_and_continues_outer_loops() -> EvalResult<()> {
+    let mut context = context_with_vars(&[]);
+    execute_src(
+        "for a in [1 2] { for b in [1 2] { break 2 }; pjsh-missing-command }",
+        &mut context,
+    )?;
+    execute_src(
+        "for a in [1 2] { for b in [1 2] { continue 2 }; pjsh-missing-command }",
+        &mut context,
+    )
+}
+
+#[test]
+fn it_rejects_loop_control_outside_loops() {
+    let mut context = context_with_vars(&[]);
+    assert!(matches!(
+        execute_src("break", &mut context),
+        Err(EvalError::LoopControlOutsideLoop(_))
+    ));
+    assert!(matches!(
+        execute_src("fn f() { continue }\nfor i in [1] { f }", &mut context),
+        Err(EvalError::LoopControlOutsideLoop(_))
+    ));
+}
+
+#[test]
+fn it_returns_from_functions() -> EvalResult<()> {
+    let mut context = context_with_vars(&[]);
+    execute_src(
+        "fn f() { for i in [1 2] { if [[ $i == 1 ]] { return 3 } }; pjsh-missing-command }\nf",
+        &mut context,
+    )?;
+    assert_eq!(context.last_exit(), 3);
+
+    execute_src("fn g() { [[ a == b ]]; return; true }\ng", &mut context)?;
+    assert_eq!(context.last_exit(), 1); // The last exit code is kept.
+
+    execute_src("code := 4; fn h() { return $code }\nh", &mut context)?;
+    assert_eq!(context.last_exit(), 4);
+    Ok(())
+}
+
+#[test]
+fn it_binds_function_arguments() -> EvalResult<()> {
+    let mut context = context_with_vars(&[]);
+    execute_src(
+        "fn f(a b rest...) { return `$a$b` }\nf 1 2 3 4",
+        &mut context,
+    )?;
+    assert_eq!(context.last_exit(), 12);
+
+    execute_src(
+        "fn g(a rest...) { for r in $rest { return $r } }\ng 1 2 3",
+        &mut context,
+    )?;
+    assert_eq!(context.last_exit(), 2);
+
+    assert!(matches!(
+        execute_src("fn h(a b) { true }\nh 1", &mut context),
+        Err(EvalError::UndefinedFunctionArguments(args)) if args == ["b"]
+    ));
+    assert!(matches!(
+        execute_src("fn i(a) { true }\ni 1 2 3", &mut context),
+        Err(EvalError::UnboundFunctionArguments(args)) if args == ["2", "3"]
+    ));
+    Ok(())
+}
+
+#[test]
+fn it_exits_functions_with_the_last_exit_code() -> EvalResult<()> {
+    let mut context = context_with_vars(&[]);
+    execute_src("fn f() { [[ a == b ]] }\nf", &mut context)?;
+    assert_eq!(context.last_exit(), 1);
+
+    execute_src("fn g() { [[ a == b ]]; [[ a == a ]] }\ng", &mut context)?;
+    assert_eq!(context.last_exit(), 0);
+    Ok(())
+}
+
+#[test]
+fn it_expands_arithmetic_expressions() -> EvalResult<()> {
+    let mut context = context_with_vars(&[("i", "1".into())]);
+    execute_src(
+        "i := $((i + 1))\nj := `${i}/$(( (i + 1) * 2 ))`",
+        &mut context,
+    )?;
+    assert_eq!(
+        context.get_var("i"),
+        Some(&pjsh_core::Value::Word("2".into()))
+    );
+    assert_eq!(
+        context.get_var("j"),
+        Some(&pjsh_core::Value::Word("2/6".into()))
+    );
+
+    assert!(matches!(
+        execute_src("k := $((i / 0))", &mut context),
+        Err(EvalError::InvalidArithmetic(expression, _)) if expression == "i / 0"
+    ));
+    Ok(())
+}
+
+#[test]
+fn it_expands_dynamic_variables() -> EvalResult<()> {
+    let mut context = context_with_vars(&[]);
+    execute_src(
+        "SECONDS := 100\nelapsed := $SECONDS\nlater := $((SECONDS + 1))\nrandom := $RANDOM",
+        &mut context,
+    )?;
+    assert_eq!(context.get_var("SECONDS"), None);
+    assert!(matches!(
+        context.get_var("elapsed"),
+        Some(pjsh_core::Value::Word(seconds)) if seconds.starts_with("10")
+    ));
+    assert!(matches!(
+        context.get_var("later"),
+        Some(pjsh_core::Value::Word(seconds)) if seconds.starts_with("10")
+    ));
+    assert!(matches!(
+        context.get_var("random"),
+        Some(pjsh_core::Value::Word(random)) if random.parse::<u16>().is_ok()
+    ));
+
+    // Regular variables shadow dynamic variables.
+    execute_src("RANDOM := 4\nrandom := $RANDOM", &mut context)?;
+    assert_eq!(
+        context.get_var("random"),
+        Some(&pjsh_core::Value::Word("4".into()))
+    );
+    Ok(())
+}
+
+#[test]
+fn it_expands_the_last_async_pid() -> EvalResult<()> {
+    let mut context = context_with_vars(&[]);
+    assert!(matches!(
+        execute_src("pid := $!", &mut context),
+        Err(EvalError::UndefinedVariable(name)) if name == "!"
+    ));
+
+    execute_src("sleep 0 &\npid := $!", &mut context)?;
+    let pid = context.last_async_pid.expect("async pid should be set");
+    assert_eq!(
+        context.get_var("pid"),
+        Some(&pjsh_core::Value::Word(pid.to_string()))
+    );
+    Ok(())
+}
+
+#[test]
+fn it_expands_the_last_exit_code() -> EvalResult<()> {
+    let mut context = context_with_vars(&[]);
+    execute_src("[[ a == b ]]\nfailed := $?", &mut context)?;
+    assert_eq!(
+        context.get_var("failed"),
+        Some(&pjsh_core::Value::Word("1".into()))
+    );
+
+    execute_src("[[ a == a ]]\nsucceeded := $?", &mut context)?;
+    assert_eq!(
+        context.get_var("succeeded"),
+        Some(&pjsh_core::Value::Word("0".into()))
+    );
+
+    context.register_builtin(Box::new(ExitCommand));
+    execute_src(
+        "( exit 3; exit 4 )
+exited := $?",
+        &mut context,
+    )?;
+    assert_eq!(
+        context.get_var("exited"),
+        Some(&pjsh_core::Value::Word("3".into()))
+    );
+
+    context.options.errexit = true;
+    assert!(matches!(
+        execute_src(
+            "( exit 5 )
+unreachable := 1",
+            &mut context
+        ),
+        Err(EvalError::CommandFailed(5))
+    ));
+    assert_eq!(context.last_exit(), 5);
+    assert_eq!(context.get_var("unreachable"), None);
+    Ok(())
+}
+
+#[test]
+fn it_propagates_subshell_exit_codes() -> EvalResult<()> {
+    let mut context = context_with_vars(&[]);
+    execute_src("( [[ a == a ]]; [[ a == b ]] )\nfailed := $?", &mut context)?;
+    assert_eq!(
+        context.get_var("failed"),
+        Some(&pjsh_core::Value::Word("1".into()))
+    );
+
+    execute_src(
+        "( [[ a == b ]]; [[ a == a ]] )\nsucceeded := $?",
+        &mut context,
+    )?;
+    assert_eq!(
+        context.get_var("succeeded"),
+        Some(&pjsh_core::Value::Word("0".into()))
+    );
+    Ok(())
+}
+
+#[test]
+fn it_calls_the_command_not_found_handler() -> EvalResult<()> {
+    let mut context = context_with_vars(&[]);
+    assert!(matches!(
+        execute_src("pjsh-missing-command", &mut context),
+        Err(EvalError::UnknownCommand(name)) if name == "pjsh-missing-command"
+    ));
+
+    execute_src(
+        "fn command_not_found_handle(name code) { if [[ $name == pjsh-missing-command ]] { return $code } }",
+        &mut context,
+    )?;
+    execute_src("pjsh-missing-command 3", &mut context)?;
+    assert_eq!(context.last_exit(), 3);
+    Ok(())
+}
+
+#[test]
+fn it_does_not_handle_unknown_commands_recursively() -> EvalResult<()> {
+    let mut context = context_with_vars(&[]);
+    execute_src(
+        "fn command_not_found_handle(name args...) { pjsh-missing-handler-command }",
+        &mut context,
+    )?;
+    assert!(matches!(
+        execute_src("pjsh-missing-command", &mut context),
+        Err(EvalError::UnknownCommand(name)) if name == "pjsh-missing-handler-command"
+    ));
+    Ok(())
+}
+
+#[test]
+fn it_removes_scopes_after_errors() -> EvalResult<()> {
+    let mut context = context_with_vars(&[]);
+    execute_src(
+        "fn f() { pjsh-missing-command $undefined }
+        fn command_not_found_handle(name args...) { pjsh-missing-command $undefined }",
+        &mut context,
+    )?;
+    let depth = context.scope_depth();
+
+    for src in [
+        "f",
+        "for item in [a b] { pjsh-missing-command $undefined }",
+        "for item in [a $undefined] { pjsh-missing-command }",
+        "switch a { a as name { pjsh-missing-command $undefined } }",
+        "pjsh-missing-command",
+    ] {
+        assert!(
+            matches!(
+                execute_src(src, &mut context),
+                Err(EvalError::UndefinedVariable(name)) if name == "undefined"
+            ),
+            "{src}"
+        );
+        assert_eq!(context.scope_depth(), depth, "{src}");
+    }
+    Ok(())
+}
+
+#[test]
+fn it_rejects_return_outside_functions() {
+    let mut context = context_with_vars(&[]);
+    assert!(matches!(
+        execute_src("return", &mut context),
+        Err(EvalError::ReturnOutsideFunction)
+    ));
+    assert!(matches!(
+        execute_src("fn f() { return invalid }\nf", &mut context),
+        Err(EvalError::InvalidExitCode(_))
+    ));
+}
+
+#[test]
+fn it_iterates_over_lines_of_files() -> EvalResult<()> {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("input.txt");
+    let first = dir.path().join("first");
+    let second = dir.path().join("second");
+    let lines = format!("{}\n{}\n", path_to_string(&first), path_to_string(&second));
+    std::fs::write(&input, lines).unwrap();
+
+    let mut context = context_with_vars(&[("input", path_to_string(&input))]);
+    execute_src(
+        "for line in lines of file $input { touch $line }",
+        &mut context,
+    )?;
+
+    assert!(first.is_file());
+    assert!(second.is_file());
+    Ok(())
+}
+
+#[test]
+fn it_iterates_over_large_files() -> EvalResult<()> {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("input.txt");
+    let output = dir.path().join("output.txt");
+    let lines: String = (0..2_000)
+        .map(|i| format!("{i} {}\n", "x".repeat(40)))
+        .collect();
+    std::fs::write(&input, &lines).unwrap();
+
+    let mut context = context_with_vars(&[
+        ("input", path_to_string(&input)),
+        ("output", path_to_string(&output)),
+    ]);
+    execute_src(
+        "for line in lines of file $input { echo $line >> $output }",
+        &mut context,
+    )?;
+
+    assert_eq!(std::fs::read_to_string(&output).unwrap(), lines);
+    Ok(())
+}
+
+#[test]
+fn it_fails_to_iterate_over_missing_files() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("missing.txt");
+
+    let mut context = context_with_vars(&[("input", path_to_string(&input))]);
+    assert!(matches!(
+        execute_src("for line in lines of file $input { }", &mut context),
+        Err(EvalError::ReadFileFailed(path, _)) if path == input
+    ));
+}
+
+#[test]
+fn it_aborts_pipeline_segments_that_time_out() -> EvalResult<()> {
+    let mut context = context_with_vars(&[("PJSH_PIPELINE_TIMEOUT", "0.1".into())]);
+    let start = std::time::Instant::now();
+    execute_src("/bin/sleep 5 | cat", &mut context)?;
+
+    assert_eq!(context.last_exit(), 124);
+    assert!(start.elapsed() < std::time::Duration::from_secs(5));
+
+    execute_src("true | cat", &mut context)?;
+    assert_eq!(context.last_exit(), 0);
+    Ok(())
+}
+
+#[test]
+fn it_does_not_abort_functions_that_time_out() -> EvalResult<()> {
+    let mut context = context_with_vars(&[("PJSH_PIPELINE_TIMEOUT", "0.3".into())]);
+    let start = std::time::Instant::now();
+    execute_src(
+        "fn f() { /bin/sleep 0.2; /bin/sleep 0.2 }\nf | /bin/sleep 5",
+        &mut context,
+    )?;
+
+    // The program is killed once the pipeline's deadline has passed, but the
+    // function runs to completion.
+    assert_eq!(context.last_exit(), 124);
+    assert!(start.elapsed() >= std::time::Duration::from_millis(400));
+    assert!(start.elapsed() < std::time::Duration::from_secs(5));
+    Ok(())
+}
+
+#[test]
+fn it_rejects_invalid_pipeline_timeouts() {
+    let mut context = context_with_vars(&[("PJSH_PIPELINE_TIMEOUT", "soon".into())]);
+    assert!(matches!(
+        execute_src("true", &mut context),
+        Err(EvalError::InvalidPipelineTimeout(_))
+    ));
+}
+
+#[test]
+fn it_executes_the_first_matching_switch_branch() -> EvalResult<()> {
+    let mut context = context_with_vars(&[]);
+    execute_src(
+        "switch b {
+          a { in_a := yes }
+          b { in_b := yes }
+          c { in_c := yes }
+        }",
+        &mut context,
+    )?;
+
+    assert_eq!(word_var(&context, "in_a"), None);
+    assert_eq!(word_var(&context, "in_b"), Some("yes"));
+    assert_eq!(word_var(&context, "in_c"), None);
+    Ok(())
+}
+
+#[test]
+fn it_succeeds_without_a_matching_switch_branch() -> EvalResult<()> {
+    let mut context = context_with_vars(&[]);
+    execute_src("[[ a == b ]]", &mut context)?;
+    assert_eq!(context.last_exit(), 1);
+
+    execute_src("switch d { a { pjsh-missing-command } }", &mut context)?;
+    assert_eq!(context.last_exit(), 0);
+    Ok(())
+}
+
+#[test]
+fn it_falls_through_switch_branches() -> EvalResult<()> {
+    let mut context = context_with_vars(&[]);
+    execute_src(
+        "switch b {
+          a { first := yes }
+          b { second := yes; fallthrough }
+          c { third := yes; fallthrough }
+          d { fourth := yes }
+          e { fifth := yes }
+        }",
+        &mut context,
+    )?;
+
+    let yes = Some(pjsh_core::Value::Word("yes".into()));
+    assert_eq!(context.get_var("first"), None);
+    assert_eq!(context.get_var("second"), yes.as_ref());
+    assert_eq!(context.get_var("third"), yes.as_ref());
+    assert_eq!(context.get_var("fourth"), yes.as_ref());
+    assert_eq!(context.get_var("fifth"), None);
+    Ok(())
+}
+
+#[test]
+fn it_binds_matched_switch_input() -> EvalResult<()> {
+    let mut context = context_with_vars(&[("file", "notes.txt".into())]);
+    execute_src(
+        "switch $file {
+          *.md as name { pjsh-missing-command }
+          *.txt as name { [[ $name == notes.txt ]] || pjsh-missing-command }
+        }",
+        &mut context,
+    )?;
+
+    assert_eq!(context.get_var("name"), None);
+    Ok(())
+}
+
+#[test]
+fn it_takes_default_switch_branches() -> EvalResult<()> {
+    let mut context = context_with_vars(&[]);
+    let src = |input: &str| {
+        format!(
+            "switch {input} {{
+              _ {{ branch := default; fallthrough }}
+              a {{ branch := a }}
+              b {{ branch := b }}
+            }}"
+        )
+    };
+
+    execute_src(&src("b"), &mut context)?;
+    assert_eq!(word_var(&context, "branch"), Some("b"));
+
+    execute_src(&src("c"), &mut context)?;
+    assert_eq!(word_var(&context, "branch"), Some("a")); // Falls through.
+
+    context.unset_var("branch").unwrap();
+    execute_src("switch c { a { branch := a } }", &mut context)?;
+    assert_eq!(word_var(&context, "branch"), None);
+    Ok(())
+}
+
+#[test]
+fn it_rejects_misplaced_fallthrough() {
+    let mut context = context_with_vars(&[]);
+    assert!(matches!(
+        execute_src("fallthrough", &mut context),
+        Err(EvalError::MisplacedFallthrough)
+    ));
+}
+
+#[test]
+fn it_does_not_reassign_readonly_variables() {
+    let mut context = context_with_vars(&[("var", "value".into())]);
+    context.make_var_readonly("var".into()).unwrap();
+
+    assert!(matches!(
+        execute_src("var := other", &mut context),
+        Err(EvalError::ReadonlyVariable(name)) if name == "var"
+    ));
+    assert!(matches!(
+        execute_src("for var in [a b] { true }", &mut context),
+        Err(EvalError::ReadonlyVariable(_))
+    ));
+    assert_eq!(
+        context.get_var("var"),
+        Some(&pjsh_core::Value::Word("value".into()))
+    );
+}
@@ -0,0 +1,76 @@
+use pjsh_core::{utils::diff_lines, Filter, FilterError, FilterResult, Value};
+
+/// A filter that returns the differences between two values.
+///
+/// For lists, items are compared with the filter's arguments.
+///
+/// For words, lines are compared with the lines of the filter's argument.
+#[derive(Debug, Clone)]
+pub struct DiffFilter;
+impl Filter for DiffFilter {
+    fn name(&self) -> &str {
+        "diff"
+    }
+
+    fn filter_list(&self, list: Vec<String>, args: &[String]) -> FilterResult {
+        Ok(Value::Word(diff_lines(&list, args).join("\n")))
+    }
+
+    fn filter_word(&self, word: String, args: &[String]) -> FilterResult {
+        let other = match &args {
+            [] => return Err(FilterError::MissingArg("other")),
+            [other] => other,
+            _ => return Err(FilterError::TooManyArgs),
+        };
+
+        let old: Vec<&str> = word.lines().collect();
+        let new: Vec<&str> = other.lines().collect();
+        Ok(Value::Word(diff_lines(&old, &new).join("\n")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_accepts_one_arg_for_words() {
+        assert_eq!(
+            DiffFilter.filter_word("word".into(), &[]),
+            Err(FilterError::MissingArg("other"))
+        );
+        assert_eq!(
+            DiffFilter.filter_word("word".into(), &["1".into(), "2".into()]),
+            Err(FilterError::TooManyArgs)
+        );
+    }
+
+    #[test]
+    fn it_diffs_lists() -> Result<(), FilterError> {
+        assert_eq!(
+            DiffFilter.filter_list(
+                vec!["a".into(), "b".into()],
+                &["a".into(), "c".into(), "d".into()]
+            )?,
+            Value::Word(" a\n-b\n+c\n+d".into())
+        );
+        assert_eq!(
+            DiffFilter.filter_list(vec!["a".into()], &["a".into()])?,
+            Value::Word(String::new())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn it_diffs_words() -> Result<(), FilterError> {
+        assert_eq!(
+            DiffFilter.filter_word("a\nold line\nc".into(), &["a\nnew line\nc\n".into()])?,
+            Value::Word(" a\n-[-old-] line\n+{+new+} line\n c".into())
+        );
+        assert_eq!(
+            DiffFilter.filter_word("same\n".into(), &["same".into()])?,
+            Value::Word(String::new())
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,117 @@
+use pjsh_core::{Filter, FilterError, FilterResult, Value};
+
+/// A filter that keeps the first `n` lines of a word.
+///
+/// Line endings are preserved.
+#[derive(Debug, Clone)]
+pub struct HeadFilter;
+impl Filter for HeadFilter {
+    fn name(&self) -> &str {
+        "head"
+    }
+
+    fn filter_word(&self, word: String, args: &[String]) -> FilterResult {
+        let count = parse_count(args)?;
+        Ok(Value::Word(
+            word.split_inclusive('\n').take(count).collect(),
+        ))
+    }
+}
+
+/// A filter that keeps the last `n` lines of a word.
+///
+/// Line endings are preserved.
+#[derive(Debug, Clone)]
+pub struct TailFilter;
+impl Filter for TailFilter {
+    fn name(&self) -> &str {
+        "tail"
+    }
+
+    fn filter_word(&self, word: String, args: &[String]) -> FilterResult {
+        let count = parse_count(args)?;
+        let lines: Vec<&str> = word.split_inclusive('\n').collect();
+        let skip = lines.len().saturating_sub(count);
+        Ok(Value::Word(lines[skip..].concat()))
+    }
+}
+
+/// Parses a line count from filter arguments.
+///
+/// Negative counts are treated as 0.
+fn parse_count(args: &[String]) -> Result<usize, FilterError> {
+    match args {
+        [] => Err(FilterError::MissingArg("count")),
+        [count] => match count.parse::<i64>() {
+            Ok(count) => Ok(usize::try_from(count).unwrap_or(0)),
+            Err(err) => Err(FilterError::InvalidArgs(format!("invalid count: {err}"))),
+        },
+        _ => Err(FilterError::TooManyArgs),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Applies a filter to a word, returning the filtered word.
+    fn filter(filter: &dyn Filter, word: &str, count: &str) -> FilterResult {
+        filter.filter_word(word.into(), &[count.into()])
+    }
+
+    #[test]
+    fn it_accepts_a_count() {
+        for line_filter in [&HeadFilter as &dyn Filter, &TailFilter] {
+            assert_eq!(
+                line_filter.filter_word("line".into(), &[]),
+                Err(FilterError::MissingArg("count"))
+            );
+            assert_eq!(
+                line_filter.filter_word("line".into(), &["1".into(), "2".into()]),
+                Err(FilterError::TooManyArgs)
+            );
+            assert!(matches!(
+                filter(line_filter, "line", "one"),
+                Err(FilterError::InvalidArgs(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn it_keeps_the_first_lines() {
+        let text = "first\nsecond\r\nthird\n";
+        assert_eq!(
+            filter(&HeadFilter, text, "2"),
+            Ok(Value::Word("first\nsecond\r\n".into()))
+        );
+        assert_eq!(
+            filter(&HeadFilter, "first\nsecond", "5"),
+            Ok(Value::Word("first\nsecond".into()))
+        );
+    }
+
+    #[test]
+    fn it_keeps_the_last_lines() {
+        let text = "first\nsecond\r\nthird\n";
+        assert_eq!(
+            filter(&TailFilter, text, "2"),
+            Ok(Value::Word("second\r\nthird\n".into()))
+        );
+        assert_eq!(
+            filter(&TailFilter, "first\nsecond", "5"),
+            Ok(Value::Word("first\nsecond".into()))
+        );
+    }
+
+    #[test]
+    fn it_returns_nothing_for_non_positive_counts() {
+        for line_filter in [&HeadFilter as &dyn Filter, &TailFilter] {
+            for count in ["0", "-3"] {
+                assert_eq!(
+                    filter(line_filter, "first\nsecond\n", count),
+                    Ok(Value::Word("".into()))
+                );
+            }
+        }
+    }
+}
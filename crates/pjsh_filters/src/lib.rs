@@ -1,23 +1,37 @@
+mod diff;
+mod head_tail;
 mod join;
 mod len;
 mod lines;
 mod list_items;
+mod map;
+mod match_lines;
+mod path_list;
 mod replace;
 mod reverse;
 mod sort;
 mod split;
 mod text_case;
+mod time;
+mod trim;
 mod unique;
 mod words;
 
+pub use diff::DiffFilter;
+pub use head_tail::{HeadFilter, TailFilter};
 pub use join::JoinFilter;
 pub use len::LenFilter;
 pub use lines::LinesFilter;
 pub use list_items::{FirstFilter, LastFilter, NthFilter};
+pub use map::MapFilter;
+pub use match_lines::MatchFilter;
+pub use path_list::{PathJoinFilter, PathListFilter};
 pub use replace::ReplaceFilter;
 pub use reverse::ReverseFilter;
 pub use sort::SortFilter;
 pub use split::SplitFilter;
 pub use text_case::{LowercaseFilter, UcfirstFilter, UppercaseFilter};
+pub use time::{EpochFilter, NowFilter, NowUtcFilter, DEFAULT_TIME_FORMAT};
+pub use trim::{TrimEndFilter, TrimFilter, TrimStartFilter};
 pub use unique::UniqueFilter;
 pub use words::WordsFilter;
@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+
+use pjsh_core::{Filter, FilterError, FilterResult, Value};
+
+/// A filter that applies another filter to each line in a word, or to each
+/// item in a list.
+///
+/// The first argument is the name of the filter to apply. Remaining arguments
+/// are passed to that filter.
+#[derive(Clone)]
+pub struct MapFilter {
+    /// Filters that can be applied, indexed by name.
+    filters: HashMap<String, Box<dyn Filter>>,
+}
+
+impl MapFilter {
+    /// Constructs a new map filter that can apply any of the given filters.
+    pub fn new(filters: HashMap<String, Box<dyn Filter>>) -> Self {
+        Self { filters }
+    }
+
+    /// Applies a named filter to each item.
+    fn map(&self, items: Vec<String>, args: &[String]) -> Result<Vec<String>, FilterError> {
+        let [name, args @ ..] = args else {
+            return Err(FilterError::MissingArg("filter"));
+        };
+
+        let Some(filter) = self.filters.get(name) else {
+            return Err(FilterError::InvalidArgs(format!("unknown filter '{name}'")));
+        };
+
+        items
+            .into_iter()
+            .map(|item| match filter.filter_word(item, args)? {
+                Value::Word(word) => Ok(word),
+                Value::List(_) => Err(FilterError::InvalidArgs(format!(
+                    "filter '{name}' does not return a word"
+                ))),
+            })
+            .collect()
+    }
+}
+
+impl Filter for MapFilter {
+    fn name(&self) -> &str {
+        "map"
+    }
+
+    fn filter_list(&self, list: Vec<String>, args: &[String]) -> FilterResult {
+        Ok(Value::List(self.map(list, args)?))
+    }
+
+    fn filter_word(&self, word: String, args: &[String]) -> FilterResult {
+        // A trailing newline ends the last line rather than starting a new one.
+        let (text, newline) = match word.strip_suffix('\n') {
+            Some(text) => (text, "\n"),
+            None => (word.as_str(), ""),
+        };
+
+        let lines = text.split('\n').map(ToString::to_string).collect();
+        Ok(Value::Word(self.map(lines, args)?.join("\n") + newline))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{LinesFilter, TrimFilter, UppercaseFilter};
+
+    use super::*;
+
+    /// Returns a map filter that can apply some filters.
+    fn map_filter() -> MapFilter {
+        let filters: [Box<dyn Filter>; 3] = [
+            Box::new(LinesFilter),
+            Box::new(TrimFilter),
+            Box::new(UppercaseFilter),
+        ];
+        MapFilter::new(
+            filters
+                .into_iter()
+                .map(|filter| (filter.name().to_string(), filter))
+                .collect(),
+        )
+    }
+
+    /// Converts a slice of string slices into a vector of strings.
+    fn strings(strs: &[&str]) -> Vec<String> {
+        strs.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn it_maps_lines() {
+        let filter = map_filter();
+        assert_eq!(
+            filter.filter_word("first\nsecond\n\nlast".into(), &strings(&["uppercase"])),
+            Ok(Value::Word("FIRST\nSECOND\n\nLAST".into()))
+        );
+        assert_eq!(
+            filter.filter_word("  first \n second\n".into(), &strings(&["trim"])),
+            Ok(Value::Word("first\nsecond\n".into()))
+        );
+        assert_eq!(
+            filter.filter_word("".into(), &strings(&["uppercase"])),
+            Ok(Value::Word("".into()))
+        );
+    }
+
+    #[test]
+    fn it_maps_list_items() {
+        assert_eq!(
+            map_filter().filter_list(strings(&["-a-", "b-"]), &strings(&["trim", "-"])),
+            Ok(Value::List(strings(&["a", "b"])))
+        );
+    }
+
+    #[test]
+    fn it_forwards_filter_errors() {
+        assert_eq!(
+            map_filter().filter_word("line".into(), &strings(&["trim", "a", "b"])),
+            Err(FilterError::TooManyArgs)
+        );
+    }
+
+    #[test]
+    fn it_requires_word_results() {
+        assert_eq!(
+            map_filter().filter_word("line".into(), &strings(&["lines"])),
+            Err(FilterError::InvalidArgs(
+                "filter 'lines' does not return a word".into()
+            ))
+        );
+    }
+
+    #[test]
+    fn it_requires_a_known_filter() {
+        let filter = map_filter();
+        assert_eq!(
+            filter.filter_word("line".into(), &[]),
+            Err(FilterError::MissingArg("filter"))
+        );
+        assert_eq!(
+            filter.filter_word("line".into(), &strings(&["unknown"])),
+            Err(FilterError::InvalidArgs("unknown filter 'unknown'".into()))
+        );
+    }
+}
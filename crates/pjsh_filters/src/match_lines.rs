@@ -0,0 +1,194 @@
+use pjsh_core::{Filter, FilterError, FilterResult, Value};
+use regex::Regex;
+
+/// A filter that keeps the lines of a word, or the items of a list, that
+/// contain a pattern.
+///
+/// The pattern is a substring unless `-r` is given, in which case it is a
+/// regular expression. Non-matching lines are kept instead if `-v` is given.
+/// Flags must precede the pattern, and `--` ends the flags. Line endings and
+/// the order of lines are preserved.
+#[derive(Debug, Clone)]
+pub struct MatchFilter;
+impl Filter for MatchFilter {
+    fn name(&self) -> &str {
+        "match"
+    }
+
+    fn filter_list(&self, list: Vec<String>, args: &[String]) -> FilterResult {
+        let matcher = Matcher::parse(args)?;
+        Ok(Value::List(
+            list.into_iter()
+                .filter(|item| matcher.keeps(item))
+                .collect(),
+        ))
+    }
+
+    fn filter_word(&self, word: String, args: &[String]) -> FilterResult {
+        let matcher = Matcher::parse(args)?;
+        Ok(Value::Word(
+            word.split_inclusive('\n')
+                .filter(|line| matcher.keeps(trim_line_ending(line)))
+                .collect(),
+        ))
+    }
+}
+
+/// Decides which lines to keep based on a pattern.
+struct Matcher {
+    /// Pattern to match lines against.
+    pattern: Pattern,
+
+    /// Keep lines that do not match the pattern.
+    invert: bool,
+}
+
+/// A pattern that lines can match.
+enum Pattern {
+    /// Lines match if they contain a substring.
+    Substring(String),
+
+    /// Lines match if they contain a match for a regular expression.
+    Regex(Regex),
+}
+
+impl Matcher {
+    /// Parses a matcher from filter arguments.
+    fn parse(args: &[String]) -> Result<Self, FilterError> {
+        let mut invert = false;
+        let mut regex = false;
+        let mut args = args.iter().map(String::as_str);
+
+        let pattern = loop {
+            match args.next() {
+                Some("-v") => invert = true,
+                Some("-r") => regex = true,
+                Some("--") => break args.next(),
+                arg => break arg,
+            }
+        };
+
+        let Some(pattern) = pattern else {
+            return Err(FilterError::MissingArg("pattern"));
+        };
+        if args.next().is_some() {
+            return Err(FilterError::TooManyArgs);
+        }
+
+        let pattern = match regex {
+            true => Pattern::Regex(
+                Regex::new(pattern)
+                    .map_err(|err| FilterError::InvalidArgs(format!("invalid regex: {err}")))?,
+            ),
+            false => Pattern::Substring(pattern.to_owned()),
+        };
+
+        Ok(Self { pattern, invert })
+    }
+
+    /// Returns `true` if a line should be kept.
+    fn keeps(&self, line: &str) -> bool {
+        let is_match = match &self.pattern {
+            Pattern::Substring(substring) => line.contains(substring.as_str()),
+            Pattern::Regex(regex) => regex.is_match(line),
+        };
+
+        is_match != self.invert
+    }
+}
+
+/// Removes a trailing `\n` or `\r\n` from a line.
+fn trim_line_ending(line: &str) -> &str {
+    let line = line.strip_suffix('\n').unwrap_or(line);
+    line.strip_suffix('\r').unwrap_or(line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Applies the filter to a word using some arguments.
+    fn filter_word(word: &str, args: &[&str]) -> FilterResult {
+        let args: Vec<String> = args.iter().map(ToString::to_string).collect();
+        MatchFilter.filter_word(word.into(), &args)
+    }
+
+    #[test]
+    fn it_requires_a_pattern() {
+        assert_eq!(
+            filter_word("word", &[]),
+            Err(FilterError::MissingArg("pattern"))
+        );
+        assert_eq!(
+            filter_word("word", &["-v"]),
+            Err(FilterError::MissingArg("pattern"))
+        );
+        assert_eq!(
+            filter_word("word", &["a", "b"]),
+            Err(FilterError::TooManyArgs)
+        );
+        assert!(matches!(
+            filter_word("word", &["-r", "("]),
+            Err(FilterError::InvalidArgs(_))
+        ));
+    }
+
+    #[test]
+    fn it_keeps_matching_lines() -> Result<(), FilterError> {
+        assert_eq!(
+            filter_word("foo\nbar\nfood\r\n", &["foo"])?,
+            Value::Word("foo\nfood\r\n".into())
+        );
+        assert_eq!(filter_word("foo\nbar", &["baz"])?, Value::Word("".into()));
+        assert_eq!(
+            filter_word("-v\nfoo", &["--", "-v"])?,
+            Value::Word("-v\n".into())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn it_keeps_non_matching_lines() -> Result<(), FilterError> {
+        assert_eq!(
+            filter_word("foo\nbar\nfood", &["-v", "foo"])?,
+            Value::Word("bar\n".into())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn it_matches_regular_expressions() -> Result<(), FilterError> {
+        assert_eq!(
+            filter_word("foo\r\nbar\nfood\n", &["-r", "^fo+$"])?,
+            Value::Word("foo\r\n".into())
+        );
+        assert_eq!(
+            filter_word("foo\nbar\nfood\n", &["-r", "-v", "d$"])?,
+            Value::Word("foo\nbar\n".into())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn it_accepts_empty_input() -> Result<(), FilterError> {
+        assert_eq!(filter_word("", &["foo"])?, Value::Word("".into()));
+        assert_eq!(filter_word("", &["-v", "foo"])?, Value::Word("".into()));
+        assert_eq!(
+            MatchFilter.filter_list(Vec::new(), &["foo".into()])?,
+            Value::List(Vec::new())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn it_keeps_matching_list_items() -> Result<(), FilterError> {
+        assert_eq!(
+            MatchFilter.filter_list(
+                vec!["foo".into(), "bar".into(), "food".into()],
+                &["foo".into()]
+            )?,
+            Value::List(vec!["foo".into(), "food".into()])
+        );
+        Ok(())
+    }
+}
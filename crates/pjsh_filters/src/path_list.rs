@@ -0,0 +1,95 @@
+use pjsh_core::{split_path_list, Filter, FilterError, FilterResult, Value, PATH_SEPARATOR};
+
+/// A filter that splits `$PATH`-like words into lists of paths.
+///
+/// Paths are separated by the platform's separator unless another separator
+/// is given. Empty paths are skipped.
+#[derive(Debug, Clone)]
+pub struct PathListFilter;
+impl Filter for PathListFilter {
+    fn name(&self) -> &str {
+        "pathlist"
+    }
+
+    fn filter_word(&self, word: String, args: &[String]) -> FilterResult {
+        Ok(Value::List(split_path_list(&word, separator(args)?)))
+    }
+}
+
+/// A filter that joins lists of paths into `$PATH`-like words.
+///
+/// Paths are separated by the platform's separator unless another separator
+/// is given.
+#[derive(Debug, Clone)]
+pub struct PathJoinFilter;
+impl Filter for PathJoinFilter {
+    fn name(&self) -> &str {
+        "pathjoin"
+    }
+
+    fn filter_list(&self, list: Vec<String>, args: &[String]) -> FilterResult {
+        Ok(Value::Word(list.join(separator(args)?)))
+    }
+}
+
+/// Returns the path separator to use given some filter arguments.
+fn separator(args: &[String]) -> Result<&str, FilterError> {
+    match args {
+        [] => Ok(PATH_SEPARATOR),
+        [separator] => Ok(separator),
+        _ => Err(FilterError::TooManyArgs),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_accepts_at_most_one_arg() {
+        let args = [":".into(), ";".into()];
+        assert_eq!(
+            PathListFilter.filter_word("/bin".into(), &args),
+            Err(FilterError::TooManyArgs)
+        );
+        assert_eq!(
+            PathJoinFilter.filter_list(vec!["/bin".into()], &args),
+            Err(FilterError::TooManyArgs)
+        );
+    }
+
+    #[test]
+    fn it_splits_path_lists() -> Result<(), FilterError> {
+        assert_eq!(
+            PathListFilter.filter_word("/bin::/usr/bin".into(), &[":".into()])?,
+            Value::List(vec!["/bin".into(), "/usr/bin".into()])
+        );
+        assert_eq!(
+            PathListFilter.filter_word(r"C:\bin;D:\bin".into(), &[";".into()])?,
+            Value::List(vec![r"C:\bin".into(), r"D:\bin".into()])
+        );
+        assert_eq!(
+            PathListFilter.filter_word(format!("/bin{PATH_SEPARATOR}/usr/bin"), &[])?,
+            Value::List(vec!["/bin".into(), "/usr/bin".into()])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn it_joins_path_lists() -> Result<(), FilterError> {
+        let list = vec!["/bin".into(), "/usr/bin".into()];
+        assert_eq!(
+            PathJoinFilter.filter_list(list.clone(), &[":".into()])?,
+            Value::Word("/bin:/usr/bin".into())
+        );
+        assert_eq!(
+            PathJoinFilter.filter_list(list.clone(), &[";".into()])?,
+            Value::Word("/bin;/usr/bin".into())
+        );
+        assert_eq!(
+            PathJoinFilter.filter_list(list, &[])?,
+            Value::Word(format!("/bin{PATH_SEPARATOR}/usr/bin"))
+        );
+        Ok(())
+    }
+}
@@ -12,10 +12,7 @@ fn filter_word(&self, word: String, args: &[String]) -> FilterResult {
         match &args {
             [] => Err(FilterError::MissingArg("separator")),
             [separator] => Ok(Value::List(
-                word.split(separator)
-                    .into_iter()
-                    .map(ToString::to_string)
-                    .collect(),
+                word.split(separator).map(ToString::to_string).collect(),
             )),
             _ => Err(FilterError::TooManyArgs),
         }
@@ -0,0 +1,183 @@
+use std::fmt::Write;
+
+use chrono::{
+    format::{Item, StrftimeItems},
+    DateTime, Local, TimeZone, Utc,
+};
+use pjsh_core::{Filter, FilterError, FilterResult, Value};
+
+/// Default format string used to render times.
+pub const DEFAULT_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// A filter that formats the current local time.
+///
+/// The word is used as a `strftime`-like format string, such as `%Y-%m-%d`.
+/// Empty words are replaced by the format returned by [`time_format`].
+#[derive(Debug, Clone)]
+pub struct NowFilter;
+impl Filter for NowFilter {
+    fn name(&self) -> &str {
+        "now"
+    }
+
+    fn filter_word(&self, word: String, args: &[String]) -> FilterResult {
+        if !args.is_empty() {
+            return Err(FilterError::NoArgsAllowed);
+        }
+
+        format_time(&Local::now(), &time_format(word))
+    }
+}
+
+/// A filter that formats the current time in UTC.
+///
+/// The word is used as a `strftime`-like format string, such as `%Y-%m-%d`.
+/// Empty words are replaced by the format returned by [`time_format`].
+#[derive(Debug, Clone)]
+pub struct NowUtcFilter;
+impl Filter for NowUtcFilter {
+    fn name(&self) -> &str {
+        "now_utc"
+    }
+
+    fn filter_word(&self, word: String, args: &[String]) -> FilterResult {
+        if !args.is_empty() {
+            return Err(FilterError::NoArgsAllowed);
+        }
+
+        format_time(&Utc::now(), &time_format(word))
+    }
+}
+
+/// A filter that returns the number of seconds since the Unix epoch.
+///
+/// The word is ignored.
+#[derive(Debug, Clone)]
+pub struct EpochFilter;
+impl Filter for EpochFilter {
+    fn name(&self) -> &str {
+        "epoch"
+    }
+
+    fn filter_word(&self, _word: String, args: &[String]) -> FilterResult {
+        if !args.is_empty() {
+            return Err(FilterError::NoArgsAllowed);
+        }
+
+        Ok(Value::Word(Utc::now().timestamp().to_string()))
+    }
+}
+
+/// Returns the format string to use given a filter's input word.
+///
+/// Empty words fall back to `$PJSH_TIME_FORMAT` from the environment, or to
+/// [`DEFAULT_TIME_FORMAT`] if it is unset or empty.
+fn time_format(word: String) -> String {
+    if !word.is_empty() {
+        return word;
+    }
+
+    std::env::var("PJSH_TIME_FORMAT")
+        .ok()
+        .filter(|format| !format.is_empty())
+        .unwrap_or_else(|| DEFAULT_TIME_FORMAT.to_owned())
+}
+
+/// Formats a time using a `strftime`-like format string.
+fn format_time<Tz: TimeZone>(time: &DateTime<Tz>, format: &str) -> FilterResult
+where
+    Tz::Offset: std::fmt::Display,
+{
+    let items: Vec<Item> = StrftimeItems::new(format).collect();
+    if items.contains(&Item::Error) {
+        return Err(FilterError::InvalidInput(format!(
+            "invalid time format: {format}"
+        )));
+    }
+
+    let mut formatted = String::new();
+    write!(formatted, "{}", time.format_with_items(items.into_iter()))
+        .map_err(|_| FilterError::InvalidInput(format!("invalid time format: {format}")))?;
+
+    Ok(Value::Word(formatted))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    #[test]
+    fn it_formats_times() {
+        let time = NaiveDate::from_ymd_opt(2022, 3, 4)
+            .and_then(|date| date.and_hms_opt(5, 6, 7))
+            .expect("time should be valid")
+            .and_utc();
+
+        assert_eq!(
+            format_time(&time, "%Y-%m-%d %H:%M:%S"),
+            Ok(Value::Word("2022-03-04 05:06:07".into()))
+        );
+        assert_eq!(
+            format_time(&time, "%s %z"),
+            Ok(Value::Word("1646370367 +0000".into()))
+        );
+        assert_eq!(format_time(&time, ""), Ok(Value::Word(String::new())));
+    }
+
+    #[test]
+    fn it_uses_the_default_format_for_empty_words() {
+        assert_eq!(time_format("%H".into()), "%H");
+
+        let format = time_format(String::new());
+        match std::env::var("PJSH_TIME_FORMAT") {
+            Ok(expected) if !expected.is_empty() => assert_eq!(format, expected),
+            _ => assert_eq!(format, DEFAULT_TIME_FORMAT),
+        }
+    }
+
+    #[test]
+    fn it_rejects_invalid_formats() {
+        assert_eq!(
+            NowFilter.filter_word("%Q".into(), &[]),
+            Err(FilterError::InvalidInput("invalid time format: %Q".into()))
+        );
+        assert_eq!(
+            NowUtcFilter.filter_word("%".into(), &[]),
+            Err(FilterError::InvalidInput("invalid time format: %".into()))
+        );
+    }
+
+    #[test]
+    fn it_returns_the_current_time() {
+        let before = Utc::now().timestamp();
+        let Ok(Value::Word(epoch)) = EpochFilter.filter_word(String::new(), &[]) else {
+            panic!("epoch should be a word");
+        };
+        let epoch: i64 = epoch.parse().expect("epoch should be numeric");
+        assert!(before <= epoch && epoch <= Utc::now().timestamp());
+
+        assert_eq!(
+            NowUtcFilter.filter_word("%z".into(), &[]),
+            Ok(Value::Word("+0000".into()))
+        );
+    }
+
+    #[test]
+    fn it_accepts_no_args() {
+        let args = ["not-allowed".to_owned()];
+        assert_eq!(
+            NowFilter.filter_word("%Y".into(), &args),
+            Err(FilterError::NoArgsAllowed)
+        );
+        assert_eq!(
+            NowUtcFilter.filter_word("%Y".into(), &args),
+            Err(FilterError::NoArgsAllowed)
+        );
+        assert_eq!(
+            EpochFilter.filter_word(String::new(), &args),
+            Err(FilterError::NoArgsAllowed)
+        );
+    }
+}
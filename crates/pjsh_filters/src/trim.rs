@@ -0,0 +1,149 @@
+use pjsh_core::{Filter, FilterError, FilterResult, Value};
+
+/// Sides of a word to trim.
+#[derive(Clone, Copy)]
+enum Side {
+    Both,
+    Start,
+    End,
+}
+
+/// A filter that removes leading and trailing whitespace from words.
+///
+/// Other characters can be removed by passing them as an argument.
+#[derive(Debug, Clone)]
+pub struct TrimFilter;
+impl Filter for TrimFilter {
+    fn name(&self) -> &str {
+        "trim"
+    }
+
+    fn filter_word(&self, word: String, args: &[String]) -> FilterResult {
+        trim(&word, args, Side::Both)
+    }
+}
+
+/// A filter that removes leading whitespace from words.
+///
+/// Other characters can be removed by passing them as an argument.
+#[derive(Debug, Clone)]
+pub struct TrimStartFilter;
+impl Filter for TrimStartFilter {
+    fn name(&self) -> &str {
+        "trim_start"
+    }
+
+    fn filter_word(&self, word: String, args: &[String]) -> FilterResult {
+        trim(&word, args, Side::Start)
+    }
+}
+
+/// A filter that removes trailing whitespace from words.
+///
+/// Other characters can be removed by passing them as an argument.
+#[derive(Debug, Clone)]
+pub struct TrimEndFilter;
+impl Filter for TrimEndFilter {
+    fn name(&self) -> &str {
+        "trim_end"
+    }
+
+    fn filter_word(&self, word: String, args: &[String]) -> FilterResult {
+        trim(&word, args, Side::End)
+    }
+}
+
+/// Trims one or both sides of a word.
+///
+/// Unicode whitespace is trimmed unless an argument containing the characters
+/// to trim is given.
+fn trim(word: &str, args: &[String], side: Side) -> FilterResult {
+    let trimmed = match args {
+        [] => match side {
+            Side::Both => word.trim(),
+            Side::Start => word.trim_start(),
+            Side::End => word.trim_end(),
+        },
+        [chars] => {
+            let chars: Vec<char> = chars.chars().collect();
+            match side {
+                Side::Both => word.trim_matches(chars.as_slice()),
+                Side::Start => word.trim_start_matches(chars.as_slice()),
+                Side::End => word.trim_end_matches(chars.as_slice()),
+            }
+        }
+        _ => return Err(FilterError::TooManyArgs),
+    };
+
+    Ok(Value::Word(trimmed.to_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Applies a filter to a word, returning the filtered word.
+    fn filter(filter: &dyn Filter, word: &str, args: &[&str]) -> FilterResult {
+        let args: Vec<String> = args.iter().map(ToString::to_string).collect();
+        filter.filter_word(word.into(), &args)
+    }
+
+    #[test]
+    fn it_accepts_at_most_one_arg() {
+        for trim_filter in [&TrimFilter as &dyn Filter, &TrimStartFilter, &TrimEndFilter] {
+            assert_eq!(
+                filter(trim_filter, "word", &["a", "b"]),
+                Err(FilterError::TooManyArgs)
+            );
+        }
+    }
+
+    #[test]
+    fn it_trims_whitespace() {
+        let word = " \t word \u{3000}\n";
+        assert_eq!(
+            filter(&TrimFilter, word, &[]),
+            Ok(Value::Word("word".into()))
+        );
+        assert_eq!(
+            filter(&TrimStartFilter, word, &[]),
+            Ok(Value::Word("word \u{3000}\n".into()))
+        );
+        assert_eq!(
+            filter(&TrimEndFilter, word, &[]),
+            Ok(Value::Word(" \t word".into()))
+        );
+    }
+
+    #[test]
+    fn it_trims_chars() {
+        let path = "//path/to/dir/";
+        assert_eq!(
+            filter(&TrimFilter, path, &["/"]),
+            Ok(Value::Word("path/to/dir".into()))
+        );
+        assert_eq!(
+            filter(&TrimStartFilter, path, &["/"]),
+            Ok(Value::Word("path/to/dir/".into()))
+        );
+        assert_eq!(
+            filter(&TrimEndFilter, path, &["/"]),
+            Ok(Value::Word("//path/to/dir".into()))
+        );
+        assert_eq!(
+            filter(&TrimFilter, "-_word_-", &["_-"]),
+            Ok(Value::Word("word".into()))
+        );
+    }
+
+    #[test]
+    fn it_trims_empty_and_whitespace_words() {
+        for trim_filter in [&TrimFilter as &dyn Filter, &TrimStartFilter, &TrimEndFilter] {
+            assert_eq!(filter(trim_filter, "", &[]), Ok(Value::Word("".into())));
+            assert_eq!(
+                filter(trim_filter, " \t\n ", &[]),
+                Ok(Value::Word("".into()))
+            );
+        }
+    }
+}
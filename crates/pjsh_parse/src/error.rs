@@ -1,3 +1,7 @@
+use annotate_snippets::{
+    display_list::{DisplayList, FormatOptions},
+    snippet::{Annotation, AnnotationType, Slice, Snippet, SourceAnnotation},
+};
 use thiserror::Error;
 
 use crate::{
@@ -36,6 +40,14 @@ pub enum ParseError {
     #[error("invalid syntax: {0}")]
     InvalidSyntax(String),
 
+    /// Error indicating that an interpolation is invalid.
+    ///
+    /// Contains an error message, and the span of the invalid part of the interpolation.
+    ///
+    /// This error is not recoverable.
+    #[error("invalid interpolation: {0}")]
+    InvalidInterpolation(String, Span),
+
     /// Error indicating that there is no more input to parse.
     ///
     /// This error is only returned before consuming tokens in a new sequence.
@@ -51,6 +63,16 @@ pub enum ParseError {
     /// Note that the token may still be valid in a different context.
     #[error("unexpected token {:?}", .0.contents)]
     UnexpectedToken(Token),
+
+    /// Error indicating that a keyword from another shell's syntax, such as
+    /// `then` or `done`, is used in place of a statement.
+    ///
+    /// Contains the keyword, a hint describing the equivalent syntax, and the
+    /// span of the keyword.
+    ///
+    /// This error is not recoverable.
+    #[error("unsupported keyword `{0}`: {1}")]
+    UnsupportedKeyword(String, &'static str, Span),
 }
 
 impl ParseError {
@@ -61,8 +83,10 @@ pub fn help(&self) -> &str {
             ParseError::ExpectedToken(_, _) => "another token is expected here",
             ParseError::IncompleteSequence => "this sequence is incomplete",
             ParseError::InvalidSyntax(_) => "this syntax is invalid",
+            ParseError::InvalidInterpolation(_, _) => "this part of the interpolation is invalid",
             ParseError::UnexpectedEof => "EOF was encountered here",
             ParseError::UnexpectedToken(_) => "this token is unexpected here",
+            ParseError::UnsupportedKeyword(_, _, _) => "this keyword is not supported",
         }
     }
 
@@ -70,9 +94,76 @@ pub fn help(&self) -> &str {
     pub fn span(&self) -> Option<Span> {
         match self {
             ParseError::EmptySubshell(span) => Some(*span),
+            ParseError::InvalidInterpolation(_, span) => Some(*span),
             ParseError::ExpectedToken(_, found) => Some(found.span),
             ParseError::UnexpectedToken(token) => Some(token.span),
+            ParseError::UnsupportedKeyword(_, _, span) => Some(*span),
             _ => None,
         }
     }
+
+    /// Returns a snippet of the source `src`, annotated with the error and its help text.
+    ///
+    /// ANSI color codes are only included if `color` is `true`.
+    ///
+    /// Returns `None` if the error has no span.
+    pub fn snippet(&self, src: &str, color: bool) -> Option<String> {
+        let span = self.span()?;
+        let title = self.to_string();
+
+        let snippet = Snippet {
+            title: Some(Annotation {
+                label: Some(&title),
+                id: None,
+                annotation_type: AnnotationType::Error,
+            }),
+            footer: vec![],
+            slices: vec![Slice {
+                source: src,
+                line_start: 1,
+                origin: None,
+                fold: true,
+                annotations: vec![SourceAnnotation {
+                    label: self.help(),
+                    annotation_type: AnnotationType::Error,
+                    range: (span.start, span.end),
+                }],
+            }],
+            opt: FormatOptions {
+                color,
+                ..Default::default()
+            },
+        };
+
+        Some(DisplayList::from(snippet).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse_interpolation;
+
+    use super::*;
+
+    #[test]
+    fn it_annotates_interpolation_errors() {
+        let src = r"\e[1m\u{1F600} $USER \u{zz} \e[0m\$";
+        let error = parse_interpolation(src).unwrap_err();
+
+        assert_eq!(
+            error,
+            ParseError::InvalidInterpolation("unknown token `\\u{zz}`".into(), Span::new(21, 27))
+        );
+        assert_eq!(
+            error.snippet(src, false).unwrap(),
+            [
+                "error: invalid interpolation: unknown token `\\u{zz}`",
+                "  |",
+                r"1 | \e[1m\u{1F600} $USER \u{zz} \e[0m\$",
+                "  |                      ^^^^^^ this part of the interpolation is invalid",
+                "  |",
+            ]
+            .join("\n")
+        );
+    }
 }
@@ -86,6 +86,60 @@ pub fn skip_n(&mut self, n: usize) -> Span {
         Span::new(start, end)
     }
 
+    /// Returns the number of characters in a brace group, such as `{a,b}`, at the start of the
+    /// remaining input.
+    ///
+    /// Brace groups may only contain literal characters and nested brace groups, and must contain
+    /// a top-level `,` or `..`. Returns `None` if the input does not start with a brace group,
+    /// such as for the block in `{true}`.
+    pub fn brace_group_len(&self) -> Option<usize> {
+        self.scan_brace_group()
+            .and_then(|(len, is_expandable)| is_expandable.then_some(len))
+    }
+
+    /// Returns the number of characters in a brace group at the start of the remaining input,
+    /// regardless of whether it can be expanded. Both `{a,b}` and `{x}` are brace groups.
+    ///
+    /// Returns `None` if the input does not start with a brace group, such as for `{a b}`.
+    pub fn literal_brace_group_len(&self) -> Option<usize> {
+        self.scan_brace_group().map(|(len, _)| len)
+    }
+
+    /// Scans a brace group at the start of the remaining input.
+    ///
+    /// Returns the number of characters in the brace group along with whether it contains a
+    /// top-level `,` or `..`, or `None` if the input does not start with a brace group.
+    fn scan_brace_group(&self) -> Option<(usize, bool)> {
+        let mut input = self.chars.clone();
+        if input.next()?.1 != '{' {
+            return None;
+        }
+
+        let mut depth = 1;
+        let mut len = 1;
+        let mut is_expandable = false;
+        let mut previous = '{';
+        for (_, ch) in input {
+            len += 1;
+            match ch {
+                '{' => depth += 1,
+                '}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some((len, is_expandable));
+                    }
+                }
+                ',' if depth == 1 => is_expandable = true,
+                '.' if depth == 1 && previous == '.' => is_expandable = true,
+                ch if !is_literal(ch) => return None,
+                _ => (),
+            }
+            previous = ch;
+        }
+
+        None
+    }
+
     /// Returns a accumulated span and string for the [`next()`] values while a `predicate` returns
     /// `true`.
     pub fn eat_while(&mut self, predicate: impl Fn(char) -> bool + Copy) -> (Span, String) {
@@ -23,6 +23,17 @@ pub enum LexError {
     UnknownToken(String),
 }
 
+/// A [`LexError`] along with the span of the input in which it occurred.
+#[derive(Error, Debug, Eq, PartialEq)]
+#[error("{error}")]
+pub struct SpannedLexError {
+    /// The error.
+    pub error: LexError,
+
+    /// Span of the input in which the error occurred.
+    pub span: Span,
+}
+
 /// Lexes some input `str` and returns all tokens within the input.
 pub fn lex(src: &str, aliases: &HashMap<String, String>) -> Result<Vec<Token>, LexError> {
     let mut lexer = Lexer::new(src);
@@ -54,9 +65,12 @@ pub fn lex(src: &str, aliases: &HashMap<String, String>) -> Result<Vec<Token>, L
 }
 
 /// Lexes some input `str` for interpolation and returns all tokens within the input.
-pub fn lex_interpolation(src: &str) -> Result<Token, LexError> {
+///
+/// Errors include the span of the interpolation unit (e.g. an escape sequence or
+/// a `${...}` value pipeline) in which they occurred.
+pub fn lex_interpolation(src: &str) -> Result<Token, SpannedLexError> {
     let mut lexer = Lexer::new(src);
-    let interpolation = lexer.eat_interpolation(None)?;
+    let interpolation = lexer.eat_spanned_interpolation(None)?;
 
     assert_eq!(lexer.input.peek().1, EOF, "the input should be consumed");
 
@@ -71,6 +85,33 @@ enum LexerMode {
     QuotedMultiline(char),
 }
 
+/// Keywords of statements in which an opening brace begins a block.
+const BLOCK_KEYWORDS: [&str; 6] = ["else", "fn", "for", "if", "switch", "while"];
+
+/// A kind of command, which determines whether opening braces within it begin blocks or words.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CommandKind {
+    /// A command in which braces are part of words, such as `echo {x}`.
+    Plain,
+
+    /// A statement in which an opening brace begins a block, such as `if true {`.
+    Block,
+
+    /// A `switch` statement, whose block contains branches that are each followed by a block.
+    Switch,
+}
+
+/// A construct that has been opened, but not yet closed, in unquoted mode.
+#[derive(Debug, PartialEq)]
+enum Nesting {
+    /// A block opened by `{`. Its statements are switch branches if the block belongs to a
+    /// `switch` statement.
+    Block { is_switch: bool },
+
+    /// A construct, such as `$(` or `${`, within a command of some kind.
+    Enclosed(CommandKind),
+}
+
 /// A lexer takes some `str` input from a source `src` tokenizes it, returning identified tokens
 /// from the original input.
 ///
@@ -84,6 +125,15 @@ pub struct Lexer<'a> {
 
     /// Current lexer mode.
     mode: LexerMode,
+
+    /// Whether the next token starts a new command, where an opening brace always begins a block.
+    is_command_start: bool,
+
+    /// Kind of the current command.
+    command: CommandKind,
+
+    /// Constructs that have been opened, but not yet closed, starting with the outermost one.
+    nesting: Vec<Nesting>,
 }
 
 impl<'a> Lexer<'a> {
@@ -93,18 +143,95 @@ pub fn new(src: &'a str) -> Self {
             input: Input::new(src),
             input_length: src.len(),
             mode: LexerMode::Unquoted,
+            is_command_start: true,
+            command: CommandKind::Plain,
+            nesting: Vec::new(),
         }
     }
 
     /// Advances the cursor and returns the next delimited token.
     pub fn next_token(&mut self) -> LexResult<'a> {
-        match self.mode {
+        let token = match self.mode {
             LexerMode::Unquoted => self.next_unquoted_token(),
             LexerMode::Quoted(delimiter) => self.next_quoted_token(delimiter),
             LexerMode::QuotedMultiline(delimiter) => self.next_quoted_multiline_token(delimiter),
+        }?;
+
+        self.track_command(&token.contents);
+        Ok(token)
+    }
+
+    /// Keeps track of the command that is being lexed, so that opening braces can be lexed as
+    /// either blocks or words.
+    fn track_command(&mut self, contents: &TokenContents) {
+        match contents {
+            Whitespace | Comment(_) => (),
+            Eol | Semi | Amp | AndIf | OrIf | Pipe | PipeStart => self.start_command(),
+            OpenBrace => {
+                let is_switch = self.command == CommandKind::Switch;
+                self.nesting.push(Nesting::Block { is_switch });
+                self.start_command();
+            }
+            DollarOpenParen | ProcessSubstitutionStart => {
+                self.nesting.push(Nesting::Enclosed(self.command));
+                self.start_command();
+            }
+            OpenParen => {
+                // Parentheses following a function name enclose its arguments, not a subshell.
+                let is_subshell = self.is_command_start;
+                self.nesting.push(Nesting::Enclosed(self.command));
+                if is_subshell {
+                    self.start_command();
+                }
+            }
+            DollarOpenBrace => self.nesting.push(Nesting::Enclosed(self.command)),
+            CloseParen | CloseBrace => match self.nesting.pop() {
+                Some(Nesting::Enclosed(command)) => {
+                    self.command = command;
+                    self.is_command_start = false;
+                }
+                Some(Nesting::Block { .. }) | None => self.start_command(),
+            },
+            Literal(word) if self.is_command_start => {
+                self.is_command_start = false;
+                self.command = match word.as_str() {
+                    "switch" => CommandKind::Switch,
+                    word if BLOCK_KEYWORDS.contains(&word) => CommandKind::Block,
+                    _ if self.is_in_switch() => CommandKind::Block, // A switch branch.
+                    _ => CommandKind::Plain,
+                };
+            }
+            _ if self.is_command_start => {
+                self.is_command_start = false;
+                self.command = match self.is_in_switch() {
+                    true => CommandKind::Block,
+                    false => CommandKind::Plain,
+                };
+            }
+            _ => (),
         }
     }
 
+    /// Starts a new command.
+    fn start_command(&mut self) {
+        self.is_command_start = true;
+        self.command = CommandKind::Plain;
+    }
+
+    /// Returns `true` if the innermost open construct is the block of a `switch` statement.
+    fn is_in_switch(&self) -> bool {
+        matches!(
+            self.nesting.last(),
+            Some(Nesting::Block { is_switch: true })
+        )
+    }
+
+    /// Returns `true` if brace groups that cannot be expanded, such as `{x}`, are part of words
+    /// rather than blocks at the current position.
+    fn is_literal_brace_allowed(&self) -> bool {
+        !self.is_command_start && self.command == CommandKind::Plain
+    }
+
     /// Returns a token denoting the end of input (commonly known as EOF = end of file).
     fn eof_token(&self) -> Token {
         Token::new(Eof, Span::new(self.input_length, self.input_length + 1))
@@ -119,10 +246,13 @@ fn next_unquoted_token(&mut self) -> LexResult<'a> {
             '&' => self.eat_amp_or_andif(),
             ';' => self.eat_char(Semi),
             '<' => self.eat_fd_read_to_or_process_substitution(),
-            '>' => self.eat_file_write_or_append(),
+            '>' => {
+                let start = self.input.peek().0;
+                self.eat_file_write_or_append(1, start)
+            }
             '(' => self.eat_char(OpenParen),
             ')' => self.eat_char(CloseParen),
-            '{' => self.eat_char(OpenBrace),
+            '{' => self.eat_brace_group_or_open_brace(),
             '}' => self.eat_char(CloseBrace),
             '[' => self
                 .eat_chars(&['[', '['], DoubleOpenBracket)
@@ -139,6 +269,7 @@ fn next_unquoted_token(&mut self) -> LexResult<'a> {
             '-' => self.eat_pipeline_start_or_literal(),
             c if is_newline(c) => self.eat_newline(),
             c if is_whitespace(c) => self.eat_whitespace(),
+            c if c.is_ascii_digit() => self.eat_fd_redirect_or_literal(),
             EOF => Ok(self.eof_token()),
             _ => self.eat_literal(),
         }
@@ -169,7 +300,7 @@ fn next_quoted_token(&mut self, delimiter: char) -> LexResult<'a> {
             }
             _ => {
                 let (span, contents) = self.input.eat_while(is_quoted);
-                Ok(Token::new(Quoted(contents), span))
+                Ok(Token::new(Quoted(normalize_line_endings(contents)), span))
             }
         }
     }
@@ -202,7 +333,7 @@ fn next_quoted_multiline_token(&mut self, delimiter: char) -> LexResult<'a> {
         }
 
         let span = Span::new(start, self.input.peek().0);
-        Ok(Token::new(Quoted(contents), span))
+        Ok(Token::new(Quoted(normalize_line_endings(contents)), span))
     }
 
     /// Eats a single character.
@@ -233,24 +364,58 @@ fn eat_chars(&mut self, chars: &[char], contents: TokenContents) -> LexResult<'a
         Ok(Token::new(contents, Span::new(index, self.input.peek().0)))
     }
 
-    /// Eats [`FileAppend`] ">>" or [`FileWrite`] ">".
-    fn eat_file_write_or_append(&mut self) -> LexResult<'a> {
-        let start = self
-            .input
+    /// Eats [`FdAppendFrom`] ">>", [`FdDuplicate`] ">&m" or [`FdWriteFrom`] ">" for
+    /// file descriptor `fd`.
+    ///
+    /// The token span begins at `start`, which precedes any explicit file descriptor.
+    fn eat_file_write_or_append(&mut self, fd: usize, start: usize) -> LexResult<'a> {
+        self.input
             .next_if_eq('>')
-            .expect("the next char of input should be '>'")
-            .0;
-        if self.input.next_if_eq('>').is_some() {
-            Ok(Token::new(
-                FdAppendFrom(1),
-                Span::new(start, self.input.peek().0),
-            ))
+            .expect("the next char of input should be '>'");
+
+        let contents = if self.input.next_if_eq('>').is_some() {
+            FdAppendFrom(fd)
+        } else if self.input.next_if_eq('&').is_some() {
+            let (_, target) = self.input.eat_while(|ch| ch.is_ascii_digit());
+            match target.parse() {
+                Ok(target) => FdDuplicate(fd, target),
+                Err(_) => return Err(unexpected_char(self.input.peek().1)),
+            }
         } else {
-            Ok(Token::new(
-                FdWriteFrom(1),
-                Span::new(start, self.input.peek().0),
-            ))
+            FdWriteFrom(fd)
+        };
+
+        Ok(Token::new(contents, Span::new(start, self.input.peek().0)))
+    }
+
+    /// Eats a redirect from an explicit file descriptor, such as "2>" or "2>&1", or a
+    /// literal word if the digits are not immediately followed by a redirect operator.
+    fn eat_fd_redirect_or_literal(&mut self) -> LexResult<'a> {
+        let mut digits = 1;
+        while self.input.peek_n(digits + 1)[digits].is_ascii_digit() {
+            digits += 1;
         }
+
+        let operator = self.input.peek_n(digits + 1)[digits];
+        if operator != '>' && operator != '<' {
+            return self.eat_literal();
+        }
+
+        let start = self.input.peek().0;
+        let (_, fd) = self.input.eat_while(|ch| ch.is_ascii_digit());
+        let fd = fd.parse().map_err(|_| unexpected_char(operator))?;
+
+        if operator == '>' {
+            return self.eat_file_write_or_append(fd, start);
+        }
+
+        self.input.next();
+        let contents = if self.input.take_if_eq(&['<', '<']).is_some() {
+            HereString(fd)
+        } else {
+            FdReadTo(fd)
+        };
+        Ok(Token::new(contents, Span::new(start, self.input.peek().0)))
     }
 
     fn eat_amp_or_andif(&mut self) -> LexResult<'a> {
@@ -291,8 +456,8 @@ fn eat_pipeline_start_or_literal(&mut self) -> LexResult<'a> {
 
     /// Eats a comment.
     fn eat_comment(&mut self) -> LexResult<'a> {
-        let (span, _) = self.input.eat_while(|c| !is_newline(c));
-        Ok(Token::new(Comment, span))
+        let (span, comment) = self.input.eat_while(|c| !is_newline(c));
+        Ok(Token::new(Comment(comment), span))
     }
 
     /// Eats a newline token.
@@ -316,11 +481,42 @@ fn eat_newline(&mut self) -> LexResult<'a> {
     }
 
     /// Eats literal words.
+    ///
+    /// Brace groups, such as `{a,b}`, are included in the word to be expanded later. Brace groups
+    /// that cannot be expanded, such as `{x}`, are included as-is unless they may be blocks.
     fn eat_literal(&mut self) -> LexResult<'a> {
-        let (span, content) = self.input.eat_while(is_literal);
+        let (mut span, mut content) = self.input.eat_while(is_literal);
+
+        while let Some(len) = self.brace_group_len() {
+            for _ in 0..len {
+                content.push(self.input.next().1);
+            }
+
+            let (_, rest) = self.input.eat_while(is_literal);
+            content.push_str(&rest);
+            span.end = self.input.peek().0;
+        }
+
         Ok(Token::new(Literal(content), span))
     }
 
+    /// Eats a literal word starting with a brace group, such as `{a,b}`, or an opening brace.
+    fn eat_brace_group_or_open_brace(&mut self) -> LexResult<'a> {
+        match self.brace_group_len() {
+            Some(_) => self.eat_literal(),
+            None => self.eat_char(OpenBrace),
+        }
+    }
+
+    /// Returns the number of characters in a brace group that belongs to a word at the start of
+    /// the remaining input.
+    fn brace_group_len(&self) -> Option<usize> {
+        match self.is_literal_brace_allowed() {
+            true => self.input.literal_brace_group_len(),
+            false => self.input.brace_group_len(),
+        }
+    }
+
     /// Eats an assign operator or a literal word.
     fn eat_assign_or_literal(&mut self) -> LexResult<'a> {
         let token = self.eat_literal()?;
@@ -357,6 +553,10 @@ fn eat_variable(&mut self) -> LexResult<'a> {
             }
             '$' => self.eat_char(Variable(String::from('$'))),
             '?' => self.eat_char(Variable(String::from('?'))),
+            '!' => self.eat_char(Variable(String::from('!'))),
+            '@' => self.eat_char(Variable(String::from('@'))),
+            '*' => self.eat_char(Variable(String::from('*'))),
+            '#' => self.eat_char(Variable(String::from('#'))),
             ch if ch.is_alphabetic() || ch == '_' => {
                 let (span, content) = self.input.eat_while(|c| c.is_alphanumeric() || c == '_');
                 Ok(Token::new(Variable(content), span))
@@ -369,13 +569,76 @@ fn eat_variable(&mut self) -> LexResult<'a> {
         }
     }
 
+    /// Eats a `{#name}` variable length expansion following a `$` character.
+    ///
+    /// Returns the name of the variable along with the span of the expansion.
+    fn eat_variable_length(&mut self) -> Result<(String, Span), LexError> {
+        let start = self.input.next().0;
+        self.input.next();
+
+        let (_, name) = self.input.eat_while(|c| c.is_alphanumeric() || c == '_');
+        let next = self.input.peek();
+        if name.is_empty() || next.1 != '}' {
+            return Err(unexpected_char(next.1));
+        }
+
+        Ok((name, Span::new(start, self.input.next().0 + 1)))
+    }
+
+    /// Eats a `((expression))` arithmetic expansion following a `$` character.
+    ///
+    /// Returns the expression along with the span of the expansion.
+    fn eat_arithmetic(&mut self) -> Result<(String, Span), LexError> {
+        let start = self.input.next().0;
+        self.input.next();
+
+        let mut expression = String::new();
+        let mut depth = 0;
+        loop {
+            let (position, ch) = self.input.next();
+            match ch {
+                EOF => return Err(LexError::UnexpectedEof),
+                '(' => depth += 1,
+                ')' if depth > 0 => depth -= 1,
+                ')' => {
+                    return match self.input.next() {
+                        (_, ')') => {
+                            Ok((expression.trim().to_owned(), Span::new(start, position + 2)))
+                        }
+                        (_, EOF) => Err(LexError::UnexpectedEof),
+                        (_, ch) => Err(unexpected_char(ch)),
+                    }
+                }
+                _ => (),
+            }
+            expression.push(ch);
+        }
+    }
+
     /// Eats an expandable value that starts with a `$` character.
     fn eat_expandable(&mut self) -> LexResult<'a> {
         assert!(self.input.peek().1 == '$');
         let span_start = self.input.next().0;
 
-        let result = match self.input.peek().1 {
+        let next = self.input.peek().1;
+        let result = match next {
+            '(' if self.input.peek_n(2) == ['(', '('] => {
+                self.eat_arithmetic().map(|(expression, span)| {
+                    Token::new(
+                        Interpolation(vec![InterpolationUnit::Arithmetic(expression)]),
+                        span,
+                    )
+                })
+            }
             '(' => self.eat_char(DollarOpenParen),
+            '{' if self.input.peek_n(2) == ['{', '#'] => {
+                self.eat_variable_length().map(|(name, span)| {
+                    Token::new(
+                        Interpolation(vec![InterpolationUnit::VariableLength(name)]),
+                        span,
+                    )
+                })
+            }
             '{' => self.eat_char(DollarOpenBrace),
             _ => self.eat_variable(),
         };
@@ -389,106 +652,163 @@ fn eat_expandable(&mut self) -> LexResult<'a> {
 
     /// Eats an interpolation optionally surrounded by a delimiter.
     fn eat_interpolation(&mut self, delimiter: Option<char>) -> LexResult<'a> {
-        let delimiter_char = delimiter.unwrap_or(EOF);
+        self.eat_spanned_interpolation(delimiter)
+            .map_err(|error| error.error)
+    }
+
+    /// Eats an interpolation optionally surrounded by a delimiter.
+    ///
+    /// Errors include the span of the interpolation unit in which they occurred.
+    fn eat_spanned_interpolation(
+        &mut self,
+        delimiter: Option<char>,
+    ) -> Result<Token, SpannedLexError> {
         let start = self.input.peek().0;
-        if delimiter.is_some() {
-            assert!(self.input.peek().1 == delimiter.unwrap());
+        if let Some(delimiter) = delimiter {
+            assert!(self.input.peek().1 == delimiter);
             self.input.next();
         }
         let mut units = Vec::new();
 
         loop {
-            match self.input.peek().1 {
-                EOF if delimiter.is_some() => return Err(LexError::UnexpectedEof),
-
-                // Uses EOF as default and must be matched after an actual EOF.
-                ch if ch == delimiter_char => {
-                    self.input.next();
+            let unit_start = self.input.peek().0;
+            match self.eat_interpolation_unit(delimiter, &mut units) {
+                Ok(true) => {
                     let span = Span::new(start, self.input.peek().0);
                     return Ok(Token::new(Interpolation(units), span));
                 }
-                '\\' => {
-                    self.input.next();
+                Ok(false) => (),
+                Err(error) => {
+                    let end = self.input.peek().0.max(unit_start + 1);
+                    let span = Span::new(unit_start, end);
+                    return Err(SpannedLexError { error, span });
+                }
+            }
+        }
+    }
 
-                    if self.input.next_if_eq('e').is_some() {
-                        units.push(InterpolationUnit::Unicode('\u{001b}'));
-                        continue;
-                    } else if self.input.next_if_eq('u').is_some() {
-                        if self.input.peek().1 != '{' {
-                            return Err(unexpected_char(self.input.peek().1));
-                        }
-                        self.input.next();
+    /// Eats a single unit within an interpolation, adding it to `units`.
+    ///
+    /// Returns `true` if the end of the interpolation has been reached.
+    fn eat_interpolation_unit(
+        &mut self,
+        delimiter: Option<char>,
+        units: &mut Vec<InterpolationUnit>,
+    ) -> Result<bool, LexError> {
+        let delimiter_char = delimiter.unwrap_or(EOF);
+        match self.input.peek().1 {
+            EOF if delimiter.is_some() => return Err(LexError::UnexpectedEof),
+
+            // Uses EOF as default and must be matched after an actual EOF.
+            ch if ch == delimiter_char => {
+                self.input.next();
+                return Ok(true);
+            }
+            '\\' => {
+                self.input.next();
 
-                        let content = self.input.eat_while(|c| c != '}').1;
+                if self.input.next_if_eq('e').is_some() {
+                    units.push(InterpolationUnit::Unicode('\u{001b}'));
+                    return Ok(false);
+                } else if self.input.next_if_eq('u').is_some() {
+                    if self.input.peek().1 != '{' {
+                        return Err(unexpected_char(self.input.peek().1));
+                    }
+                    self.input.next();
 
-                        if self.input.peek().1 != '}' {
-                            return Err(unexpected_char(self.input.peek().1));
-                        }
-                        self.input.next();
+                    let content = self.input.eat_while(|c| c != '}').1;
 
-                        if let Ok(code) = u32::from_str_radix(&content, 16) {
-                            let ch = char::from_u32(code).unwrap_or(EOF);
-                            units.push(InterpolationUnit::Unicode(ch));
-                            continue;
-                        } else {
-                            return Err(LexError::UnknownToken(format!("\\u{{{}}}", content)));
-                        }
+                    if self.input.peek().1 != '}' {
+                        return Err(unexpected_char(self.input.peek().1));
                     }
+                    self.input.next();
 
-                    let (_, span_str) = self.input.next();
-                    units.push(InterpolationUnit::Literal(span_str.to_string()));
-                }
-                '$' => {
-                    let dollar = self.input.next();
-                    match self.input.peek().1 {
-                        '(' => {
-                            self.input.next();
-                            let mut subshell_tokens = Vec::new();
-                            loop {
-                                let next_token = self.next_unquoted_token()?;
-                                match next_token.contents {
-                                    CloseParen => break,
-                                    Eof => return Err(LexError::UnexpectedEof),
-                                    _ => subshell_tokens.push(next_token),
-                                }
-                            }
-                            units.push(InterpolationUnit::Subshell(subshell_tokens));
-                        }
-                        '{' => {
-                            let open_brace = self.input.next();
-                            let mut pipeline_tokens = Vec::new();
-                            pipeline_tokens.push(Token::new(
-                                TokenContents::DollarOpenBrace,
-                                Span::new(dollar.0, open_brace.0 + 1),
-                            ));
-                            loop {
-                                let next_token = self.next_unquoted_token()?;
-                                match next_token.contents {
-                                    CloseBrace => {
-                                        pipeline_tokens.push(next_token);
-                                        break;
-                                    }
-                                    Eof => return Err(LexError::UnexpectedEof),
-                                    _ => pipeline_tokens.push(next_token),
-                                }
-                            }
-                            units.push(InterpolationUnit::ValuePipeline(pipeline_tokens));
-                        }
-                        _ => match self.eat_variable()?.contents {
-                            Variable(content) => units.push(InterpolationUnit::Variable(content)),
-                            _ => {
-                                return Err(LexError::UnknownToken(self.input.peek().1.to_string()))
-                            }
-                        },
+                    if let Ok(code) = u32::from_str_radix(&content, 16) {
+                        let ch = char::from_u32(code).unwrap_or(EOF);
+                        units.push(InterpolationUnit::Unicode(ch));
+                        return Ok(false);
+                    } else {
+                        return Err(LexError::UnknownToken(format!("\\u{{{}}}", content)));
                     }
                 }
-                _ => {
-                    let (_, content) = self
-                        .input
-                        .eat_while(|c| c != '$' && c != '\\' && c != delimiter_char);
-                    units.push(InterpolationUnit::Literal(content));
+
+                let (_, span_str) = self.input.next();
+                units.push(InterpolationUnit::Literal(span_str.to_string()));
+            }
+            '$' => {
+                let dollar = self.input.next();
+                let next = self.input.peek().1;
+                match next {
+                    '(' if self.input.peek_n(2) == ['(', '('] => {
+                        let (expression, _) = self.eat_arithmetic()?;
+                        units.push(InterpolationUnit::Arithmetic(expression));
+                    }
+                    '(' => {
+                        self.input.next();
+                        let openers = [DollarOpenParen, OpenParen, ProcessSubstitutionStart];
+                        let (subshell_tokens, _) = self.eat_until_closing(&openers, &CloseParen)?;
+                        units.push(InterpolationUnit::Subshell(subshell_tokens));
+                    }
+                    '{' if self.input.peek_n(2) == ['{', '#'] => {
+                        let (name, _) = self.eat_variable_length()?;
+                        units.push(InterpolationUnit::VariableLength(name));
+                    }
+                    '{' => {
+                        let open_brace = self.input.next();
+                        let mut pipeline_tokens = Vec::new();
+                        pipeline_tokens.push(Token::new(
+                            TokenContents::DollarOpenBrace,
+                            Span::new(dollar.0, open_brace.0 + 1),
+                        ));
+                        let openers = [DollarOpenBrace, OpenBrace];
+                        let (tokens, close_brace) =
+                            self.eat_until_closing(&openers, &CloseBrace)?;
+                        pipeline_tokens.extend(tokens);
+                        pipeline_tokens.push(close_brace);
+                        units.push(InterpolationUnit::ValuePipeline(pipeline_tokens));
+                    }
+                    _ => match self.eat_variable()?.contents {
+                        Variable(content) => units.push(InterpolationUnit::Variable(content)),
+                        _ => return Err(LexError::UnknownToken(self.input.peek().1.to_string())),
+                    },
                 }
             }
+            _ => {
+                let (_, content) = self
+                    .input
+                    .eat_while(|c| c != '$' && c != '\\' && c != delimiter_char);
+                units.push(InterpolationUnit::Literal(normalize_line_endings(content)));
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Eats tokens until a closing token is found outside of any nested sections.
+    ///
+    /// Each token in `openers` starts a nested section that must be closed by a
+    /// separate `closing` token. Tokens are lexed in their own mode, allowing
+    /// quoted strings to contain closing characters.
+    ///
+    /// Returns the tokens within the section, along with the closing token.
+    fn eat_until_closing(
+        &mut self,
+        openers: &[TokenContents],
+        closing: &TokenContents,
+    ) -> Result<(Vec<Token>, Token), LexError> {
+        let mut tokens = Vec::new();
+        let mut depth = 0usize;
+
+        loop {
+            let token = self.next_token()?;
+            match &token.contents {
+                Eof => return Err(LexError::UnexpectedEof),
+                contents if contents == closing && depth == 0 => return Ok((tokens, token)),
+                contents if contents == closing => depth -= 1,
+                contents if openers.contains(contents) => depth += 1,
+                _ => (),
+            }
+            tokens.push(token);
         }
     }
 
@@ -518,6 +838,8 @@ fn eat_fd_read_to_or_process_substitution(&mut self) -> Result<Token, LexError>
 
         let contents = if self.input.next_if_eq('(').is_some() {
             ProcessSubstitutionStart
+        } else if self.input.take_if_eq(&['<', '<']).is_some() {
+            HereString(0)
         } else {
             FdReadTo(0)
         };
@@ -527,6 +849,17 @@ fn eat_fd_read_to_or_process_substitution(&mut self) -> Result<Token, LexError>
     }
 }
 
+/// Replaces "\r\n" line endings within quoted content with "\n".
+///
+/// Line endings are thus treated uniformly regardless of the platform that a
+/// script was written on.
+fn normalize_line_endings(content: String) -> String {
+    match content.contains('\r') {
+        true => content.replace("\r\n", "\n"),
+        false => content,
+    }
+}
+
 /// Returns a [`LexError`] indicating that an unexpected character was encountered.
 fn unexpected_char(ch: char) -> LexError {
     match ch {
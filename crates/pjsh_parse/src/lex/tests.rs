@@ -21,6 +21,10 @@ fn lex_operators() {
     assert_eq!(tokens("..."), vec![Token::new(Spread, Span::new(0, 3))]);
 
     assert_eq!(tokens("<"), vec![Token::new(FdReadTo(0), Span::new(0, 1))]);
+    assert_eq!(
+        tokens("<<<"),
+        vec![Token::new(HereString(0), Span::new(0, 3))]
+    );
     assert_eq!(
         tokens(">"),
         vec![Token::new(FdWriteFrom(1), Span::new(0, 1))]
@@ -34,6 +38,35 @@ fn lex_operators() {
     assert_eq!(tokens("||"), vec![Token::new(OrIf, Span::new(0, 2))]);
 }
 
+#[test]
+fn lex_file_descriptor_redirects() {
+    assert_eq!(
+        tokens("2>"),
+        vec![Token::new(FdWriteFrom(2), Span::new(0, 2))]
+    );
+    assert_eq!(
+        tokens("2>>"),
+        vec![Token::new(FdAppendFrom(2), Span::new(0, 3))]
+    );
+    assert_eq!(tokens("3<"), vec![Token::new(FdReadTo(3), Span::new(0, 2))]);
+    assert_eq!(
+        tokens(">&2"),
+        vec![Token::new(FdDuplicate(1, 2), Span::new(0, 3))]
+    );
+    assert_eq!(
+        tokens("2>&1"),
+        vec![Token::new(FdDuplicate(2, 1), Span::new(0, 4))]
+    );
+    assert_eq!(
+        tokens("12"),
+        vec![Token::new(Literal("12".into()), Span::new(0, 2))]
+    );
+    assert_eq!(
+        lex(">&x", &HashMap::new()),
+        Err(LexError::UnexpectedChar('x'))
+    );
+}
+
 #[test]
 fn lex_eol() {
     assert_eq!(tokens("\n"), vec![Token::new(Eol, Span::new(0, 1))]);
@@ -54,7 +87,25 @@ fn lex_eol() {
 fn lex_comment() {
     assert_eq!(
         tokens("# This is a comment"),
-        vec![Token::new(Comment, Span::new(0, 19))]
+        vec![Token::new(
+            Comment("# This is a comment".into()),
+            Span::new(0, 19)
+        )]
+    );
+}
+
+#[test]
+fn lex_shebang() {
+    assert_eq!(
+        tokens("#!/usr/bin/env pjsh\necho hello\n"),
+        vec![
+            Token::new(Comment("#!/usr/bin/env pjsh".into()), Span::new(0, 19)),
+            Token::new(Eol, Span::new(19, 20)),
+            Token::new(Literal("echo".into()), Span::new(20, 24)),
+            Token::new(Whitespace, Span::new(24, 25)),
+            Token::new(Literal("hello".into()), Span::new(25, 30)),
+            Token::new(Eol, Span::new(30, 31)),
+        ]
     );
 }
 
@@ -100,6 +151,88 @@ fn lex_literal() {
     );
 }
 
+#[test]
+fn lex_brace_groups() {
+    assert_eq!(
+        tokens("file-{a,b}.txt"),
+        vec![Token::new(
+            Literal("file-{a,b}.txt".into()),
+            Span::new(0, 14)
+        )]
+    );
+    assert_eq!(
+        tokens("{1..3}{a,{b,c}}"),
+        vec![Token::new(
+            Literal("{1..3}{a,{b,c}}".into()),
+            Span::new(0, 15)
+        )]
+    );
+    assert_eq!(
+        tokens("{}"),
+        vec![
+            Token::new(OpenBrace, Span::new(0, 1)),
+            Token::new(CloseBrace, Span::new(1, 2))
+        ]
+    );
+    assert_eq!(tokens("{a b}")[0], Token::new(OpenBrace, Span::new(0, 1)));
+    assert_eq!(
+        tokens("{true}"),
+        vec![
+            Token::new(OpenBrace, Span::new(0, 1)),
+            Token::new(Literal("true".into()), Span::new(1, 5)),
+            Token::new(CloseBrace, Span::new(5, 6))
+        ]
+    );
+    assert_eq!(
+        tokens("{x{a,b}}")[0],
+        Token::new(OpenBrace, Span::new(0, 1))
+    );
+}
+
+#[test]
+fn lex_literal_brace_groups_in_arguments() {
+    assert_eq!(
+        tokens("echo {x} a{x}b {}"),
+        vec![
+            Token::new(Literal("echo".into()), Span::new(0, 4)),
+            Token::new(Whitespace, Span::new(4, 5)),
+            Token::new(Literal("{x}".into()), Span::new(5, 8)),
+            Token::new(Whitespace, Span::new(8, 9)),
+            Token::new(Literal("a{x}b".into()), Span::new(9, 14)),
+            Token::new(Whitespace, Span::new(14, 15)),
+            Token::new(Literal("{}".into()), Span::new(15, 17)),
+        ]
+    );
+    assert_eq!(
+        tokens("(echo {x})")[3],
+        Token::new(Literal("{x}".into()), Span::new(6, 9))
+    );
+}
+
+#[test]
+fn lex_brace_groups_in_block_statements_as_blocks() {
+    for src in [
+        "fn f() {x}",
+        "if true {x}",
+        "if false {} else {x}",
+        "for i in [a] {x}",
+        "while true {x}",
+        "switch a {\nb {x}\n}",
+        "if true { echo {y} } else {x}",
+    ] {
+        let tokens = tokens(src);
+        let end = src.rfind('{').unwrap();
+        assert_eq!(
+            tokens
+                .iter()
+                .find(|token| token.span.start == end)
+                .map(|token| &token.contents),
+            Some(&OpenBrace),
+            "{src}"
+        );
+    }
+}
+
 #[test]
 fn lex_variable() {
     assert_eq!(
@@ -118,10 +251,24 @@ fn lex_variable() {
         tokens("$?"),
         vec![Token::new(Variable("?".into()), Span::new(0, 2))]
     );
+    assert_eq!(
+        tokens("$!"),
+        vec![Token::new(Variable("!".into()), Span::new(0, 2))]
+    );
     assert_eq!(
         tokens("$0"),
         vec![Token::new(Variable("0".into()), Span::new(0, 2))]
     );
+    assert_eq!(
+        tokens("$@ $* $#"),
+        vec![
+            Token::new(Variable("@".into()), Span::new(0, 2)),
+            Token::new(Whitespace, Span::new(2, 3)),
+            Token::new(Variable("*".into()), Span::new(3, 5)),
+            Token::new(Whitespace, Span::new(5, 6)),
+            Token::new(Variable("#".into()), Span::new(6, 8)),
+        ]
+    );
 }
 
 #[test]
@@ -151,6 +298,52 @@ fn lex_interpolation_token() {
     );
 }
 
+#[test]
+fn lex_arithmetic() {
+    assert_eq!(
+        tokens("$(( (i + 1) * 2 ))"),
+        vec![Token::new(
+            Interpolation(vec![InterpolationUnit::Arithmetic("(i + 1) * 2".into())]),
+            Span::new(0, 18)
+        )]
+    );
+    assert_eq!(
+        tokens("`sum: $((1+2))`"),
+        vec![Token::new(
+            Interpolation(vec![
+                InterpolationUnit::Literal("sum: ".into()),
+                InterpolationUnit::Arithmetic("1+2".into()),
+            ]),
+            Span::new(0, 15)
+        )]
+    );
+    assert_eq!(
+        lex("$((1 + 2)", &HashMap::new()),
+        Err(LexError::UnexpectedEof)
+    );
+}
+
+#[test]
+fn lex_variable_length() {
+    assert_eq!(
+        tokens("${#name}"),
+        vec![Token::new(
+            Interpolation(vec![InterpolationUnit::VariableLength("name".into())]),
+            Span::new(0, 8)
+        )]
+    );
+    assert_eq!(
+        tokens("`length: ${#name}`"),
+        vec![Token::new(
+            Interpolation(vec![
+                InterpolationUnit::Literal("length: ".into()),
+                InterpolationUnit::VariableLength("name".into()),
+            ]),
+            Span::new(0, 18)
+        )]
+    );
+}
+
 #[test]
 fn lex_quoted_double() {
     assert_eq!(
@@ -253,6 +446,33 @@ fn lex_quoted_multiline_single() {
     );
 }
 
+#[test]
+fn lex_quoted_crlf_line_endings() {
+    assert_eq!(
+        tokens("\"first\r\nsecond\""),
+        vec![
+            Token::new(Quote, Span::new(0, 1)),
+            Token::new(Quoted("first\nsecond".into()), Span::new(1, 14)),
+            Token::new(Quote, Span::new(14, 15)),
+        ]
+    );
+    assert_eq!(
+        tokens("'''first\r\nsecond'''"),
+        vec![
+            Token::new(TripleQuote, Span::new(0, 3)),
+            Token::new(Quoted("first\nsecond".into()), Span::new(3, 16)),
+            Token::new(TripleQuote, Span::new(16, 19))
+        ]
+    );
+    assert_eq!(
+        tokens("`first\r\nsecond`"),
+        vec![Token::new(
+            Interpolation(vec![InterpolationUnit::Literal("first\nsecond".into())]),
+            Span::new(0, 15)
+        )]
+    );
+}
+
 #[test]
 fn lex_whitespace() {
     assert_eq!(tokens(" "), vec![Token::new(Whitespace, Span::new(0, 1))]);
@@ -299,12 +519,40 @@ fn lex_word_interpolation() {
 fn lex_incomplete_word_interpolation() {
     assert_eq!(
         crate::lex_interpolation(r#"$("#),
-        Err(LexError::UnexpectedEof)
+        Err(SpannedLexError {
+            error: LexError::UnexpectedEof,
+            span: Span::new(0, 2)
+        })
     );
     assert_eq!(
         crate::lex_interpolation(r#"${"#),
-        Err(LexError::UnexpectedEof)
+        Err(SpannedLexError {
+            error: LexError::UnexpectedEof,
+            span: Span::new(0, 2)
+        })
+    );
+}
+
+#[test]
+fn lex_invalid_word_interpolation_spans() {
+    let src = r#"\e[1m$USER\e[0m ${PWD \u{zz} $"#;
+    assert_eq!(
+        crate::lex_interpolation(src),
+        Err(SpannedLexError {
+            error: LexError::UnexpectedEof,
+            span: Span::new(16, src.len())
+        })
+    );
+
+    let src = r#"\e[1m$USER\e[0m \u{zz} \e[0m"#;
+    assert_eq!(
+        crate::lex_interpolation(src),
+        Err(SpannedLexError {
+            error: LexError::UnknownToken(r#"\u{zz}"#.into()),
+            span: Span::new(16, 22)
+        })
     );
+    assert_eq!(&src[16..22], r#"\u{zz}"#);
 }
 
 #[test]
@@ -341,6 +589,30 @@ fn lex_interpolation_with_braces() {
     );
 }
 
+#[test]
+fn lex_interpolation_with_nested_subshells() {
+    assert_eq!(
+        crate::lex_interpolation(r#"$(a ")" $(b) | c)"#)
+            .unwrap()
+            .contents,
+        Interpolation(vec![InterpolationUnit::Subshell(vec![
+            Token::new(Literal("a".into()), Span::new(2, 3)),
+            Token::new(Whitespace, Span::new(3, 4)),
+            Token::new(Quote, Span::new(4, 5)),
+            Token::new(Quoted(")".into()), Span::new(5, 6)),
+            Token::new(Quote, Span::new(6, 7)),
+            Token::new(Whitespace, Span::new(7, 8)),
+            Token::new(DollarOpenParen, Span::new(8, 10)),
+            Token::new(Literal("b".into()), Span::new(10, 11)),
+            Token::new(CloseParen, Span::new(11, 12)),
+            Token::new(Whitespace, Span::new(12, 13)),
+            Token::new(Pipe, Span::new(13, 14)),
+            Token::new(Whitespace, Span::new(14, 15)),
+            Token::new(Literal("c".into()), Span::new(15, 16)),
+        ])])
+    );
+}
+
 fn tokens(src: &str) -> Vec<Token> {
     match lex(src, &HashMap::new()) {
         Ok(tokens) => tokens,
@@ -9,4 +9,4 @@
     input::Span,
     lexer::{lex, lex_interpolation},
 };
-pub use parse::{parse, parse_interpolation, ParseResult};
+pub use parse::{parse, parse_condition_words, parse_interpolation, parse_spanned, ParseResult};
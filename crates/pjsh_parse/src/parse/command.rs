@@ -1,6 +1,6 @@
-use pjsh_ast::{Command, FileDescriptor, Redirect, RedirectMode};
+use pjsh_ast::{Command, FileDescriptor, Redirect, RedirectMode, Word};
 
-use crate::token::TokenContents;
+use crate::{token::TokenContents, ParseError};
 
 use super::{cursor::TokenCursor, utils::unexpected_token, word::parse_word, ParseResult};
 
@@ -9,12 +9,27 @@ pub fn parse_command(tokens: &mut TokenCursor) -> ParseResult<Command> {
     let mut command = Command::default();
     command.redirects.extend(parse_redirects(tokens)); // Prefix redirects.
 
+    // The "[" built-in is lexed as a bracket rather than as a literal word.
+    let is_bracket_test = tokens.next_if_eq(TokenContents::OpenBracket).is_some();
+
     // A command must include at least one argument denoting the program name.
-    command.arg(parse_word(tokens)?);
+    if is_bracket_test {
+        command.arg(Word::Literal("[".into()));
+    } else {
+        command.arg(parse_word(tokens)?);
+    }
 
-    // Additional arguments are optional.
-    while let Ok(argument) = parse_word(tokens) {
-        command.arg(argument);
+    // Additional arguments are optional, but must be valid if present.
+    loop {
+        match parse_word(tokens) {
+            Ok(argument) => command.arg(argument),
+            Err(error @ ParseError::InvalidSyntax(_)) => return Err(error),
+            Err(_) => break,
+        }
+    }
+
+    if is_bracket_test && tokens.next_if_eq(TokenContents::CloseBracket).is_some() {
+        command.arg(Word::Literal("]".into()));
     }
 
     command.redirects.extend(parse_redirects(tokens)); // Suffix redirects.
@@ -40,7 +55,15 @@ pub(crate) fn parse_redirect(tokens: &mut TokenCursor) -> ParseResult<Redirect>
             Ok(Redirect::new(
                 FileDescriptor::File(parse_word(tokens)?),
                 FileDescriptor::Number(fd),
-                RedirectMode::Write,
+                RedirectMode::Read,
+            ))
+        }
+        TokenContents::HereString(fd) => {
+            tokens.next();
+            Ok(Redirect::new(
+                FileDescriptor::Value(parse_word(tokens)?),
+                FileDescriptor::Number(fd),
+                RedirectMode::Read,
             ))
         }
         TokenContents::FdWriteFrom(fd) => {
@@ -59,6 +82,14 @@ pub(crate) fn parse_redirect(tokens: &mut TokenCursor) -> ParseResult<Redirect>
                 RedirectMode::Append,
             ))
         }
+        TokenContents::FdDuplicate(source, target) => {
+            tokens.next();
+            Ok(Redirect::new(
+                FileDescriptor::Number(source),
+                FileDescriptor::Number(target),
+                RedirectMode::Write,
+            ))
+        }
         _ => Err(unexpected_token(tokens)),
     }
 }
@@ -101,6 +132,31 @@ fn parse_muli_argument_command() {
         )
     }
 
+    #[test]
+    fn parse_bracket_test_command() {
+        let span = Span::new(0, 0); // Does not matter during this test.
+        assert_eq!(
+            parse_command(&mut TokenCursor::from(vec![
+                Token::new(TokenContents::OpenBracket, span),
+                Token::new(TokenContents::Whitespace, span),
+                Token::new(TokenContents::Literal("-n".into()), span),
+                Token::new(TokenContents::Whitespace, span),
+                Token::new(TokenContents::Literal("word".into()), span),
+                Token::new(TokenContents::Whitespace, span),
+                Token::new(TokenContents::CloseBracket, span),
+            ])),
+            Ok(Command {
+                arguments: vec![
+                    Word::Literal("[".into()),
+                    Word::Literal("-n".into()),
+                    Word::Literal("word".into()),
+                    Word::Literal("]".into()),
+                ],
+                redirects: Vec::new(),
+            })
+        )
+    }
+
     #[test]
     fn parse_command_with_prefix_redirects() {
         let span = Span::new(0, 0); // Does not matter during this test.
@@ -118,7 +174,7 @@ fn parse_command_with_prefix_redirects() {
                     Redirect {
                         source: FileDescriptor::File(Word::Literal("prefix1".into())),
                         target: FileDescriptor::Number(0),
-                        mode: RedirectMode::Write
+                        mode: RedirectMode::Read
                     },
                     Redirect {
                         source: FileDescriptor::Number(1),
@@ -147,7 +203,7 @@ fn parse_command_with_suffix_redirects() {
                     Redirect {
                         source: FileDescriptor::File(Word::Literal("suffix1".into())),
                         target: FileDescriptor::Number(0),
-                        mode: RedirectMode::Write
+                        mode: RedirectMode::Read
                     },
                     Redirect {
                         source: FileDescriptor::Number(1),
@@ -170,7 +226,7 @@ fn parse_redirect_read() {
             Ok(Redirect {
                 source: FileDescriptor::File(Word::Literal("file".into())),
                 target: FileDescriptor::Number(0),
-                mode: RedirectMode::Write
+                mode: RedirectMode::Read
             })
         )
     }
@@ -206,4 +262,36 @@ fn parse_redirect_append() {
             })
         )
     }
+
+    #[test]
+    fn parse_redirect_duplicate() {
+        let span = Span::new(0, 0); // Does not matter during this test.
+        assert_eq!(
+            parse_redirect(&mut TokenCursor::from(vec![Token::new(
+                TokenContents::FdDuplicate(2, 1),
+                span
+            )])),
+            Ok(Redirect {
+                source: FileDescriptor::Number(2),
+                target: FileDescriptor::Number(1),
+                mode: RedirectMode::Write
+            })
+        )
+    }
+
+    #[test]
+    fn parse_redirect_here_string() {
+        let span = Span::new(0, 0); // Does not matter during this test.
+        assert_eq!(
+            parse_redirect(&mut TokenCursor::from(vec![
+                Token::new(TokenContents::HereString(0), span),
+                Token::new(TokenContents::Variable("list".into()), span),
+            ])),
+            Ok(Redirect {
+                source: FileDescriptor::Value(Word::Variable("list".into())),
+                target: FileDescriptor::Number(0),
+                mode: RedirectMode::Read
+            })
+        )
+    }
 }
@@ -1,6 +1,9 @@
 use pjsh_ast::{Condition, Word};
 
-use crate::{token::TokenContents, ParseError};
+use crate::{
+    token::{Token, TokenContents},
+    ParseError, Span,
+};
 
 use super::{
     cursor::TokenCursor,
@@ -25,9 +28,20 @@ pub(crate) fn parse_condition(tokens: &mut TokenCursor) -> ParseResult<Condition
         .or_else(|_| one_word_condition(&mut lookahead, "is-file", Condition::IsFile))
         .or_else(|_| one_word_condition(&mut lookahead, "-e", Condition::IsPath))
         .or_else(|_| one_word_condition(&mut lookahead, "is-path", Condition::IsPath))
+        .or_else(|_| one_word_condition(&mut lookahead, "-r", Condition::IsReadable))
+        .or_else(|_| one_word_condition(&mut lookahead, "is-readable", Condition::IsReadable))
+        .or_else(|_| one_word_condition(&mut lookahead, "-w", Condition::IsWritable))
+        .or_else(|_| one_word_condition(&mut lookahead, "is-writable", Condition::IsWritable))
+        .or_else(|_| one_word_condition(&mut lookahead, "-x", Condition::IsExecutable))
+        .or_else(|_| one_word_condition(&mut lookahead, "is-executable", Condition::IsExecutable))
+        .or_else(|_| one_word_condition(&mut lookahead, "-s", Condition::IsNonEmpty))
+        .or_else(|_| one_word_condition(&mut lookahead, "is-non-empty", Condition::IsNonEmpty))
         .or_else(|_| two_word_condition(&mut lookahead, "==", Condition::Eq))
+        .or_else(|_| two_word_condition(&mut lookahead, "=", Condition::Eq))
         .or_else(|_| two_word_condition(&mut lookahead, "!=", Condition::Ne))
         .or_else(|_| two_word_condition(&mut lookahead, "=~", Condition::Matches))
+        .or_else(|_| two_word_condition(&mut lookahead, "-nt", Condition::IsNewer))
+        .or_else(|_| two_word_condition(&mut lookahead, "-ot", Condition::IsOlder))
         .or_else(|_| Ok(Condition::NotEmpty(parse_word(&mut lookahead)?)))?;
 
     take_token(&mut lookahead, &TokenContents::DoubleCloseBracket)?;
@@ -40,6 +54,39 @@ pub(crate) fn parse_condition(tokens: &mut TokenCursor) -> ParseResult<Condition
     Ok(condition)
 }
 
+/// Parses a condition from a list of already interpolated words.
+///
+/// The words are treated as if they were surrounded by `[[` and `]]`, allowing
+/// built-ins such as `test` to share the syntax of conditions.
+///
+/// # Errors
+///
+/// This function will return an error if the words do not form a single
+/// condition.
+pub fn parse_condition_words(words: &[String]) -> ParseResult<Condition> {
+    let mut tokens = Vec::with_capacity(words.len() + 2);
+    tokens.push(Token::new(
+        TokenContents::DoubleOpenBracket,
+        Span::new(0, 0),
+    ));
+    for (i, word) in words.iter().enumerate() {
+        let literal = TokenContents::Literal(word.clone());
+        tokens.push(Token::new(literal, Span::new(i + 1, i + 1)));
+    }
+    let end = words.len() + 1;
+    tokens.push(Token::new(
+        TokenContents::DoubleCloseBracket,
+        Span::new(end, end),
+    ));
+
+    let mut tokens = TokenCursor::from(tokens);
+    let condition = parse_condition(&mut tokens)?;
+    match tokens.peek().contents {
+        TokenContents::Eof => Ok(condition),
+        _ => Err(ParseError::UnexpectedToken(tokens.next())),
+    }
+}
+
 /// Returns a condition from a single word.
 ///
 /// Typically on the form `[[ keyword word ]]`.
@@ -156,6 +203,34 @@ fn it_parses_is_path() {
         );
     }
 
+    #[test]
+    fn it_parses_permission_conditions() {
+        let path = || Word::Literal("path".into());
+        let cases = [
+            ("-r", Condition::IsReadable(path())),
+            ("is-readable", Condition::IsReadable(path())),
+            ("-w", Condition::IsWritable(path())),
+            ("is-writable", Condition::IsWritable(path())),
+            ("-x", Condition::IsExecutable(path())),
+            ("is-executable", Condition::IsExecutable(path())),
+            ("-s", Condition::IsNonEmpty(path())),
+            ("is-non-empty", Condition::IsNonEmpty(path())),
+        ];
+
+        for (keyword, condition) in cases {
+            assert_eq!(
+                parse(vec![
+                    TokenContents::DoubleOpenBracket,
+                    TokenContents::Literal(keyword.into()),
+                    TokenContents::Whitespace,
+                    TokenContents::Literal("path".into()),
+                    TokenContents::DoubleCloseBracket,
+                ]),
+                Ok(condition)
+            );
+        }
+    }
+
     #[test]
     fn it_parses_eq() {
         assert_eq!(
@@ -175,6 +250,25 @@ fn it_parses_eq() {
         );
     }
 
+    #[test]
+    fn it_parses_single_equals_as_eq() {
+        assert_eq!(
+            parse(vec![
+                TokenContents::DoubleOpenBracket,
+                TokenContents::Literal("a".into()),
+                TokenContents::Whitespace,
+                TokenContents::Literal("=".into()),
+                TokenContents::Whitespace,
+                TokenContents::Literal("b".into()),
+                TokenContents::DoubleCloseBracket,
+            ]),
+            Ok(Condition::Eq(
+                Word::Literal("a".into()),
+                Word::Literal("b".into())
+            ))
+        );
+    }
+
     #[test]
     fn it_parses_ne() {
         assert_eq!(
@@ -213,6 +307,30 @@ fn it_parses_matches() {
         );
     }
 
+    #[test]
+    fn it_parses_modification_time_comparisons() {
+        for (operator, condition) in [
+            ("-nt", Condition::IsNewer as fn(Word, Word) -> Condition),
+            ("-ot", Condition::IsOlder),
+        ] {
+            assert_eq!(
+                parse(vec![
+                    TokenContents::DoubleOpenBracket,
+                    TokenContents::Literal("a".into()),
+                    TokenContents::Whitespace,
+                    TokenContents::Literal(operator.into()),
+                    TokenContents::Whitespace,
+                    TokenContents::Literal("b".into()),
+                    TokenContents::DoubleCloseBracket,
+                ]),
+                Ok(condition(
+                    Word::Literal("a".into()),
+                    Word::Literal("b".into())
+                ))
+            );
+        }
+    }
+
     #[test]
     fn it_parses_empty() {
         assert_eq!(
@@ -266,4 +384,25 @@ fn it_parses_inverted_condition() {
             ))))
         );
     }
+
+    #[test]
+    fn it_parses_condition_words() {
+        let words =
+            |words: &[&str]| -> Vec<String> { words.iter().map(ToString::to_string).collect() };
+
+        assert_eq!(
+            parse_condition_words(&words(&["!", "-f", "path"])),
+            Ok(Condition::Invert(Box::new(Condition::IsFile(
+                Word::Literal("path".into())
+            ))))
+        );
+        assert_eq!(
+            parse_condition_words(&words(&["", "!=", "b"])),
+            Ok(Condition::Ne(
+                Word::Literal("".into()),
+                Word::Literal("b".into())
+            ))
+        );
+        assert!(parse_condition_words(&words(&["a", "b"])).is_err());
+    }
 }
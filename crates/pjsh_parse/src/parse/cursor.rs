@@ -27,6 +27,22 @@ pub struct TokenCursor {
 
     /// Mode of operation for newline tokens.
     newline_mode: NewlineMode,
+
+    /// Comments that have been skipped, but not yet taken.
+    comments: Vec<SkippedComment>,
+
+    /// Whether a non-trivial token has been passed on the current line.
+    line_has_content: bool,
+}
+
+/// A comment that has been skipped by a [`TokenCursor`].
+#[derive(Clone, Debug)]
+struct SkippedComment {
+    /// The comment, as written.
+    text: String,
+
+    /// Whether the comment follows other tokens on the same line.
+    is_trailing: bool,
 }
 
 impl TokenCursor {
@@ -40,7 +56,13 @@ pub fn peek(&mut self) -> &Token {
     /// Returns the next non-trivial [`Token`] while advancing the cursor.
     pub fn next(&mut self) -> Token {
         self.skip_trivial_tokens();
-        self.tokens.next().unwrap_or_else(|| self.eof_token.clone())
+        match self.tokens.next() {
+            Some(token) => {
+                self.pass(&token);
+                token
+            }
+            None => self.eof_token.clone(),
+        }
     }
 
     /// Consume and return the next token if a condition is true.
@@ -51,7 +73,11 @@ pub fn next(&mut self) -> Token {
     /// Skips trivial tokens before evaluating the condition.
     pub fn next_if(&mut self, func: impl FnOnce(&Token) -> bool) -> Option<Token> {
         self.skip_trivial_tokens();
-        self.tokens.next_if(func)
+        let token = self.tokens.next_if(func);
+        if let Some(token) = &token {
+            self.pass(token);
+        }
+        token
     }
 
     /// Consume and return the next token if `contents` match the next token's contents.
@@ -72,11 +98,55 @@ pub fn newline_is_whitespace(&mut self, is_whitespace: bool) {
         };
     }
 
+    /// Returns `true` if newline is currently treated as whitespace.
+    pub fn is_newline_whitespace(&self) -> bool {
+        self.newline_mode == NewlineMode::Whitespace
+    }
+
+    /// Takes all comments that have been skipped since they were last taken.
+    pub fn take_comments(&mut self) -> Vec<String> {
+        self.comments
+            .drain(..)
+            .map(|comment| comment.text)
+            .collect()
+    }
+
+    /// Takes the last skipped comment if it follows other tokens on the same line.
+    ///
+    /// Trailing comments that are not taken are discarded.
+    pub fn take_trailing_comment(&mut self) -> Option<String> {
+        let mut trailing = None;
+        self.comments.retain(|comment| {
+            if comment.is_trailing {
+                trailing = Some(comment.text.clone());
+            }
+            !comment.is_trailing
+        });
+        trailing
+    }
+
     /// Skips all trivial tokens, stopping before the next non-trivial token.
     fn skip_trivial_tokens(&mut self) {
         let mode = self.newline_mode.clone();
         while is_trivial(self.tokens.peek().unwrap_or(&self.eof_token), &mode) {
-            self.tokens.next();
+            if let Some(token) = self.tokens.next() {
+                self.pass(&token);
+            }
+        }
+    }
+
+    /// Keeps track of comments and line contents when advancing past a token.
+    fn pass(&mut self, token: &Token) {
+        match &token.contents {
+            TokenContents::Comment(text) if !is_shebang(token) => {
+                self.comments.push(SkippedComment {
+                    text: text.clone(),
+                    is_trailing: self.line_has_content,
+                });
+            }
+            TokenContents::Comment(_) | TokenContents::Whitespace => (),
+            TokenContents::Eol => self.line_has_content = false,
+            _ => self.line_has_content = true,
         }
     }
 }
@@ -91,6 +161,8 @@ fn from(tokens: Vec<Token>) -> Self {
             eof_token: Token::new(TokenContents::Eof, Span::new(start, end)),
             tokens: tokens.into_iter().peekable(),
             newline_mode: NewlineMode::Newline,
+            comments: Vec::new(),
+            line_has_content: false,
         }
     }
 }
@@ -100,7 +172,7 @@ fn from(tokens: Vec<Token>) -> Self {
 /// Trivial tokens are typically discarded.
 fn is_trivial(token: &Token, newline_mode: &NewlineMode) -> bool {
     match token.contents {
-        TokenContents::Comment | TokenContents::Whitespace => true,
+        TokenContents::Comment(_) | TokenContents::Whitespace => true,
 
         // Eol is trivialized when treating newline as whitespace.
         TokenContents::Eol if newline_mode == &NewlineMode::Whitespace => true,
@@ -108,3 +180,13 @@ fn is_trivial(token: &Token, newline_mode: &NewlineMode) -> bool {
         _ => false,
     }
 }
+
+/// Returns `true` if a [`Token`] is an interpreter directive at the start of the input.
+///
+/// Shebangs, such as `#!/usr/bin/env pjsh`, are not considered to be comments.
+fn is_shebang(token: &Token) -> bool {
+    match &token.contents {
+        TokenContents::Comment(text) => token.span.start == 0 && text.starts_with("#!"),
+        _ => false,
+    }
+}
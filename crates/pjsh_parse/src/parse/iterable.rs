@@ -24,9 +24,7 @@ fn parse_numeric_range(word: &str) -> Option<NumericRange> {
         static ref RE: Regex = Regex::new(r#"(-?\d+)\.\.(=?)(-?\d+)"#).expect("Compile regex");
     }
 
-    let Some(captures) = RE.captures(word) else {
-        return  None;
-    };
+    let captures = RE.captures(word)?;
 
     let start = captures[1].parse::<isize>();
     let is_end_included = &captures[2] == "=";
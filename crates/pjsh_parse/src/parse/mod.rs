@@ -2,9 +2,14 @@
 
 use pjsh_ast::{Program, Word};
 
-use crate::{lex::lexer::LexError, ParseError};
+use crate::{lex::lexer::LexError, ParseError, Span};
 
-use self::{cursor::TokenCursor, program::parse_program, word::parse_word};
+pub use self::condition::parse_condition_words;
+use self::{
+    cursor::TokenCursor,
+    program::{parse_program, parse_spanned_program},
+    word::parse_word,
+};
 
 mod command;
 mod condition;
@@ -33,6 +38,25 @@ pub fn parse(src: &str, aliases: &HashMap<String, String>) -> ParseResult<Progra
     }
 }
 
+/// Parses a [`Program`] by consuming some input `src` in its entirety.
+///
+/// Returns the program along with the span of each of its statements within
+/// `src`. Spans may include surrounding whitespace and separators.
+///
+/// # Errors
+///
+/// This function will return an error if a program can't be parsed.
+pub fn parse_spanned(
+    src: &str,
+    aliases: &HashMap<String, String>,
+) -> ParseResult<(Program, Vec<Span>)> {
+    match crate::lex(src, aliases) {
+        Ok(tokens) => parse_spanned_program(&mut TokenCursor::from(tokens)),
+        Err(LexError::UnexpectedEof) => Err(ParseError::UnexpectedEof),
+        Err(error) => Err(ParseError::InvalidSyntax(error.to_string())),
+    }
+}
+
 /// Parses a [`Word`] from within an interpolation.
 ///
 /// # Errors
@@ -41,7 +65,9 @@ pub fn parse(src: &str, aliases: &HashMap<String, String>) -> ParseResult<Progra
 pub fn parse_interpolation(src: &str) -> ParseResult<Word> {
     match crate::lex_interpolation(src) {
         Ok(token) => parse_word(&mut TokenCursor::from(vec![token])),
-        Err(LexError::UnexpectedEof) => Err(ParseError::UnexpectedEof),
-        Err(error) => Err(ParseError::InvalidSyntax(error.to_string())),
+        Err(error) => Err(ParseError::InvalidInterpolation(
+            error.to_string(),
+            error.span,
+        )),
     }
 }
@@ -8,20 +8,31 @@
 use super::{
     cursor::TokenCursor,
     pipeline::parse_pipeline,
-    statement::parse_statement,
+    statement::parse_commented_statement,
     utils::{take_token, unexpected_token},
     ParseResult,
 };
 
 /// Parses [`Program`] by consuming all remaining input.
 pub fn parse_program(tokens: &mut TokenCursor) -> ParseResult<Program> {
+    parse_spanned_program(tokens).map(|(program, _)| program)
+}
+
+/// Parses [`Program`] by consuming all remaining input.
+///
+/// Returns the program along with the span of each of its statements. Spans
+/// may include surrounding whitespace and separators.
+pub fn parse_spanned_program(tokens: &mut TokenCursor) -> ParseResult<(Program, Vec<Span>)> {
     let mut program = Program::new();
+    let mut spans = Vec::new();
 
     loop {
-        match parse_statement(tokens) {
+        let start = tokens.peek().span.start;
+        match parse_commented_statement(tokens) {
             // Fill the program while more statements can be parsed.
             Ok(statement) => {
                 program.statement(statement);
+                spans.push(Span::new(start, statement_end(tokens).max(start)));
             }
 
             // There is no more input, and no half-parsed statements.
@@ -42,13 +53,22 @@ pub fn parse_program(tokens: &mut TokenCursor) -> ParseResult<Program> {
         return Err(unexpected_token(tokens));
     }
 
-    Ok(program)
+    Ok((program, spans))
+}
+
+/// Returns the end position of a statement that has just been parsed.
+fn statement_end(tokens: &mut TokenCursor) -> usize {
+    let next = tokens.peek();
+    match next.contents {
+        TokenContents::Eof => next.span.end,
+        _ => next.span.start,
+    }
 }
 
 pub(crate) fn parse_subshell_program(tokens: &mut TokenCursor) -> ParseResult<Program> {
     let mut subshell_program = Program::new();
     loop {
-        match parse_statement(tokens) {
+        match parse_commented_statement(tokens) {
             Ok(statement) => {
                 subshell_program.statement(statement);
             }
@@ -254,6 +274,64 @@ fn it_parses_programs() {
         );
     }
 
+    #[test]
+    fn it_parses_literal_brace_groups_as_arguments() {
+        let program = crate::parse("echo {x} a{x}b", &HashMap::new()).unwrap();
+        let Statement::AndOr(and_or) = &program.statements[0] else {
+            panic!("{program:?}");
+        };
+        let PipelineSegment::Command(command) = &and_or.pipelines[0].segments[0] else {
+            panic!("{program:?}");
+        };
+
+        assert_eq!(
+            command.arguments,
+            vec![
+                Word::Literal("echo".into()),
+                Word::Literal("{x}".into()),
+                Word::Literal("a{x}b".into()),
+            ]
+        );
+        assert!(crate::parse("fn f() {true}; if true {f} else {true}", &HashMap::new()).is_ok());
+    }
+
+    #[test]
+    fn it_skips_shebangs() {
+        assert_eq!(
+            crate::parse("#!/usr/bin/env pjsh\ncmd arg\n", &HashMap::new()),
+            Ok(Program {
+                statements: vec![Statement::AndOr(AndOr {
+                    operators: vec![],
+                    pipelines: vec![Pipeline {
+                        is_async: false,
+                        segments: vec![PipelineSegment::Command(Command {
+                            arguments: vec![
+                                Word::Literal("cmd".into()),
+                                Word::Literal("arg".into())
+                            ],
+                            redirects: Vec::new(),
+                        }),]
+                    }]
+                })]
+            })
+        );
+    }
+
+    #[test]
+    fn it_spans_statements() {
+        let src = "first a\n\nsecond b; third\n";
+        let (program, spans) = crate::parse_spanned(src, &HashMap::new()).unwrap();
+        assert_eq!(program.statements.len(), 3);
+
+        let excerpts: Vec<&str> = spans
+            .iter()
+            .map(|span| {
+                src[span.start..span.end].trim_matches(|c: char| c.is_whitespace() || c == ';')
+            })
+            .collect();
+        assert_eq!(excerpts, vec!["first a", "second b", "third"]);
+    }
+
     #[test]
     fn parse_subshell() {
         assert_eq!(
@@ -363,4 +441,29 @@ fn parse_subshell_interpolation() {
             })
         );
     }
+
+    #[test]
+    fn it_parses_crlf_scripts_like_lf_scripts() {
+        let lf = "# comment\nname := value\nif true {\n  echo $name\n}\n\nfor i in [a b] {\n  echo $i\n}\n";
+        let crlf = lf.replace('\n', "\r\n");
+
+        assert_eq!(
+            crate::parse(&crlf, &HashMap::new()),
+            crate::parse(lf, &HashMap::new())
+        );
+    }
+
+    #[test]
+    fn it_normalizes_crlf_in_multiline_strings() {
+        for lf in [
+            "echo '''\nfirst\n  second\n'''\n",
+            "echo \"\"\"\nfirst\n  second\n\"\"\"\n",
+            "echo ```\nfirst $name\n  second\n```\n",
+        ] {
+            let crlf = lf.replace('\n', "\r\n");
+            let program = crate::parse(&crlf, &HashMap::new());
+            assert!(program.is_ok(), "{crlf:?}");
+            assert_eq!(program, crate::parse(lf, &HashMap::new()), "{crlf:?}");
+        }
+    }
 }
@@ -1,6 +1,7 @@
 use pjsh_ast::{
-    Assignment, Block, ConditionalChain, ConditionalLoop, ForIterableLoop, ForOfIterableLoop,
-    Function, Iterable, Statement, Switch, Value, Word,
+    Assignment, Block, Comments, ConditionalChain, ConditionalLoop, ForIterableLoop,
+    ForOfIterableLoop, Function, Iterable, IterationSource, Statement, Switch, SwitchBranch, Value,
+    Word,
 };
 
 use crate::{
@@ -18,6 +19,31 @@
     ParseResult,
 };
 
+/// Tries to parse a [`Statement`] along with its surrounding comments.
+///
+/// Comments above the statement are leading comments, and a comment at the end
+/// of the statement's last line is a trailing comment. The statement is wrapped
+/// in a [`Statement::Commented`] if it has any comments.
+pub(crate) fn parse_commented_statement(tokens: &mut TokenCursor) -> ParseResult<Statement> {
+    tokens.newline_is_whitespace(false);
+    skip_newlines(tokens);
+    let leading = tokens.take_comments();
+
+    let statement = parse_statement(tokens)?;
+
+    tokens.newline_is_whitespace(false);
+    tokens.peek(); // Skip past any trailing comment.
+    let comments = Comments {
+        leading,
+        trailing: tokens.take_trailing_comment(),
+    };
+
+    match comments.is_empty() {
+        true => Ok(statement),
+        false => Ok(Statement::Commented(Box::new(statement), comments)),
+    }
+}
+
 /// Tries to parse a [`Statement`] from the next tokens of input.
 pub(crate) fn parse_statement(tokens: &mut TokenCursor) -> ParseResult<Statement> {
     tokens.newline_is_whitespace(false); // Ensure clean start.
@@ -41,6 +67,7 @@ pub(crate) fn parse_statement(tokens: &mut TokenCursor) -> ParseResult<Statement
     match parse_switch_statement(tokens) {
         Ok(statement) => return Ok(statement),
         Err(ParseError::IncompleteSequence) => return Err(ParseError::IncompleteSequence),
+        Err(error @ ParseError::InvalidSyntax(_)) => return Err(error),
         _ => (),
     }
 
@@ -58,6 +85,24 @@ pub(crate) fn parse_statement(tokens: &mut TokenCursor) -> ParseResult<Statement
         _ => (),
     }
 
+    // Try to parse a loop control statement.
+    if let Some(statement) = parse_loop_control(tokens)? {
+        return Ok(statement);
+    }
+
+    // Try to parse a function return.
+    if let Some(statement) = parse_return(tokens)? {
+        return Ok(statement);
+    }
+
+    // Try to parse a switch branch fallthrough.
+    if take_literal(tokens, "fallthrough").is_ok() {
+        return match is_statement_end(tokens) {
+            true => Ok(Statement::Fallthrough),
+            false => Err(unexpected_token(tokens)),
+        };
+    }
+
     // Try to parse a function declaration.
     match parse_function(tokens) {
         Ok(function_statement) => return Ok(function_statement),
@@ -72,9 +117,46 @@ pub(crate) fn parse_statement(tokens: &mut TokenCursor) -> ParseResult<Statement
         _ => (),
     }
 
+    // Keywords from other shells are likely pasted snippets that need to be rewritten.
+    if let Some(error) = unsupported_keyword(tokens) {
+        return Err(error);
+    }
+
     Ok(Statement::AndOr(parse_and_or(tokens)?))
 }
 
+/// Keywords from POSIX shells, along with hints describing the equivalent syntax.
+const POSIX_KEYWORDS: &[(&str, &str)] = &[
+    ("then", "use braces: if cond { ... }"),
+    ("fi", "use braces: if cond { ... }"),
+    (
+        "do",
+        "use braces: for x in a b c { ... } or while cond { ... }",
+    ),
+    (
+        "done",
+        "use braces: for x in a b c { ... } or while cond { ... }",
+    ),
+    ("case", "use switch: switch $value { pattern { ... } }"),
+    ("esac", "use switch: switch $value { pattern { ... } }"),
+];
+
+/// Returns a [`ParseError::UnsupportedKeyword`] if the next token is a POSIX
+/// shell keyword.
+fn unsupported_keyword(tokens: &mut TokenCursor) -> Option<ParseError> {
+    let token = tokens.peek();
+    let TokenContents::Literal(literal) = &token.contents else {
+        return None;
+    };
+
+    POSIX_KEYWORDS
+        .iter()
+        .find(|(keyword, _)| keyword == literal)
+        .map(|(keyword, hint)| {
+            ParseError::UnsupportedKeyword(keyword.to_string(), hint, token.span)
+        })
+}
+
 /// Parses an assignment statement.
 fn parse_assignment(tokens: &mut TokenCursor) -> ParseResult<Statement> {
     let mut peek = tokens.clone();
@@ -174,27 +256,74 @@ fn parse_switch_statement(tokens: &mut TokenCursor) -> ParseResult<Statement> {
         take_token(tokens, &TokenContents::OpenBrace)?;
         skip_newlines(tokens);
 
-        let mut branches = Vec::new();
+        let mut branches: Vec<SwitchBranch> = Vec::new();
         while take_token(tokens, &TokenContents::CloseBrace).is_err() {
             skip_newlines(tokens);
-            let mut keys = Vec::new();
-
-            // Parse one or more keys.
-            keys.push(parse_word(tokens)?);
-            while let Ok(word) = parse_word(tokens) {
-                keys.push(word);
+            let branch = parse_switch_branch(tokens)?;
+            if branch.keys.is_empty() && branches.iter().any(|branch| branch.keys.is_empty()) {
+                return Err(ParseError::InvalidSyntax(
+                    "a switch can only have one default branch".to_owned(),
+                ));
             }
+            branches.push(branch);
+            skip_newlines(tokens);
+        }
 
-            let body = parse_block(tokens)?;
+        Ok(Statement::Switch(Switch { input, branches }))
+    })
+}
 
-            for key in keys {
-                branches.push((key, body.clone()));
-            }
+/// Parses a single branch within a switch-statement.
+///
+/// A branch consists of one or more keys, an optional `as name` binding, and a
+/// body. A trailing `fallthrough` statement in the body is removed from it.
+///
+/// Default branches use a single `_` or `default` key, and are returned
+/// without any keys.
+fn parse_switch_branch(tokens: &mut TokenCursor) -> ParseResult<SwitchBranch> {
+    // Parse one or more keys.
+    let mut keys = vec![parse_word(tokens)?];
+    let mut binding = None;
+    while tokens.peek().contents != TokenContents::OpenBrace {
+        if take_literal(tokens, "as").is_ok() {
+            binding = match parse_word(tokens)? {
+                Word::Literal(name) => Some(name),
+                _ => return Err(ParseError::InvalidSyntax("expected literal".to_owned())),
+            };
+            break;
+        }
 
-            skip_newlines(tokens);
+        keys.push(parse_word(tokens)?);
+    }
+
+    if let [Word::Literal(key)] = &keys[..] {
+        if key == "_" || key == "default" {
+            keys.clear();
         }
+    }
 
-        Ok(Statement::Switch(Switch { input, branches }))
+    let mut body = parse_block(tokens)?;
+    let fallthrough =
+        body.statements.last().map(Statement::uncommented) == Some(&Statement::Fallthrough);
+    if fallthrough {
+        body.statements.pop();
+    }
+
+    if body
+        .statements
+        .iter()
+        .any(|statement| statement.uncommented() == &Statement::Fallthrough)
+    {
+        return Err(ParseError::InvalidSyntax(
+            "fallthrough must be the last statement of a switch branch".to_owned(),
+        ));
+    }
+
+    Ok(SwitchBranch {
+        keys,
+        binding,
+        body,
+        fallthrough,
     })
 }
 
@@ -212,12 +341,16 @@ pub(crate) fn parse_for_loop(tokens: &mut TokenCursor) -> Result<Statement, Pars
     let in_word = tokens.next_if(|t| matches!(t.contents, TokenContents::Literal(_)));
 
     // Determine an abstract iteration rule if the loop is a for-in-of-loop.
-    if in_word.is_some() && take_literal(tokens, "of").is_ok() {
-        let iterable = parse_word(tokens)?;
+    if let Some(rule_word) = in_word
+        .as_ref()
+        .filter(|_| take_literal(tokens, "of").is_ok())
+    {
+        let (source, iterable) = parse_iteration_source(tokens)?;
         let body = parse_block(tokens)?;
         return Ok(Statement::ForOfIn(ForOfIterableLoop {
             variable,
-            iteration_rule: iteration_rule(&in_word.expect("has iteration rule"))?,
+            iteration_rule: iteration_rule(rule_word)?,
+            source,
             iterable,
             body,
         }));
@@ -260,6 +393,86 @@ fn parse_while_loop(tokens: &mut TokenCursor) -> ParseResult<Statement> {
     }))
 }
 
+/// Parses the source and iterable of a for-in-of-loop.
+///
+/// Iterables prefixed by `file` are file paths. A lone `file` word is a
+/// regular iterable.
+fn parse_iteration_source(tokens: &mut TokenCursor) -> ParseResult<(IterationSource, Word)> {
+    let mut peek = tokens.clone();
+    if take_literal(&mut peek, "file").is_ok() {
+        if let Ok(path) = parse_word(&mut peek) {
+            *tokens = peek;
+            return Ok((IterationSource::File, path));
+        }
+    }
+
+    Ok((IterationSource::Value, parse_word(tokens)?))
+}
+
+/// Parses a loop control statement (`break` or `continue`) with an optional
+/// number of loops to exit.
+///
+/// Returns `None` if the next tokens do not start a loop control statement.
+fn parse_loop_control(tokens: &mut TokenCursor) -> ParseResult<Option<Statement>> {
+    let statement: fn(usize) -> Statement = if take_literal(tokens, "break").is_ok() {
+        Statement::Break
+    } else if take_literal(tokens, "continue").is_ok() {
+        Statement::Continue
+    } else {
+        return Ok(None);
+    };
+
+    let levels = match &tokens.peek().contents {
+        TokenContents::Literal(levels) => match levels.parse::<usize>() {
+            Ok(levels) if levels > 0 => {
+                tokens.next();
+                levels
+            }
+            _ => {
+                let message = format!("invalid loop count: {levels}");
+                return Err(ParseError::InvalidSyntax(message));
+            }
+        },
+        _ => 1,
+    };
+
+    match is_statement_end(tokens) {
+        true => Ok(Some(statement(levels))),
+        false => Err(unexpected_token(tokens)),
+    }
+}
+
+/// Parses a function return statement (`return`) with an optional exit code.
+///
+/// Returns `None` if the next tokens do not start a return statement.
+fn parse_return(tokens: &mut TokenCursor) -> ParseResult<Option<Statement>> {
+    if take_literal(tokens, "return").is_err() {
+        return Ok(None);
+    }
+
+    let code = match is_statement_end(tokens) {
+        true => None,
+        false => Some(parse_word(tokens)?),
+    };
+
+    match is_statement_end(tokens) {
+        true => Ok(Some(Statement::Return(code))),
+        false => Err(unexpected_token(tokens)),
+    }
+}
+
+/// Returns `true` if the next token ends a keyword statement.
+fn is_statement_end(tokens: &mut TokenCursor) -> bool {
+    matches!(
+        tokens.peek().contents,
+        TokenContents::Eol
+            | TokenContents::Semi
+            | TokenContents::Eof
+            | TokenContents::CloseBrace
+            | TokenContents::CloseParen
+    )
+}
+
 /// Parses a code block surrounded by curly braces.
 fn parse_block(tokens: &mut TokenCursor) -> ParseResult<Block> {
     take_token(tokens, &TokenContents::OpenBrace)?;
@@ -271,7 +484,7 @@ fn parse_block(tokens: &mut TokenCursor) -> ParseResult<Block> {
             TokenContents::Eof => return Err(ParseError::IncompleteSequence),
             TokenContents::CloseBrace => break,
             _ => {
-                block.statement(parse_statement(tokens)?);
+                block.statement(parse_commented_statement(tokens)?);
             }
         }
     }
@@ -283,6 +496,8 @@ fn parse_block(tokens: &mut TokenCursor) -> ParseResult<Block> {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
     use pjsh_ast::{AndOr, Command, IterationRule, List, Pipeline, PipelineSegment, Switch, Value};
 
     use crate::{token::Token, Span};
@@ -545,9 +760,10 @@ fn parse_switch_statement() {
             Ok(Statement::Switch(Switch {
                 input: Word::Literal("b".into()),
                 branches: vec![
-                    (
-                        Word::Literal("a".into()),
-                        Block {
+                    SwitchBranch {
+                        keys: vec![Word::Literal("a".into())],
+                        binding: None,
+                        body: Block {
                             statements: vec![Statement::AndOr(AndOr {
                                 operators: Vec::new(),
                                 pipelines: vec![Pipeline {
@@ -558,11 +774,13 @@ fn parse_switch_statement() {
                                     })]
                                 }]
                             })]
-                        }
-                    ),
-                    (
-                        Word::Literal("b".into()),
-                        Block {
+                        },
+                        fallthrough: false,
+                    },
+                    SwitchBranch {
+                        keys: vec![Word::Literal("b".into())],
+                        binding: None,
+                        body: Block {
                             statements: vec![Statement::AndOr(AndOr {
                                 operators: Vec::new(),
                                 pipelines: vec![Pipeline {
@@ -573,11 +791,13 @@ fn parse_switch_statement() {
                                     })]
                                 }]
                             })]
-                        }
-                    ),
-                    (
-                        Word::Literal("c".into()),
-                        Block {
+                        },
+                        fallthrough: false,
+                    },
+                    SwitchBranch {
+                        keys: vec![Word::Literal("c".into())],
+                        binding: None,
+                        body: Block {
                             statements: vec![Statement::AndOr(AndOr {
                                 operators: Vec::new(),
                                 pipelines: vec![Pipeline {
@@ -588,13 +808,134 @@ fn parse_switch_statement() {
                                     })]
                                 }]
                             })]
-                        }
-                    ),
+                        },
+                        fallthrough: false,
+                    },
                 ]
             }))
         )
     }
 
+    #[test]
+    fn parse_switch_branch_binding_and_fallthrough() {
+        let span = Span::new(0, 0); // Does not matter during this test.
+        let statement = parse_statement(&mut TokenCursor::from(vec![
+            Token::new(TokenContents::Literal("switch".into()), span),
+            Token::new(TokenContents::Variable("file".into()), span), // The input.
+            Token::new(TokenContents::OpenBrace, span),
+            Token::new(TokenContents::Literal("*.txt".into()), span),
+            Token::new(TokenContents::Literal("*.md".into()), span),
+            Token::new(TokenContents::Literal("as".into()), span),
+            Token::new(TokenContents::Literal("name".into()), span),
+            Token::new(TokenContents::OpenBrace, span),
+            Token::new(TokenContents::Literal("fallthrough".into()), span),
+            Token::new(TokenContents::CloseBrace, span),
+            Token::new(TokenContents::Literal("other".into()), span),
+            Token::new(TokenContents::OpenBrace, span),
+            Token::new(TokenContents::CloseBrace, span),
+            Token::new(TokenContents::CloseBrace, span),
+        ]));
+
+        assert_eq!(
+            statement,
+            Ok(Statement::Switch(Switch {
+                input: Word::Variable("file".into()),
+                branches: vec![
+                    SwitchBranch {
+                        keys: vec![Word::Literal("*.txt".into()), Word::Literal("*.md".into())],
+                        binding: Some("name".into()),
+                        body: Block::default(),
+                        fallthrough: true,
+                    },
+                    SwitchBranch {
+                        keys: vec![Word::Literal("other".into())],
+                        binding: None,
+                        body: Block::default(),
+                        fallthrough: false,
+                    },
+                ]
+            }))
+        );
+    }
+
+    #[test]
+    fn parse_switch_default_branch() {
+        let span = Span::new(0, 0); // Does not matter during this test.
+        let switch = |default_key: &str| {
+            parse_statement(&mut TokenCursor::from(vec![
+                Token::new(TokenContents::Literal("switch".into()), span),
+                Token::new(TokenContents::Literal("a".into()), span), // The input.
+                Token::new(TokenContents::OpenBrace, span),
+                Token::new(TokenContents::Literal("b".into()), span),
+                Token::new(TokenContents::OpenBrace, span),
+                Token::new(TokenContents::CloseBrace, span),
+                Token::new(TokenContents::Literal(default_key.into()), span),
+                Token::new(TokenContents::OpenBrace, span),
+                Token::new(TokenContents::CloseBrace, span),
+                Token::new(TokenContents::CloseBrace, span),
+            ]))
+        };
+
+        for default_key in ["_", "default"] {
+            assert_eq!(
+                switch(default_key),
+                Ok(Statement::Switch(Switch {
+                    input: Word::Literal("a".into()),
+                    branches: vec![
+                        SwitchBranch {
+                            keys: vec![Word::Literal("b".into())],
+                            binding: None,
+                            body: Block::default(),
+                            fallthrough: false,
+                        },
+                        SwitchBranch {
+                            keys: Vec::new(),
+                            binding: None,
+                            body: Block::default(),
+                            fallthrough: false,
+                        },
+                    ]
+                }))
+            );
+        }
+    }
+
+    #[test]
+    fn parse_multiple_switch_default_branches() {
+        let span = Span::new(0, 0); // Does not matter during this test.
+        assert!(parse_statement(&mut TokenCursor::from(vec![
+            Token::new(TokenContents::Literal("switch".into()), span),
+            Token::new(TokenContents::Literal("a".into()), span), // The input.
+            Token::new(TokenContents::OpenBrace, span),
+            Token::new(TokenContents::Literal("_".into()), span),
+            Token::new(TokenContents::OpenBrace, span),
+            Token::new(TokenContents::CloseBrace, span),
+            Token::new(TokenContents::Literal("default".into()), span),
+            Token::new(TokenContents::OpenBrace, span),
+            Token::new(TokenContents::CloseBrace, span),
+            Token::new(TokenContents::CloseBrace, span),
+        ]))
+        .is_err_and(|error| matches!(error, ParseError::InvalidSyntax(_))));
+    }
+
+    #[test]
+    fn parse_misplaced_switch_fallthrough() {
+        let span = Span::new(0, 0); // Does not matter during this test.
+        assert!(parse_statement(&mut TokenCursor::from(vec![
+            Token::new(TokenContents::Literal("switch".into()), span),
+            Token::new(TokenContents::Literal("a".into()), span), // The input.
+            Token::new(TokenContents::OpenBrace, span),
+            Token::new(TokenContents::Literal("a".into()), span),
+            Token::new(TokenContents::OpenBrace, span),
+            Token::new(TokenContents::Literal("fallthrough".into()), span),
+            Token::new(TokenContents::Semi, span),
+            Token::new(TokenContents::Literal("cmd".into()), span),
+            Token::new(TokenContents::CloseBrace, span),
+            Token::new(TokenContents::CloseBrace, span),
+        ]))
+        .is_err_and(|error| matches!(error, ParseError::InvalidSyntax(_))));
+    }
+
     #[test]
     fn parse_while_loop() {
         let span = Span::new(0, 0); // Does not matter during this test.
@@ -790,6 +1131,7 @@ fn parse_for_of_in_loop() {
             Ok(Statement::ForOfIn(ForOfIterableLoop {
                 variable: "color".into(),
                 iteration_rule: IterationRule::Words,
+                source: IterationSource::Value,
                 iterable: Word::Literal("red green blue".into()),
                 body: Block {
                     statements: vec![Statement::AndOr(AndOr {
@@ -810,6 +1152,51 @@ fn parse_for_of_in_loop() {
         );
     }
 
+    #[test]
+    fn parse_for_of_file_in_loop() {
+        let span = Span::new(0, 0); // Does not matter during this test.
+        let for_loop = |iterable: Vec<TokenContents>| {
+            let mut tokens = vec![
+                Token::new(TokenContents::Literal("for".into()), span),
+                Token::new(TokenContents::Literal("line".into()), span),
+                Token::new(TokenContents::Literal("in".into()), span),
+                Token::new(TokenContents::Literal("lines".into()), span),
+                Token::new(TokenContents::Literal("of".into()), span),
+            ];
+            tokens.extend(iterable.into_iter().map(|c| Token::new(c, span)));
+            tokens.push(Token::new(TokenContents::OpenBrace, span));
+            tokens.push(Token::new(TokenContents::CloseBrace, span));
+            parse_for_loop(&mut TokenCursor::from(tokens))
+        };
+
+        assert_eq!(
+            for_loop(vec![
+                TokenContents::Literal("file".into()),
+                TokenContents::Whitespace,
+                TokenContents::Variable("path".into()),
+            ]),
+            Ok(Statement::ForOfIn(ForOfIterableLoop {
+                variable: "line".into(),
+                iteration_rule: IterationRule::Lines,
+                source: IterationSource::File,
+                iterable: Word::Variable("path".into()),
+                body: Block::default(),
+            }))
+        );
+
+        // A lone "file" word is iterated over as a value.
+        assert_eq!(
+            for_loop(vec![TokenContents::Literal("file".into())]),
+            Ok(Statement::ForOfIn(ForOfIterableLoop {
+                variable: "line".into(),
+                iteration_rule: IterationRule::Lines,
+                source: IterationSource::Value,
+                iterable: Word::Literal("file".into()),
+                body: Block::default(),
+            }))
+        );
+    }
+
     #[test]
     fn parse_for_invalid_of_in_loop() {
         let span = Span::new(0, 0); // Does not matter during this test.
@@ -835,6 +1222,50 @@ fn parse_for_invalid_of_in_loop() {
         .is_err());
     }
 
+    #[test]
+    fn it_hints_about_posix_keywords() {
+        let cases = [
+            ("if [ a ]; then echo a; fi", "then", 10),
+            ("if true {}\nfi", "fi", 11),
+            ("for x in a b; do echo $x; done", "do", 14),
+            ("while true {}; done", "done", 15),
+            ("case $x in", "case", 0),
+            ("echo a\n  esac", "esac", 9),
+        ];
+
+        for (src, keyword, start) in cases {
+            match crate::parse(src, &HashMap::new()) {
+                Err(ParseError::UnsupportedKeyword(actual, hint, span)) => {
+                    assert_eq!(actual, keyword, "{src}");
+                    assert!(hint.starts_with("use "), "{src}");
+                    assert_eq!(span, Span::new(start, start + keyword.len()), "{src}");
+                }
+                other => panic!("unexpected result for {src:?}: {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn it_accepts_interpolated_backtick_words() {
+        for src in [
+            "echo `date`",
+            "x := `$PWD/bin`",
+            "`$program` arg",
+            "x := ``",
+            "`date`",
+            "echo a | ` ls -la `",
+            "msg := `hello world`",
+            "msg := `first line\nsecond line`",
+        ] {
+            assert!(crate::parse(src, &HashMap::new()).is_ok(), "{src}");
+        }
+    }
+
+    #[test]
+    fn it_accepts_posix_keywords_as_arguments() {
+        assert!(crate::parse("echo then do done fi case esac", &HashMap::new()).is_ok());
+    }
+
     #[test]
     fn parse_statement_before_unexpected() {
         let span = Span::new(0, 0); // Does not matter during this test.
@@ -869,4 +1300,127 @@ fn parse_statement_before_unexpected() {
             }))
         );
     }
+
+    #[test]
+    fn parse_loop_control() {
+        let span = Span::new(0, 0); // Does not matter during this test.
+        assert_eq!(
+            parse_statement(&mut TokenCursor::from(vec![Token::new(
+                TokenContents::Literal("break".into()),
+                span
+            )])),
+            Ok(Statement::Break(1))
+        );
+        assert_eq!(
+            parse_statement(&mut TokenCursor::from(vec![
+                Token::new(TokenContents::Literal("continue".into()), span),
+                Token::new(TokenContents::Whitespace, span),
+                Token::new(TokenContents::Literal("2".into()), span),
+                Token::new(TokenContents::Semi, span),
+            ])),
+            Ok(Statement::Continue(2))
+        );
+    }
+
+    #[test]
+    fn parse_return() {
+        let span = Span::new(0, 0); // Does not matter during this test.
+        assert_eq!(
+            parse_statement(&mut TokenCursor::from(vec![Token::new(
+                TokenContents::Literal("return".into()),
+                span
+            )])),
+            Ok(Statement::Return(None))
+        );
+        assert_eq!(
+            parse_statement(&mut TokenCursor::from(vec![
+                Token::new(TokenContents::Literal("return".into()), span),
+                Token::new(TokenContents::Whitespace, span),
+                Token::new(TokenContents::Literal("3".into()), span),
+                Token::new(TokenContents::Semi, span),
+            ])),
+            Ok(Statement::Return(Some(Word::Literal("3".into()))))
+        );
+        assert!(parse_statement(&mut TokenCursor::from(vec![
+            Token::new(TokenContents::Literal("return".into()), span),
+            Token::new(TokenContents::Whitespace, span),
+            Token::new(TokenContents::Literal("1".into()), span),
+            Token::new(TokenContents::Whitespace, span),
+            Token::new(TokenContents::Literal("extra".into()), span),
+        ]))
+        .is_err());
+    }
+
+    #[test]
+    fn parse_loop_control_with_invalid_count() {
+        let span = Span::new(0, 0); // Does not matter during this test.
+        assert!(parse_statement(&mut TokenCursor::from(vec![
+            Token::new(TokenContents::Literal("break".into()), span),
+            Token::new(TokenContents::Whitespace, span),
+            Token::new(TokenContents::Literal("0".into()), span),
+        ]))
+        .is_err());
+        assert!(parse_statement(&mut TokenCursor::from(vec![
+            Token::new(TokenContents::Literal("continue".into()), span),
+            Token::new(TokenContents::Whitespace, span),
+            Token::new(TokenContents::Literal("1".into()), span),
+            Token::new(TokenContents::Whitespace, span),
+            Token::new(TokenContents::Literal("extra".into()), span),
+        ]))
+        .is_err());
+    }
+
+    #[test]
+    fn it_attaches_comments_to_statements() {
+        let command = |name: &str| {
+            Statement::AndOr(AndOr {
+                operators: Vec::new(),
+                pipelines: vec![Pipeline {
+                    is_async: false,
+                    segments: vec![PipelineSegment::Command(Command {
+                        arguments: vec![Word::Literal(name.into())],
+                        redirects: Vec::new(),
+                    })],
+                }],
+            })
+        };
+        let commented = |statement, leading: &[&str], trailing: Option<&str>| {
+            Statement::Commented(
+                Box::new(statement),
+                Comments {
+                    leading: leading.iter().map(ToString::to_string).collect(),
+                    trailing: trailing.map(ToString::to_string),
+                },
+            )
+        };
+
+        let program = crate::parse(
+            "# Greet.\n# Twice.\nfirst # Once.\nsecond\nif true {\n  # Inner.\n  third\n}\n",
+            &HashMap::new(),
+        )
+        .expect("program should parse");
+
+        assert_eq!(
+            program.statements,
+            vec![
+                commented(command("first"), &["# Greet.", "# Twice."], Some("# Once.")),
+                command("second"),
+                Statement::If(ConditionalChain {
+                    conditions: vec![AndOr {
+                        operators: Vec::new(),
+                        pipelines: vec![Pipeline {
+                            is_async: false,
+                            segments: vec![PipelineSegment::Command(Command {
+                                arguments: vec![Word::Literal("true".into())],
+                                redirects: Vec::new(),
+                            })],
+                        }],
+                    }],
+                    branches: vec![Block {
+                        statements: vec![commented(command("third"), &["# Inner."], None)],
+                    }],
+                }),
+            ]
+        );
+    }
 }
@@ -1,4 +1,4 @@
-use pjsh_ast::{InterpolationUnit, List, ValuePipeline, Word};
+use pjsh_ast::{Filter, InterpolationUnit, List, ValuePipeline, Word};
 
 use crate::{
     token::{self, TokenContents},
@@ -85,10 +85,13 @@ fn parse_interpolation_unit(
         token::InterpolationUnit::Literal(literal) => Ok(InterpolationUnit::Literal(literal)),
         token::InterpolationUnit::Unicode(ch) => Ok(InterpolationUnit::Unicode(ch)),
         token::InterpolationUnit::Variable(var) => Ok(InterpolationUnit::Variable(var)),
+        token::InterpolationUnit::VariableLength(var) => Ok(InterpolationUnit::VariableLength(var)),
+        token::InterpolationUnit::Arithmetic(expr) => Ok(InterpolationUnit::Arithmetic(expr)),
         token::InterpolationUnit::ValuePipeline(pipeline_tokens) => {
             match parse_value_pipeline(&mut TokenCursor::from(pipeline_tokens))? {
                 Word::Variable(variable) => Ok(InterpolationUnit::Variable(variable)),
                 Word::ValuePipeline(pipeline) => Ok(InterpolationUnit::ValuePipeline(*pipeline)),
+                Word::Interpolation(mut units) if units.len() == 1 => Ok(units.remove(0)),
                 _ => unreachable!("All possible parsed values should be covered"),
             }
         }
@@ -184,6 +187,11 @@ fn parse_value_pipeline(tokens: &mut TokenCursor) -> ParseResult<Word> {
         _ => return Err(ParseError::UnexpectedToken(base_token)),
     };
 
+    // Substring expansions, such as `${name:offset:length}`, are written within the base.
+    if let Some((name, range)) = base.split_once(':') {
+        return parse_substring(name.to_owned(), range, tokens);
+    }
+
     // Value pipelines without any filters can be simplified into single variables.
     // This does, however, require the function to return values of type Word rather
     // than values of type ValuePipeline.
@@ -191,7 +199,57 @@ fn parse_value_pipeline(tokens: &mut TokenCursor) -> ParseResult<Word> {
         return Ok(Word::Variable(base));
     }
 
+    let was_newline_whitespace = tokens.is_newline_whitespace();
     tokens.newline_is_whitespace(true);
+    let filters = parse_filters(tokens);
+    tokens.newline_is_whitespace(was_newline_whitespace); // Ensure a clean exit.
+    let filters = filters?;
+
+    Ok(Word::ValuePipeline(Box::new(ValuePipeline {
+        base,
+        filters,
+    })))
+}
+
+/// Parses the remainder of a substring expansion, such as `${name:offset:length}`,
+/// including its closing brace.
+///
+/// The `range` contains the text following the first colon. If it is empty,
+/// the range is instead taken from the next token, allowing negative offsets to
+/// be written as `${name: -3}`.
+fn parse_substring(name: String, range: &str, tokens: &mut TokenCursor) -> ParseResult<Word> {
+    let range = match range.is_empty() {
+        true => match &tokens.peek().contents {
+            TokenContents::Literal(_) => match tokens.next().contents {
+                TokenContents::Literal(literal) => literal,
+                _ => unreachable!("the token should be a literal"),
+            },
+            TokenContents::Eof => return Err(ParseError::IncompleteSequence),
+            _ => return Err(unexpected_token(tokens)),
+        },
+        false => range.to_owned(),
+    };
+
+    let invalid_range = || ParseError::InvalidSyntax(format!("invalid substring range: {range}"));
+    let (offset, length) = match range.split_once(':') {
+        Some((offset, length)) => (offset, Some(length)),
+        None => (range.as_str(), None),
+    };
+    let offset = offset.parse().map_err(|_| invalid_range())?;
+    let length = match length {
+        Some(length) => Some(length.parse().map_err(|_| invalid_range())?),
+        None => None,
+    };
+
+    take_token(tokens, &TokenContents::CloseBrace)?;
+
+    Ok(Word::Interpolation(vec![InterpolationUnit::Substring(
+        name, offset, length,
+    )]))
+}
+
+/// Parses the filters of a value pipeline, including its closing brace.
+fn parse_filters(tokens: &mut TokenCursor) -> ParseResult<Vec<Filter>> {
     let mut filters = Vec::new();
 
     loop {
@@ -206,10 +264,7 @@ fn parse_value_pipeline(tokens: &mut TokenCursor) -> ParseResult<Word> {
         filters.push(parse_filter(tokens)?);
     }
 
-    Ok(Word::ValuePipeline(Box::new(ValuePipeline {
-        base,
-        filters,
-    })))
+    Ok(filters)
 }
 
 #[cfg(test)]
@@ -310,6 +365,58 @@ fn it_parses_value_pipelines() {
         );
     }
 
+    #[test]
+    fn it_parses_substrings() {
+        let substring = |offset, length| {
+            Word::Interpolation(vec![InterpolationUnit::Substring(
+                "v".into(),
+                offset,
+                length,
+            )])
+        };
+
+        let program = crate::parse("echo ${v:1:2} ${v: -3} ${v:2} `a${v:0:1}`", &HashMap::new());
+        let Ok(Program { statements }) = program else {
+            panic!("unexpected result: {program:?}");
+        };
+        let [Statement::AndOr(and_or)] = &statements[..] else {
+            panic!("unexpected statements: {statements:?}");
+        };
+        let [PipelineSegment::Command(command)] = &and_or.pipelines[0].segments[..] else {
+            panic!("unexpected segments: {and_or:?}");
+        };
+        assert_eq!(
+            command.arguments[1..],
+            [
+                substring(1, Some(2)),
+                substring(-3, None),
+                substring(2, None),
+                Word::Interpolation(vec![
+                    InterpolationUnit::Literal("a".into()),
+                    InterpolationUnit::Substring("v".into(), 0, Some(1)),
+                ]),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_rejects_invalid_substring_ranges() {
+        for (src, range) in [
+            ("echo ${v:a}", "a"),
+            ("echo ${v:1:-1}", "1:-1"),
+            ("echo ${v:1:2:3}", "1:2:3"),
+        ] {
+            assert_eq!(
+                crate::parse(src, &HashMap::new()),
+                Err(ParseError::InvalidSyntax(format!(
+                    "invalid substring range: {range}"
+                ))),
+                "{src}"
+            );
+        }
+        assert!(crate::parse("echo ${v:}", &HashMap::new()).is_err());
+    }
+
     #[test]
     fn parse_dollar_dollar() {
         assert_eq!(
@@ -21,7 +21,7 @@ pub fn new(contents: TokenContents, span: Span) -> Self {
 #[derive(Debug, Clone, PartialEq)]
 pub enum TokenContents {
     /// "# ..."
-    Comment,
+    Comment(String),
 
     Interpolation(Vec<InterpolationUnit>),
     Literal(String),
@@ -80,6 +80,10 @@ pub enum TokenContents {
     FdWriteFrom(usize),
     /// ">>"
     FdAppendFrom(usize),
+    /// ">&"
+    FdDuplicate(usize, usize),
+    /// "<<<"
+    HereString(usize),
 
     /// ","
     Comma,
@@ -113,6 +117,12 @@ pub enum InterpolationUnit {
     /// The name of a variable unit that is evaluated at runtime.
     Variable(String),
 
+    /// The name of a variable whose length is evaluated at runtime.
+    VariableLength(String),
+
+    /// An arithmetic expression that is evaluated at runtime.
+    Arithmetic(String),
+
     /// A value pipeline inside an interpolation.
     ValuePipeline(Vec<Token>),
 